@@ -0,0 +1,187 @@
+//! LED blink demo
+//!
+//! Toggles a GPIO until a blink count is reached or the program is
+//! stopped:
+//!
+//! ```text
+//! blink [--gpio <N>] [--interval-ms <N>] [--count <N>]
+//! ```
+//!
+//! On NuttX the pin is `/dev/gpioN` (`/dev/gpio0` is the ESP32-S3 onboard
+//! LED on GPIO 2); on Linux it is a sysfs GPIO number.
+
+use hal::gpio::{GpioDirection, GpioError, GpioPin, GpioResult};
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Pin used when `--gpio` is not given
+#[cfg(feature = "platform-nuttx")]
+pub const DEFAULT_GPIO: u32 = 0;
+/// Pin used when `--gpio` is not given
+#[cfg(not(feature = "platform-nuttx"))]
+pub const DEFAULT_GPIO: u32 = 18;
+
+/// Time the LED stays on, and then off, when `--interval-ms` is not given
+pub const DEFAULT_INTERVAL_MS: u64 = 500;
+
+/// Command-line options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlinkConfig {
+    /// `--gpio <N>`: pin to toggle
+    pub gpio: u32,
+    /// `--interval-ms <N>`: time spent in each state
+    pub interval_ms: u64,
+    /// `--count <N>`: number of blinks, 0 to blink until stopped
+    pub count: u32,
+}
+
+impl Default for BlinkConfig {
+    fn default() -> Self {
+        Self {
+            gpio: DEFAULT_GPIO,
+            interval_ms: DEFAULT_INTERVAL_MS,
+            count: 0,
+        }
+    }
+}
+
+impl BlinkConfig {
+    /// Parse a C `argv` array; `argv[0]` (the program name) is skipped
+    ///
+    /// Null or non-UTF-8 arguments are ignored, and a null `argv` gives
+    /// the default config.
+    ///
+    /// # Safety
+    ///
+    /// Unless null, `argv` must point to `argc` pointers, each null or
+    /// pointing to a NUL-terminated string.
+    pub unsafe fn from_args(argc: i32, argv: *const *const u8) -> Result<BlinkConfig, String> {
+        if argv.is_null() || argc <= 1 {
+            return Ok(BlinkConfig::default());
+        }
+
+        let args = (1..argc as usize).filter_map(|i| {
+            let arg = *argv.add(i);
+            if arg.is_null() {
+                return None;
+            }
+            CStr::from_ptr(arg as *const core::ffi::c_char)
+                .to_str()
+                .ok()
+        });
+        Self::parse(args)
+    }
+
+    /// Parse arguments (without the program name)
+    pub fn parse<I, S>(args: I) -> Result<BlinkConfig, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        fn value<T: std::str::FromStr>(name: &str, value: Option<&str>) -> Result<T, String> {
+            let value = value.ok_or_else(|| format!("{} needs a value", name))?;
+            value
+                .parse()
+                .map_err(|_| format!("invalid {} '{}'", name, value))
+        }
+
+        let mut config = BlinkConfig::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let next = args.next();
+            let next = next.as_ref().map(AsRef::as_ref);
+            match arg.as_ref() {
+                "--gpio" => config.gpio = value("--gpio", next)?,
+                "--interval-ms" => config.interval_ms = value("--interval-ms", next)?,
+                "--count" => config.count = value("--count", next)?,
+                other => return Err(format!("unknown argument '{}'", other)),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Anything that can drive the LED; implemented for `GpioPin`
+pub trait Led {
+    /// Drive the LED on (`true`) or off
+    fn set(&mut self, on: bool) -> GpioResult<()>;
+}
+
+impl Led for GpioPin {
+    fn set(&mut self, on: bool) -> GpioResult<()> {
+        self.set_value(on)
+    }
+}
+
+/// Blink `led` as configured, calling `sleep` between state changes
+///
+/// Stops early once `stop` is set, always leaving the LED off. Returns the
+/// number of completed blinks.
+pub fn blink<L: Led>(
+    led: &mut L,
+    config: &BlinkConfig,
+    stop: &AtomicBool,
+    mut sleep: impl FnMut(Duration),
+) -> GpioResult<u32> {
+    let interval = Duration::from_millis(config.interval_ms);
+    let mut blinks = 0;
+
+    while config.count == 0 || blinks < config.count {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        blinks += 1;
+
+        led.set(true)?;
+        println!("Blink {}: GPIO high", blinks);
+        sleep(interval);
+
+        led.set(false)?;
+        println!("Blink {}: GPIO low", blinks);
+        sleep(interval);
+    }
+
+    Ok(blinks)
+}
+
+/// Export the configured pin and blink it until done or `stop` is set
+pub fn run(config: &BlinkConfig, stop: &AtomicBool) -> i32 {
+    let result = GpioPin::export(config.gpio).and_then(|mut pin| {
+        pin.set_direction(GpioDirection::Output)?;
+        blink(&mut pin, config, stop, thread::sleep)
+    });
+
+    match result {
+        Ok(blinks) => {
+            println!("Done after {} blinks", blinks);
+            0
+        }
+        Err(e) => {
+            println!("GPIO {} failed: {}", config.gpio, e);
+            if e == GpioError::PermissionDenied {
+                println!("Try again as root, e.g. sudo blink --gpio {}", config.gpio);
+            }
+            1
+        }
+    }
+}
+
+/// NuttX entry point
+///
+/// # Safety
+///
+/// `argc` and `argv` must be the task arguments passed by NuttX.
+#[cfg(feature = "platform-nuttx")]
+#[no_mangle]
+pub unsafe extern "C" fn blink_main(argc: i32, argv: *const *const u8) -> i32 {
+    match BlinkConfig::from_args(argc, argv) {
+        Ok(config) => run(&config, &AtomicBool::new(false)),
+        Err(e) => {
+            println!("Error: {}", e);
+            println!("Usage: blink [--gpio <N>] [--interval-ms <N>] [--count <N>]");
+            2
+        }
+    }
+}
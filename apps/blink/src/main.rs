@@ -0,0 +1,35 @@
+//! LED blink entry point; Ctrl-C stops blinking with the LED off
+
+use blink::BlinkConfig;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the SIGINT/SIGTERM handler
+static STOP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_signal(_signum: libc::c_int) {
+    STOP.store(true, Ordering::Relaxed);
+}
+
+fn main() {
+    let config = match BlinkConfig::parse(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: blink [--gpio <N>] [--interval-ms <N>] [--count <N>]");
+            std::process::exit(2);
+        }
+    };
+
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+        libc::signal(
+            libc::SIGTERM,
+            on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    std::process::exit(blink::run(&config, &STOP));
+}
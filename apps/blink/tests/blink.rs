@@ -0,0 +1,91 @@
+//! Blink loop and argument parsing, using a pin that records its calls
+
+use blink::{blink, BlinkConfig, Led};
+use hal::gpio::{GpioError, GpioResult};
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+/// Records every level it is driven to; fails after `fail_after` calls
+#[derive(Default)]
+struct RecordingPin {
+    levels: Vec<bool>,
+    fail_after: Option<usize>,
+}
+
+impl Led for RecordingPin {
+    fn set(&mut self, on: bool) -> GpioResult<()> {
+        if self.fail_after == Some(self.levels.len()) {
+            return Err(GpioError::PermissionDenied);
+        }
+        self.levels.push(on);
+        Ok(())
+    }
+}
+
+fn config(count: u32) -> BlinkConfig {
+    BlinkConfig {
+        gpio: 2,
+        interval_ms: 250,
+        count,
+    }
+}
+
+#[test]
+fn test_blinks_count_times() {
+    let mut pin = RecordingPin::default();
+    let mut sleeps = Vec::new();
+
+    let blinks = blink(&mut pin, &config(3), &AtomicBool::new(false), |d| {
+        sleeps.push(d)
+    });
+
+    assert_eq!(blinks, Ok(3));
+    assert_eq!(pin.levels, [true, false, true, false, true, false]);
+    assert_eq!(sleeps, vec![Duration::from_millis(250); 6]);
+}
+
+#[test]
+fn test_stop_flag_ends_infinite_blink() {
+    let mut pin = RecordingPin::default();
+    let stop = AtomicBool::new(false);
+    let mut sleeps = 0;
+
+    // count 0 blinks forever; stop after the second blink
+    let blinks = blink(&mut pin, &config(0), &stop, |_| {
+        sleeps += 1;
+        if sleeps == 4 {
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+    });
+
+    assert_eq!(blinks, Ok(2));
+    assert_eq!(pin.levels.last(), Some(&false));
+}
+
+#[test]
+fn test_error_stops_blinking() {
+    let mut pin = RecordingPin {
+        fail_after: Some(3),
+        ..RecordingPin::default()
+    };
+
+    let result = blink(&mut pin, &config(5), &AtomicBool::new(false), |_| {});
+
+    assert_eq!(result, Err(GpioError::PermissionDenied));
+    assert_eq!(pin.levels, [true, false, true]);
+}
+
+#[test]
+fn test_parse_args() {
+    assert_eq!(
+        BlinkConfig::parse::<_, &str>([]),
+        Ok(BlinkConfig::default())
+    );
+    assert_eq!(
+        BlinkConfig::parse(["--gpio", "2", "--interval-ms", "250", "--count", "3"]),
+        Ok(config(3))
+    );
+    assert!(BlinkConfig::parse(["--count"]).is_err());
+    assert!(BlinkConfig::parse(["--gpio", "led"]).is_err());
+    assert!(BlinkConfig::parse(["--fast"]).is_err());
+}
@@ -0,0 +1,256 @@
+//! Heap allocation regression benchmark
+//!
+//! Runs the heap report cases many times and checks the mean allocation
+//! of each against a fixed budget:
+//!
+//! ```text
+//! mem_bench [--json]
+//! ```
+//!
+//! Exits with 1 if any case is over budget, so CI fails on regressions.
+//! `mem_bench.expected.json` next to this crate holds the output of a
+//! reference run for comparison.
+//!
+//! glibc's per-thread cache hands freed chunks straight back without
+//! mallinfo() ever seeing them released, which would make every run after
+//! the first read as zero. The benchmark therefore re-executes itself with
+//! the cache disabled unless `GLIBC_TUNABLES` is already set.
+
+use rustcam::heap_report::{HeapReport, Measurement};
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+/// Measured runs per case, including warmup
+const ITERATIONS: usize = 100;
+
+/// Leading runs discarded while allocator caches settle
+const WARMUP: usize = 10;
+
+/// Tunables that turn off glibc's per-thread cache
+const NO_TCACHE: &str = "glibc.malloc.tcache_count=0";
+
+/// Allowed heap growth for one case
+struct BudgetConfig {
+    /// Case name as used by `HeapReport`
+    name: &'static str,
+    /// Expected allocation in bytes
+    budget_bytes: i32,
+    /// Headroom over the budget before the case fails, in percent
+    tolerance_pct: f32,
+}
+
+impl BudgetConfig {
+    /// Largest mean allocation that still passes
+    fn limit(&self) -> f64 {
+        self.budget_bytes as f64 * (1.0 + self.tolerance_pct as f64 / 100.0)
+    }
+}
+
+/// Budgets per case, including glibc chunk overhead
+const BUDGETS: &[BudgetConfig] = &[
+    BudgetConfig {
+        name: "Vec<i32> (100 items)",
+        budget_bytes: 450,
+        tolerance_pct: 5.0,
+    },
+    BudgetConfig {
+        name: "String (20 chars)",
+        budget_bytes: 48,
+        tolerance_pct: 5.0,
+    },
+    BudgetConfig {
+        name: "Box<[u8; 256]>",
+        budget_bytes: 288,
+        tolerance_pct: 5.0,
+    },
+    BudgetConfig {
+        name: "HashMap (empty)",
+        budget_bytes: 0,
+        tolerance_pct: 0.0,
+    },
+    BudgetConfig {
+        name: "HashMap (10 i32,i32)",
+        budget_bytes: 192,
+        tolerance_pct: 5.0,
+    },
+    BudgetConfig {
+        name: "Arc<[u8; 128]>",
+        budget_bytes: 160,
+        tolerance_pct: 5.0,
+    },
+    BudgetConfig {
+        name: "Arc<AtomicBool>",
+        budget_bytes: 48,
+        tolerance_pct: 5.0,
+    },
+];
+
+/// Statistics over the kept runs of one case
+struct CaseResult {
+    name: &'static str,
+    mean: f64,
+    stddev: f64,
+    min: i32,
+    max: i32,
+    budget: Option<&'static BudgetConfig>,
+}
+
+impl CaseResult {
+    fn from_samples(name: &'static str, samples: &[i32]) -> CaseResult {
+        let n = samples.len().max(1) as f64;
+        let mean = samples.iter().map(|&s| s as f64).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        CaseResult {
+            name,
+            mean,
+            stddev: variance.sqrt(),
+            min: samples.iter().copied().min().unwrap_or(0),
+            max: samples.iter().copied().max().unwrap_or(0),
+            budget: BUDGETS.iter().find(|b| b.name == name),
+        }
+    }
+
+    /// Bytes over the limit, if the mean exceeds it
+    fn excess(&self) -> Option<f64> {
+        let limit = self.budget?.limit();
+        (self.mean > limit).then_some(self.mean - limit)
+    }
+}
+
+/// Run every case `ITERATIONS` times and summarize the runs after warmup
+fn run_cases() -> Vec<CaseResult> {
+    let mut samples: Vec<(&'static str, Vec<i32>)> = Vec::new();
+    for iteration in 0..ITERATIONS {
+        let measurements: Vec<Measurement> = HeapReport::measure_cases();
+        if iteration < WARMUP {
+            continue;
+        }
+        for m in measurements {
+            match samples.iter_mut().find(|(name, _)| *name == m.name) {
+                Some((_, values)) => values.push(m.allocated()),
+                None => {
+                    let mut values = Vec::with_capacity(ITERATIONS - WARMUP);
+                    values.push(m.allocated());
+                    samples.push((m.name, values));
+                }
+            }
+        }
+    }
+    samples
+        .iter()
+        .map(|(name, values)| CaseResult::from_samples(name, values))
+        .collect()
+}
+
+fn print_table(results: &[CaseResult]) {
+    println!(
+        "{} runs per case, first {} discarded as warmup\n",
+        ITERATIONS, WARMUP
+    );
+    println!(
+        "{:<24} {:>18} {:>8} {:>8} {:>8}  Status",
+        "Case", "Mean (bytes)", "Min", "Max", "Limit"
+    );
+    for r in results {
+        let mean = format!("{:.1} ± {:.1}", r.mean, r.stddev);
+        let limit = r
+            .budget
+            .map_or("-".to_string(), |b| format!("{:.0}", b.limit()));
+        let status = match (r.budget, r.excess()) {
+            (None, _) => "no budget".to_string(),
+            (Some(_), None) => "ok".to_string(),
+            (Some(_), Some(excess)) => format!("OVER by {:.1} bytes", excess),
+        };
+        println!(
+            "{:<24} {:>18} {:>8} {:>8} {:>8}  {}",
+            r.name, mean, r.min, r.max, limit, status
+        );
+    }
+}
+
+fn to_json(results: &[CaseResult]) -> String {
+    let cases: Vec<String> = results
+        .iter()
+        .map(|r| {
+            let (budget, limit) = match r.budget {
+                Some(b) => (b.budget_bytes.to_string(), format!("{:.1}", b.limit())),
+                None => ("null".to_string(), "null".to_string()),
+            };
+            // Case names are plain ASCII, so Debug quoting is valid JSON
+            format!(
+                "{{\"name\":{:?},\"mean\":{:.1},\"stddev\":{:.1},\"min\":{},\"max\":{},\
+                 \"budget\":{},\"limit\":{},\"pass\":{}}}",
+                r.name,
+                r.mean,
+                r.stddev,
+                r.min,
+                r.max,
+                budget,
+                limit,
+                r.excess().is_none()
+            )
+        })
+        .collect();
+    format!(
+        "{{\"iterations\":{},\"warmup\":{},\"cases\":[\n  {}\n]}}",
+        ITERATIONS,
+        WARMUP,
+        cases.join(",\n  ")
+    )
+}
+
+fn main() {
+    if std::env::var_os("GLIBC_TUNABLES").is_none() {
+        if let Ok(exe) = std::env::current_exe() {
+            // Only returns if the exec failed; fall through and measure anyway
+            let err = Command::new(exe)
+                .args(std::env::args_os().skip(1))
+                .env("GLIBC_TUNABLES", NO_TCACHE)
+                .exec();
+            eprintln!("Warning: could not disable the malloc cache: {}", err);
+        }
+    }
+
+    let mut json = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => {
+                eprintln!("Error: unknown argument '{}'", arg);
+                eprintln!("Usage: mem_bench [--json]");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let results = run_cases();
+    if json {
+        println!("{}", to_json(&results));
+    } else {
+        print_table(&results);
+    }
+
+    let mut failed = false;
+    for r in &results {
+        if let (Some(budget), Some(excess)) = (r.budget, r.excess()) {
+            eprintln!(
+                "Over budget: {} averaged {:.1} bytes, {:.1} over the limit of {:.1} \
+                 ({} bytes + {}%)",
+                r.name,
+                r.mean,
+                excess,
+                budget.limit(),
+                budget.budget_bytes,
+                budget.tolerance_pct
+            );
+            failed = true;
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
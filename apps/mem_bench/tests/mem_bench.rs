@@ -0,0 +1,31 @@
+//! End-to-end test of the `mem_bench` binary
+
+use std::process::Command;
+
+#[test]
+fn test_json_covers_every_case() {
+    let output = Command::new(env!("CARGO_BIN_EXE_mem_bench"))
+        .arg("--json")
+        .output()
+        .unwrap();
+    let json = String::from_utf8(output.stdout).unwrap();
+
+    let expected = include_str!("../mem_bench.expected.json");
+    for line in expected.lines().filter(|l| l.contains("\"name\"")) {
+        let name = line.split('"').nth(3).unwrap();
+        assert!(
+            json.contains(&format!("\"name\":\"{}\"", name)),
+            "{} missing",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_unknown_argument() {
+    let status = Command::new(env!("CARGO_BIN_EXE_mem_bench"))
+        .arg("--bogus")
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(2));
+}
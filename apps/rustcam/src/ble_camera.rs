@@ -0,0 +1,127 @@
+//! Camera frame over BLE
+//!
+//! Serves the most recent camera frame through the HAL GATT server:
+//!
+//! - Write `snap`: capture a new frame
+//! - Read (after `snap`): frame info - length (u32 LE), width (u16 LE),
+//!   height (u16 LE), pixel format (u8)
+//! - Write a 4-byte offset (u32 LE): select where the frame data starts
+//! - Long read (after offset): frame data starting at that offset
+//!
+//! A long read (Read Request, then Read Blob Requests) reaches at most
+//! 64 KiB, so larger frames take one offset write per 64 KiB. A QQVGA
+//! JPEG needs a single long read at offset 0.
+
+use hal::ble::{self, BleResult, GattHandler, GATT_READ_HANDLE, GATT_WRITE_HANDLE};
+use hal::camera::{self, CameraConfig, FrameBuffer, PixelFormat, Resolution};
+
+/// Smallest preset keeps the JPEG to a few KB for the slow BLE link
+const FRAME_RESOLUTION: Resolution = Resolution::Qqvga;
+
+/// What the next read of the read characteristic returns
+enum ReadMode {
+    /// Frame info header
+    Info,
+    /// Frame data from the given offset
+    Data(usize),
+}
+
+/// GATT handler serving the last captured frame
+struct FrameServer {
+    frame: Option<FrameBuffer>,
+    mode: ReadMode,
+}
+
+impl FrameServer {
+    fn capture(&mut self) {
+        match camera::camera_capture_frame() {
+            Ok(frame) => {
+                println!(
+                    "  [CAM] Captured {}x{} {}, {} bytes",
+                    frame.width, frame.height, frame.format, frame.len()
+                );
+                self.frame = Some(frame);
+            }
+            Err(e) => println!("  [CAM] Capture failed: {}", e),
+        }
+    }
+
+    fn info(&self) -> Vec<u8> {
+        let mut info = Vec::with_capacity(9);
+        match &self.frame {
+            Some(frame) => {
+                info.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+                info.extend_from_slice(&(frame.width as u16).to_le_bytes());
+                info.extend_from_slice(&(frame.height as u16).to_le_bytes());
+                info.push(frame.format as u8);
+            }
+            None => info.extend_from_slice(&[0; 9]),
+        }
+        info
+    }
+}
+
+impl GattHandler for FrameServer {
+    fn on_read(&mut self, attr_handle: u16) -> Vec<u8> {
+        if attr_handle != GATT_READ_HANDLE {
+            return Vec::new();
+        }
+
+        match self.mode {
+            ReadMode::Info => self.info(),
+            ReadMode::Data(offset) => match &self.frame {
+                // The GATT server splits this across Read Blob responses
+                Some(frame) if offset < frame.len() => frame.data[offset..].to_vec(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    fn on_write(&mut self, attr_handle: u16, data: &[u8]) {
+        if attr_handle != GATT_WRITE_HANDLE {
+            return;
+        }
+
+        if data == b"snap" {
+            self.capture();
+            self.mode = ReadMode::Info;
+        } else if let Ok(offset) = <[u8; 4]>::try_from(data) {
+            self.mode = ReadMode::Data(u32::from_le_bytes(offset) as usize);
+        }
+    }
+}
+
+/// Run the GATT server, serving camera frames to connected clients
+///
+/// Initializes the camera (QQVGA JPEG) if needed and captures an initial
+/// frame. BLE must already be initialized. Runs until `timeout_ms` expires
+/// or the client disconnects.
+pub fn ble_serve_camera_frame(name: &str, timeout_ms: u32) -> BleResult<()> {
+    // Only tear down the camera afterwards if we brought it up
+    let camera_owned = !camera::camera_is_initialized() && {
+        let config = CameraConfig::builder()
+            .format(PixelFormat::Jpeg)
+            .resolution(FRAME_RESOLUTION)
+            .build();
+        match camera::camera_initialize(config) {
+            Ok(()) => true,
+            Err(e) => {
+                println!("  [CAM] Camera init failed: {}", e);
+                false
+            }
+        }
+    };
+
+    let mut server = FrameServer {
+        frame: None,
+        mode: ReadMode::Info,
+    };
+    server.capture();
+
+    let result = ble::ble_run_gatt_server_with_handler(name, timeout_ms, &mut server);
+
+    if camera_owned {
+        let _ = camera::camera_deinitialize();
+    }
+    result
+}
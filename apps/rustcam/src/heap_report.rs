@@ -0,0 +1,297 @@
+//! Heap profiling report
+//!
+//! Measures the heap cost of common std types and of spawning a thread,
+//! and formats the results as an aligned table, JSON or CSV.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub use hal::get_heap_used;
+
+/// Measure the heap growth of a block and push it to a `Vec<Measurement>`
+///
+/// Values created inside the block are dropped after the measurement.
+#[macro_export]
+macro_rules! measure_block {
+    ($name:literal, $vec:expr, $code:block) => {
+        $crate::measure_fn!($name, $vec, $code)
+    };
+}
+
+/// Measure the heap growth of evaluating an expression and push it to a
+/// `Vec<Measurement>`; the value is dropped after the measurement
+#[macro_export]
+macro_rules! measure_fn {
+    ($name:expr, $vec:expr, $expr:expr) => {{
+        let _value = $crate::measure_fn_returning!($name, $vec, $expr);
+    }};
+}
+
+/// Like `measure_fn!`, but returns the value so it stays allocated
+#[macro_export]
+macro_rules! measure_fn_returning {
+    ($name:expr, $vec:expr, $expr:expr) => {{
+        let heap_before = $crate::heap_report::get_heap_used();
+        let value = $expr;
+        let heap_after = $crate::heap_report::get_heap_used();
+        $vec.push($crate::heap_report::Measurement {
+            name: $name,
+            heap_before,
+            heap_after,
+        });
+        value
+    }};
+}
+
+/// Measurement result for a single allocation
+pub struct Measurement {
+    pub name: &'static str,
+    pub heap_before: i32,
+    pub heap_after: i32,
+}
+
+impl Measurement {
+    pub fn allocated(&self) -> i32 {
+        self.heap_after - self.heap_before
+    }
+}
+
+/// Results of a profiling run
+pub struct HeapReport {
+    /// Heap usage before any measured allocation
+    pub baseline: i32,
+    /// Per-type allocation measurements
+    pub measurements: Vec<Measurement>,
+    /// Heap usage while all measured values were alive
+    pub heap_with_all: i32,
+    /// Heap usage after dropping all measured values
+    pub heap_after_drop: i32,
+    /// Heap growth from spawning an idle thread
+    pub thread_spawn_cost: i32,
+    /// Heap freed by joining that thread
+    pub thread_join_freed: i32,
+}
+
+/// Values allocated by `allocate_cases`, kept alive until dropped
+struct AllocatedCases {
+    _vec: Vec<i32>,
+    _string: String,
+    _boxed: Box<[u8; 256]>,
+    _hashmap_empty: HashMap<i32, i32>,
+    _hashmap: HashMap<i32, i32>,
+    _arc: Arc<[u8; 128]>,
+    _atomic: Arc<AtomicBool>,
+}
+
+/// Allocate each measured type in turn, pushing one measurement per type
+fn allocate_cases(measurements: &mut Vec<Measurement>) -> AllocatedCases {
+    let vec_data = measure_fn_returning!("Vec<i32> (100 items)", measurements, {
+        (1..=100).collect::<Vec<i32>>()
+    });
+    let string_data = measure_fn_returning!("String (20 chars)", measurements, {
+        String::from("Hello from Rust std!")
+    });
+    let box_data = measure_fn_returning!("Box<[u8; 256]>", measurements, {
+        Box::new([0u8; 256])
+    });
+    let hashmap_empty = measure_fn_returning!("HashMap (empty)", measurements, {
+        HashMap::<i32, i32>::new()
+    });
+    let hashmap_data = measure_fn_returning!("HashMap (10 i32,i32)", measurements, {
+        let mut map: HashMap<i32, i32> = HashMap::new();
+        for i in 0..10 {
+            map.insert(i, i * 10);
+        }
+        map
+    });
+    let arc_data = measure_fn_returning!("Arc<[u8; 128]>", measurements, {
+        Arc::new([0u8; 128])
+    });
+    let atomic_data = measure_fn_returning!("Arc<AtomicBool>", measurements, {
+        Arc::new(AtomicBool::new(false))
+    });
+
+    AllocatedCases {
+        _vec: vec_data,
+        _string: string_data,
+        _boxed: box_data,
+        _hashmap_empty: hashmap_empty,
+        _hashmap: hashmap_data,
+        _arc: arc_data,
+        _atomic: atomic_data,
+    }
+}
+
+impl HeapReport {
+    /// Run all allocations and capture their measurements
+    pub fn from_run() -> HeapReport {
+        let mut measurements: Vec<Measurement> = Vec::with_capacity(8);
+
+        let baseline = get_heap_used();
+        let cases = allocate_cases(&mut measurements);
+        let heap_with_all = get_heap_used();
+
+        // Drop all allocations
+        drop(cases);
+        let heap_after_drop = get_heap_used();
+
+        // Thread measurement
+        let before_thread = get_heap_used();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_clone = Arc::clone(&stop_flag);
+
+        let handle = thread::spawn(move || {
+            while !stop_clone.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        let after_spawn = get_heap_used();
+
+        stop_flag.store(true, Ordering::Relaxed);
+        let _ = handle.join();
+        let after_join = get_heap_used();
+
+        HeapReport {
+            baseline,
+            measurements,
+            heap_with_all,
+            heap_after_drop,
+            thread_spawn_cost: after_spawn - before_thread,
+            thread_join_freed: after_spawn - after_join,
+        }
+    }
+
+    /// Measure each allocation once, freeing everything before returning
+    ///
+    /// Same cases as `from_run`, without the thread measurement.
+    pub fn measure_cases() -> Vec<Measurement> {
+        let mut measurements: Vec<Measurement> = Vec::with_capacity(8);
+        drop(allocate_cases(&mut measurements));
+        measurements
+    }
+
+    /// Sum of all measured allocations
+    pub fn total_allocated(&self) -> i32 {
+        self.measurements.iter().map(Measurement::allocated).sum()
+    }
+
+    /// Human-readable report with aligned columns
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "=== Rust std Memory Profiler ===\n");
+        let _ = writeln!(out, "Baseline heap: {} bytes\n", self.baseline);
+
+        let _ = writeln!(out, "Memory usage by feature:");
+        let _ = writeln!(out, "---------------------------------------------");
+        for m in &self.measurements {
+            let _ = writeln!(
+                out,
+                "  {:24} {:+6} bytes  [heap: {} -> {}]",
+                m.name,
+                m.allocated(),
+                m.heap_before,
+                m.heap_after
+            );
+        }
+        let _ = writeln!(out, "---------------------------------------------");
+        let _ = writeln!(
+            out,
+            "Total allocated: {} bytes (heap: {} -> {})\n",
+            self.total_allocated(),
+            self.baseline,
+            self.heap_with_all
+        );
+        let _ = writeln!(
+            out,
+            "After dropping all: {:+} bytes freed (heap: {} -> {})\n",
+            self.heap_with_all - self.heap_after_drop,
+            self.heap_with_all,
+            self.heap_after_drop
+        );
+
+        let _ = writeln!(out, "Thread memory usage:");
+        let _ = writeln!(out, "---------------------------------------------");
+        let _ = writeln!(out, "  {:24} {:+6} bytes", "thread::spawn", self.thread_spawn_cost);
+        let _ = writeln!(out, "  {:24} {:+6} bytes freed", "after join", self.thread_join_freed);
+        let _ = writeln!(out, "---------------------------------------------");
+        out
+    }
+
+    /// Machine-readable report as a single JSON object
+    pub fn to_json(&self) -> String {
+        let measurements: Vec<String> = self
+            .measurements
+            .iter()
+            .map(|m| {
+                format!(
+                    "{{\"name\":{},\"heap_before\":{},\"heap_after\":{},\"allocated\":{}}}",
+                    json_string(m.name),
+                    m.heap_before,
+                    m.heap_after,
+                    m.allocated()
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"baseline\":{},\"measurements\":[{}],\"heap_with_all\":{},\
+             \"heap_after_drop\":{},\"thread_spawn_cost\":{},\"thread_join_freed\":{}}}",
+            self.baseline,
+            measurements.join(","),
+            self.heap_with_all,
+            self.heap_after_drop,
+            self.thread_spawn_cost,
+            self.thread_join_freed
+        )
+    }
+
+    /// Per-allocation measurements as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("name,heap_before,heap_after,allocated\n");
+        for m in &self.measurements {
+            let _ = writeln!(
+                out,
+                "{},{},{},{}",
+                csv_field(m.name),
+                m.heap_before,
+                m.heap_after,
+                m.allocated()
+            );
+        }
+        out
+    }
+}
+
+/// Quote and escape a string for JSON
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Quote a CSV field if it contains a separator or quote
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
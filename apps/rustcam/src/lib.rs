@@ -8,209 +8,95 @@
 //! - Implementations in hal/<module>/<platform>.rs
 //! - Selection via Cargo features (platform-linux, platform-nuttx)
 
-use std::collections::HashMap;
-use std::io::{self, BufRead, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
+mod ble_camera;
+pub mod heap_report;
+pub mod pool;
+pub mod run_config;
+pub mod thread_watchdog;
+pub mod threads;
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
 use std::sync::Arc;
-use std::thread::{self, JoinHandle};
-use std::time::{Duration, Instant};
+use std::thread;
+use std::time::Duration;
 
 // Hardware Abstraction Layer (shared crate)
-use hal::{get_heap_stats, get_heap_used};
+use hal::{get_heap_peak, get_heap_stats, get_heap_used, HeapFragmentationMonitor, HeapTimeSeries};
 use hal::ble;
 use hal::wifi;
 use hal::camera;
+use hal::temp;
 
-// ============================================================================
-// Common types
-// ============================================================================
-
-/// Measurement result for a single allocation
-struct Measurement {
-    name: &'static str,
-    heap_before: i32,
-    heap_after: i32,
-}
-
-impl Measurement {
-    fn allocated(&self) -> i32 {
-        self.heap_after - self.heap_before
-    }
-}
-
-/// Thread instance with stop flag and join handle
-struct ThreadInstance {
-    id: u32,
-    stop_flag: Arc<AtomicBool>,
-    handle: Option<JoinHandle<()>>,
-}
+use heap_report::HeapReport;
+use pool::ThreadPool;
+pub use run_config::RunConfig;
+use thread_watchdog::ThreadWatchdog;
+use threads::{ThreadInstance, ThreadMemoryReport};
 
 // ============================================================================
 // Main application logic
 // ============================================================================
 
-/// Run the demo - portable entry point
-pub fn run() -> i32 {
-    let mut measurements: Vec<Measurement> = Vec::with_capacity(8);
-
-    let baseline = get_heap_used();
-
-    // Vec
-    let heap_before = get_heap_used();
-    let vec_data: Vec<i32> = (1..=100).collect();
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "Vec<i32> (100 items)",
-        heap_before,
-        heap_after,
-    });
-
-    // String
-    let heap_before = get_heap_used();
-    let string_data = String::from("Hello from Rust std!");
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "String (20 chars)",
-        heap_before,
-        heap_after,
-    });
-
-    // Box
-    let heap_before = get_heap_used();
-    let box_data: Box<[u8; 256]> = Box::new([0u8; 256]);
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "Box<[u8; 256]>",
-        heap_before,
-        heap_after,
-    });
-
-    // HashMap empty
-    let heap_before = get_heap_used();
-    let hashmap_empty: HashMap<i32, i32> = HashMap::new();
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "HashMap (empty)",
-        heap_before,
-        heap_after,
-    });
-
-    // HashMap with entries
-    let heap_before = get_heap_used();
-    let mut hashmap_data: HashMap<i32, i32> = HashMap::new();
-    for i in 0..10 {
-        hashmap_data.insert(i, i * 10);
-    }
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "HashMap (10 i32,i32)",
-        heap_before,
-        heap_after,
-    });
-
-    // Arc
-    let heap_before = get_heap_used();
-    let arc_data: Arc<[u8; 128]> = Arc::new([0u8; 128]);
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "Arc<[u8; 128]>",
-        heap_before,
-        heap_after,
-    });
-
-    // Arc<AtomicBool>
-    let heap_before = get_heap_used();
-    let atomic_data = Arc::new(AtomicBool::new(false));
-    let heap_after = get_heap_used();
-    measurements.push(Measurement {
-        name: "Arc<AtomicBool>",
-        heap_before,
-        heap_after,
-    });
-
-    let total_with_all = get_heap_used();
-
-    // Drop all allocations
-    drop(vec_data);
-    drop(string_data);
-    drop(box_data);
-    drop(hashmap_empty);
-    drop(hashmap_data);
-    drop(arc_data);
-    drop(atomic_data);
-    let after_drop = get_heap_used();
-
-    // Thread measurement
-    let before_thread = get_heap_used();
-    let stop_flag = Arc::new(AtomicBool::new(false));
-    let stop_clone = Arc::clone(&stop_flag);
-
-    let handle = thread::spawn(move || {
-        while !stop_clone.load(Ordering::Relaxed) {
-            thread::sleep(Duration::from_millis(100));
-        }
-    });
-
-    thread::sleep(Duration::from_millis(50));
-    let after_spawn = get_heap_used();
-
-    stop_flag.store(true, Ordering::Relaxed);
-    let _ = handle.join();
-    let after_join = get_heap_used();
-
-    // Print results
-    println!("=== Rust std Memory Profiler ===\n");
-    println!("Baseline heap: {} bytes\n", baseline);
-
-    println!("Memory usage by feature:");
-    println!("---------------------------------------------");
+/// Longest a demo thread may go without a tick before the watchdog reports it
+const THREAD_WATCHDOG_TIMEOUT_MS: u32 = 3000;
 
-    let mut total_allocated = 0i32;
-    for m in &measurements {
-        let alloc = m.allocated();
-        total_allocated += alloc;
-        println!(
-            "  {:22} {:+6} bytes  [heap: {} -> {}]",
-            m.name, alloc, m.heap_before, m.heap_after
-        );
+/// Run the demo - portable entry point
+pub fn run(config: &RunConfig) -> i32 {
+    if config.verbose {
+        println!("{:?}", config);
     }
 
-    println!("---------------------------------------------");
-    println!(
-        "Total allocated: {} bytes (heap: {} -> {})\n",
-        total_allocated, baseline, total_with_all
-    );
-    println!(
-        "After dropping all: {:+} bytes freed (heap: {} -> {})\n",
-        total_with_all - after_drop,
-        total_with_all,
-        after_drop
-    );
+    // Single test modes skip the heap report
+    if config.camera_only {
+        return camera_test();
+    }
+    if config.wifi_only {
+        return wifi_test();
+    }
+    if config.ble_only {
+        return ble_scan_test();
+    }
 
-    println!("Thread memory usage:");
-    println!("---------------------------------------------");
-    println!(
-        "  thread::spawn:         {:+6} bytes  [heap: {} -> {}]",
-        after_spawn - before_thread,
-        before_thread,
-        after_spawn
-    );
-    println!(
-        "  after join:            {:+6} bytes freed  [heap: {} -> {}]",
-        after_spawn - after_join,
-        after_spawn,
-        after_join
-    );
-    println!("---------------------------------------------\n");
+    let report = HeapReport::from_run();
+    println!("{}", report.to_table());
+    if config.no_interactive {
+        return 0;
+    }
 
     // Interactive demo
     println!("=== Interactive Demo ===");
-    println!("Commands: s=spawn, t=terminate, m=memory, b=ble scan, a=advertise, g=gatt server, w=wifi, c=camera, q=quit\n");
+    println!("Commands: s [name]=spawn, t=terminate, l=list threads, m=memory, b=ble scan, a=advertise, g=gatt server, f=ble camera frame, w=wifi, wp [none|light|max]=wifi power save, c=camera, p=pool capture, q=quit\n");
 
     let mut threads: Vec<ThreadInstance> = Vec::new();
+    let mut joined_threads: Vec<ThreadInstance> = Vec::new();
     let mut next_id: u32 = 1;
 
-    let stdin = io::stdin();
+    // Demo threads pet the watchdog on every tick; report any that stop
+    let watchdog = Arc::new(ThreadWatchdog::new(
+        500,
+        Box::new(|id| println!("[Watchdog] Thread {} stopped responding", id)),
+    ));
+
+    // Heap usage history, sampled once per command
+    let mut heap_series = HeapTimeSeries::new(64);
+
+    // Large allocations (camera frames) start failing well before the heap is full
+    let fragmentation = HeapFragmentationMonitor::new(
+        0.8,
+        Box::new(|ratio| println!("  Warning: heap is {:.0}% fragmented", ratio * 100.0)),
+    );
+
+    let mut commands: Box<dyn BufRead> = match &config.script_path {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                println!("Failed to open script '{}': {}", path, e);
+                return 1;
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
     let mut stdout = io::stdout();
 
     loop {
@@ -218,128 +104,144 @@ pub fn run() -> i32 {
         let _ = stdout.flush();
 
         let mut input = String::new();
-        if stdin.lock().read_line(&mut input).is_err() {
-            break;
+        match commands.read_line(&mut input) {
+            // End of input quits as if 'q' was entered
+            Ok(0) => input.push('q'),
+            Ok(_) => {}
+            Err(_) => break,
         }
+        if config.script_path.is_some() {
+            println!("{}", input.trim());
+        }
+        heap_series.record();
+
+        // Commands may take a single argument, e.g. "s worker-1"
+        let input = input.trim();
+        let (command, arg) = match input.split_once(' ') {
+            Some((command, arg)) => (command, arg.trim()),
+            None => (input, ""),
+        };
 
-        match input.trim() {
+        match command {
             "s" => {
-                let heap_before = get_heap_used();
                 let id = next_id;
                 next_id += 1;
-
-                let stop_flag = Arc::new(AtomicBool::new(false));
-                let stop_flag_clone = Arc::clone(&stop_flag);
-                let thread_start = Instant::now();
-
-                let handle = thread::spawn(move || {
-                    let mut tick: u64 = 0;
-                    while !stop_flag_clone.load(Ordering::Relaxed) {
-                        thread::sleep(Duration::from_secs(1));
-                        tick += 1;
-                        if stop_flag_clone.load(Ordering::Relaxed) {
-                            break;
-                        }
-                        let elapsed = thread_start.elapsed();
-                        println!(
-                            "[Thread {}] Tick {}: {}.{:03}s",
-                            id,
-                            tick,
-                            elapsed.as_secs(),
-                            elapsed.subsec_millis()
-                        );
+                let name = if arg.is_empty() {
+                    format!("rustcam-thread-{}", id)
+                } else {
+                    arg.to_string()
+                };
+
+                watchdog.register(id, THREAD_WATCHDOG_TIMEOUT_MS);
+                let thread_watchdog = Arc::clone(&watchdog);
+                let spawned = ThreadInstance::spawn(id, name.clone(), move || thread_watchdog.pet(id));
+                let instance = match spawned {
+                    Ok(instance) => instance,
+                    Err(e) => {
+                        println!("Failed to spawn thread '{}': {}", name, e);
+                        watchdog.unregister(id);
+                        continue;
                     }
-                });
-
-                thread::sleep(Duration::from_millis(50));
-                let heap_after = get_heap_used();
-
-                threads.push(ThreadInstance {
-                    id,
-                    stop_flag,
-                    handle: Some(handle),
-                });
+                };
 
                 println!(
-                    "Spawned thread {} (+{} bytes, total threads: {})",
+                    "Spawned thread {} '{}' (+{} bytes, total threads: {})",
                     id,
-                    heap_after - heap_before,
-                    threads.len()
+                    name,
+                    instance.spawn_cost(),
+                    threads.len() + 1
                 );
+                threads.push(instance);
             }
 
             "t" => {
                 if let Some(mut instance) = threads.pop() {
+                    watchdog.unregister(instance.id);
                     let heap_before = get_heap_used();
-                    instance.stop_flag.store(true, Ordering::Relaxed);
-                    if let Some(handle) = instance.handle.take() {
-                        let _ = handle.join();
-                    }
-                    let heap_after = get_heap_used();
+                    instance.join();
                     println!(
-                        "Terminated thread {} (+{} bytes freed, remaining: {})",
+                        "Terminated thread {} '{}' (+{} bytes freed, remaining: {})",
                         instance.id,
-                        heap_before - heap_after,
+                        instance.name,
+                        heap_before - instance.heap_at_join,
                         threads.len()
                     );
+                    joined_threads.push(instance);
                 } else {
                     println!("No threads to stop");
                 }
             }
 
+            "l" => {
+                if threads.is_empty() {
+                    println!("No active threads");
+                } else {
+                    println!("Active threads (watchdog checks every {} ms):", watchdog.period_ms());
+                }
+                for instance in &threads {
+                    let uptime = instance.started.elapsed();
+                    println!(
+                        "  {:3}  {:24} up {}.{:03}s",
+                        instance.id,
+                        instance.name,
+                        uptime.as_secs(),
+                        uptime.subsec_millis()
+                    );
+                }
+            }
+
             "m" => {
                 if let Some(info) = get_heap_stats() {
                     println!("Heap stats:");
                     println!("  Arena (total):  {} bytes", info.arena);
                     println!("  Used:           {} bytes", info.uordblks);
+                    println!("  Peak heap:      {} bytes", get_heap_peak());
                     println!("  Free:           {} bytes", info.fordblks);
                     println!("  Free chunks:    {}", info.ordblks);
                     println!("  Largest free:   {} bytes", info.mxordblk);
+                    println!("  Fragmentation:  {:.0}%", info.fragmentation_ratio() * 100.0);
                     println!("  Active threads: {}", threads.len());
+                    fragmentation.check();
                 } else {
                     println!("Heap stats not available on this platform");
                     println!("  Active threads: {}", threads.len());
                 }
-            }
 
-            "b" => {
-                println!("Initializing BLE...");
-                match ble::ble_initialize() {
-                    Ok(()) => println!("  BLE initialized"),
-                    Err(ble::BleError::AlreadyInitialized) => println!("  BLE already initialized"),
-                    Err(e) => {
-                        println!("  BLE init failed: {}", e);
-                        println!("  (Try running with sudo for raw socket access)");
-                        continue;
+                match temp::temp_read_cpu() {
+                    Ok(cpu) => {
+                        let throttling = cpu > temp::THROTTLE_TEMP_C;
+                        let note = if throttling { " (throttling)" } else { "" };
+                        println!("  CPU temp:       {:.1} C{}", cpu, note);
                     }
+                    Err(e) => println!("  CPU temp:       unavailable ({})", e),
+                }
+                if let Ok(ambient) = temp::temp_read_ambient() {
+                    println!("  Ambient temp:   {:.1} C", ambient);
                 }
 
-                println!("Scanning for BLE devices (3 seconds)...");
-                match ble::ble_start_scan(3000) {
-                    Ok(()) => {
-                        match ble::ble_get_scan_results() {
-                            Ok(results) => {
-                                if results.is_empty() {
-                                    println!("  No devices found");
-                                } else {
-                                    println!("  Found {} device(s):", results.len());
-                                    for result in &results {
-                                        let name = result.name_str().unwrap_or("<unknown>");
-                                        println!(
-                                            "    {} ({:?}) RSSI: {} dBm  Name: {}",
-                                            result.address, result.address_type, result.rssi, name
-                                        );
-                                    }
-                                }
-                            }
-                            Err(e) => println!("  Failed to get results: {}", e),
-                        }
-                    }
-                    Err(e) => println!("  Scan failed: {}", e),
+                for instance in &threads {
+                    println!("    Thread {}: +{} bytes at spawn", instance.id, instance.spawn_cost());
+                }
+                let thread_report = ThreadMemoryReport::new(&threads, &joined_threads);
+                if thread_report.active + thread_report.joined > 0 {
+                    println!(
+                        "  Per thread (avg): +{} bytes at spawn, {} bytes freed on join ({} joined)",
+                        thread_report.average_spawn_cost(),
+                        thread_report.average_freed(),
+                        thread_report.joined
+                    );
                 }
 
-                let _ = ble::ble_deinitialize();
-                println!("  BLE deinitialized");
+                if heap_series.samples().len() >= 5 {
+                    println!("\nHeap usage history (one sample per command):");
+                    print!("{}", heap_series.plot_ascii(40, 8));
+                    let growth = heap_series.growth_rate_bytes_per_sec();
+                    println!("Growth rate: {:+.1} bytes/s", growth);
+                }
+            }
+
+            "b" => {
+                ble_scan_test();
             }
 
             "a" => {
@@ -361,7 +263,7 @@ pub fn run() -> i32 {
                         println!("  Press Enter to stop advertising...");
                         let _ = stdout.flush();
                         let mut dummy = String::new();
-                        let _ = stdin.lock().read_line(&mut dummy);
+                        let _ = commands.read_line(&mut dummy);
                         let _ = ble::ble_stop_advertising();
                         println!("  Advertising stopped");
                     }
@@ -386,11 +288,36 @@ pub fn run() -> i32 {
                 println!("  Running GATT server as 'RustCam' (60 seconds timeout)");
                 println!("  Connect from your phone using nRF Connect!");
                 println!("  Service UUID: 0x1234");
-                println!("  - Read characteristic (handle 3): Returns 'Hello from RustCam!'");
-                println!("  - Write characteristic (handle 5): Send commands");
+                println!("  - Read characteristic 0x1235: Returns 'Hello from RustCam!'");
+                println!("  - Write characteristic 0x1236: Send commands");
                 println!();
 
-                match ble::ble_run_gatt_server("RustCam", 60000) {
+                let service = ble::GattService {
+                    uuid: 0x1234,
+                    characteristics: vec![
+                        ble::GattCharacteristic {
+                            uuid: 0x1235,
+                            properties: ble::CHR_PROP_READ,
+                            initial_value: b"Hello from RustCam!".to_vec(),
+                            writable: false,
+                            notify: false,
+                        },
+                        ble::GattCharacteristic {
+                            uuid: 0x1236,
+                            properties: ble::CHR_PROP_READ,
+                            initial_value: Vec::new(),
+                            writable: true,
+                            notify: false,
+                        },
+                    ],
+                };
+                let config = ble::GattServerConfig::builder()
+                    .name("RustCam")
+                    .timeout_ms(60000)
+                    .add_service(service)
+                    .build();
+
+                match ble::ble_run_gatt_server(&config) {
                     Ok(()) => println!("  GATT server finished"),
                     Err(e) => println!("  GATT server error: {}", e),
                 }
@@ -399,139 +326,49 @@ pub fn run() -> i32 {
                 println!("  BLE deinitialized");
             }
 
-            "w" => {
-                println!("WiFi Test");
-                println!("=========");
-
-                // Initialize WiFi
-                println!("Initializing WiFi...");
-                match wifi::wifi_initialize() {
-                    Ok(()) => println!("  WiFi initialized"),
-                    Err(e) => {
-                        println!("  WiFi init failed: {:?}", e);
-                        continue;
-                    }
-                }
-
-                // Scan first to find the network
-                println!("Scanning for networks...");
-                match wifi::wifi_start_scan() {
-                    Ok(()) => println!("  Scan started"),
+            "f" => {
+                println!("Serving camera frames over BLE...");
+                match ble::ble_initialize() {
+                    Ok(()) => println!("  BLE initialized"),
+                    Err(ble::BleError::AlreadyInitialized) => println!("  BLE already initialized"),
                     Err(e) => {
-                        println!("  Scan failed: {:?}", e);
+                        println!("  BLE init failed: {}", e);
                         continue;
                     }
                 }
 
-                // Wait for scan to complete
-                for i in 0..20 {
-                    thread::sleep(Duration::from_millis(300));
-                    match wifi::wifi_scan_is_complete() {
-                        Ok(true) => {
-                            println!("  Scan complete after {}ms", (i + 1) * 300);
-                            break;
-                        }
-                        Ok(false) => {
-                            if i == 19 {
-                                println!("  Timeout waiting for scan");
-                            }
-                        }
-                        Err(e) => {
-                            println!("  Scan error: {:?}", e);
-                            break;
-                        }
-                    }
-                }
+                println!("  Running GATT server as 'RustCam' (60 seconds timeout)");
+                println!("  - Write 'snap' to handle 5, read handle 3 for frame info");
+                println!("  - Write a u32 LE offset to handle 5, read handle 3 for frame data");
+                println!();
 
-                // Get scan results
-                match wifi::wifi_get_scan_results() {
-                    Ok((results, count)) => {
-                        println!("  Found {} networks:", count);
-                        for i in 0..count {
-                            let r = &results[i];
-                            let ssid = r.ssid_str().unwrap_or("<hidden>");
-                            println!(
-                                "    {:2}. {:32} ch{:2} {:3}dBm",
-                                i + 1, ssid, r.channel, r.rssi
-                            );
-                        }
-                    }
-                    Err(e) => println!("  Failed to get results: {:?}", e),
+                match ble_camera::ble_serve_camera_frame("RustCam", 60000) {
+                    Ok(()) => println!("  GATT server finished"),
+                    Err(e) => println!("  GATT server error: {}", e),
                 }
 
-                // Connect to eduheim
-                println!("\nConnecting to 'eduheim' with WPA2...");
-                let config = wifi::StationConfig::new("eduheim", "10220727");
-                match wifi::wifi_connect(&config) {
-                    Ok(()) => println!("  Connection initiated"),
-                    Err(e) => {
-                        println!("  Connection failed: {:?}", e);
-                        continue;
-                    }
-                }
+                let _ = ble::ble_deinitialize();
+                println!("  BLE deinitialized");
+            }
 
-                // Wait for connection
-                println!("Waiting for connection...");
-                for i in 0..30 {
-                    thread::sleep(Duration::from_millis(500));
-                    match wifi::wifi_get_connection_status() {
-                        Ok(wifi::ConnectionStatus::Connected) => {
-                            println!("  Connected after {}ms!", (i + 1) * 500);
-
-                            // Get IP info
-                            match wifi::wifi_get_ip_info() {
-                                Ok(ip) => {
-                                    println!("  IP: {}.{}.{}.{}", ip.ip[0], ip.ip[1], ip.ip[2], ip.ip[3]);
-                                    println!("  Netmask: {}.{}.{}.{}", ip.netmask[0], ip.netmask[1], ip.netmask[2], ip.netmask[3]);
-                                }
-                                Err(_) => println!("  (IP info not available yet)"),
-                            }
-                            break;
-                        }
-                        Ok(wifi::ConnectionStatus::Connecting) => {
-                            if i % 4 == 0 {
-                                println!("  Still connecting...");
-                            }
-                        }
-                        Ok(wifi::ConnectionStatus::Disconnected) => {
-                            if i >= 10 {
-                                println!("  Connection not established after 5s");
-                                // Check ESSID to see if it was set
-                                if let Ok((essid, len)) = wifi::wifi_get_essid() {
-                                    if len > 0 {
-                                        if let Ok(s) = core::str::from_utf8(&essid[..len]) {
-                                            println!("  ESSID set to: {}", s);
-                                        }
-                                    }
-                                }
-                                break;
-                            }
-                        }
-                        Ok(wifi::ConnectionStatus::Failed) => {
-                            println!("  Connection failed");
-                            break;
-                        }
-                        Err(e) => {
-                            println!("  Status error: {:?}", e);
-                            break;
-                        }
-                    }
-                }
+            "w" => {
+                wifi_test();
+            }
 
-                println!("WiFi test done\n");
+            "wp" => {
+                wifi_power_save(arg);
             }
 
             "c" => {
-                println!("Camera Test");
-                println!("===========");
-
-                // Initialize camera with VGA JPEG
-                println!("Initializing camera (VGA JPEG)...");
-                let config = camera::CameraConfig::new(
-                    camera::PixelFormat::Jpeg,
-                    camera::Resolution::Vga,
-                );
+                camera_test();
+            }
 
+            "p" => {
+                println!("Thread pool capture (4 workers, 8 frames)");
+                let config = camera::CameraConfig::builder()
+                    .format(camera::PixelFormat::Jpeg)
+                    .resolution(camera::Resolution::Qvga)
+                    .build();
                 match camera::camera_initialize(config) {
                     Ok(()) => println!("  Camera initialized"),
                     Err(e) => {
@@ -540,57 +377,38 @@ pub fn run() -> i32 {
                     }
                 }
 
-                // Capture a few frames
-                println!("Capturing 3 frames...");
-                for i in 1..=3 {
-                    match camera::camera_capture_frame() {
-                        Ok(frame) => {
-                            println!(
-                                "  Frame {}: {}x{} {:?}, {} bytes",
-                                i, frame.width, frame.height, frame.format, frame.len()
-                            );
-                        }
-                        Err(e) => {
-                            println!("  Frame {} capture failed: {}", i, e);
+                let pool = ThreadPool::new(4);
+                for i in 1..=8 {
+                    pool.submit(move || {
+                        let worker = thread::current();
+                        let worker = worker.name().unwrap_or("<unnamed>");
+                        match camera::camera_capture_frame() {
+                            Ok(frame) => println!("  [{}] Frame {}: {} bytes", worker, i, frame.len()),
+                            Err(e) => println!("  [{}] Frame {} capture failed: {}", worker, i, e),
                         }
-                    }
-                    thread::sleep(Duration::from_millis(100));
-                }
-
-                // Get settings
-                println!("Camera settings:");
-                match camera::camera_get_settings() {
-                    Ok(settings) => {
-                        println!("  Brightness: {}", settings.brightness);
-                        println!("  Contrast: {}", settings.contrast);
-                        println!("  Saturation: {}", settings.saturation);
-                    }
-                    Err(e) => println!("  Failed to get settings: {}", e),
+                    });
                 }
+                println!("  Submitted 8 captures (queue depth: {})", pool.queue_depth());
+                pool.drain_and_shutdown();
 
-                // Cleanup
-                match camera::camera_deinitialize() {
-                    Ok(()) => println!("  Camera deinitialized"),
-                    Err(e) => println!("  Deinit failed: {}", e),
+                if let Err(e) = camera::camera_deinitialize() {
+                    println!("  Deinit failed: {}", e);
                 }
-
-                println!("Camera test done\n");
+                println!("Thread pool done\n");
             }
 
             "q" => {
                 for instance in &threads {
-                    instance.stop_flag.store(true, Ordering::Relaxed);
+                    instance.stop();
                 }
                 for mut instance in threads {
-                    if let Some(handle) = instance.handle.take() {
-                        let _ = handle.join();
-                    }
+                    instance.join();
                 }
                 break;
             }
 
             "" => {}
-            _ => println!("Unknown command. Use 's', 't', 'm', 'b', 'a', 'g', 'w', 'c', or 'q'"),
+            _ => println!("Unknown command. Use 's', 't', 'l', 'm', 'b', 'a', 'g', 'f', 'w', 'wp', 'c', 'p', or 'q'"),
         }
     }
 
@@ -598,6 +416,220 @@ pub fn run() -> i32 {
     0
 }
 
+/// Scan for BLE devices for 3 seconds and list them
+fn ble_scan_test() -> i32 {
+    println!("Initializing BLE...");
+    match ble::ble_initialize() {
+        Ok(()) => println!("  BLE initialized"),
+        Err(ble::BleError::AlreadyInitialized) => println!("  BLE already initialized"),
+        Err(e) => {
+            println!("  BLE init failed: {}", e);
+            println!("  (Try running with sudo for raw socket access)");
+            return 1;
+        }
+    }
+
+    println!("Scanning for BLE devices (3 seconds)...");
+    match ble::ble_start_scan(3000) {
+        Ok(()) => {
+            match ble::ble_get_scan_results() {
+                Ok(results) => {
+                    if results.is_empty() {
+                        println!("  No devices found");
+                    } else {
+                        println!("  Found {} device(s):", results.len());
+                        for result in &results {
+                            let name = result.name_str().unwrap_or("<unknown>");
+                            println!(
+                                "    {} ({:?}) RSSI: {} dBm  Name: {}",
+                                result.address, result.address_type, result.rssi, name
+                            );
+                        }
+                    }
+                }
+                Err(e) => println!("  Failed to get results: {}", e),
+            }
+        }
+        Err(e) => println!("  Scan failed: {}", e),
+    }
+
+    let _ = ble::ble_deinitialize();
+    println!("  BLE deinitialized");
+    0
+}
+
+/// Scan for WiFi networks, then connect with the configured credentials
+/// Show the WiFi power saving mode, or set it to `arg` (none, light or max)
+fn wifi_power_save(arg: &str) {
+    let mode = match arg {
+        "" => None,
+        "none" => Some(wifi::PowerSaveMode::None),
+        "light" => Some(wifi::PowerSaveMode::Light),
+        "max" => Some(wifi::PowerSaveMode::Maximum),
+        _ => {
+            println!("Usage: wp [none|light|max]");
+            return;
+        }
+    };
+
+    if let Err(e) = wifi::wifi_initialize() {
+        println!("  WiFi init failed: {}", e);
+        return;
+    }
+    if let Some(mode) = mode {
+        match wifi::wifi_set_power_save(mode) {
+            Ok(()) => println!("  Power save set to {:?}", mode),
+            Err(e) => println!("  Failed to set power save: {}", e),
+        }
+    }
+    match wifi::wifi_get_power_save() {
+        Ok(mode) => println!("  Power save: {:?}", mode),
+        Err(e) => println!("  Failed to get power save: {}", e),
+    }
+}
+
+fn wifi_test() -> i32 {
+    println!("WiFi Test");
+    println!("=========");
+
+    // Initialize WiFi
+    println!("Initializing WiFi...");
+    match wifi::wifi_initialize() {
+        Ok(()) => println!("  WiFi initialized"),
+        Err(e) => {
+            println!("  WiFi init failed: {:?}", e);
+            return 1;
+        }
+    }
+
+    // Scan first to find the network
+    println!("Scanning for networks...");
+    match wifi::wifi_start_scan() {
+        Ok(()) => println!("  Scan started"),
+        Err(e) => {
+            println!("  Scan failed: {:?}", e);
+            return 1;
+        }
+    }
+
+    // Wait for scan to complete
+    for i in 0..20 {
+        thread::sleep(Duration::from_millis(300));
+        match wifi::wifi_scan_is_complete() {
+            Ok(true) => {
+                println!("  Scan complete after {}ms", (i + 1) * 300);
+                break;
+            }
+            Ok(false) => {
+                if i == 19 {
+                    println!("  Timeout waiting for scan");
+                }
+            }
+            Err(e) => {
+                println!("  Scan error: {:?}", e);
+                break;
+            }
+        }
+    }
+
+    // Get scan results
+    match wifi::wifi_get_scan_results() {
+        Ok((results, count)) => {
+            println!("  Found {} networks:", count);
+            for i in 0..count {
+                let r = &results[i];
+                let ssid = r.ssid_str().unwrap_or("<hidden>");
+                println!(
+                    "    {:2}. {:32} ch{:2} {:3}dBm",
+                    i + 1, ssid, r.channel, r.rssi
+                );
+            }
+        }
+        Err(e) => println!("  Failed to get results: {:?}", e),
+    }
+
+    // Connect using credentials from the environment if set
+    let config = wifi::StationConfig::from_env_default()
+        .unwrap_or_else(|| wifi::StationConfig::new("eduheim", "10220727"));
+    let ssid = config.ssid_str().unwrap_or("");
+    let pass = core::str::from_utf8(&config.password[..config.password_len]).unwrap_or("");
+    println!("\nConnecting to '{}' with WPA2 (15s timeout)...", ssid);
+    match wifi::wifi_connect_simple(ssid, pass, 15_000) {
+        Ok(ip) => {
+            println!("  Connected!");
+            println!("  IP: {}.{}.{}.{}", ip.ip[0], ip.ip[1], ip.ip[2], ip.ip[3]);
+            println!("  Netmask: {}.{}.{}.{}", ip.netmask[0], ip.netmask[1], ip.netmask[2], ip.netmask[3]);
+        }
+        Err(e) => println!("  Connection failed: {}", e),
+    }
+
+    println!("WiFi test done\n");
+    0
+}
+
+/// Capture a few VGA JPEG frames and print the camera settings
+fn camera_test() -> i32 {
+    println!("Camera Test");
+    println!("===========");
+
+    // Initialize camera with VGA JPEG
+    println!("Initializing camera (VGA JPEG)...");
+    let config = camera::CameraConfig::builder()
+        .format(camera::PixelFormat::Jpeg)
+        .resolution(camera::Resolution::Vga)
+        .build();
+
+    match camera::camera_initialize(config) {
+        Ok(()) => println!("  Camera initialized"),
+        Err(e) => {
+            println!("  Camera init failed: {}", e);
+            return 1;
+        }
+    }
+
+    // Capture a few frames
+    println!("Capturing 3 frames...");
+    for i in 1..=3 {
+        match camera::camera_capture_frame() {
+            Ok(frame) => {
+                println!(
+                    "  Frame {}: {}x{} {:?}, {} bytes",
+                    i, frame.width, frame.height, frame.format, frame.len()
+                );
+            }
+            Err(e) => {
+                println!("  Frame {} capture failed: {}", i, e);
+            }
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    match camera::camera_get_stats() {
+        Ok(stats) => println!("  Stats: {}", stats),
+        Err(e) => println!("  Stats unavailable: {}", e),
+    }
+
+    // Get settings
+    println!("Camera settings:");
+    match camera::camera_get_settings() {
+        Ok(settings) => {
+            println!("  Brightness: {}", settings.brightness);
+            println!("  Contrast: {}", settings.contrast);
+            println!("  Saturation: {}", settings.saturation);
+        }
+        Err(e) => println!("  Failed to get settings: {}", e),
+    }
+
+    // Cleanup
+    match camera::camera_deinitialize() {
+        Ok(()) => println!("  Camera deinitialized"),
+        Err(e) => println!("  Deinit failed: {}", e),
+    }
+
+    println!("Camera test done\n");
+    0
+}
+
 // ============================================================================
 // Platform-specific entry points
 // ============================================================================
@@ -611,11 +643,20 @@ extern "C" {
 /// NuttX entry point (called from C wrapper)
 #[cfg(feature = "platform-nuttx")]
 #[no_mangle]
-pub extern "C" fn rust_rustcam_main(_argc: i32, _argv: *const *const u8) -> i32 {
+pub extern "C" fn rust_rustcam_main(argc: i32, argv: *const *const u8) -> i32 {
     unsafe {
         rust_debug_print(b"rust_rustcam_main entered\0".as_ptr());
     }
 
+    // The C wrapper passes the task's argc/argv straight through
+    let config = unsafe { RunConfig::from_args(argc, argv) };
+    if config.camera_only {
+        return camera_test_nuttx();
+    }
+    if config.wifi_only {
+        return wifi_test_nuttx();
+    }
+
     // Run camera test
     let cam_result = camera_test_nuttx();
 
@@ -698,9 +739,10 @@ fn wifi_test_nuttx() -> i32 {
         }
     }
 
-    // Connect to eduheim
-    unsafe { rust_debug_print(b"\nConnecting to eduheim...\0".as_ptr()); }
-    let config = wifi::StationConfig::new("eduheim", "10220727");
+    // Connect using credentials from the environment if set
+    unsafe { rust_debug_print(b"\nConnecting...\0".as_ptr()); }
+    let config = wifi::StationConfig::from_env_default()
+        .unwrap_or_else(|| wifi::StationConfig::new("eduheim", "10220727"));
     match wifi::wifi_connect(&config) {
         Ok(()) => unsafe { rust_debug_print(b"  Connection initiated\0".as_ptr()); },
         Err(_) => {
@@ -762,10 +804,10 @@ fn camera_test_nuttx() -> i32 {
 
     // Initialize camera with VGA JPEG
     unsafe { rust_debug_print(b"Initializing camera (VGA JPEG)...\0".as_ptr()); }
-    let config = camera::CameraConfig::new(
-        camera::PixelFormat::Jpeg,
-        camera::Resolution::Vga,
-    );
+    let config = camera::CameraConfig::builder()
+        .format(camera::PixelFormat::Jpeg)
+        .resolution(camera::Resolution::Vga)
+        .build();
 
     match camera::camera_initialize(config) {
         Ok(()) => unsafe { rust_debug_print(b"  Camera initialized OK\0".as_ptr()); },
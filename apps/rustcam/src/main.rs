@@ -4,5 +4,6 @@
 //! For NuttX: entry point is rustcam_main() in lib.rs (built as staticlib)
 
 fn main() {
-    std::process::exit(rustcam::run());
+    let config = rustcam::RunConfig::from_env_args();
+    std::process::exit(rustcam::run(&config));
 }
@@ -0,0 +1,108 @@
+//! Fixed-size thread pool
+//!
+//! Bounds the number of threads used for background work such as camera
+//! captures. Jobs are queued in FIFO order and picked up by idle workers.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// State shared between the pool handle and its workers
+struct Shared {
+    queue: Mutex<VecDeque<Job>>,
+    /// Signalled when a job is queued or the pool is stopping
+    available: Condvar,
+    stop: AtomicBool,
+}
+
+/// Pool of worker threads consuming a shared job queue
+pub struct ThreadPool {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Spawn `size` worker threads (at least one)
+    pub fn new(size: usize) -> ThreadPool {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(VecDeque::new()),
+            available: Condvar::new(),
+            stop: AtomicBool::new(false),
+        });
+
+        let workers = (0..size.max(1))
+            .filter_map(|i| {
+                let shared = Arc::clone(&shared);
+                thread::Builder::new()
+                    .name(format!("rustcam-pool-{}", i))
+                    .spawn(move || worker_loop(&shared))
+                    .ok()
+            })
+            .collect();
+
+        ThreadPool { shared, workers }
+    }
+
+    /// Queue a job for the next idle worker
+    pub fn submit<F: FnOnce() + Send + 'static>(&self, f: F) {
+        if let Ok(mut queue) = self.shared.queue.lock() {
+            queue.push_back(Box::new(f));
+            self.shared.available.notify_one();
+        }
+    }
+
+    /// Number of jobs waiting for a worker
+    pub fn queue_depth(&self) -> usize {
+        self.shared.queue.lock().map(|queue| queue.len()).unwrap_or(0)
+    }
+
+    /// Run all queued jobs, then stop and join the workers
+    pub fn drain_and_shutdown(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        {
+            // Set the flag under the lock so no worker misses the wakeup
+            let _queue = self.shared.queue.lock();
+            self.shared.stop.store(true, Ordering::Relaxed);
+            self.shared.available.notify_all();
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Take jobs until the pool is stopping and the queue is empty
+fn worker_loop(shared: &Shared) {
+    loop {
+        let job = {
+            let Ok(mut queue) = shared.queue.lock() else {
+                return;
+            };
+            loop {
+                if let Some(job) = queue.pop_front() {
+                    break job;
+                }
+                if shared.stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                queue = match shared.available.wait(queue) {
+                    Ok(queue) => queue,
+                    Err(_) => return,
+                };
+            }
+        };
+        job();
+    }
+}
@@ -0,0 +1,107 @@
+//! Command-line options
+//!
+//! Parsed from `std::env::args()` on Linux and from the C `argv` array
+//! handed to the NuttX entry point.
+
+use std::ffi::CStr;
+
+/// Log level used unless `--verbose` is given
+const DEFAULT_LOG_LEVEL: u8 = 1;
+
+/// Log level selected by `--verbose`
+const VERBOSE_LOG_LEVEL: u8 = 2;
+
+/// What to run, selected on the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunConfig {
+    /// `--verbose`: print more detail
+    pub verbose: bool,
+    /// `--no-interactive`: print the heap report and exit
+    pub no_interactive: bool,
+    /// `--camera`: run only the camera test
+    pub camera_only: bool,
+    /// `--wifi`: run only the WiFi test
+    pub wifi_only: bool,
+    /// `--ble`: run only the BLE scan
+    pub ble_only: bool,
+    /// `--script=<path>`: read commands from a file instead of stdin
+    pub script_path: Option<String>,
+    /// 1 by default, 2 with `--verbose`
+    pub log_level: u8,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            no_interactive: false,
+            camera_only: false,
+            wifi_only: false,
+            ble_only: false,
+            script_path: None,
+            log_level: DEFAULT_LOG_LEVEL,
+        }
+    }
+}
+
+impl RunConfig {
+    /// Parse a C `argv` array; `argv[0]` (the program name) is skipped
+    ///
+    /// Null or non-UTF-8 arguments are ignored, and a null `argv` gives
+    /// the default config.
+    ///
+    /// # Safety
+    ///
+    /// Unless null, `argv` must point to `argc` pointers, each null or
+    /// pointing to a NUL-terminated string.
+    pub unsafe fn from_args(argc: i32, argv: *const *const u8) -> RunConfig {
+        if argv.is_null() || argc <= 1 {
+            return RunConfig::default();
+        }
+
+        let args = (1..argc as usize).filter_map(|i| {
+            let arg = *argv.add(i);
+            if arg.is_null() {
+                return None;
+            }
+            CStr::from_ptr(arg as *const core::ffi::c_char)
+                .to_str()
+                .ok()
+        });
+        Self::parse(args)
+    }
+
+    /// Parse the arguments of the current process
+    pub fn from_env_args() -> RunConfig {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// Parse arguments (without the program name)
+    ///
+    /// Unknown arguments are reported and ignored.
+    pub fn parse<I, S>(args: I) -> RunConfig
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut config = RunConfig::default();
+        for arg in args {
+            let arg = arg.as_ref();
+            match arg {
+                "--verbose" => {
+                    config.verbose = true;
+                    config.log_level = VERBOSE_LOG_LEVEL;
+                }
+                "--no-interactive" => config.no_interactive = true,
+                "--camera" => config.camera_only = true,
+                "--wifi" => config.wifi_only = true,
+                "--ble" => config.ble_only = true,
+                _ => match arg.strip_prefix("--script=") {
+                    Some(path) if !path.is_empty() => config.script_path = Some(path.to_string()),
+                    _ => println!("Ignoring unknown argument '{}'", arg),
+                },
+            }
+        }
+        config
+    }
+}
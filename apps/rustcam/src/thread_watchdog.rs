@@ -0,0 +1,106 @@
+//! Software watchdog for worker threads
+//!
+//! Registered threads must call `pet()` at least once per their maximum
+//! period. A background thread checks all registrations every `period_ms`
+//! and reports threads that went silent through a user callback.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Callback invoked with the ID of an unresponsive thread
+pub type WatchdogCallback = Box<dyn Fn(u32) + Send>;
+
+/// Detects registered threads that stop petting the watchdog
+pub struct ThreadWatchdog {
+    period_ms: u32,
+    handle: Option<JoinHandle<()>>,
+    stop_flag: Arc<AtomicBool>,
+    /// Thread ID -> (last pet, maximum period in ms)
+    registrations: Arc<Mutex<HashMap<u32, (Instant, u32)>>>,
+}
+
+impl ThreadWatchdog {
+    /// Start the watchdog thread, checking registrations every `period_ms`
+    pub fn new(period_ms: u32, on_timeout: WatchdogCallback) -> ThreadWatchdog {
+        let registrations: Arc<Mutex<HashMap<u32, (Instant, u32)>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let stop_flag = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let registrations = Arc::clone(&registrations);
+            let stop_flag = Arc::clone(&stop_flag);
+            thread::Builder::new()
+                .name("rustcam-watchdog".to_string())
+                .spawn(move || {
+                    while !stop_flag.load(Ordering::Relaxed) {
+                        thread::sleep(Duration::from_millis(period_ms as u64));
+
+                        let mut expired = Vec::new();
+                        if let Ok(mut registrations) = registrations.lock() {
+                            let now = Instant::now();
+                            for (id, (last_pet, max_period_ms)) in registrations.iter_mut() {
+                                if now.duration_since(*last_pet).as_millis() > *max_period_ms as u128 {
+                                    expired.push(*id);
+                                    // Report again only after another silent period
+                                    *last_pet = now;
+                                }
+                            }
+                        }
+
+                        // Call back without holding the lock so it may use the watchdog
+                        for id in expired {
+                            on_timeout(id);
+                        }
+                    }
+                })
+                .ok()
+        };
+
+        ThreadWatchdog {
+            period_ms,
+            handle,
+            stop_flag,
+            registrations,
+        }
+    }
+
+    /// How often registrations are checked
+    pub fn period_ms(&self) -> u32 {
+        self.period_ms
+    }
+
+    /// Start watching thread `id`, which must pet at least every `max_period_ms`
+    pub fn register(&self, id: u32, max_period_ms: u32) {
+        if let Ok(mut registrations) = self.registrations.lock() {
+            registrations.insert(id, (Instant::now(), max_period_ms));
+        }
+    }
+
+    /// Signal that thread `id` is alive
+    pub fn pet(&self, id: u32) {
+        if let Ok(mut registrations) = self.registrations.lock() {
+            if let Some((last_pet, _)) = registrations.get_mut(&id) {
+                *last_pet = Instant::now();
+            }
+        }
+    }
+
+    /// Stop watching thread `id`
+    pub fn unregister(&self, id: u32) {
+        if let Ok(mut registrations) = self.registrations.lock() {
+            registrations.remove(&id);
+        }
+    }
+}
+
+impl Drop for ThreadWatchdog {
+    fn drop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
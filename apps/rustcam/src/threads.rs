@@ -0,0 +1,146 @@
+//! Demo threads spawned from the interactive `s` command
+//!
+//! Each thread prints a tick once per second until stopped, and records
+//! the heap usage around its spawn and join.
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use hal::get_heap_used;
+
+/// Thread instance with stop flag and join handle
+pub struct ThreadInstance {
+    pub id: u32,
+    /// OS-visible thread name
+    pub name: String,
+    /// When the thread was spawned
+    pub started: Instant,
+    stop_flag: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+    /// Heap usage right before spawning
+    pub heap_before_spawn: i32,
+    /// Heap usage once the thread has started
+    pub heap_at_spawn: i32,
+    /// Heap usage after joining (0 while the thread is running)
+    pub heap_at_join: i32,
+}
+
+impl ThreadInstance {
+    /// Spawn a named thread that calls `on_tick` once per second
+    ///
+    /// Waits briefly for the thread to start so that `heap_at_spawn`
+    /// includes its setup.
+    pub fn spawn<F>(id: u32, name: String, on_tick: F) -> io::Result<ThreadInstance>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let heap_before_spawn = get_heap_used();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stop_flag_clone = Arc::clone(&stop_flag);
+        let started = Instant::now();
+
+        let handle = thread::Builder::new().name(name.clone()).spawn(move || {
+            let mut tick: u64 = 0;
+            while !stop_flag_clone.load(Ordering::Relaxed) {
+                thread::sleep(Duration::from_secs(1));
+                tick += 1;
+                if stop_flag_clone.load(Ordering::Relaxed) {
+                    break;
+                }
+                on_tick();
+                let elapsed = started.elapsed();
+                println!(
+                    "[Thread {}] Tick {}: {}.{:03}s",
+                    id,
+                    tick,
+                    elapsed.as_secs(),
+                    elapsed.subsec_millis()
+                );
+            }
+        })?;
+
+        // Let the thread start before measuring
+        thread::sleep(Duration::from_millis(50));
+        let heap_at_spawn = get_heap_used();
+
+        Ok(ThreadInstance {
+            id,
+            name,
+            started,
+            stop_flag,
+            handle: Some(handle),
+            heap_before_spawn,
+            heap_at_spawn,
+            heap_at_join: 0,
+        })
+    }
+
+    /// Ask the thread to exit without waiting for it
+    pub fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop the thread, wait for it and record the heap usage afterwards
+    pub fn join(&mut self) {
+        self.stop();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+            self.heap_at_join = get_heap_used();
+        }
+    }
+
+    /// Heap growth caused by spawning this thread
+    pub fn spawn_cost(&self) -> i32 {
+        self.heap_at_spawn - self.heap_before_spawn
+    }
+
+    /// Bytes freed when this thread was joined
+    pub fn net_heap_cost(&self) -> i32 {
+        self.heap_at_spawn - self.heap_at_join
+    }
+}
+
+/// Heap cost aggregated over running and joined threads
+pub struct ThreadMemoryReport {
+    pub active: usize,
+    pub joined: usize,
+    pub total_spawn_cost: i32,
+    pub total_freed: i32,
+}
+
+impl ThreadMemoryReport {
+    pub fn new(active: &[ThreadInstance], joined: &[ThreadInstance]) -> Self {
+        Self {
+            active: active.len(),
+            joined: joined.len(),
+            total_spawn_cost: active
+                .iter()
+                .chain(joined)
+                .map(ThreadInstance::spawn_cost)
+                .sum(),
+            total_freed: joined.iter().map(ThreadInstance::net_heap_cost).sum(),
+        }
+    }
+
+    /// Average heap growth per spawned thread
+    pub fn average_spawn_cost(&self) -> i32 {
+        let spawned = (self.active + self.joined) as i32;
+        if spawned == 0 {
+            0
+        } else {
+            self.total_spawn_cost / spawned
+        }
+    }
+
+    /// Average bytes freed per joined thread
+    pub fn average_freed(&self) -> i32 {
+        if self.joined == 0 {
+            0
+        } else {
+            self.total_freed / self.joined as i32
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests for the heap profiling report formats
+
+use rustcam::heap_report::{HeapReport, Measurement};
+
+fn report() -> HeapReport {
+    HeapReport {
+        baseline: 1000,
+        measurements: vec![
+            Measurement {
+                name: "Vec<i32> (100 elements)",
+                heap_before: 1000,
+                heap_after: 1416,
+            },
+            Measurement {
+                name: "String, with comma",
+                heap_before: 1416,
+                heap_after: 1432,
+            },
+        ],
+        heap_with_all: 1432,
+        heap_after_drop: 1000,
+        thread_spawn_cost: 512,
+        thread_join_freed: 480,
+    }
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let report = report();
+    let csv = report.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("name,heap_before,heap_after,allocated"));
+
+    let rows: Vec<Vec<String>> = lines
+        .map(|line| {
+            // Only the (quoted) name may contain a comma, so split from the right
+            let fields: Vec<&str> = line.rsplitn(4, ',').collect();
+            let mut fields: Vec<String> = fields.iter().rev().map(|f| f.to_string()).collect();
+            fields[0] = fields[0].trim_matches('"').to_string();
+            fields
+        })
+        .collect();
+
+    assert_eq!(rows.len(), report.measurements.len());
+    for (row, m) in rows.iter().zip(&report.measurements) {
+        assert_eq!(row[0], m.name);
+        assert_eq!(row[1].parse::<i32>(), Ok(m.heap_before));
+        assert_eq!(row[2].parse::<i32>(), Ok(m.heap_after));
+        assert_eq!(row[3].parse::<i32>(), Ok(m.allocated()));
+    }
+}
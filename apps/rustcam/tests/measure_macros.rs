@@ -0,0 +1,27 @@
+//! Tests for the heap measurement macros
+//!
+//! Heap usage is process-wide, so this file holds a single test to keep
+//! other tests' allocations out of the measurements.
+
+use rustcam::heap_report::{get_heap_used, Measurement};
+use rustcam::{measure_block, measure_fn_returning};
+
+#[test]
+fn test_macros_match_manual_measurement() {
+    // Larger than glibc's per-thread cache, so freed chunks are not counted
+    const LEN: usize = 4096;
+
+    let before = get_heap_used();
+    let manual = vec![1u8; LEN];
+    let manual_allocated = get_heap_used() - before;
+
+    let mut measurements: Vec<Measurement> = Vec::with_capacity(2);
+    measure_block!("block", measurements, { vec![1u8; LEN] });
+    let kept = measure_fn_returning!("returning", measurements, vec![1u8; LEN]);
+
+    assert!(manual_allocated >= LEN as i32, "{manual_allocated}");
+    assert_eq!(measurements[0].name, "block");
+    assert_eq!(measurements[0].allocated(), manual_allocated);
+    assert_eq!(measurements[1].allocated(), manual_allocated);
+    assert_eq!((manual.len(), kept.len()), (LEN, LEN));
+}
@@ -0,0 +1,25 @@
+//! Tests for the fixed-size thread pool
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rustcam::pool::ThreadPool;
+
+#[test]
+fn test_drain_runs_all_jobs() {
+    let pool = ThreadPool::new(4);
+    let done = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..32 {
+        let done = Arc::clone(&done);
+        pool.submit(move || {
+            thread::sleep(Duration::from_millis(5));
+            done.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    pool.drain_and_shutdown();
+    assert_eq!(done.load(Ordering::SeqCst), 32);
+}
@@ -0,0 +1,35 @@
+//! Tests for command-line parsing
+
+use rustcam::RunConfig;
+use std::ffi::CString;
+
+#[test]
+fn test_from_args_null_is_default() {
+    let config = unsafe { RunConfig::from_args(0, std::ptr::null()) };
+    assert_eq!(config, RunConfig::default());
+}
+
+#[test]
+fn test_from_args_parses_flags() {
+    let args: Vec<CString> = ["rustcam", "--camera", "--verbose", "--script=/tmp/cmds"]
+        .iter()
+        .map(|a| CString::new(*a).unwrap())
+        .collect();
+    // A null entry is skipped rather than dereferenced
+    let mut argv: Vec<*const u8> = args.iter().map(|a| a.as_ptr() as *const u8).collect();
+    argv.push(std::ptr::null());
+
+    let config = unsafe { RunConfig::from_args(argv.len() as i32, argv.as_ptr()) };
+    assert!(config.camera_only);
+    assert!(config.verbose);
+    assert_eq!(config.log_level, 2);
+    assert_eq!(config.script_path.as_deref(), Some("/tmp/cmds"));
+    assert!(!config.wifi_only && !config.ble_only && !config.no_interactive);
+}
+
+#[test]
+fn test_parse_ignores_unknown_arguments() {
+    let config = RunConfig::parse(["--bogus", "--no-interactive", "--script="]);
+    assert!(config.no_interactive);
+    assert_eq!(config.script_path, None);
+}
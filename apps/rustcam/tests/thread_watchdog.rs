@@ -0,0 +1,33 @@
+//! Tests for the software thread watchdog
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rustcam::thread_watchdog::ThreadWatchdog;
+
+const PERIOD_MS: u32 = 20;
+const MAX_PERIOD_MS: u32 = 100;
+
+#[test]
+fn test_silent_thread_triggers_callback() {
+    let (tx, rx) = mpsc::channel();
+    let watchdog = ThreadWatchdog::new(PERIOD_MS, Box::new(move |id| tx.send(id).unwrap()));
+    watchdog.register(7, MAX_PERIOD_MS);
+
+    // No report while the thread keeps petting
+    for _ in 0..10 {
+        thread::sleep(Duration::from_millis(MAX_PERIOD_MS as u64 / 4));
+        watchdog.pet(7);
+    }
+    assert!(rx.try_recv().is_err());
+
+    // Then it goes silent
+    let last_pet = Instant::now();
+    let deadline = Duration::from_millis((MAX_PERIOD_MS + 2 * PERIOD_MS) as u64);
+    let id = rx
+        .recv_timeout(deadline)
+        .expect("callback not called in time");
+    assert_eq!(id, 7);
+    assert!(last_pet.elapsed() >= Duration::from_millis(MAX_PERIOD_MS as u64));
+}
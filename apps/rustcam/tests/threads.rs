@@ -0,0 +1,47 @@
+//! Tests for the demo threads' heap accounting
+
+use std::sync::{Mutex, MutexGuard};
+
+use rustcam::threads::{ThreadInstance, ThreadMemoryReport};
+
+/// Heap usage is process-wide, so tests spawning threads run one at a time
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_net_heap_cost_after_join() {
+    let _serial = serial();
+    let mut instance = ThreadInstance::spawn(1, "heap-cost".into(), || {}).unwrap();
+    assert_eq!(instance.heap_at_join, 0);
+
+    instance.join();
+    let cost = instance.net_heap_cost();
+    assert!(cost >= 0, "{cost}");
+
+    let report = ThreadMemoryReport::new(&[], std::slice::from_ref(&instance));
+    assert_eq!((report.active, report.joined), (0, 1));
+    assert_eq!(report.average_freed(), cost);
+}
+
+#[test]
+fn test_spawned_thread_is_named() {
+    let _serial = serial();
+    let mut instance = ThreadInstance::spawn(2, "foobar".into(), || {}).unwrap();
+    assert_eq!(instance.name, "foobar");
+
+    // The name is also visible to the OS, as in `ps -T`
+    #[cfg(target_os = "linux")]
+    {
+        let comms: Vec<String> = std::fs::read_dir("/proc/self/task")
+            .unwrap()
+            .filter_map(|task| std::fs::read_to_string(task.ok()?.path().join("comm")).ok())
+            .map(|comm| comm.trim_end().to_string())
+            .collect();
+        assert!(comms.iter().any(|comm| comm == "foobar"), "{comms:?}");
+    }
+
+    instance.join();
+}
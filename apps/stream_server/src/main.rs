@@ -0,0 +1,121 @@
+//! MJPEG streaming server
+//!
+//! Serves the camera as VGA MJPEG over HTTP until Ctrl-C:
+//!
+//! ```text
+//! stream_server [--port <N>] [--device <path>]
+//! ```
+//!
+//! Open the printed URL in a browser, VLC or ffplay to watch the stream.
+
+use hal::camera;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Port used when `--port` is not given
+const DEFAULT_PORT: u16 = 8080;
+
+/// Set by the SIGINT/SIGTERM handler
+static STOP: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_signal(_signum: libc::c_int) {
+    STOP.store(true, Ordering::Relaxed);
+}
+
+/// Command-line options
+struct Args {
+    port: u16,
+    device: Option<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut parsed = Args {
+            port: DEFAULT_PORT,
+            device: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => {
+                    let value = args.next().ok_or("--port needs a value")?;
+                    parsed.port = value
+                        .parse()
+                        .map_err(|_| format!("invalid port '{}'", value))?;
+                }
+                "--device" => {
+                    parsed.device = Some(args.next().ok_or("--device needs a path")?);
+                }
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: stream_server [--port <N>] [--device <path>]");
+            std::process::exit(2);
+        }
+    };
+    std::process::exit(run(&args));
+}
+
+fn run(args: &Args) -> i32 {
+    if let Some(device) = &args.device {
+        camera::camera_set_device_path(device);
+    }
+
+    let config = camera::CameraConfig::builder()
+        .format(camera::PixelFormat::Jpeg)
+        .resolution(camera::Resolution::Vga)
+        .build();
+    if let Err(e) = camera::camera_initialize(config) {
+        eprintln!("Camera init failed: {}", e);
+        return 1;
+    }
+
+    let handle = match camera::camera_stream_mjpeg(args.port, config) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("Failed to start streaming on port {}: {}", args.port, e);
+            let _ = camera::camera_deinitialize();
+            return 1;
+        }
+    };
+
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+        libc::signal(
+            libc::SIGTERM,
+            on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    println!(
+        "Streaming on http://localhost:{}/ (all interfaces)",
+        args.port
+    );
+    println!("Press Ctrl-C to stop");
+    while !STOP.load(Ordering::Relaxed) {
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    println!("\nStopping...");
+    let stats = camera::stream_stop(handle);
+    if let Err(e) = camera::camera_deinitialize() {
+        eprintln!("Camera deinit failed: {}", e);
+    }
+    println!(
+        "Sent {} frames, {} bytes",
+        stats.frames_sent, stats.bytes_sent
+    );
+    0
+}
@@ -0,0 +1,59 @@
+//! End-to-end test of the `stream_server` binary
+//!
+//! Needs a camera at `/dev/video0` that can deliver MJPEG, so it is
+//! ignored by default. Run it with:
+//!
+//! ```text
+//! cargo test -p stream_server -- --include-ignored
+//! ```
+
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const PORT: u16 = 18083;
+
+#[test]
+#[ignore = "needs an MJPEG-capable camera"]
+fn test_stream_and_shutdown() {
+    let mut server = Command::new(env!("CARGO_BIN_EXE_stream_server"))
+        .args(["--port", &PORT.to_string()])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(server.stdout.take().unwrap());
+
+    // Wait for the URL to be printed before connecting
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    assert!(
+        line.contains(&format!("http://localhost:{}/", PORT)),
+        "{line:?}"
+    );
+
+    let stream = TcpStream::connect(("127.0.0.1", PORT)).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut head = [0u8; 200];
+    let mut reader = BufReader::new(stream);
+    reader.read_exact(&mut head).unwrap();
+    let head = String::from_utf8_lossy(&head);
+    assert!(head.contains("boundary=frame"), "{head:?}");
+    assert!(
+        head.contains("--frame\r\nContent-Type: image/jpeg"),
+        "{head:?}"
+    );
+    drop(reader);
+
+    // Let the client thread notice the disconnect, then stop like Ctrl-C
+    thread::sleep(Duration::from_millis(300));
+    unsafe { libc::kill(server.id() as libc::pid_t, libc::SIGINT) };
+    assert!(server.wait().unwrap().success());
+
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest).unwrap();
+    assert!(rest.contains("Sent "), "{rest:?}");
+}
@@ -0,0 +1,260 @@
+//! WiFi scanner for network diagnostics
+//!
+//! Scans one or more times, filters the networks found and prints them as
+//! a table, JSON or CSV. Run with `--help` for the options.
+
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+compile_error!("wifi_scan needs the platform-linux or platform-nuttx feature");
+
+use hal::wifi::{self, ScanResult};
+use std::ffi::CStr;
+use std::fmt::Write;
+use std::thread;
+use std::time::Duration;
+
+/// Exit code when the scan worked but nothing matched
+pub const EXIT_NO_RESULTS: i32 = 2;
+
+/// Scan completion is polled this often...
+const SCAN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// ...this many times before giving up (10 s)
+const SCAN_POLL_ATTEMPTS: u32 = 20;
+
+/// Printed for `--help`
+pub const HELP: &str = "\
+NAME
+    wifi_scan - list nearby WiFi networks
+
+SYNOPSIS
+    wifi_scan [--format table|json|csv] [--min-rssi <dBm>] [--ssid <text>]
+              [--count <N>] [--help]
+
+DESCRIPTION
+    Scans for access points and prints one line per BSSID. Repeated
+    scans are merged, keeping the strongest signal seen for each BSSID.
+    On Linux, triggering a scan needs CAP_NET_ADMIN.
+
+OPTIONS
+    --format table|json|csv
+        Output format (default: table).
+
+    --min-rssi <dBm>
+        Only show networks at least this strong, e.g. -70.
+
+    --ssid <text>
+        Only show networks whose SSID contains <text>.
+
+    --count <N>
+        Scan N times and merge the results (default: 0, scan once).
+
+    --help
+        Show this help.
+
+EXIT STATUS
+    0   At least one network was found
+    1   WiFi could not be initialized or the scan failed
+    2   No network matched
+";
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Aligned columns with a header row
+    #[default]
+    Table,
+    /// Array of objects, as written by `wifi_scan_results_to_json`
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Command-line options
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScanOptions {
+    /// `--format`
+    pub format: OutputFormat,
+    /// `--min-rssi <dBm>`
+    pub min_rssi: Option<i8>,
+    /// `--ssid <text>`
+    pub ssid: Option<String>,
+    /// `--count <N>`: number of scans, 0 and 1 both scan once
+    pub count: u32,
+    /// `--help`
+    pub help: bool,
+}
+
+impl ScanOptions {
+    /// Parse a C `argv` array; `argv[0]` (the program name) is skipped
+    ///
+    /// Null or non-UTF-8 arguments are ignored, and a null `argv` gives
+    /// the default options.
+    ///
+    /// # Safety
+    ///
+    /// Unless null, `argv` must point to `argc` pointers, each null or
+    /// pointing to a NUL-terminated string.
+    pub unsafe fn from_args(argc: i32, argv: *const *const u8) -> Result<ScanOptions, String> {
+        if argv.is_null() || argc <= 1 {
+            return Ok(ScanOptions::default());
+        }
+
+        let args = (1..argc as usize).filter_map(|i| {
+            let arg = *argv.add(i);
+            if arg.is_null() {
+                return None;
+            }
+            CStr::from_ptr(arg as *const core::ffi::c_char)
+                .to_str()
+                .ok()
+        });
+        Self::parse(args)
+    }
+
+    /// Parse arguments (without the program name)
+    pub fn parse<I, S>(args: I) -> Result<ScanOptions, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = ScanOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            if arg == "--help" {
+                options.help = true;
+                continue;
+            }
+
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{} needs a value", arg))?;
+            let value = value.as_ref();
+            let invalid = || format!("invalid {} '{}'", arg, value);
+            match arg {
+                "--format" => {
+                    options.format = match value {
+                        "table" => OutputFormat::Table,
+                        "json" => OutputFormat::Json,
+                        "csv" => OutputFormat::Csv,
+                        _ => return Err(invalid()),
+                    }
+                }
+                "--min-rssi" => options.min_rssi = Some(value.parse().map_err(|_| invalid())?),
+                "--ssid" => options.ssid = Some(value.to_string()),
+                "--count" => options.count = value.parse().map_err(|_| invalid())?,
+                _ => return Err(format!("unknown argument '{}'", arg)),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Keep only the results passing `--min-rssi` and `--ssid`
+    pub fn filter(&self, mut results: Vec<ScanResult>) -> Vec<ScanResult> {
+        results.retain(|r| {
+            let strong_enough = self.min_rssi.is_none_or(|min| r.rssi >= min);
+            let ssid = String::from_utf8_lossy(&r.ssid[..r.ssid_len.min(32)]);
+            let ssid_matches = self
+                .ssid
+                .as_ref()
+                .is_none_or(|text| ssid.contains(text.as_str()));
+            strong_enough && ssid_matches
+        });
+        results
+    }
+}
+
+/// Quote a CSV field if it contains a separator or quote
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Scan results as CSV with a header row
+pub fn scan_results_to_csv(results: &[ScanResult]) -> String {
+    let mut out = String::from("ssid,bssid,channel,rssi,auth_mode\n");
+    for r in results {
+        let bssid = r.bssid_str();
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{:?}",
+            csv_field(&String::from_utf8_lossy(&r.ssid[..r.ssid_len.min(32)])),
+            core::str::from_utf8(&bssid).unwrap_or(""),
+            r.channel,
+            r.rssi,
+            r.auth_mode
+        );
+    }
+    out
+}
+
+/// Run one scan and wait for its results
+fn scan_once() -> wifi::WifiResult<Vec<ScanResult>> {
+    wifi::wifi_start_scan()?;
+    for _ in 0..SCAN_POLL_ATTEMPTS {
+        thread::sleep(SCAN_POLL_INTERVAL);
+        if wifi::wifi_scan_is_complete()? {
+            break;
+        }
+    }
+    let (results, count) = wifi::wifi_get_scan_results()?;
+    Ok(results[..count].to_vec())
+}
+
+/// Scan as configured and print the results; returns the exit code
+pub fn run(options: &ScanOptions) -> i32 {
+    if options.help {
+        print!("{}", HELP);
+        return 0;
+    }
+
+    if let Err(e) = wifi::wifi_initialize() {
+        eprintln!("WiFi init failed: {}", e);
+        return 1;
+    }
+
+    let mut merged = Vec::new();
+    for _ in 0..options.count.max(1) {
+        match scan_once() {
+            Ok(results) => merged.extend(results),
+            Err(e) => {
+                eprintln!("Scan failed: {}", e);
+                return 1;
+            }
+        }
+    }
+    let results = options.filter(wifi::dedup_scan_results(merged));
+
+    match options.format {
+        OutputFormat::Table => print!("{}", wifi::wifi_scan_results_to_table(&results)),
+        OutputFormat::Json => println!("{}", wifi::wifi_scan_results_to_json(&results)),
+        OutputFormat::Csv => print!("{}", scan_results_to_csv(&results)),
+    }
+
+    if results.is_empty() {
+        EXIT_NO_RESULTS
+    } else {
+        0
+    }
+}
+
+/// NuttX entry point
+///
+/// # Safety
+///
+/// `argc` and `argv` must be the task arguments passed by NuttX.
+#[cfg(feature = "platform-nuttx")]
+#[no_mangle]
+pub unsafe extern "C" fn wifi_scan_main(argc: i32, argv: *const *const u8) -> i32 {
+    match ScanOptions::from_args(argc, argv) {
+        Ok(options) => run(&options),
+        Err(e) => {
+            println!("Error: {}", e);
+            println!("Run 'wifi_scan --help' for usage");
+            1
+        }
+    }
+}
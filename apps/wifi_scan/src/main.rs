@@ -0,0 +1,15 @@
+//! WiFi scanner entry point
+
+use wifi_scan::ScanOptions;
+
+fn main() {
+    let options = match ScanOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Run 'wifi_scan --help' for usage");
+            std::process::exit(1);
+        }
+    };
+    std::process::exit(wifi_scan::run(&options));
+}
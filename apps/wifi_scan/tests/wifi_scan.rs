@@ -0,0 +1,69 @@
+//! Option parsing, filtering and CSV output of the scanner
+
+use hal::wifi::{AuthMode, ScanResult};
+use wifi_scan::{scan_results_to_csv, OutputFormat, ScanOptions};
+
+fn network(ssid: &str, last_bssid_byte: u8, rssi: i8) -> ScanResult {
+    let mut result = ScanResult {
+        ssid_len: ssid.len(),
+        bssid: [0xAA, 0xBB, 0xCC, 0x00, 0x00, last_bssid_byte],
+        channel: 6,
+        rssi,
+        auth_mode: AuthMode::Wpa2Psk,
+        ..ScanResult::default()
+    };
+    result.ssid[..ssid.len()].copy_from_slice(ssid.as_bytes());
+    result
+}
+
+#[test]
+fn test_parse_options() {
+    let options = ScanOptions::parse([
+        "--format",
+        "csv",
+        "--min-rssi",
+        "-70",
+        "--ssid",
+        "cam",
+        "--count",
+        "3",
+    ])
+    .unwrap();
+    assert_eq!(options.format, OutputFormat::Csv);
+    assert_eq!(options.min_rssi, Some(-70));
+    assert_eq!(options.ssid.as_deref(), Some("cam"));
+    assert_eq!(options.count, 3);
+
+    assert_eq!(
+        ScanOptions::parse::<_, &str>([]),
+        Ok(ScanOptions::default())
+    );
+    assert!(ScanOptions::parse(["--help"]).unwrap().help);
+    assert!(ScanOptions::parse(["--format", "xml"]).is_err());
+    assert!(ScanOptions::parse(["--min-rssi"]).is_err());
+    assert!(ScanOptions::parse(["--verbose", "1"]).is_err());
+}
+
+#[test]
+fn test_filter() {
+    let results = vec![
+        network("rustcam", 1, -50),
+        network("rustcam-guest", 2, -80),
+        network("office", 3, -40),
+    ];
+    let options = ScanOptions::parse(["--min-rssi", "-70", "--ssid", "rustcam"]).unwrap();
+
+    let filtered = options.filter(results);
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].ssid_str(), Some("rustcam"));
+}
+
+#[test]
+fn test_csv_quotes_ssid() {
+    let csv = scan_results_to_csv(&[network("cafe, upstairs", 1, -60)]);
+    assert_eq!(
+        csv,
+        "ssid,bssid,channel,rssi,auth_mode\n\
+         \"cafe, upstairs\",AA:BB:CC:00:00:01,6,-60,Wpa2Psk\n"
+    );
+}
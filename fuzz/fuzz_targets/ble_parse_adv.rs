@@ -0,0 +1,11 @@
+//! Fuzz the LE advertising report parser with arbitrary report parameters
+//!
+//! Run with `cargo +nightly fuzz run ble_parse_adv` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = hal::ble::parse_advertising_report(data);
+});
@@ -0,0 +1,11 @@
+//! Fuzz HCI event packet parsing as done by the scan loop
+//!
+//! Run with `cargo +nightly fuzz run ble_parse_hci_event` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = hal::ble::parse_hci_event_packet(data);
+});
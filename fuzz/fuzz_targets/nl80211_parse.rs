@@ -0,0 +1,11 @@
+//! Fuzz nl80211 BSS parsing with arbitrary scan result attributes
+//!
+//! Run with `cargo +nightly fuzz run nl80211_parse` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = hal::wifi::fuzz_parse_bss(data);
+});
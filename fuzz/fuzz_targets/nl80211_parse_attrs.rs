@@ -0,0 +1,11 @@
+//! Fuzz generic netlink attribute parsing
+//!
+//! Run with `cargo +nightly fuzz run nl80211_parse_attrs` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = hal::wifi::fuzz_parse_attrs(data);
+});
@@ -0,0 +1,63 @@
+//! Linux ADC implementation using the IIO sysfs interface
+//!
+//! Each channel N of the first IIO device is exposed as in_voltageN_raw,
+//! with the millivolts-per-count factor in in_voltageN_scale.
+
+use super::{AdcChannel, AdcError, AdcResult};
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+
+const IIO_DEVICE: &str = "/sys/bus/iio/devices/iio:device0";
+
+/// IIO device directory set with `adc_set_device_path`
+static IIO_DEVICE_PATH: Mutex<Option<String>> = Mutex::new(None);
+
+/// Use the IIO device directory `path` instead of iio:device0
+///
+/// Useful on boards with several IIO devices. Affects channels opened
+/// afterwards and reads on open channels; an empty path restores the
+/// default.
+pub fn adc_set_device_path(path: &str) {
+    *IIO_DEVICE_PATH.lock().unwrap_or_else(|e| e.into_inner()) =
+        (!path.is_empty()).then(|| path.to_string());
+}
+
+/// Map an I/O error from a sysfs access to an ADC error
+fn map_io_error(e: io::Error) -> AdcError {
+    match e.kind() {
+        io::ErrorKind::NotFound => AdcError::NotFound,
+        io::ErrorKind::PermissionDenied => AdcError::PermissionDenied,
+        _ => AdcError::SystemError(e.raw_os_error().unwrap_or(-1)),
+    }
+}
+
+/// Read a sysfs attribute of this channel as a trimmed string
+fn read_attr(channel: u8, attr: &str) -> AdcResult<String> {
+    let device = IIO_DEVICE_PATH.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let device = device.as_deref().unwrap_or(IIO_DEVICE);
+    fs::read_to_string(format!("{}/in_voltage{}_{}", device, channel, attr))
+        .map(|s| s.trim().to_string())
+        .map_err(map_io_error)
+}
+
+impl AdcChannel {
+    pub(super) fn open(channel: u8) -> AdcResult<AdcChannel> {
+        read_attr(channel, "raw")?;
+        Ok(AdcChannel { channel })
+    }
+
+    /// Read the raw conversion result
+    pub fn read_raw(&self) -> AdcResult<u16> {
+        read_attr(self.channel, "raw")?
+            .parse()
+            .map_err(|_| AdcError::ReadFailed)
+    }
+
+    /// Read the driver-provided scale in millivolts per count
+    pub fn read_scale_mv(&self) -> AdcResult<f32> {
+        read_attr(self.channel, "scale")?
+            .parse()
+            .map_err(|_| AdcError::ReadFailed)
+    }
+}
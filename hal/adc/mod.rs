@@ -0,0 +1,119 @@
+//! Analog-to-digital converter HAL
+//!
+//! Reads voltages from ADC channels.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the ADC driver (/dev/adcN) with ANIOC_TRIGGER
+//! - Linux: Uses the IIO sysfs interface (/sys/bus/iio/devices/iio:device0)
+
+// Platform-specific implementations
+
+// NuttX uses the ADC upper-half driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Linux uses Industrial I/O sysfs attributes
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::adc_set_device_path;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::thread;
+use std::time::Duration;
+
+/// Full-scale count of a 12-bit converter
+const ADC_FULL_SCALE: u64 = 4096;
+
+/// ADC operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdcError {
+    /// ADC device or channel not found
+    NotFound,
+    /// Permission denied
+    PermissionDenied,
+    /// Conversion result could not be read
+    ReadFailed,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for AdcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdcError::NotFound => write!(f, "ADC channel not found"),
+            AdcError::PermissionDenied => write!(f, "Permission denied"),
+            AdcError::ReadFailed => write!(f, "ADC read failed"),
+            AdcError::NotSupported => write!(f, "Not supported on this platform"),
+            AdcError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl AdcError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            AdcError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("run as root or grant access to the IIO device in /sys/bus/iio")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for ADC operations
+pub type AdcResult<T> = Result<T, AdcError>;
+
+/// A single ADC input channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdcChannel {
+    channel: u8,
+}
+
+/// Open an ADC channel, checking that it exists
+pub fn adc_open(channel: u8) -> AdcResult<AdcChannel> {
+    AdcChannel::open(channel)
+}
+
+/// Convert a 12-bit raw reading to millivolts for a reference voltage
+pub fn adc_raw_to_millivolts(raw: u16, vref_mv: u32) -> u32 {
+    (raw as u64 * vref_mv as u64 / ADC_FULL_SCALE) as u32
+}
+
+impl AdcChannel {
+    /// Channel number
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Read the input voltage in millivolts, assuming a 12-bit converter
+    pub fn read_millivolts(&self, vref_mv: u32) -> AdcResult<u32> {
+        self.read_raw().map(|raw| adc_raw_to_millivolts(raw, vref_mv))
+    }
+
+    /// Average `samples` readings taken `delay_ms` apart, in millivolts
+    pub fn read_average_mv(&self, samples: u8, delay_ms: u32, vref_mv: u32) -> AdcResult<u32> {
+        let samples = samples.max(1);
+        let mut total: u64 = 0;
+        for i in 0..samples {
+            if i > 0 && delay_ms > 0 {
+                thread::sleep(Duration::from_millis(delay_ms as u64));
+            }
+            total += self.read_millivolts(vref_mv)? as u64;
+        }
+        Ok((total / samples as u64) as u32)
+    }
+}
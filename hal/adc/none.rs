@@ -0,0 +1,19 @@
+//! Stub ADC implementation for unsupported platforms
+
+use super::{AdcChannel, AdcError, AdcResult};
+
+impl AdcChannel {
+    pub(super) fn open(_channel: u8) -> AdcResult<AdcChannel> {
+        Err(AdcError::NotSupported)
+    }
+
+    /// Read the raw conversion result (stub: returns NotSupported)
+    pub fn read_raw(&self) -> AdcResult<u16> {
+        Err(AdcError::NotSupported)
+    }
+
+    /// Read the scale in millivolts per count (stub: returns NotSupported)
+    pub fn read_scale_mv(&self) -> AdcResult<f32> {
+        Err(AdcError::NotSupported)
+    }
+}
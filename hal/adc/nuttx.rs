@@ -0,0 +1,65 @@
+//! ADC HAL for NuttX
+//!
+//! Uses the ADC upper-half character driver. Each channel is expected to
+//! be registered by the board as its own /dev/adcN device; a conversion is
+//! started with ANIOC_TRIGGER and the result read as `struct adc_msg_s`.
+
+use super::{AdcChannel, AdcError, AdcResult};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read};
+use std::os::unix::io::AsRawFd;
+
+// ADC ioctl commands (from nuttx/analog/ioctl.h)
+// _ANIOC(nr) = _IOC(_ANIOCBASE, nr), _ANIOCBASE = 0x0b00
+const ANIOC_TRIGGER: i32 = 0x0b01;
+
+/// Size of packed struct adc_msg_s { uint8_t am_channel; int32_t am_data; }
+const ADC_MSG_SIZE: usize = 5;
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to an ADC error
+fn map_errno(errno: i32) -> AdcError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => AdcError::NotFound,
+        libc::EPERM | libc::EACCES => AdcError::PermissionDenied,
+        _ => AdcError::SystemError(errno),
+    }
+}
+
+fn open_device(channel: u8) -> AdcResult<File> {
+    OpenOptions::new()
+        .read(true)
+        .open(format!("/dev/adc{}", channel))
+        .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))
+}
+
+impl AdcChannel {
+    pub(super) fn open(channel: u8) -> AdcResult<AdcChannel> {
+        open_device(channel)?;
+        Ok(AdcChannel { channel })
+    }
+
+    /// Trigger a conversion and read the raw result
+    pub fn read_raw(&self) -> AdcResult<u16> {
+        let mut file = open_device(self.channel)?;
+
+        if unsafe { ioctl(file.as_raw_fd(), ANIOC_TRIGGER, 0) } < 0 {
+            return Err(map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0)));
+        }
+
+        let mut msg = [0u8; ADC_MSG_SIZE];
+        file.read_exact(&mut msg).map_err(|_| AdcError::ReadFailed)?;
+        let data = i32::from_ne_bytes([msg[1], msg[2], msg[3], msg[4]]);
+        Ok(data.clamp(0, u16::MAX as i32) as u16)
+    }
+
+    /// Read the scale in millivolts per count (not supported: the NuttX
+    /// driver does not report its reference voltage)
+    pub fn read_scale_mv(&self) -> AdcResult<f32> {
+        Err(AdcError::NotSupported)
+    }
+}
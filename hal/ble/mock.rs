@@ -0,0 +1,298 @@
+//! Mock BLE backend for testing without hardware
+//!
+//! Replaces the unsupported-platform stub when the `mock` feature is
+//! enabled. Nothing requires initialization: scans (including continuous
+//! ones) return the devices set with `ble_mock_set_scan_results`,
+//! advertising only records the name, and
+//! the GATT server delivers writes queued with `ble_mock_inject_write` to
+//! its handler until the timeout expires.
+
+use super::{
+    eddystone_url_advertising_data, AdvertisingData, BleAddress, BleError, BleResult, BleScanner,
+    CharacteristicHandle, ConnectionHandle, GattDb, GattHandler, GattServerConfig, IBeacon,
+    ScanCallback, ScanHandle, ScanResult, Uuid,
+};
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Simulated scan duration, independent of the requested timeout
+const SCAN_DELAY: Duration = Duration::from_millis(10);
+
+/// How often the GATT server checks for injected writes
+const GATT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Handle given to every mock connection
+const MOCK_CONN_HANDLE: ConnectionHandle = ConnectionHandle(0x0040);
+
+/// Static random address (two most significant bits set)
+const MOCK_RANDOM_ADDRESS: [u8; 6] = [0xC0, 0x12, 0x34, 0x56, 0x78, 0x9A];
+
+/// Devices reported by every scan
+static MOCK_SCAN_RESULTS: Mutex<Vec<ScanResult>> = Mutex::new(Vec::new());
+
+/// Results of the last `ble_start_scan`
+static SCAN_RESULTS: Mutex<Vec<ScanResult>> = Mutex::new(Vec::new());
+
+/// Name passed to the last `ble_start_advertising`, cleared when advertising stops
+static ADVERTISED_NAME: Mutex<Option<String>> = Mutex::new(None);
+
+/// Connected device and its RSSI
+static CONNECTION: Mutex<Option<(BleAddress, i8)>> = Mutex::new(None);
+
+/// Writes waiting for the GATT server, as (attribute handle, data)
+static PENDING_WRITES: Mutex<VecDeque<(u16, Vec<u8>)>> = Mutex::new(VecDeque::new());
+
+/// Lock a mock static, recovering from a panic in another test thread
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Set the devices reported by subsequent scans
+pub fn ble_mock_set_scan_results(results: Vec<ScanResult>) {
+    *lock(&MOCK_SCAN_RESULTS) = results;
+}
+
+/// Get the name passed to `ble_start_advertising`, if still advertising
+pub fn ble_mock_get_advertised_name() -> Option<String> {
+    lock(&ADVERTISED_NAME).clone()
+}
+
+/// Queue a GATT write of `data` to `handle` for the running (or next) server
+pub fn ble_mock_inject_write(handle: u16, data: Vec<u8>) {
+    lock(&PENDING_WRITES).push_back((handle, data));
+}
+
+/// Initialize BLE subsystem (always succeeds)
+pub fn ble_initialize() -> BleResult<()> {
+    Ok(())
+}
+
+/// Deinitialize BLE subsystem, clearing scan results, advertising and connection
+pub fn ble_deinitialize() -> BleResult<()> {
+    lock(&SCAN_RESULTS).clear();
+    *lock(&ADVERTISED_NAME) = None;
+    *lock(&CONNECTION) = None;
+    Ok(())
+}
+
+/// Start BLE scanning, reporting the mock scan results
+pub fn ble_start_scan(_timeout_ms: u32) -> BleResult<()> {
+    thread::sleep(SCAN_DELAY);
+    *lock(&SCAN_RESULTS) = lock(&MOCK_SCAN_RESULTS).clone();
+    Ok(())
+}
+
+/// Stop BLE scanning (scans finish immediately, so this does nothing)
+pub fn ble_stop_scan() -> BleResult<()> {
+    Ok(())
+}
+
+/// Get the results of the last scan
+pub fn ble_get_scan_results() -> BleResult<Vec<ScanResult>> {
+    Ok(lock(&SCAN_RESULTS).clone())
+}
+
+/// Report each device of the mock scan results to `callback` once,
+/// including devices set after the scan started
+pub fn ble_start_scan_continuous(callback: ScanCallback) -> BleResult<ScanHandle> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    let thread = thread::spawn(move || {
+        let mut seen = HashSet::new();
+        while !thread_stop.load(Ordering::Relaxed) {
+            // Collect first so the callback may set new results without deadlocking
+            let new: Vec<_> = lock(&MOCK_SCAN_RESULTS)
+                .iter()
+                .filter(|result| seen.insert(result.address))
+                .cloned()
+                .collect();
+            for result in new {
+                callback(result);
+            }
+            thread::sleep(SCAN_DELAY);
+        }
+    });
+    Ok(ScanHandle { stop, thread })
+}
+
+/// Run a filtered scan over the mock scan results
+///
+/// Mock results carry no service UUIDs, so a service UUID filter rejects
+/// every device.
+pub(super) fn scan_with_filter(
+    filter: &BleScanner,
+    _timeout_ms: u32,
+    stop_on_first: bool,
+) -> BleResult<Vec<ScanResult>> {
+    thread::sleep(SCAN_DELAY);
+    let limit = if stop_on_first { 1 } else { filter.max_results };
+    Ok(lock(&MOCK_SCAN_RESULTS)
+        .iter()
+        .filter(|r| {
+            let name = r.name.as_ref().map(|n| &n[..r.name_len]);
+            filter.accepts(r.rssi, name, &[])
+        })
+        .take(limit)
+        .cloned()
+        .collect())
+}
+
+/// Connect to a device from the mock scan results
+pub fn ble_connect(address: &BleAddress, _timeout_ms: u32) -> BleResult<ConnectionHandle> {
+    let rssi = lock(&MOCK_SCAN_RESULTS)
+        .iter()
+        .find(|r| r.address == *address)
+        .map(|r| r.rssi)
+        .ok_or(BleError::DeviceNotFound)?;
+    *lock(&CONNECTION) = Some((*address, rssi));
+    Ok(MOCK_CONN_HANDLE)
+}
+
+/// Disconnect from the mock device
+pub fn ble_disconnect(handle: ConnectionHandle) -> BleResult<()> {
+    let mut connection = lock(&CONNECTION);
+    if handle != MOCK_CONN_HANDLE || connection.is_none() {
+        return Err(BleError::DisconnectionError);
+    }
+    *connection = None;
+    Ok(())
+}
+
+/// Read the RSSI of the connected device from its scan result
+pub fn ble_get_rssi(handle: ConnectionHandle) -> BleResult<i8> {
+    match *lock(&CONNECTION) {
+        Some((_, rssi)) if handle == MOCK_CONN_HANDLE => Ok(rssi),
+        _ => Err(BleError::DeviceNotFound),
+    }
+}
+
+/// Read the transmit power level of the active connection (always 0 dBm)
+pub fn ble_get_tx_power() -> BleResult<i8> {
+    lock(&CONNECTION).map(|_| 0).ok_or(BleError::DeviceNotFound)
+}
+
+/// Discover GATT services (mock: returns NotSupported)
+pub fn gatt_discover_services(_handle: ConnectionHandle) -> BleResult<Vec<Uuid>> {
+    Err(BleError::NotSupported)
+}
+
+/// Discover the characteristics of a service (mock: returns NotSupported)
+pub fn gatt_discover_characteristics(
+    _conn: ConnectionHandle,
+    _service_uuid: Uuid,
+) -> BleResult<Vec<CharacteristicHandle>> {
+    Err(BleError::NotSupported)
+}
+
+/// Read a GATT characteristic (mock: returns NotSupported)
+pub fn gatt_read_characteristic(_char: CharacteristicHandle) -> BleResult<Vec<u8>> {
+    Err(BleError::NotSupported)
+}
+
+/// Write to a GATT characteristic (mock: returns NotSupported)
+pub fn gatt_write_characteristic(_char: CharacteristicHandle, _data: &[u8]) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Start BLE advertising, recording the device name
+pub fn ble_start_advertising(name: &str) -> BleResult<()> {
+    *lock(&ADVERTISED_NAME) = Some(name.to_string());
+    Ok(())
+}
+
+/// Start BLE advertising with a custom payload (no name is recorded)
+pub fn ble_start_advertising_with_data(_data: &AdvertisingData) -> BleResult<()> {
+    *lock(&ADVERTISED_NAME) = None;
+    Ok(())
+}
+
+/// Advertise as an Apple iBeacon (no name is recorded)
+pub fn ble_advertise_ibeacon(beacon: &IBeacon) -> BleResult<()> {
+    ble_start_advertising_with_data(&beacon.advertising_data()?)
+}
+
+/// Advertise an Eddystone-URL beacon (no name is recorded)
+pub fn ble_advertise_eddystone_url(url: &str, tx_power: i8) -> BleResult<()> {
+    ble_start_advertising_with_data(&eddystone_url_advertising_data(url, tx_power)?)
+}
+
+/// Stop BLE advertising, clearing the recorded name
+pub fn ble_stop_advertising() -> BleResult<()> {
+    *lock(&ADVERTISED_NAME) = None;
+    Ok(())
+}
+
+/// Rotate the random address (the mock address never changes)
+pub fn ble_rotate_random_address(interval_secs: u64) -> BleResult<()> {
+    if interval_secs == 0 {
+        return Err(BleError::InvalidParameter);
+    }
+    Ok(())
+}
+
+/// Stop random address rotation
+pub fn ble_stop_address_rotation() -> BleResult<()> {
+    Ok(())
+}
+
+/// Get the fixed mock random address
+pub fn ble_get_random_address() -> BleResult<BleAddress> {
+    Ok(BleAddress::new(MOCK_RANDOM_ADDRESS))
+}
+
+/// Run a GATT server for `config.timeout_ms`, discarding injected writes
+pub fn ble_run_gatt_server(config: &GattServerConfig) -> BleResult<()> {
+    run_gatt_server(config.timeout_ms, None)
+}
+
+/// Run a GATT server for `timeout_ms`, passing injected writes to `handler`
+pub fn ble_run_gatt_server_with_handler(
+    _name: &str,
+    timeout_ms: u32,
+    handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    run_gatt_server(timeout_ms, Some(handler))
+}
+
+/// Run a GATT server for `timeout_ms`, passing injected writes to `handler`
+///
+/// Writes are delivered whether or not `db` contains the handle.
+pub fn ble_run_gatt_server_with_db(
+    _name: &str,
+    timeout_ms: u32,
+    _db: &GattDb,
+    handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    run_gatt_server(timeout_ms, Some(handler))
+}
+
+/// Deliver injected writes until `timeout_ms` has elapsed
+fn run_gatt_server(timeout_ms: u32, mut handler: Option<&mut dyn GattHandler>) -> BleResult<()> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+    loop {
+        // Take the writes first so the handler may inject more without deadlocking
+        let writes: Vec<_> = lock(&PENDING_WRITES).drain(..).collect();
+        if let Some(handler) = handler.as_deref_mut() {
+            for (attr_handle, data) in writes {
+                handler.on_write(attr_handle, &data);
+            }
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        thread::sleep(remaining.min(GATT_POLL_INTERVAL));
+    }
+}
+
+/// Send a GATT notification (discarded)
+pub fn gatt_send_notification(conn_handle: u16, _attr_handle: u16, _data: &[u8]) -> BleResult<()> {
+    if !ConnectionHandle(conn_handle).is_valid() {
+        return Err(BleError::InvalidParameter);
+    }
+    Ok(())
+}
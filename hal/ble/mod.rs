@@ -4,6 +4,8 @@
 //! Implementation is selected at compile time based on platform feature.
 //!
 //! Note: Bluetooth sockets are a Linux extension, not POSIX standard.
+//!
+//! Without a platform, the `mock` feature provides a simulated adapter for tests.
 
 // Platform-specific implementations
 
@@ -20,12 +22,21 @@ mod nuttx;
 pub use nuttx::*;
 
 // Fallback stub for other platforms
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 mod none;
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 pub use none::*;
 
+// Canned scan results and injectable GATT writes for tests, in place of the stub
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+mod mock;
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+pub use mock::*;
+
 use core::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 
 /// BLE error types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -77,6 +88,49 @@ impl fmt::Display for BleError {
             BleError::PermissionDenied => write!(f, "Permission denied"),
             BleError::DeviceNotFound => write!(f, "Device not found"),
             BleError::NoAdapter => write!(f, "No Bluetooth adapter available"),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl BleError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            BleError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("run as root or: sudo setcap cap_net_raw,cap_net_admin+ep <binary>")
+            }
+            BleError::NoAdapter if cfg!(feature = "platform-linux") => {
+                Some("check the adapter with hciconfig or rfkill list")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for BleError {}
+
+/// Map an errno value; negated values (as returned by NuttX drivers) are
+/// accepted too. Anything unrecognised is reported as a socket error,
+/// since most BLE operations go through the HCI socket.
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+impl From<i32> for BleError {
+    fn from(errno: i32) -> Self {
+        match errno.wrapping_abs() {
+            libc::EPERM | libc::EACCES => BleError::PermissionDenied,
+            libc::ENODEV => BleError::NoAdapter,
+            libc::ENOENT => BleError::DeviceNotFound,
+            libc::EINVAL => BleError::InvalidParameter,
+            libc::EALREADY => BleError::AlreadyInitialized,
+            libc::ETIMEDOUT => BleError::Timeout,
+            libc::ECONNREFUSED | libc::ENOTCONN => BleError::ConnectionError,
+            libc::EOPNOTSUPP => BleError::NotSupported,
+            _ => BleError::SocketError,
         }
     }
 }
@@ -85,7 +139,10 @@ impl fmt::Display for BleError {
 pub type BleResult<T> = Result<T, BleError>;
 
 /// Bluetooth address (6 bytes, big-endian)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Ordering compares the bytes lexicographically, which for big-endian
+/// storage matches the numeric order of the address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BleAddress {
     pub bytes: [u8; 6],
 }
@@ -109,6 +166,36 @@ impl BleAddress {
         }
         Some(Self { bytes })
     }
+
+    /// The broadcast address FF:FF:FF:FF:FF:FF
+    pub fn broadcast() -> Self {
+        Self { bytes: [0xFF; 6] }
+    }
+
+    /// Check whether this is the broadcast address
+    pub fn is_broadcast(&self) -> bool {
+        self.bytes == [0xFF; 6]
+    }
+}
+
+impl From<[u8; 6]> for BleAddress {
+    fn from(bytes: [u8; 6]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<BleAddress> for [u8; 6] {
+    fn from(address: BleAddress) -> Self {
+        address.bytes
+    }
+}
+
+impl TryFrom<&str> for BleAddress {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s).ok_or("invalid Bluetooth address, expected AA:BB:CC:DD:EE:FF")
+    }
 }
 
 impl fmt::Display for BleAddress {
@@ -159,10 +246,405 @@ impl ScanResult {
     }
 }
 
+/// Default number of devices kept by a scan
+const DEFAULT_MAX_SCAN_RESULTS: usize = 32;
+
+/// Filtered BLE scan
+///
+/// Filters are applied as advertising reports arrive, so only matching
+/// devices take up result slots:
+///
+/// ```ignore
+/// let devices = BleScanner::new()
+///     .with_name_prefix("RustCam")
+///     .with_min_rssi(-80)
+///     .with_max_results(10)
+///     .scan(5000)?;
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(not(any(feature = "platform-linux", feature = "mock")), allow(dead_code))]
+pub struct BleScanner {
+    name_prefix: Option<String>,
+    min_rssi: i8,
+    service_uuid_filter: Option<u16>,
+    max_results: usize,
+}
+
+impl BleScanner {
+    /// Create a scanner accepting every device
+    pub fn new() -> Self {
+        Self {
+            name_prefix: None,
+            min_rssi: i8::MIN,
+            service_uuid_filter: None,
+            max_results: DEFAULT_MAX_SCAN_RESULTS,
+        }
+    }
+
+    /// Only accept devices whose advertised name starts with `prefix`
+    pub fn with_name_prefix(mut self, prefix: &str) -> Self {
+        self.name_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Only accept devices received at `dbm` or stronger
+    pub fn with_min_rssi(mut self, dbm: i8) -> Self {
+        self.min_rssi = dbm;
+        self
+    }
+
+    /// Only accept devices advertising the 16-bit service UUID `uuid`
+    pub fn with_service_uuid(mut self, uuid: u16) -> Self {
+        self.service_uuid_filter = Some(uuid);
+        self
+    }
+
+    /// Stop collecting after `max` matching devices
+    pub fn with_max_results(mut self, max: usize) -> Self {
+        self.max_results = max;
+        self
+    }
+
+    /// Scan for `timeout_ms` and return the matching devices
+    pub fn scan(self, timeout_ms: u32) -> BleResult<Vec<ScanResult>> {
+        scan_with_filter(&self, timeout_ms, false)
+    }
+
+    /// Scan until the first matching device is seen
+    ///
+    /// Returns `DeviceNotFound` if nothing matches within `timeout_ms`.
+    pub fn scan_until_found(self, timeout_ms: u32) -> BleResult<ScanResult> {
+        scan_with_filter(&self, timeout_ms, true)?
+            .into_iter()
+            .next()
+            .ok_or(BleError::DeviceNotFound)
+    }
+
+    /// Check an advertising report against the filters
+    #[cfg_attr(not(any(feature = "platform-linux", feature = "mock")), allow(dead_code))]
+    fn accepts(&self, rssi: i8, name: Option<&[u8]>, service_uuids: &[u16]) -> bool {
+        if rssi < self.min_rssi {
+            return false;
+        }
+        if let Some(prefix) = &self.name_prefix {
+            if !name.is_some_and(|n| n.starts_with(prefix.as_bytes())) {
+                return false;
+            }
+        }
+        if let Some(uuid) = self.service_uuid_filter {
+            if !service_uuids.contains(&uuid) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl Default for BleScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Callback receiving each newly discovered device of a continuous scan
+pub type ScanCallback = Box<dyn Fn(ScanResult) + Send + 'static>;
+
+/// Background scan started by `ble_start_scan_continuous`
+///
+/// Scanning continues until `stop` is called; dropping the handle without
+/// stopping leaves the scan running.
+#[derive(Debug)]
+pub struct ScanHandle {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl ScanHandle {
+    /// Stop scanning and wait for the scan thread to exit
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.thread.join();
+    }
+
+    /// Whether the scan is still running
+    ///
+    /// Also false if the scan thread ended on its own after an HCI error.
+    pub fn is_running(&self) -> bool {
+        !self.stop.load(Ordering::Relaxed) && !self.thread.is_finished()
+    }
+}
+
+// AD types (Bluetooth Core Supplement, Part A)
+const AD_TYPE_FLAGS: u8 = 0x01;
+const AD_TYPE_UUID16_COMPLETE: u8 = 0x03;
+const AD_TYPE_SHORTENED_NAME: u8 = 0x08;
+const AD_TYPE_COMPLETE_NAME: u8 = 0x09;
+const AD_TYPE_TX_POWER: u8 = 0x0A;
+const AD_TYPE_SERVICE_DATA_16: u8 = 0x16;
+const AD_TYPE_MANUFACTURER_DATA: u8 = 0xFF;
+
+/// Legacy advertising payload (up to 31 bytes of AD structures)
+///
+/// Built with chained calls, each of which fails with `InvalidParameter`
+/// if the field would not fit:
+///
+/// ```ignore
+/// let data = AdvertisingData::new()
+///     .add_flags(0x06)?
+///     .add_complete_name("RustCam")?;
+/// ble_start_advertising_with_data(&data)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdvertisingData {
+    buf: [u8; 31],
+    len: u8,
+}
+
+impl AdvertisingData {
+    /// Maximum legacy advertising payload size
+    pub const MAX_LEN: usize = 31;
+
+    /// Create an empty advertising payload
+    pub fn new() -> Self {
+        Self {
+            buf: [0; 31],
+            len: 0,
+        }
+    }
+
+    /// Add a Flags field (e.g. 0x06: LE General Discoverable, BR/EDR not supported)
+    pub fn add_flags(self, flags: u8) -> Result<Self, BleError> {
+        self.add_field(AD_TYPE_FLAGS, &[flags])
+    }
+
+    /// Add a Complete Local Name field
+    pub fn add_complete_name(self, name: &str) -> Result<Self, BleError> {
+        self.add_field(AD_TYPE_COMPLETE_NAME, name.as_bytes())
+    }
+
+    /// Add a Shortened Local Name field
+    pub fn add_shortened_name(self, name: &str) -> Result<Self, BleError> {
+        self.add_field(AD_TYPE_SHORTENED_NAME, name.as_bytes())
+    }
+
+    /// Add a Complete List of 16-bit Service UUIDs field with a single UUID
+    pub fn add_service_uuid_16(self, uuid: u16) -> Result<Self, BleError> {
+        self.add_field(AD_TYPE_UUID16_COMPLETE, &uuid.to_le_bytes())
+    }
+
+    /// Add a Manufacturer Specific Data field (company ID is sent little-endian)
+    pub fn add_manufacturer_data(self, company_id: u16, data: &[u8]) -> Result<Self, BleError> {
+        let mut payload = [0u8; 29];
+        if data.len() > payload.len() - 2 {
+            return Err(BleError::InvalidParameter);
+        }
+        payload[..2].copy_from_slice(&company_id.to_le_bytes());
+        payload[2..2 + data.len()].copy_from_slice(data);
+        self.add_field(AD_TYPE_MANUFACTURER_DATA, &payload[..2 + data.len()])
+    }
+
+    /// Add a Service Data field for a 16-bit UUID (UUID is sent little-endian)
+    pub fn add_service_data_16(self, uuid: u16, data: &[u8]) -> Result<Self, BleError> {
+        let mut payload = [0u8; 29];
+        if data.len() > payload.len() - 2 {
+            return Err(BleError::InvalidParameter);
+        }
+        payload[..2].copy_from_slice(&uuid.to_le_bytes());
+        payload[2..2 + data.len()].copy_from_slice(data);
+        self.add_field(AD_TYPE_SERVICE_DATA_16, &payload[..2 + data.len()])
+    }
+
+    /// Add a TX Power Level field in dBm
+    pub fn add_tx_power(self, dbm: i8) -> Result<Self, BleError> {
+        self.add_field(AD_TYPE_TX_POWER, &[dbm as u8])
+    }
+
+    /// Append one AD structure: length (type + data), type, data
+    fn add_field(mut self, ad_type: u8, data: &[u8]) -> Result<Self, BleError> {
+        let pos = self.len as usize;
+        if pos + 2 + data.len() > Self::MAX_LEN {
+            return Err(BleError::InvalidParameter);
+        }
+        self.buf[pos] = (data.len() + 1) as u8;
+        self.buf[pos + 1] = ad_type;
+        self.buf[pos + 2..pos + 2 + data.len()].copy_from_slice(data);
+        self.len = (pos + 2 + data.len()) as u8;
+        Ok(self)
+    }
+
+    /// Get the encoded AD structures
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+
+    /// Number of payload bytes used
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Check if no fields have been added
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Default for AdvertisingData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apple's Bluetooth SIG company identifier
+const APPLE_COMPANY_ID: u16 = 0x004C;
+
+/// Apple iBeacon advertisement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IBeacon {
+    /// Beacon proximity UUID (big-endian, as written)
+    pub proximity_uuid: [u8; 16],
+    /// Major group identifier
+    pub major: u16,
+    /// Minor identifier within the group
+    pub minor: u16,
+    /// Calibrated RSSI at 1 m, in dBm
+    pub measured_power: i8,
+}
+
+impl IBeacon {
+    /// Create an iBeacon advertisement
+    pub fn new(proximity_uuid: [u8; 16], major: u16, minor: u16, measured_power: i8) -> Self {
+        Self {
+            proximity_uuid,
+            major,
+            minor,
+            measured_power,
+        }
+    }
+
+    /// Build the 30-byte advertising payload: LE flags followed by Apple
+    /// manufacturer data (subtype 0x02, length 0x15, UUID, major, minor, power)
+    pub fn advertising_data(&self) -> BleResult<AdvertisingData> {
+        let mut payload = [0u8; 23];
+        payload[0] = 0x02; // iBeacon subtype
+        payload[1] = 0x15; // Remaining length: 21 bytes
+        payload[2..18].copy_from_slice(&self.proximity_uuid);
+        payload[18..20].copy_from_slice(&self.major.to_be_bytes());
+        payload[20..22].copy_from_slice(&self.minor.to_be_bytes());
+        payload[22] = self.measured_power as u8;
+
+        AdvertisingData::new()
+            .add_flags(0x06)?
+            .add_manufacturer_data(APPLE_COMPANY_ID, &payload)
+    }
+}
+
+/// Google's Eddystone service UUID
+const EDDYSTONE_SERVICE_UUID: u16 = 0xFEAA;
+
+/// Eddystone-URL frame type
+const EDDYSTONE_FRAME_URL: u8 = 0x10;
+
+/// Longest encoded URL that fits an Eddystone-URL frame
+pub const EDDYSTONE_URL_MAX_LEN: usize = 17;
+
+/// Eddystone-URL scheme prefixes, indexed by their code
+const EDDYSTONE_URL_SCHEMES: [&str; 4] = ["http://www.", "https://www.", "http://", "https://"];
+
+/// Eddystone-URL expansions, indexed by their code
+const EDDYSTONE_URL_EXPANSIONS: [&str; 14] = [
+    ".com/", ".org/", ".edu/", ".net/", ".info/", ".biz/", ".gov/", ".com", ".org", ".edu", ".net",
+    ".info", ".biz", ".gov",
+];
+
+/// Encode a URL with the Eddystone-URL compression scheme
+///
+/// The scheme becomes a one-byte prefix code and common domain endings
+/// one-byte expansion codes; everything else is copied as is. Returns
+/// `None` if the URL has no http(s) scheme or contains characters outside
+/// printable ASCII. The result is not length-checked.
+pub fn encode_eddystone_url(url: &str) -> Option<Vec<u8>> {
+    // "https://www." before "https://", so the longest prefix wins
+    let (scheme, rest) = EDDYSTONE_URL_SCHEMES
+        .iter()
+        .enumerate()
+        .filter(|(_, prefix)| url.starts_with(*prefix))
+        .max_by_key(|(_, prefix)| prefix.len())
+        .map(|(code, prefix)| (code as u8, &url[prefix.len()..]))?;
+
+    let mut encoded = vec![scheme];
+    let mut rest = rest;
+    while let Some(c) = rest.chars().next() {
+        // The expansions with a trailing '/' come first, so they win
+        if let Some((code, expansion)) = EDDYSTONE_URL_EXPANSIONS
+            .iter()
+            .enumerate()
+            .find(|(_, expansion)| rest.starts_with(*expansion))
+        {
+            encoded.push(code as u8);
+            rest = &rest[expansion.len()..];
+            continue;
+        }
+        if !c.is_ascii_graphic() {
+            return None;
+        }
+        encoded.push(c as u8);
+        rest = &rest[1..];
+    }
+    Some(encoded)
+}
+
+/// Build an Eddystone-URL advertisement: LE flags, the Eddystone service
+/// UUID and a URL frame (frame type, TX power at 0 m, encoded URL)
+///
+/// Fails with `InvalidParameter` if the URL cannot be encoded or encodes
+/// to more than 17 bytes.
+pub fn eddystone_url_advertising_data(url: &str, tx_power: i8) -> BleResult<AdvertisingData> {
+    let encoded = encode_eddystone_url(url).ok_or(BleError::InvalidParameter)?;
+    if encoded.len() > EDDYSTONE_URL_MAX_LEN {
+        return Err(BleError::InvalidParameter);
+    }
+
+    let mut frame = [0u8; 2 + EDDYSTONE_URL_MAX_LEN];
+    frame[0] = EDDYSTONE_FRAME_URL;
+    frame[1] = tx_power as u8;
+    frame[2..2 + encoded.len()].copy_from_slice(&encoded);
+
+    AdvertisingData::new()
+        .add_flags(0x06)?
+        .add_service_uuid_16(EDDYSTONE_SERVICE_UUID)?
+        .add_service_data_16(EDDYSTONE_SERVICE_UUID, &frame[..2 + encoded.len()])
+}
+
 /// Handle to a BLE connection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ConnectionHandle(pub u16);
 
+impl ConnectionHandle {
+    /// Placeholder for "no connection" (outside the valid HCI handle range)
+    pub const INVALID: ConnectionHandle = ConnectionHandle(0xFFFF);
+
+    /// Maximum valid HCI connection handle (handles are 12 bits, 0x0F00+ reserved)
+    const MAX: u16 = 0x0EFF;
+
+    /// Create a handle from the first two bytes of an HCI ACL header
+    ///
+    /// The upper 4 bits of the ACL header carry the PB/BC flags and are masked off.
+    pub fn from_acl_header(lo: u8, hi: u8) -> Self {
+        Self(u16::from_le_bytes([lo, hi]) & 0x0FFF)
+    }
+
+    /// Check if this handle is within the valid HCI connection handle range
+    pub fn is_valid(&self) -> bool {
+        self.0 <= Self::MAX
+    }
+}
+
+impl fmt::Display for ConnectionHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:04X}", self.0)
+    }
+}
+
 /// UUID for GATT services and characteristics
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Uuid {
@@ -200,6 +682,82 @@ impl Uuid {
     pub fn from_bytes(bytes: [u8; 16]) -> Self {
         Self { bytes, is_16bit: false }
     }
+
+    /// Parse a UUID string
+    ///
+    /// Accepts the 16-bit short form ("180F") or the full hyphenated 128-bit
+    /// form ("0000180F-0000-1000-8000-00805F9B34FB"). 128-bit UUIDs built on
+    /// the Bluetooth Base UUID are returned as 16-bit UUIDs.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        // from_str_radix alone would also accept a leading '+'
+        if !s.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-') {
+            return None;
+        }
+        if s.len() == 4 {
+            return u16::from_str_radix(s, 16).ok().map(Self::from_u16);
+        }
+
+        // 8-4-4-4-12 hex digit groups
+        let groups: Vec<&str> = s.split('-').collect();
+        let lens: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+        if lens != [8, 4, 4, 4, 12] {
+            return None;
+        }
+
+        let hex: String = groups.concat();
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+        }
+
+        let uuid = Self::from_bytes(bytes);
+        if uuid.is_bluetooth_base() && bytes[0] == 0 && bytes[1] == 0 {
+            return Some(Self::from_u16(u16::from_be_bytes([bytes[2], bytes[3]])));
+        }
+        Some(uuid)
+    }
+
+    /// Check whether the 96-bit suffix matches the Bluetooth Base UUID
+    /// (xxxxxxxx-0000-1000-8000-00805F9B34FB)
+    pub fn is_bluetooth_base(&self) -> bool {
+        self.bytes[4..] == BLUETOOTH_BASE_SUFFIX
+    }
+
+    /// The 16-bit value of a 16-bit UUID
+    fn as_u16(&self) -> Option<u16> {
+        if self.is_16bit {
+            Some(u16::from_be_bytes([self.bytes[0], self.bytes[1]]))
+        } else {
+            None
+        }
+    }
+}
+
+// Last 96 bits of the Bluetooth Base UUID 00000000-0000-1000-8000-00805F9B34FB
+const BLUETOOTH_BASE_SUFFIX: [u8; 12] = [
+    0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x80, 0x5F, 0x9B, 0x34, 0xFB,
+];
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(uuid) = self.as_u16() {
+            return write!(f, "0x{:04X}", uuid);
+        }
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                write!(f, "-")?;
+            }
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq<u16> for Uuid {
+    fn eq(&self, other: &u16) -> bool {
+        self.as_u16() == Some(*other)
+    }
 }
 
 /// Handle to a GATT characteristic
@@ -212,3 +770,322 @@ pub struct CharacteristicHandle {
     /// Value handle
     pub value_handle: u16,
 }
+
+/// Attribute handle of the GATT server's readable characteristic value (UUID 0x1235)
+pub const GATT_READ_HANDLE: u16 = 3;
+
+/// Attribute handle of the GATT server's writable characteristic value (UUID 0x1236)
+pub const GATT_WRITE_HANDLE: u16 = 5;
+
+/// Attribute handle of the Client Characteristic Configuration Descriptor (UUID 0x2902)
+/// for the readable characteristic; writing 0x0001 enables notifications
+pub const GATT_CCCD_HANDLE: u16 = 6;
+
+/// Application callbacks for the GATT server's characteristic values
+///
+/// Used with `ble_run_gatt_server_with_handler` to serve dynamic data
+/// from the service layout of `GattDb::with_custom_service`.
+pub trait GattHandler {
+    /// Return the current value of the attribute at `attr_handle`
+    ///
+    /// Clients read values longer than one ATT packet in pieces (Read
+    /// Blob, up to 64 KiB) and this is called again for each piece, so the
+    /// value should not change between them.
+    fn on_read(&mut self, attr_handle: u16) -> Vec<u8>;
+
+    /// Handle a write of `data` to the attribute at `attr_handle`
+    fn on_write(&mut self, attr_handle: u16, data: &[u8]);
+}
+
+// GATT attribute types
+const GATT_PRIMARY_SERVICE_UUID: u16 = 0x2800;
+const GATT_CHARACTERISTIC_UUID: u16 = 0x2803;
+const GATT_CCCD_UUID: u16 = 0x2902;
+
+/// Characteristic property: readable
+pub const CHR_PROP_READ: u8 = 0x02;
+/// Characteristic property: writable without response
+pub const CHR_PROP_WRITE_NO_RSP: u8 = 0x04;
+/// Characteristic property: writable
+pub const CHR_PROP_WRITE: u8 = 0x08;
+/// Characteristic property: supports notifications
+pub const CHR_PROP_NOTIFY: u8 = 0x10;
+
+/// Source of an attribute's value in a `GattDb`
+#[derive(Debug, Clone)]
+pub enum AttributeValue {
+    /// Fixed bytes (declarations and constant characteristic values)
+    Static(Vec<u8>),
+    /// Read and written through the server's `GattHandler`
+    Handler,
+    /// Single byte read from a shared atomic (e.g. battery level)
+    Byte(Arc<AtomicU8>),
+    /// Bytes held by the server; client writes replace them
+    Stored(Arc<Mutex<Vec<u8>>>),
+    /// Client Characteristic Configuration, stored by the server
+    Cccd,
+}
+
+/// A single attribute in a `GattDb`
+#[derive(Debug, Clone)]
+pub struct GattAttribute {
+    /// Attribute handle
+    pub handle: u16,
+    /// Attribute type (16-bit UUID)
+    pub uuid: u16,
+    /// Attribute value
+    pub value: AttributeValue,
+}
+
+/// GATT attribute table served by the GATT server
+///
+/// Attributes are kept sorted by handle; each `add_*` call appends at the
+/// next available handle, starting from 1.
+#[derive(Debug, Clone, Default)]
+pub struct GattDb {
+    attributes: Vec<GattAttribute>,
+}
+
+impl GattDb {
+    /// Create an empty attribute table
+    pub fn new() -> Self {
+        Self { attributes: Vec::new() }
+    }
+
+    /// Table with the custom service used by `ble_run_gatt_server_with_handler`
+    ///
+    /// Handle 1: Primary Service 0x1234
+    /// Handle 2/3: Characteristic 0x1235 (read, notify) - `GATT_READ_HANDLE`
+    /// Handle 4/5: Characteristic 0x1236 (read, write) - `GATT_WRITE_HANDLE`
+    /// Handle 6: CCCD for handle 3 - `GATT_CCCD_HANDLE`
+    pub fn with_custom_service() -> Self {
+        let mut db = Self::new();
+        db.add_primary_service(0x1234);
+        db.add_characteristic(0x1235, CHR_PROP_READ | CHR_PROP_NOTIFY, AttributeValue::Handler);
+        db.add_characteristic(0x1236, CHR_PROP_READ | CHR_PROP_WRITE, AttributeValue::Handler);
+        db.add_cccd();
+        db
+    }
+
+    /// Handle the next added attribute will get
+    pub fn next_handle(&self) -> u16 {
+        self.attributes.last().map_or(1, |attr| attr.handle + 1)
+    }
+
+    /// Append an attribute and return its handle
+    pub fn add_attribute(&mut self, uuid: u16, value: AttributeValue) -> u16 {
+        let handle = self.next_handle();
+        self.attributes.push(GattAttribute { handle, uuid, value });
+        handle
+    }
+
+    /// Start a primary service and return its declaration handle
+    pub fn add_primary_service(&mut self, uuid: u16) -> u16 {
+        let value = AttributeValue::Static(uuid.to_le_bytes().to_vec());
+        self.add_attribute(GATT_PRIMARY_SERVICE_UUID, value)
+    }
+
+    /// Add a characteristic declaration followed by its value to the current
+    /// service and return the value handle
+    pub fn add_characteristic(&mut self, uuid: u16, properties: u8, value: AttributeValue) -> u16 {
+        let value_handle = self.next_handle() + 1;
+        let mut decl = vec![properties];
+        decl.extend_from_slice(&value_handle.to_le_bytes());
+        decl.extend_from_slice(&uuid.to_le_bytes());
+        self.add_attribute(GATT_CHARACTERISTIC_UUID, AttributeValue::Static(decl));
+        self.add_attribute(uuid, value)
+    }
+
+    /// Add a Client Characteristic Configuration Descriptor for the last
+    /// characteristic and return its handle
+    pub fn add_cccd(&mut self) -> u16 {
+        self.add_attribute(GATT_CCCD_UUID, AttributeValue::Cccd)
+    }
+
+    /// Look up an attribute by handle
+    pub fn get(&self, handle: u16) -> Option<&GattAttribute> {
+        self.attributes
+            .binary_search_by_key(&handle, |attr| attr.handle)
+            .ok()
+            .map(|index| &self.attributes[index])
+    }
+
+    /// All attributes, sorted by handle
+    pub fn attributes(&self) -> &[GattAttribute] {
+        &self.attributes
+    }
+
+    /// Primary services as (start handle, end handle, service UUID)
+    pub fn services(&self) -> Vec<(u16, u16, u16)> {
+        let mut services = Vec::new();
+        for (index, attr) in self.attributes.iter().enumerate() {
+            if attr.uuid != GATT_PRIMARY_SERVICE_UUID {
+                continue;
+            }
+            let uuid = match &attr.value {
+                AttributeValue::Static(v) if v.len() == 2 => u16::from_le_bytes([v[0], v[1]]),
+                _ => continue,
+            };
+            // A service ends right before the next service declaration
+            let end = self.attributes[index + 1..]
+                .iter()
+                .take_while(|a| a.uuid != GATT_PRIMARY_SERVICE_UUID)
+                .last()
+                .map_or(attr.handle, |a| a.handle);
+            services.push((attr.handle, end, uuid));
+        }
+        services
+    }
+}
+
+/// Characteristic of a `GattService`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GattCharacteristic {
+    /// Characteristic UUID
+    pub uuid: u16,
+    /// Extra `CHR_PROP_*` flags; write and notify are added from the fields below
+    pub properties: u8,
+    /// Value served until a client writes one
+    pub initial_value: Vec<u8>,
+    /// Clients may write the value (adds `CHR_PROP_WRITE`)
+    pub writable: bool,
+    /// Clients may subscribe to notifications (adds `CHR_PROP_NOTIFY` and a CCCD)
+    pub notify: bool,
+}
+
+/// Primary service of a `GattServerConfig`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GattService {
+    /// Service UUID
+    pub uuid: u16,
+    /// Characteristics in handle order
+    pub characteristics: Vec<GattCharacteristic>,
+}
+
+/// Services and advertising parameters for `ble_run_gatt_server`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GattServerConfig {
+    /// Advertised device name (truncated to 20 bytes)
+    pub name: String,
+    /// How long to run before returning
+    pub timeout_ms: u32,
+    /// Services in handle order
+    pub services: Vec<GattService>,
+}
+
+impl Default for GattServerConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            timeout_ms: 60_000,
+            services: Vec::new(),
+        }
+    }
+}
+
+impl GattServerConfig {
+    /// Create a builder, starting from the defaults (no services, 60 s)
+    pub fn builder() -> GattServerConfigBuilder {
+        GattServerConfigBuilder {
+            config: Self::default(),
+        }
+    }
+
+    /// Attribute table for the configured services
+    ///
+    /// Handles are assigned from 1 without gaps: for each service its
+    /// declaration, then for each characteristic its declaration, its value
+    /// and (with `notify`) a CCCD. Writable values are `Stored`, the rest
+    /// `Static`.
+    pub fn build_db(&self) -> GattDb {
+        let mut db = GattDb::new();
+        for service in &self.services {
+            db.add_primary_service(service.uuid);
+            for chr in &service.characteristics {
+                let mut properties = chr.properties;
+                let initial = chr.initial_value.clone();
+                let value = if chr.writable {
+                    properties |= CHR_PROP_WRITE;
+                    AttributeValue::Stored(Arc::new(Mutex::new(initial)))
+                } else {
+                    AttributeValue::Static(initial)
+                };
+                if chr.notify {
+                    properties |= CHR_PROP_NOTIFY;
+                }
+                db.add_characteristic(chr.uuid, properties, value);
+                if chr.notify {
+                    db.add_cccd();
+                }
+            }
+        }
+        db
+    }
+}
+
+/// Builder for `GattServerConfig`
+#[derive(Debug, Clone)]
+pub struct GattServerConfigBuilder {
+    config: GattServerConfig,
+}
+
+impl GattServerConfigBuilder {
+    /// Set the advertised device name
+    pub fn name(mut self, name: &str) -> Self {
+        self.config.name = name.to_string();
+        self
+    }
+
+    /// Set how long the server runs
+    pub fn timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.config.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Append a service after the ones added so far
+    pub fn add_service(mut self, service: GattService) -> Self {
+        self.config.services.push(service);
+        self
+    }
+
+    /// Finish building the configuration
+    pub fn build(self) -> GattServerConfig {
+        self.config
+    }
+}
+
+/// Device Information Service (0x180A) contents
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceInfoService<'a> {
+    /// Manufacturer Name String (0x2A29)
+    pub manufacturer: &'a str,
+    /// Model Number String (0x2A24)
+    pub model: &'a str,
+    /// Firmware Revision String (0x2A26)
+    pub firmware_rev: &'a str,
+}
+
+/// Add the standard Battery Service (0x180F)
+///
+/// Battery Level (0x2A19) is readable and notify-capable; reads return
+/// `level` (0-100 %) as a single byte.
+pub fn gatt_add_battery_service(db: &mut GattDb, level: Arc<AtomicU8>) {
+    db.add_primary_service(0x180F);
+    db.add_characteristic(0x2A19, CHR_PROP_READ | CHR_PROP_NOTIFY, AttributeValue::Byte(level));
+    db.add_cccd();
+}
+
+/// Add the standard Device Information Service (0x180A) with read-only
+/// manufacturer, model and firmware revision strings
+pub fn gatt_add_device_info_service(db: &mut GattDb, info: &DeviceInfoService) {
+    db.add_primary_service(0x180A);
+    let strings = [
+        (0x2A29, info.manufacturer),
+        (0x2A24, info.model),
+        (0x2A26, info.firmware_rev),
+    ];
+    for (uuid, value) in strings {
+        let value = AttributeValue::Static(value.as_bytes().to_vec());
+        db.add_characteristic(uuid, CHR_PROP_READ, value);
+    }
+}
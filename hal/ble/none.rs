@@ -4,7 +4,9 @@
 //! All functions return NotSupported error.
 
 use super::{
-    BleAddress, BleError, BleResult, CharacteristicHandle, ConnectionHandle, ScanResult, Uuid,
+    AdvertisingData, BleAddress, BleError, BleResult, BleScanner, CharacteristicHandle,
+    ConnectionHandle, GattDb, GattHandler, GattServerConfig, IBeacon, ScanCallback, ScanHandle,
+    ScanResult, Uuid,
 };
 
 /// Initialize BLE subsystem (stub: returns NotSupported)
@@ -22,6 +24,11 @@ pub fn ble_start_scan(_timeout_ms: u32) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Scan in the background (stub: returns NotSupported)
+pub fn ble_start_scan_continuous(_callback: ScanCallback) -> BleResult<ScanHandle> {
+    Err(BleError::NotSupported)
+}
+
 /// Stop BLE scanning (stub: returns NotSupported)
 pub fn ble_stop_scan() -> BleResult<()> {
     Err(BleError::NotSupported)
@@ -32,6 +39,15 @@ pub fn ble_get_scan_results() -> BleResult<Vec<ScanResult>> {
     Err(BleError::NotSupported)
 }
 
+/// Run a filtered scan (stub: returns NotSupported)
+pub(super) fn scan_with_filter(
+    _filter: &BleScanner,
+    _timeout_ms: u32,
+    _stop_on_first: bool,
+) -> BleResult<Vec<ScanResult>> {
+    Err(BleError::NotSupported)
+}
+
 /// Connect to a BLE device (stub: returns NotSupported)
 pub fn ble_connect(_address: &BleAddress, _timeout_ms: u32) -> BleResult<ConnectionHandle> {
     Err(BleError::NotSupported)
@@ -42,11 +58,29 @@ pub fn ble_disconnect(_handle: ConnectionHandle) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Read the RSSI of a connection (stub: returns NotSupported)
+pub fn ble_get_rssi(_handle: ConnectionHandle) -> BleResult<i8> {
+    Err(BleError::NotSupported)
+}
+
+/// Read the transmit power level of the active connection (stub: returns NotSupported)
+pub fn ble_get_tx_power() -> BleResult<i8> {
+    Err(BleError::NotSupported)
+}
+
 /// Discover GATT services (stub: returns NotSupported)
 pub fn gatt_discover_services(_handle: ConnectionHandle) -> BleResult<Vec<Uuid>> {
     Err(BleError::NotSupported)
 }
 
+/// Discover the characteristics of a service (stub: returns NotSupported)
+pub fn gatt_discover_characteristics(
+    _conn: ConnectionHandle,
+    _service_uuid: Uuid,
+) -> BleResult<Vec<CharacteristicHandle>> {
+    Err(BleError::NotSupported)
+}
+
 /// Read a GATT characteristic (stub: returns NotSupported)
 pub fn gatt_read_characteristic(_char: CharacteristicHandle) -> BleResult<Vec<u8>> {
     Err(BleError::NotSupported)
@@ -62,12 +96,66 @@ pub fn ble_start_advertising(_name: &str) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Start BLE advertising with a custom payload (stub: returns NotSupported)
+pub fn ble_start_advertising_with_data(_data: &AdvertisingData) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Advertise as an Apple iBeacon (stub: returns NotSupported)
+pub fn ble_advertise_ibeacon(_beacon: &IBeacon) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Advertise an Eddystone-URL beacon (stub: returns NotSupported)
+pub fn ble_advertise_eddystone_url(_url: &str, _tx_power: i8) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
 /// Stop BLE advertising (stub: returns NotSupported)
 pub fn ble_stop_advertising() -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Rotate the random address (stub: returns NotSupported)
+pub fn ble_rotate_random_address(_interval_secs: u64) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Stop random address rotation (stub: returns NotSupported)
+pub fn ble_stop_address_rotation() -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Get the own random address (stub: returns NotSupported)
+pub fn ble_get_random_address() -> BleResult<BleAddress> {
+    Err(BleError::NotSupported)
+}
+
 /// Run a GATT server (stub: returns NotSupported)
-pub fn ble_run_gatt_server(_name: &str, _timeout_ms: u32) -> BleResult<()> {
+pub fn ble_run_gatt_server(_config: &GattServerConfig) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Run a GATT server with custom handlers (stub: returns NotSupported)
+pub fn ble_run_gatt_server_with_handler(
+    _name: &str,
+    _timeout_ms: u32,
+    _handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Run a GATT server with a custom attribute table (stub: returns NotSupported)
+pub fn ble_run_gatt_server_with_db(
+    _name: &str,
+    _timeout_ms: u32,
+    _db: &GattDb,
+    _handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Send a GATT notification (stub: returns NotSupported)
+pub fn gatt_send_notification(_conn_handle: u16, _attr_handle: u16, _data: &[u8]) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
@@ -5,7 +5,9 @@
 //! callback handling in Rust.
 
 use super::{
-    BleAddress, BleError, BleResult, CharacteristicHandle, ConnectionHandle, ScanResult, Uuid,
+    AdvertisingData, BleAddress, BleError, BleResult, BleScanner, CharacteristicHandle,
+    ConnectionHandle, GattDb, GattHandler, GattServerConfig, IBeacon, ScanCallback, ScanHandle,
+    ScanResult, Uuid,
 };
 use core::ffi::{c_char, c_int};
 use std::ffi::CString;
@@ -30,6 +32,9 @@ extern "C" {
     /// Check if connected
     fn rust_ble_wrapper_is_connected() -> c_int;
 
+    /// Read the RSSI of a connection
+    fn rust_ble_wrapper_get_rssi(conn_handle: u16, rssi_out: *mut i8) -> c_int;
+
     /// Run BLE host task (blocking)
     fn rust_ble_wrapper_run();
 
@@ -84,6 +89,13 @@ pub fn ble_start_scan(_timeout_ms: u32) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Scan in the background (not supported)
+///
+/// Scanning requires the central role, which the wrapper does not implement.
+pub fn ble_start_scan_continuous(_callback: ScanCallback) -> BleResult<ScanHandle> {
+    Err(BleError::NotSupported)
+}
+
 /// Stop BLE scanning
 pub fn ble_stop_scan() -> BleResult<()> {
     Err(BleError::NotSupported)
@@ -110,6 +122,27 @@ pub fn ble_start_advertising(name: &str) -> BleResult<()> {
     }
 }
 
+/// Start BLE advertising with a custom payload (not supported)
+///
+/// The C wrapper builds its own advertising data from the device name.
+pub fn ble_start_advertising_with_data(_data: &AdvertisingData) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Advertise as an Apple iBeacon (not supported)
+///
+/// The C wrapper builds its own advertising data from the device name.
+pub fn ble_advertise_ibeacon(_beacon: &IBeacon) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Advertise an Eddystone-URL beacon (not supported)
+///
+/// The C wrapper builds its own advertising data from the device name.
+pub fn ble_advertise_eddystone_url(_url: &str, _tx_power: i8) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
 /// Stop BLE advertising
 pub fn ble_stop_advertising() -> BleResult<()> {
     let rc = unsafe { rust_ble_wrapper_stop_advertising() };
@@ -120,6 +153,23 @@ pub fn ble_stop_advertising() -> BleResult<()> {
     }
 }
 
+/// Rotate the random address (not supported)
+///
+/// NimBLE manages the own address inside the C wrapper.
+pub fn ble_rotate_random_address(_interval_secs: u64) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Stop random address rotation (not supported)
+pub fn ble_stop_address_rotation() -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Get the own random address (not supported)
+pub fn ble_get_random_address() -> BleResult<BleAddress> {
+    Err(BleError::NotSupported)
+}
+
 /// Run a simple GATT server
 ///
 /// This starts advertising and waits for connections. When a client connects
@@ -127,13 +177,15 @@ pub fn ble_stop_advertising() -> BleResult<()> {
 /// printed. The read characteristic (UUID 0x1235) returns "Hello from RustCam!"
 /// by default.
 ///
-/// # Arguments
-/// * `name` - Device name for advertising
-/// * `timeout_ms` - Maximum time to run (0 for no timeout)
+/// The NimBLE wrapper registers its service at build time, so only
+/// `config.name` and `config.timeout_ms` (0 for no timeout) are used;
+/// `config.services` is ignored.
 ///
 /// # Returns
 /// Ok(()) when timeout expires or error occurs
-pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
+pub fn ble_run_gatt_server(config: &GattServerConfig) -> BleResult<()> {
+    let (name, timeout_ms) = (config.name.as_str(), config.timeout_ms);
+
     // Set the read message
     let c_hello = CString::new("Hello from RustCam!").map_err(|_| BleError::InvalidParameter)?;
     unsafe { rust_ble_wrapper_gatt_set_read_msg(c_hello.as_ptr()); }
@@ -194,6 +246,30 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
     Ok(())
 }
 
+/// Run the GATT server with custom handlers (not supported)
+///
+/// The C wrapper only serves a fixed NUL-terminated read message, so
+/// dynamic binary characteristic values cannot be provided.
+pub fn ble_run_gatt_server_with_handler(
+    _name: &str,
+    _timeout_ms: u32,
+    _handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
+/// Run the GATT server with a custom attribute table (not supported)
+///
+/// The NimBLE wrapper registers its services at build time.
+pub fn ble_run_gatt_server_with_db(
+    _name: &str,
+    _timeout_ms: u32,
+    _db: &GattDb,
+    _handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
 /// Set the message returned when the read characteristic is read
 pub fn gatt_set_read_message(msg: &str) -> BleResult<()> {
     let c_msg = CString::new(msg).map_err(|_| BleError::InvalidParameter)?;
@@ -219,6 +295,15 @@ pub fn gatt_get_command() -> Option<Vec<u8>> {
     }
 }
 
+/// Run a filtered scan (central role - not supported)
+pub(super) fn scan_with_filter(
+    _filter: &BleScanner,
+    _timeout_ms: u32,
+    _stop_on_first: bool,
+) -> BleResult<Vec<ScanResult>> {
+    Err(BleError::NotSupported)
+}
+
 /// Connect to a BLE device (central role - not supported)
 pub fn ble_connect(_address: &BleAddress, _timeout_ms: u32) -> BleResult<ConnectionHandle> {
     Err(BleError::NotSupported)
@@ -229,11 +314,42 @@ pub fn ble_disconnect(_handle: ConnectionHandle) -> BleResult<()> {
     Err(BleError::NotSupported)
 }
 
+/// Read the RSSI of a connection in dBm
+pub fn ble_get_rssi(handle: ConnectionHandle) -> BleResult<i8> {
+    let mut rssi: i8 = 0;
+    let rc = unsafe { rust_ble_wrapper_get_rssi(handle.0, &mut rssi) };
+
+    if rc == 0 {
+        Ok(rssi)
+    } else if rc == -libc::ENOTCONN {
+        Err(BleError::DeviceNotFound)
+    } else if rc == -libc::ENODEV {
+        Err(BleError::NotInitialized)
+    } else if rc == -libc::ENOTSUP {
+        Err(BleError::NotSupported)
+    } else {
+        Err(BleError::SocketError)
+    }
+}
+
+/// Read the transmit power level of the active connection (not supported)
+pub fn ble_get_tx_power() -> BleResult<i8> {
+    Err(BleError::NotSupported)
+}
+
 /// Discover GATT services (central role - not supported)
 pub fn gatt_discover_services(_handle: ConnectionHandle) -> BleResult<Vec<Uuid>> {
     Err(BleError::NotSupported)
 }
 
+/// Discover the characteristics of a service (central role - not supported)
+pub fn gatt_discover_characteristics(
+    _conn: ConnectionHandle,
+    _service_uuid: Uuid,
+) -> BleResult<Vec<CharacteristicHandle>> {
+    Err(BleError::NotSupported)
+}
+
 /// Read a GATT characteristic (central role - not supported)
 pub fn gatt_read_characteristic(_char: CharacteristicHandle) -> BleResult<Vec<u8>> {
     Err(BleError::NotSupported)
@@ -244,6 +360,17 @@ pub fn gatt_write_characteristic(_char: CharacteristicHandle, _data: &[u8]) -> B
     Err(BleError::NotSupported)
 }
 
+/// Send a GATT notification (not supported)
+///
+/// The C wrapper does not expose NimBLE's notification API.
+pub fn gatt_send_notification(
+    _conn_handle: u16,
+    _attr_handle: u16,
+    _data: &[u8],
+) -> BleResult<()> {
+    Err(BleError::NotSupported)
+}
+
 /// Print debug status information for troubleshooting GATT issues
 pub fn ble_debug_print_status() {
     unsafe { rust_ble_wrapper_debug_print_status(); }
@@ -7,13 +7,18 @@
 //! NuttX implements the Linux BlueZ socket API for Bluetooth support.
 
 use super::{
-    AddressType, BleAddress, BleError, BleResult, CharacteristicHandle, ConnectionHandle,
-    ScanResult, Uuid,
+    eddystone_url_advertising_data, AddressType, AdvertisingData, AttributeValue, BleAddress,
+    BleError, BleResult, BleScanner, CharacteristicHandle, ConnectionHandle, GattDb, GattHandler,
+    GattServerConfig, IBeacon, ScanCallback, ScanHandle, ScanResult, Uuid, AD_TYPE_COMPLETE_NAME,
+    GATT_CCCD_HANDLE, GATT_CHARACTERISTIC_UUID, GATT_PRIMARY_SERVICE_UUID, GATT_READ_HANDLE,
 };
+use std::collections::{HashMap, HashSet};
 use socket2::{Domain, Protocol, Socket, Type};
 use std::io::{Read, Write};
 use std::os::unix::io::AsRawFd;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 // Bluetooth socket constants (Linux BlueZ extension)
@@ -28,11 +33,15 @@ const HCI_CHANNEL_USER: u16 = 1; // Exclusive access, bypasses BlueZ
 
 // HCI packet types
 const HCI_COMMAND_PKT: u8 = 0x01;
+const HCI_ACLDATA_PKT: u8 = 0x02;
 const HCI_EVENT_PKT: u8 = 0x04;
 
 // HCI commands (OGF << 10 | OCF)
+const HCI_OP_DISCONNECT: u16 = 0x0406;
 const HCI_OP_RESET: u16 = 0x0C03;
 const HCI_OP_SET_EVENT_MASK: u16 = 0x0C01;
+const HCI_OP_READ_TX_POWER_LEVEL: u16 = 0x0C2D;
+const HCI_OP_READ_RSSI: u16 = 0x1405;
 const HCI_OP_LE_SET_EVENT_MASK: u16 = 0x2001;
 const HCI_OP_LE_SET_RANDOM_ADDR: u16 = 0x2005;
 const HCI_OP_LE_SET_ADV_PARAM: u16 = 0x2006;
@@ -41,9 +50,12 @@ const HCI_OP_LE_SET_SCAN_RSP_DATA: u16 = 0x2009;
 const HCI_OP_LE_SET_ADV_ENABLE: u16 = 0x200A;
 const HCI_OP_LE_SET_SCAN_PARAM: u16 = 0x200B;
 const HCI_OP_LE_SET_SCAN_ENABLE: u16 = 0x200C;
+const HCI_OP_LE_CREATE_CONN: u16 = 0x200D;
+const HCI_OP_LE_CREATE_CONN_CANCEL: u16 = 0x200E;
 
 // HCI events
 const HCI_EV_DISCONN_COMPLETE: u8 = 0x05;
+const HCI_EV_CMD_STATUS: u8 = 0x0F;
 const HCI_EV_LE_META: u8 = 0x3E;
 const HCI_EV_LE_CONN_COMPLETE: u8 = 0x01;
 const HCI_EV_LE_ADVERTISING_REPORT: u8 = 0x02;
@@ -51,6 +63,9 @@ const HCI_EV_LE_ADVERTISING_REPORT: u8 = 0x02;
 // L2CAP
 const L2CAP_CID_ATT: u16 = 0x0004; // ATT channel
 
+// ATT default MTU (we never negotiate a larger one)
+const ATT_DEFAULT_MTU: usize = 23;
+
 // ATT opcodes
 const ATT_OP_ERROR_RSP: u8 = 0x01;
 const ATT_OP_MTU_REQ: u8 = 0x02;
@@ -61,15 +76,34 @@ const ATT_OP_READ_BY_TYPE_REQ: u8 = 0x08;
 const ATT_OP_READ_BY_TYPE_RSP: u8 = 0x09;
 const ATT_OP_READ_REQ: u8 = 0x0A;
 const ATT_OP_READ_RSP: u8 = 0x0B;
+const ATT_OP_READ_BLOB_REQ: u8 = 0x0C;
+const ATT_OP_READ_BLOB_RSP: u8 = 0x0D;
 const ATT_OP_READ_BY_GROUP_REQ: u8 = 0x10;
 const ATT_OP_READ_BY_GROUP_RSP: u8 = 0x11;
 const ATT_OP_WRITE_REQ: u8 = 0x12;
 const ATT_OP_WRITE_RSP: u8 = 0x13;
+const ATT_OP_HANDLE_VALUE_NOTIF: u8 = 0x1B;
 const ATT_OP_WRITE_CMD: u8 = 0x52;
 
 // ATT error codes
+const ATT_ERR_INVALID_HANDLE: u8 = 0x01;
+const ATT_ERR_WRITE_NOT_PERMITTED: u8 = 0x03;
+const ATT_ERR_INVALID_OFFSET: u8 = 0x07;
 const ATT_ERR_ATTR_NOT_FOUND: u8 = 0x0A;
 
+// Time to wait for the peer's response to an ATT request
+const ATT_RSP_TIMEOUT: Duration = Duration::from_millis(5000);
+
+// GATT server notification tick period
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(2);
+
+// Advertising flags: LE General Discoverable, BR/EDR Not Supported
+const ADV_FLAGS_GENERAL_DISC: u8 = 0x06;
+
+// Advertising types
+const ADV_IND: u8 = 0x00; // Connectable undirected
+const ADV_NONCONN_IND: u8 = 0x03; // Non-connectable undirected
+
 // Scan types
 const LE_SCAN_ACTIVE: u8 = 0x01;
 
@@ -77,6 +111,15 @@ const LE_SCAN_ACTIVE: u8 = 0x01;
 const LE_PUBLIC_ADDRESS: u8 = 0x00;
 const LE_RANDOM_ADDRESS: u8 = 0x01;
 
+// Disconnect reason: Remote User Terminated Connection
+const HCI_ERR_REMOTE_USER_TERM: u8 = 0x13;
+
+// Time to wait for Disconnection Complete after HCI_Disconnect
+const DISCONNECT_TIMEOUT: Duration = Duration::from_millis(2000);
+
+// Default static random address (HCI byte order: LSB first, so EF:BE:FE:CA:DE:C0)
+const DEFAULT_RANDOM_ADDR: [u8; 6] = [0xC0, 0xDE, 0xCA, 0xFE, 0xBE, 0xEF];
+
 // Maximum scan results to store
 const MAX_SCAN_RESULTS: usize = 32;
 
@@ -214,6 +257,11 @@ impl HciSocket {
         (&self.socket).read(buf)
     }
 
+    /// Duplicate the underlying socket (for writing from another thread)
+    fn try_clone(&self) -> BleResult<Socket> {
+        self.socket.try_clone().map_err(|_| BleError::SocketError)
+    }
+
     /// Initialize controller for USER channel (reset + set event masks)
     fn init_user_channel(&mut self) -> BleResult<()> {
         // Send HCI Reset
@@ -233,6 +281,12 @@ impl HciSocket {
 
     /// Send HCI command and wait for command complete
     fn send_cmd_wait(&mut self, opcode: u16, params: &[u8]) -> BleResult<()> {
+        self.send_cmd_return(opcode, params).map(|_| ())
+    }
+
+    /// Send HCI command, wait for command complete and return the return
+    /// parameters following the status byte
+    fn send_cmd_return(&mut self, opcode: u16, params: &[u8]) -> BleResult<Vec<u8>> {
         // Build command packet
         let mut buf = [0u8; 260];
         buf[0] = HCI_COMMAND_PKT;
@@ -250,19 +304,10 @@ impl HciSocket {
         let mut resp = [0u8; 260];
         for _ in 0..10 {
             match self.read(&mut resp) {
-                Ok(len) if len >= 7 && resp[0] == HCI_EVENT_PKT && resp[1] == 0x0E => {
-                    let resp_opcode = u16::from_le_bytes([resp[4], resp[5]]);
-                    if resp_opcode == opcode {
-                        let status = resp[6];
-                        if status == 0 {
-                            return Ok(());
-                        } else {
-                            eprintln!("  [DEBUG] Command 0x{:04X} failed with status 0x{:02X}", opcode, status);
-                            return Err(BleError::SocketError);
-                        }
-                    }
-                }
-                Ok(_) => continue, // Not command complete, keep waiting
+                Ok(len) => match parse_command_complete(&resp[..len], opcode) {
+                    Some(result) => return result.map(<[u8]>::to_vec),
+                    None => continue, // Not our command complete, keep waiting
+                },
                 Err(_) => break,
             }
         }
@@ -270,6 +315,28 @@ impl HciSocket {
     }
 }
 
+/// Return parameters of a Command Complete event (packet type byte first)
+/// for `opcode`, after its status byte
+///
+/// Returns `None` for any other event, and `SocketError` if the command
+/// failed.
+#[doc(hidden)] // pub for tests
+pub fn parse_command_complete(event: &[u8], opcode: u16) -> Option<BleResult<&[u8]>> {
+    if event.len() < 7 || event[0] != HCI_EVENT_PKT || event[1] != 0x0E {
+        return None;
+    }
+    if u16::from_le_bytes([event[4], event[5]]) != opcode {
+        return None;
+    }
+
+    let status = event[6];
+    if status != 0 {
+        eprintln!("  [DEBUG] Command 0x{:04X} failed with status 0x{:02X}", opcode, status);
+        return Some(Err(BleError::SocketError));
+    }
+    Some(Ok(&event[7..]))
+}
+
 // Socket automatically closes when dropped - no manual cleanup needed!
 
 // =============================================================================
@@ -281,20 +348,54 @@ struct BleState {
     scanning: bool,
     advertising: bool,
     scan_results: Vec<ScanResult>,
+    /// Connection established by `ble_connect` (central role)
+    connection: Option<ConnectionHandle>,
+    /// Own random address used for advertising (HCI byte order)
+    random_addr: [u8; 6],
+    /// Address rotation thread started by `ble_rotate_random_address`
+    rotation_thread: Option<JoinHandle<()>>,
+    rotation_stop: Option<Arc<AtomicBool>>,
 }
 
-impl BleState {
-    const fn new() -> Self {
+impl Default for BleState {
+    fn default() -> Self {
         Self {
             socket: None,
             scanning: false,
             advertising: false,
             scan_results: Vec::new(),
+            connection: None,
+            random_addr: DEFAULT_RANDOM_ADDR,
+            rotation_thread: None,
+            rotation_stop: None,
         }
     }
 }
 
-static STATE: Mutex<BleState> = Mutex::new(BleState::new());
+impl BleState {
+    /// Stop the address rotation thread, if running
+    fn stop_rotation(&mut self) {
+        if let Some(stop) = self.rotation_stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        if let Some(handle) = self.rotation_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Created by the first `ble_initialize`
+static STATE: OnceLock<Mutex<BleState>> = OnceLock::new();
+
+/// Lock the global state; `NotInitialized` before the first `ble_initialize`
+fn lock_state() -> BleResult<MutexGuard<'static, BleState>> {
+    let state = STATE.get().ok_or(BleError::NotInitialized)?;
+    state.lock().map_err(|_| BleError::SocketError)
+}
+
+/// Clone of the HCI socket while the GATT server runs, used to send
+/// notifications without taking the `STATE` lock the server holds
+static NOTIFY_SOCKET: Mutex<Option<Socket>> = Mutex::new(None);
 
 // =============================================================================
 // Public API
@@ -302,7 +403,8 @@ static STATE: Mutex<BleState> = Mutex::new(BleState::new());
 
 /// Initialize BLE subsystem
 pub fn ble_initialize() -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let state = STATE.get_or_init(|| Mutex::new(BleState::default()));
+    let mut state = state.lock().map_err(|_| BleError::SocketError)?;
 
     if state.socket.is_some() {
         return Err(BleError::AlreadyInitialized);
@@ -319,7 +421,7 @@ pub fn ble_initialize() -> BleResult<()> {
 
 /// Deinitialize BLE subsystem
 pub fn ble_deinitialize() -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -333,13 +435,22 @@ pub fn ble_deinitialize() -> BleResult<()> {
         state.scanning = false;
     }
 
+    state.stop_rotation();
+
+    // Drop any central-role connection
+    if let Some(handle) = state.connection.take() {
+        if let Some(ref mut socket) = state.socket {
+            let _ = send_disconnect(socket, handle);
+        }
+    }
+
     state.socket = None; // Socket automatically closes
     Ok(())
 }
 
 /// Start BLE scanning
 pub fn ble_start_scan(timeout_ms: u32) -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -349,26 +460,107 @@ pub fn ble_start_scan(timeout_ms: u32) -> BleResult<()> {
         return Ok(()); // Already scanning
     }
 
-    // Clear previous scan results
-    state.scan_results.clear();
+    let filter = BleScanner::new().with_max_results(MAX_SCAN_RESULTS);
+    let socket = state.socket.as_mut().unwrap();
+    state.scan_results = run_scan(socket, timeout_ms, &filter, false)?;
+
+    Ok(())
+}
+
+/// Scan in the background, calling `callback` once for each new device
+///
+/// Returns immediately; reports are read on a separate thread until the
+/// returned handle is stopped, which also disables scanning. Fails with
+/// `ScanError` if a scan is already running.
+pub fn ble_start_scan_continuous(callback: ScanCallback) -> BleResult<ScanHandle> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    if state.scanning {
+        return Err(BleError::ScanError);
+    }
 
-    // Get mutable reference to socket
     let socket = state.socket.as_mut().unwrap();
+    let reader = HciSocket { socket: socket.try_clone()?, channel: socket.channel };
+    enable_scanning(socket)?;
+    // Short timeout so the thread notices the stop flag
+    socket.set_read_timeout(Duration::from_millis(100))?;
+    state.scanning = true;
 
-    // Set scan parameters: active scan, 10ms interval, 10ms window
-    let scan_params = [
-        LE_SCAN_ACTIVE,
-        0x10, 0x00,         // Interval: 16 * 0.625ms = 10ms
-        0x10, 0x00,         // Window: 16 * 0.625ms = 10ms
-        LE_PUBLIC_ADDRESS,
-        0x00,               // Accept all advertisements
-    ];
-    send_hci_cmd(socket, HCI_OP_LE_SET_SCAN_PARAM, &scan_params)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    let thread = thread::spawn(move || continuous_scan_loop(reader, &callback, &thread_stop));
 
-    std::thread::sleep(Duration::from_millis(10)); // Wait for command to complete
+    Ok(ScanHandle { stop, thread })
+}
 
-    // Enable scanning
-    send_hci_cmd(socket, HCI_OP_LE_SET_SCAN_ENABLE, &[0x01, 0x00])?;
+fn continuous_scan_loop(mut socket: HciSocket, callback: &ScanCallback, stop: &AtomicBool) {
+    let filter = BleScanner::new();
+    let mut seen = HashSet::new();
+    let mut buf = [0u8; 258];
+
+    while !stop.load(Ordering::Relaxed) {
+        match socket.read(&mut buf) {
+            Ok(len) => {
+                if let Some(result) = parse_filtered_event(&buf[..len], &filter) {
+                    if seen.insert(result.address) {
+                        callback(result);
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => {
+                eprintln!("  [DEBUG] Continuous scan read error: {:?}", e.kind());
+                break;
+            }
+        }
+    }
+
+    let _ = send_hci_cmd(&mut socket, HCI_OP_LE_SET_SCAN_ENABLE, &[0x00, 0x00]);
+    if let Ok(mut state) = lock_state() {
+        state.scanning = false;
+    }
+}
+
+/// Run a filtered scan (used by `BleScanner`)
+pub(super) fn scan_with_filter(
+    filter: &BleScanner,
+    timeout_ms: u32,
+    stop_on_first: bool,
+) -> BleResult<Vec<ScanResult>> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    if state.scanning {
+        return Err(BleError::ScanError);
+    }
+
+    let socket = state.socket.as_mut().unwrap();
+    let results = run_scan(socket, timeout_ms, filter, stop_on_first)?;
+
+    // Keep the results so a later ble_connect can look up the address type
+    state.scan_results = results.clone();
+
+    Ok(results)
+}
+
+/// Scan for `timeout_ms`, collecting reports accepted by `filter`
+///
+/// Stops early once `filter`'s result limit is reached, or after the first
+/// match if `stop_on_first` is set.
+fn run_scan(
+    socket: &mut HciSocket,
+    timeout_ms: u32,
+    filter: &BleScanner,
+    stop_on_first: bool,
+) -> BleResult<Vec<ScanResult>> {
+    enable_scanning(socket)?;
 
     // Use short socket timeout for non-blocking reads, track elapsed time ourselves
     socket.set_read_timeout(Duration::from_millis(100))?;
@@ -408,20 +600,21 @@ pub fn ble_start_scan(timeout_ms: u32) -> BleResult<()> {
                     }
                 }
 
-                if buf[0] == HCI_EVENT_PKT
-                    && buf[1] == HCI_EV_LE_META
-                    && buf[3] == HCI_EV_LE_ADVERTISING_REPORT
-                {
-                    if let Some(result) = parse_advertising_report(&buf[4..len]) {
-                        // Check for duplicate in local results
-                        if !local_results.iter().any(|r| r.address == result.address) {
-                            if local_results.len() < MAX_SCAN_RESULTS {
-                                eprintln!("  [DEBUG] Found device: {}", result.address);
-                                local_results.push(result);
-                            }
+                if let Some(result) = parse_filtered_event(&buf[..len], filter) {
+                    // Check for duplicate in local results
+                    if !local_results.iter().any(|r| r.address == result.address) {
+                        if local_results.len() < filter.max_results {
+                            eprintln!("  [DEBUG] Found device: {}", result.address);
+                            local_results.push(result);
                         }
                     }
                 }
+
+                if local_results.len() >= filter.max_results
+                    || (stop_on_first && !local_results.is_empty())
+                {
+                    break;
+                }
             }
             Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                 eprintln!("  [DEBUG] Timeout after {} events", event_count);
@@ -437,13 +630,25 @@ pub fn ble_start_scan(timeout_ms: u32) -> BleResult<()> {
     // Disable scanning
     let _ = send_hci_cmd(socket, HCI_OP_LE_SET_SCAN_ENABLE, &[0x00, 0x00]);
 
-    // Transfer local results to state (socket borrow ended)
-    state.scan_results = local_results;
+    Ok(local_results)
+}
 
-    // Update state (socket borrow ended, can mutate state again)
-    state.scanning = false;
+/// Set active scan parameters and enable scanning
+fn enable_scanning(socket: &mut HciSocket) -> BleResult<()> {
+    // Set scan parameters: active scan, 10ms interval, 10ms window
+    let scan_params = [
+        LE_SCAN_ACTIVE,
+        0x10, 0x00,         // Interval: 16 * 0.625ms = 10ms
+        0x10, 0x00,         // Window: 16 * 0.625ms = 10ms
+        LE_PUBLIC_ADDRESS,
+        0x00,               // Accept all advertisements
+    ];
+    send_hci_cmd(socket, HCI_OP_LE_SET_SCAN_PARAM, &scan_params)?;
 
-    Ok(())
+    std::thread::sleep(Duration::from_millis(10)); // Wait for command to complete
+
+    // Enable scanning
+    send_hci_cmd(socket, HCI_OP_LE_SET_SCAN_ENABLE, &[0x01, 0x00])
 }
 
 /// Send an HCI command
@@ -459,8 +664,37 @@ fn send_hci_cmd(socket: &mut HciSocket, opcode: u16, params: &[u8]) -> BleResult
     socket.write_all(&buf[..len]).map_err(|_| BleError::SocketError)
 }
 
-/// Parse advertising report and return ScanResult if valid
-fn parse_advertising_report(data: &[u8]) -> Option<ScanResult> {
+/// Parse a raw HCI event packet (packet type byte first) into a ScanResult
+///
+/// Returns `None` for anything but a well-formed LE advertising report.
+/// Exposed so the parser can be fuzzed without an adapter.
+pub fn parse_hci_event_packet(buf: &[u8]) -> Option<ScanResult> {
+    parse_filtered_event(buf, &BleScanner::new())
+}
+
+/// Parse the parameters of an LE advertising report event into a ScanResult
+///
+/// `data` starts at the report count, after the LE meta subevent code.
+/// Exposed so the parser can be fuzzed without an adapter.
+pub fn parse_advertising_report(data: &[u8]) -> Option<ScanResult> {
+    parse_filtered_report(data, &BleScanner::new())
+}
+
+/// Parse a raw HCI event packet if it is an advertising report accepted by `filter`
+fn parse_filtered_event(buf: &[u8], filter: &BleScanner) -> Option<ScanResult> {
+    if buf.len() < 4
+        || buf[0] != HCI_EVENT_PKT
+        || buf[1] != HCI_EV_LE_META
+        || buf[3] != HCI_EV_LE_ADVERTISING_REPORT
+    {
+        return None;
+    }
+    parse_filtered_report(&buf[4..], filter)
+}
+
+/// Parse advertising report and return ScanResult if valid and accepted by `filter`
+#[doc(hidden)] // pub for tests
+pub fn parse_filtered_report(data: &[u8], filter: &BleScanner) -> Option<ScanResult> {
     if data.len() < 10 {
         return None;
     }
@@ -489,36 +723,42 @@ fn parse_advertising_report(data: &[u8]) -> Option<ScanResult> {
 
     let data_len = data[9] as usize;
     let rssi_offset = 10 + data_len;
-    let rssi = if rssi_offset < data.len() {
-        data[rssi_offset] as i8
-    } else {
-        -127
-    };
+    let rssi = data.get(rssi_offset).map_or(-127, |&b| b as i8);
 
-    // Parse advertising data for device name
+    // Parse advertising data for device name and 16-bit service UUIDs
     let mut name: Option<[u8; 32]> = None;
     let mut name_len = 0;
-
-    if data_len > 0 && data.len() >= 10 + data_len {
-        let ad_data = &data[10..10 + data_len];
-        let mut i = 0;
-        while i + 1 < ad_data.len() {
-            let len = ad_data[i] as usize;
-            if len == 0 || i + len >= ad_data.len() {
-                break;
-            }
-            let ad_type = ad_data[i + 1];
-            if (ad_type == 0x09 || ad_type == 0x08) && len > 1 {
-                let name_data = &ad_data[i + 2..i + 1 + len];
-                let copy_len = std::cmp::min(name_data.len(), 32);
-                let mut name_buf = [0u8; 32];
-                name_buf[..copy_len].copy_from_slice(&name_data[..copy_len]);
-                name = Some(name_buf);
-                name_len = copy_len;
-                break;
-            }
-            i += len + 1;
+    let mut service_uuids: Vec<u16> = Vec::new();
+
+    // AD structures are length(1) + type(1) + value(length - 1). A zero
+    // length ends the data early; a structure running past the end is dropped.
+    let ad_data = data.get(10..rssi_offset).unwrap_or(&[]);
+    let mut i = 0;
+    while let Some(&len) = ad_data.get(i) {
+        let len = len as usize;
+        let Some(structure) = ad_data.get(i + 1..i + 1 + len) else {
+            break;
+        };
+        let Some((&ad_type, value)) = structure.split_first() else {
+            break;
+        };
+        if (ad_type == 0x09 || ad_type == 0x08) && !value.is_empty() && name.is_none() {
+            let copy_len = std::cmp::min(value.len(), 32);
+            let mut name_buf = [0u8; 32];
+            name_buf[..copy_len].copy_from_slice(&value[..copy_len]);
+            name = Some(name_buf);
+            name_len = copy_len;
+        } else if ad_type == 0x02 || ad_type == 0x03 {
+            // Incomplete / complete list of 16-bit service UUIDs
+            service_uuids.extend(
+                value.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])),
+            );
         }
+        i += len + 1;
+    }
+
+    if !filter.accepts(rssi, name.as_ref().map(|n| &n[..name_len]), &service_uuids) {
+        return None;
     }
 
     Some(ScanResult {
@@ -536,7 +776,7 @@ fn parse_advertising_report(data: &[u8]) -> Option<ScanResult> {
 
 /// Stop BLE scanning
 pub fn ble_stop_scan() -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -555,7 +795,7 @@ pub fn ble_stop_scan() -> BleResult<()> {
 
 /// Get scan results
 pub fn ble_get_scan_results() -> BleResult<Vec<ScanResult>> {
-    let state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -566,7 +806,21 @@ pub fn ble_get_scan_results() -> BleResult<Vec<ScanResult>> {
 
 /// Start BLE advertising with the given device name
 pub fn ble_start_advertising(name: &str) -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    // Flags take 3 bytes and the name header 2, leaving 26 for the name itself
+    let name_bytes = &name.as_bytes()[..name.len().min(AdvertisingData::MAX_LEN - 5)];
+    let data = AdvertisingData::new()
+        .add_flags(ADV_FLAGS_GENERAL_DISC)?
+        .add_field(AD_TYPE_COMPLETE_NAME, name_bytes)?;
+
+    ble_start_advertising_with_data(&data)?;
+    eprintln!("  [DEBUG] Advertising started as \"{}\"", name);
+
+    Ok(())
+}
+
+/// Start BLE advertising with a caller-built advertising payload
+pub fn ble_start_advertising_with_data(data: &AdvertisingData) -> BleResult<()> {
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -576,22 +830,83 @@ pub fn ble_start_advertising(name: &str) -> BleResult<()> {
         return Ok(()); // Already advertising
     }
 
+    let random_addr = state.random_addr;
     let socket = state.socket.as_mut().unwrap();
+    start_advertising(socket, &random_addr, data, ADV_IND)?;
 
-    // Generate and set a static random address
-    // Static random address: two MSBs of the address must be '11'
-    let random_addr: [u8; 6] = [0xC0, 0xDE, 0xCA, 0xFE, 0xBE, 0xEF]; // C0:DE:CA:FE:BE:EF
-    socket.send_cmd_wait(HCI_OP_LE_SET_RANDOM_ADDR, &random_addr)?;
+    state.advertising = true;
+
+    Ok(())
+}
+
+/// Advertise as an Apple iBeacon (non-connectable)
+///
+/// Replaces any advertising already running, so calling this again
+/// updates the beacon.
+pub fn ble_advertise_ibeacon(beacon: &IBeacon) -> BleResult<()> {
+    start_beacon_advertising(&beacon.advertising_data()?)?;
+    eprintln!(
+        "  [DEBUG] iBeacon advertising started (major {}, minor {})",
+        beacon.major, beacon.minor
+    );
+    Ok(())
+}
+
+/// Advertise an Eddystone-URL beacon (non-connectable)
+///
+/// Fails with `InvalidParameter` if the URL cannot be encoded in 17 bytes.
+/// Replaces any advertising already running.
+pub fn ble_advertise_eddystone_url(url: &str, tx_power: i8) -> BleResult<()> {
+    start_beacon_advertising(&eddystone_url_advertising_data(url, tx_power)?)?;
+    eprintln!("  [DEBUG] Eddystone-URL advertising started ({})", url);
+    Ok(())
+}
+
+/// Start non-connectable advertising with an empty scan response,
+/// restarting it if already running
+fn start_beacon_advertising(data: &AdvertisingData) -> BleResult<()> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    let was_advertising = std::mem::replace(&mut state.advertising, false);
+    let random_addr = state.random_addr;
+    let socket = state.socket.as_mut().unwrap();
+
+    // The controller rejects new parameters while advertising is enabled
+    if was_advertising {
+        let _ = socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x00]);
+    }
+
+    // Empty scan response (significant length 0)
+    socket.send_cmd_wait(HCI_OP_LE_SET_SCAN_RSP_DATA, &[0u8; 32])?;
+    start_advertising(socket, &random_addr, data, ADV_NONCONN_IND)?;
+
+    state.advertising = true;
+    Ok(())
+}
+
+/// Set the random address, advertising parameters and payload, then enable advertising
+fn start_advertising(
+    socket: &mut HciSocket,
+    random_addr: &[u8; 6],
+    data: &AdvertisingData,
+    adv_type: u8,
+) -> BleResult<()> {
+    // Set our static random address (two MSBs of the address are '11')
+    socket.send_cmd_wait(HCI_OP_LE_SET_RANDOM_ADDR, random_addr)?;
 
     // Set advertising parameters
     // - Interval: 100ms (0x00A0 = 160 * 0.625ms)
-    // - Type: ADV_IND (connectable undirected)
+    // - Type: adv_type (ADV_IND or ADV_NONCONN_IND)
     // - Own address type: Random
     // - Channel map: All channels (37, 38, 39)
     let adv_params = [
         0xA0, 0x00, // Min interval: 160 * 0.625ms = 100ms
         0xA0, 0x00, // Max interval: 160 * 0.625ms = 100ms
-        0x00,       // Type: ADV_IND (connectable undirected)
+        adv_type,   // Type
         0x01,       // Own address type: Random
         0x00,       // Peer address type: Public (not used for ADV_IND)
         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Peer address (not used)
@@ -600,43 +915,20 @@ pub fn ble_start_advertising(name: &str) -> BleResult<()> {
     ];
     socket.send_cmd_wait(HCI_OP_LE_SET_ADV_PARAM, &adv_params)?;
 
-    // Build advertising data
-    // Format: [length, type, data...]
-    let mut adv_data = [0u8; 32];
-    let mut pos = 0;
-
-    // Flags: LE General Discoverable, BR/EDR Not Supported
-    adv_data[pos] = 0x02; // Length
-    adv_data[pos + 1] = 0x01; // Type: Flags
-    adv_data[pos + 2] = 0x06; // Flags: LE General Discoverable + BR/EDR Not Supported
-    pos += 3;
-
-    // Complete Local Name
-    let name_bytes = name.as_bytes();
-    let name_len = std::cmp::min(name_bytes.len(), 28 - pos); // Leave room
-    adv_data[pos] = (name_len + 1) as u8; // Length (type + name)
-    adv_data[pos + 1] = 0x09; // Type: Complete Local Name
-    adv_data[pos + 2..pos + 2 + name_len].copy_from_slice(&name_bytes[..name_len]);
-    pos += 2 + name_len;
-
-    // Set advertising data (first byte is total length)
+    // Set advertising data (first byte is significant length, rest zero-padded)
+    let bytes = data.as_bytes();
     let mut adv_cmd = [0u8; 32];
-    adv_cmd[0] = pos as u8; // Length of advertising data
-    adv_cmd[1..1 + pos].copy_from_slice(&adv_data[..pos]);
+    adv_cmd[0] = bytes.len() as u8;
+    adv_cmd[1..1 + bytes.len()].copy_from_slice(bytes);
     socket.send_cmd_wait(HCI_OP_LE_SET_ADV_DATA, &adv_cmd)?;
 
     // Enable advertising
-    socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x01])?;
-
-    state.advertising = true;
-    eprintln!("  [DEBUG] Advertising started as \"{}\"", name);
-
-    Ok(())
+    socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x01])
 }
 
 /// Stop BLE advertising
 pub fn ble_stop_advertising() -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
@@ -657,63 +949,199 @@ pub fn ble_stop_advertising() -> BleResult<()> {
     Ok(())
 }
 
-/// Run a simple GATT server
-/// This starts advertising, waits for a connection, and handles ATT requests
-pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
-    let mut state = STATE.lock().map_err(|_| BleError::SocketError)?;
+/// Periodically replace the own random address to hinder tracking
+///
+/// Every `interval_secs` a new static random address is generated and
+/// applied; if advertising is active it is briefly stopped around the
+/// change. Replaces any rotation already running.
+pub fn ble_rotate_random_address(interval_secs: u64) -> BleResult<()> {
+    if interval_secs == 0 {
+        return Err(BleError::InvalidParameter);
+    }
+
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
     }
 
-    let socket = state.socket.as_mut().unwrap();
+    state.stop_rotation();
 
-    // Start advertising (reuse existing logic but inline here for socket borrow)
-    // Set random address
-    let random_addr: [u8; 6] = [0xC0, 0xDE, 0xCA, 0xFE, 0xBE, 0xEF];
-    socket.send_cmd_wait(HCI_OP_LE_SET_RANDOM_ADDR, &random_addr)?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    let interval = Duration::from_secs(interval_secs);
+    state.rotation_thread = Some(thread::spawn(move || rotation_loop(interval, &thread_stop)));
+    state.rotation_stop = Some(stop);
 
-    // Set advertising parameters
-    let adv_params = [
-        0xA0, 0x00, 0xA0, 0x00, 0x00, 0x01, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00,
-    ];
-    socket.send_cmd_wait(HCI_OP_LE_SET_ADV_PARAM, &adv_params)?;
+    Ok(())
+}
 
-    // Build and set advertising data
-    let mut adv_data = [0u8; 32];
-    adv_data[0] = 0x02; adv_data[1] = 0x01; adv_data[2] = 0x06; // Flags
-    let name_bytes = name.as_bytes();
-    let name_len = std::cmp::min(name_bytes.len(), 20);
-    adv_data[3] = (name_len + 1) as u8;
-    adv_data[4] = 0x09; // Complete Local Name
-    adv_data[5..5 + name_len].copy_from_slice(&name_bytes[..name_len]);
-    let total_len = 5 + name_len;
-    let mut adv_cmd = [0u8; 32];
-    adv_cmd[0] = total_len as u8;
-    adv_cmd[1..1 + total_len].copy_from_slice(&adv_data[..total_len]);
-    socket.send_cmd_wait(HCI_OP_LE_SET_ADV_DATA, &adv_cmd)?;
+/// Stop the address rotation started by `ble_rotate_random_address`
+///
+/// The last generated address stays in use.
+pub fn ble_stop_address_rotation() -> BleResult<()> {
+    let mut state = lock_state()?;
 
-    // Enable advertising
-    socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x01])?;
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    state.stop_rotation();
+    Ok(())
+}
+
+/// Get the own random address used for advertising
+pub fn ble_get_random_address() -> BleResult<BleAddress> {
+    let state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    let mut bytes = state.random_addr;
+    bytes.reverse();
+    Ok(BleAddress::new(bytes))
+}
+
+fn rotation_loop(interval: Duration, stop: &AtomicBool) {
+    while crate::sleep_unless_stopped(interval, stop) {
+        let addr = match generate_static_random_address() {
+            Some(addr) => addr,
+            None => continue,
+        };
+
+        // Don't block behind a long-running scan or GATT server; try again next interval
+        let mut state = match STATE.get().map(Mutex::try_lock) {
+            Some(Ok(state)) => state,
+            _ => continue,
+        };
+
+        let advertising = state.advertising;
+        let socket = match state.socket.as_mut() {
+            Some(socket) => socket,
+            None => return, // Deinitialized
+        };
+
+        // The controller rejects a new random address while advertising
+        if advertising {
+            let _ = socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x00]);
+        }
+        let applied = socket.send_cmd_wait(HCI_OP_LE_SET_RANDOM_ADDR, &addr).is_ok();
+        if advertising {
+            let _ = socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x01]);
+        }
+
+        if applied {
+            state.random_addr = addr;
+        }
+    }
+}
+
+/// Generate a static random address (HCI byte order, two MSBs '11')
+#[doc(hidden)] // pub for tests
+pub fn generate_static_random_address() -> Option<[u8; 6]> {
+    let mut addr = [0u8; 6];
+    // SAFETY: getrandom writes at most addr.len() bytes into the buffer
+    let ret = unsafe { libc::getrandom(addr.as_mut_ptr() as *mut libc::c_void, addr.len(), 0) };
+    if ret != addr.len() as isize {
+        return None;
+    }
+    addr[5] |= 0xC0;
+
+    // The random part must not be all zeros or all ones
+    let all_zero = addr[..5].iter().all(|&b| b == 0x00) && addr[5] & 0x3F == 0x00;
+    let all_one = addr[..5].iter().all(|&b| b == 0xFF) && addr[5] & 0x3F == 0x3F;
+    if all_zero || all_one {
+        return None;
+    }
+    Some(addr)
+}
+
+/// Handler for tables without `AttributeValue::Handler` attributes
+struct NoHandler;
+
+impl GattHandler for NoHandler {
+    fn on_read(&mut self, _attr_handle: u16) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn on_write(&mut self, _attr_handle: u16, _data: &[u8]) {}
+}
+
+/// Run a GATT server with the services in `config`
+///
+/// This starts advertising as `config.name`, waits for a connection and
+/// answers ATT requests from the table built by
+/// `GattServerConfig::build_db` until the client disconnects or
+/// `config.timeout_ms` passes. Writable values keep the last value written.
+pub fn ble_run_gatt_server(config: &GattServerConfig) -> BleResult<()> {
+    let db = config.build_db();
+    run_gatt_server(&config.name, config.timeout_ms, &db, &mut NoHandler, false)
+}
+
+/// Run the GATT server with application-provided characteristic values
+///
+/// Serves the service of `GattDb::with_custom_service`; reads and writes of
+/// its characteristic values are delegated to `handler`. Once the client
+/// enables notifications (CCCD at handle 6), a "tick N" notification is sent
+/// on the read characteristic every 2 seconds.
+pub fn ble_run_gatt_server_with_handler(
+    name: &str,
+    timeout_ms: u32,
+    handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    run_gatt_server(name, timeout_ms, &GattDb::with_custom_service(), handler, true)
+}
+
+/// Run the GATT server with a custom attribute table
+///
+/// Serves every service in `db`; attributes with `AttributeValue::Handler`
+/// values are read and written through `handler`. The periodic tick
+/// notification of `ble_run_gatt_server_with_handler` is not sent.
+pub fn ble_run_gatt_server_with_db(
+    name: &str,
+    timeout_ms: u32,
+    db: &GattDb,
+    handler: &mut dyn GattHandler,
+) -> BleResult<()> {
+    run_gatt_server(name, timeout_ms, db, handler, false)
+}
+
+fn run_gatt_server(
+    name: &str,
+    timeout_ms: u32,
+    db: &GattDb,
+    handler: &mut dyn GattHandler,
+    tick_notifications: bool,
+) -> BleResult<()> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    let random_addr = state.random_addr;
+    let socket = state.socket.as_mut().unwrap();
+
+    // Start advertising (name limited to 20 bytes as before)
+    let name_bytes = &name.as_bytes()[..name.len().min(20)];
+    let data = AdvertisingData::new()
+        .add_flags(ADV_FLAGS_GENERAL_DISC)?
+        .add_field(AD_TYPE_COMPLETE_NAME, name_bytes)?;
+    start_advertising(socket, &random_addr, &data, ADV_IND)?;
     eprintln!("  [GATT] Advertising as '{}', waiting for connection...", name);
 
-    // Simple GATT database (inline)
-    // Handle 1: Primary Service (0x2800) = Custom Service UUID
-    // Handle 2: Characteristic Declaration (0x2803)
-    // Handle 3: Characteristic Value - "Hello from RustCam!"
-    // Handle 4: Characteristic Declaration (0x2803)
-    // Handle 5: Characteristic Value - writable command buffer
-    let mut command_buffer: [u8; 32] = [0; 32];
-    let mut command_len: usize = 0;
-    let hello_msg = b"Hello from RustCam!";
+    // Notification ticker, stopped when this function returns. It also makes
+    // `gatt_send_notification` available while the server runs.
+    let ticker = NotifyTicker::start(socket.try_clone()?);
+    let mut cccd_values: HashMap<u16, u16> = HashMap::new();
 
     // Wait for connection and handle ATT requests
     socket.set_read_timeout(Duration::from_millis(timeout_ms as u64))?;
     let start = std::time::Instant::now();
     let timeout = Duration::from_millis(timeout_ms as u64);
 
-    let mut conn_handle: Option<u16> = None;
+    let mut conn_handle: Option<ConnectionHandle> = None;
     let mut buf = [0u8; 512];
 
     loop {
@@ -737,15 +1165,19 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
                         // Connection Complete
                         if subevent == HCI_EV_LE_CONN_COMPLETE && len >= 7 {
                             let status = buf[4];
-                            if status == 0 {
-                                conn_handle = Some(u16::from_le_bytes([buf[5], buf[6]]));
-                                eprintln!("  [GATT] Connected! Handle: 0x{:04X}", conn_handle.unwrap());
+                            let handle = ConnectionHandle::from_acl_header(buf[5], buf[6]);
+                            if status == 0 && handle.is_valid() {
+                                conn_handle = Some(handle);
+                                ticker.conn_handle.store(handle.0, Ordering::Relaxed);
+                                eprintln!("  [GATT] Connected! Handle: {}", handle);
                             }
                         }
                     }
                     // Disconnection Complete
                     else if event_code == HCI_EV_DISCONN_COMPLETE && len >= 5 {
                         eprintln!("  [GATT] Disconnected");
+                        ticker.disconnect();
+                        cccd_values.clear();
                         conn_handle = None;
                         break;
                     }
@@ -753,12 +1185,13 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
                 // HCI ACL Data packet (0x02)
                 else if pkt_type == 0x02 && conn_handle.is_some() && len >= 9 {
                     // ACL header: handle(2) + length(2) + L2CAP header: length(2) + CID(2)
+                    let acl_handle = ConnectionHandle::from_acl_header(buf[1], buf[2]);
                     let l2cap_cid = u16::from_le_bytes([buf[7], buf[8]]);
 
-                    // ATT channel
-                    if l2cap_cid == L2CAP_CID_ATT && len >= 10 {
+                    // ATT channel (ignore data for any other connection)
+                    if l2cap_cid == L2CAP_CID_ATT && len >= 10 && conn_handle == Some(acl_handle) {
                         let att_opcode = buf[9];
-                        let handle = conn_handle.unwrap();
+                        let handle = acl_handle.0;
 
                         match att_opcode {
                             ATT_OP_MTU_REQ => {
@@ -768,25 +1201,39 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
                             }
                             ATT_OP_READ_BY_GROUP_REQ => {
                                 eprintln!("  [GATT] Read By Group Type Request (Service Discovery)");
-                                // Return our custom service at handles 1-5
-                                let response = build_read_by_group_response(handle, &buf[10..len]);
+                                let response = build_read_by_group_response(handle, db, &buf[10..len]);
                                 send_acl_data(socket, &response)?;
                             }
                             ATT_OP_READ_BY_TYPE_REQ => {
                                 eprintln!("  [GATT] Read By Type Request (Characteristic Discovery)");
-                                let response = build_read_by_type_response(handle, &buf[10..len]);
+                                let response = build_read_by_type_response(handle, db, &buf[10..len]);
                                 send_acl_data(socket, &response)?;
                             }
                             ATT_OP_FIND_INFO_REQ => {
                                 eprintln!("  [GATT] Find Information Request");
-                                let response = build_find_info_response(handle, &buf[10..len]);
+                                let response = build_find_info_response(handle, db, &buf[10..len]);
                                 send_acl_data(socket, &response)?;
                             }
                             ATT_OP_READ_REQ => {
                                 if len >= 12 {
                                     let attr_handle = u16::from_le_bytes([buf[10], buf[11]]);
                                     eprintln!("  [GATT] Read Request for handle {}", attr_handle);
-                                    let response = build_read_response(handle, attr_handle, hello_msg, &command_buffer[..command_len]);
+                                    let response = match read_attribute(db, handler, &cccd_values, attr_handle) {
+                                        Some(value) => build_read_response(handle, &value),
+                                        None => build_error_response(handle, ATT_OP_READ_REQ, attr_handle, ATT_ERR_INVALID_HANDLE),
+                                    };
+                                    send_acl_data(socket, &response)?;
+                                }
+                            }
+                            ATT_OP_READ_BLOB_REQ => {
+                                if len >= 14 {
+                                    let attr_handle = u16::from_le_bytes([buf[10], buf[11]]);
+                                    let offset = u16::from_le_bytes([buf[12], buf[13]]);
+                                    eprintln!("  [GATT] Read Blob Request for handle {} at {}", attr_handle, offset);
+                                    let response = match read_attribute(db, handler, &cccd_values, attr_handle) {
+                                        Some(value) => build_read_blob_response(handle, attr_handle, &value, offset),
+                                        None => build_error_response(handle, ATT_OP_READ_BLOB_REQ, attr_handle, ATT_ERR_INVALID_HANDLE),
+                                    };
                                     send_acl_data(socket, &response)?;
                                 }
                             }
@@ -794,20 +1241,37 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
                                 if len >= 12 {
                                     let attr_handle = u16::from_le_bytes([buf[10], buf[11]]);
                                     let data_start = 12;
-                                    let data_len = len - data_start;
-                                    eprintln!("  [GATT] Write to handle {}: {:?}", attr_handle, &buf[data_start..len]);
-
-                                    // Handle 5 is our writable characteristic
-                                    if attr_handle == 5 && data_len <= 32 {
-                                        command_buffer[..data_len].copy_from_slice(&buf[data_start..len]);
-                                        command_len = data_len;
-                                        eprintln!("  [GATT] Command received: {:?}",
-                                            std::str::from_utf8(&command_buffer[..command_len]).unwrap_or("<binary>"));
-                                    }
+                                    let data = &buf[data_start..len];
+                                    eprintln!("  [GATT] Write to handle {}: {:?}", attr_handle, data);
+                                    let error = match db.get(attr_handle).map(|attr| &attr.value) {
+                                        Some(AttributeValue::Handler) => {
+                                            handler.on_write(attr_handle, data);
+                                            None
+                                        }
+                                        Some(AttributeValue::Stored(value)) => {
+                                            *value.lock().unwrap() = data.to_vec();
+                                            None
+                                        }
+                                        Some(AttributeValue::Cccd) => {
+                                            if let [lo, hi, ..] = *data {
+                                                let cccd = u16::from_le_bytes([lo, hi]);
+                                                cccd_values.insert(attr_handle, cccd);
+                                                if tick_notifications && attr_handle == GATT_CCCD_HANDLE {
+                                                    ticker.set_enabled(cccd & 0x0001 != 0);
+                                                }
+                                            }
+                                            None
+                                        }
+                                        Some(_) => Some(ATT_ERR_WRITE_NOT_PERMITTED),
+                                        None => Some(ATT_ERR_INVALID_HANDLE),
+                                    };
 
                                     // Send write response for WRITE_REQ
                                     if att_opcode == ATT_OP_WRITE_REQ {
-                                        let response = build_write_response(handle);
+                                        let response = match error {
+                                            None => build_write_response(handle),
+                                            Some(err) => build_error_response(handle, ATT_OP_WRITE_REQ, attr_handle, err),
+                                        };
                                         send_acl_data(socket, &response)?;
                                     }
                                 }
@@ -829,13 +1293,101 @@ pub fn ble_run_gatt_server(name: &str, timeout_ms: u32) -> BleResult<()> {
         }
     }
 
-    // Stop advertising
+    // Stop notifications and advertising
+    drop(ticker);
     let _ = socket.send_cmd_wait(HCI_OP_LE_SET_ADV_ENABLE, &[0x00]);
     eprintln!("  [GATT] Server stopped");
 
     Ok(())
 }
 
+/// Background thread sending a tick notification on the readable
+/// characteristic every `NOTIFY_INTERVAL` while the client has enabled them
+struct NotifyTicker {
+    notify_enabled: Arc<AtomicBool>,
+    conn_handle: Arc<AtomicU16>,
+    stop_flag: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl NotifyTicker {
+    fn start(socket: Socket) -> Self {
+        if let Ok(mut notify_socket) = NOTIFY_SOCKET.lock() {
+            *notify_socket = Some(socket);
+        }
+
+        let notify_enabled = Arc::new(AtomicBool::new(false));
+        let conn_handle = Arc::new(AtomicU16::new(ConnectionHandle::INVALID.0));
+        let stop_flag = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let notify_enabled = Arc::clone(&notify_enabled);
+            let conn_handle = Arc::clone(&conn_handle);
+            let stop_flag = Arc::clone(&stop_flag);
+            thread::spawn(move || {
+                let mut tick = 0u32;
+                while crate::sleep_unless_stopped(NOTIFY_INTERVAL, &stop_flag) {
+                    let conn = conn_handle.load(Ordering::Relaxed);
+                    if notify_enabled.load(Ordering::Relaxed) && ConnectionHandle(conn).is_valid() {
+                        tick += 1;
+                        let msg = format!("tick {}", tick);
+                        if gatt_send_notification(conn, GATT_READ_HANDLE, msg.as_bytes()).is_err() {
+                            eprintln!("  [GATT] Failed to send notification");
+                        }
+                    }
+                }
+            })
+        };
+
+        Self {
+            notify_enabled,
+            conn_handle,
+            stop_flag,
+            handle: Some(handle),
+        }
+    }
+
+    /// Handle a client write to the CCCD
+    fn set_enabled(&self, enabled: bool) {
+        self.notify_enabled.store(enabled, Ordering::Relaxed);
+        eprintln!("  [GATT] Notifications {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Stop ticking until the next connection enables notifications again
+    fn disconnect(&self) {
+        self.conn_handle.store(ConnectionHandle::INVALID.0, Ordering::Relaxed);
+        self.notify_enabled.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Drop for NotifyTicker {
+    fn drop(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        if let Ok(mut notify_socket) = NOTIFY_SOCKET.lock() {
+            *notify_socket = None;
+        }
+    }
+}
+
+/// Send a Handle Value Notification to a connected GATT client
+///
+/// Only available while the GATT server is running. Values longer than
+/// the ATT MTU allows are truncated.
+pub fn gatt_send_notification(conn_handle: u16, attr_handle: u16, data: &[u8]) -> BleResult<()> {
+    if !ConnectionHandle(conn_handle).is_valid() {
+        return Err(BleError::InvalidParameter);
+    }
+
+    let notify_socket = NOTIFY_SOCKET.lock().map_err(|_| BleError::SocketError)?;
+    let socket = notify_socket.as_ref().ok_or(BleError::NotInitialized)?;
+
+    let pkt = build_notification(conn_handle, attr_handle, data);
+    (&*socket).write_all(&pkt).map_err(|_| BleError::SocketError)
+}
+
 // Helper functions for building ATT responses
 fn build_att_mtu_response(conn_handle: u16, mtu: u16) -> Vec<u8> {
     let mut pkt = vec![
@@ -850,185 +1402,146 @@ fn build_att_mtu_response(conn_handle: u16, mtu: u16) -> Vec<u8> {
     pkt
 }
 
-fn build_read_by_group_response(conn_handle: u16, req_data: &[u8]) -> Vec<u8> {
+/// Read By Group Type Response (ACL packet) listing primary services in range
+#[doc(hidden)] // pub for tests
+pub fn build_read_by_group_response(conn_handle: u16, db: &GattDb, req_data: &[u8]) -> Vec<u8> {
     // Request format: start_handle(2) + end_handle(2) + uuid(2 or 16)
-    // Our service is at handles 1-5. If start_handle > 5, return error.
-    if req_data.len() >= 2 {
-        let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
-        eprintln!("  [GATT] Service discovery from handle {}", start_handle);
-
-        if start_handle > 5 {
-            // No more services - return Attribute Not Found
-            return build_error_response(conn_handle, ATT_OP_READ_BY_GROUP_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND);
+    if req_data.len() < 6 {
+        return build_error_response(conn_handle, ATT_OP_READ_BY_GROUP_REQ, 0x0001, ATT_ERR_ATTR_NOT_FOUND);
+    }
+    let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
+    let end_handle = u16::from_le_bytes([req_data[2], req_data[3]]);
+    eprintln!("  [GATT] Service discovery from handle {}", start_handle);
+
+    // Only primary services are grouped
+    let mut pdu = vec![ATT_OP_READ_BY_GROUP_RSP, 0x06]; // Entry: start(2) + end(2) + uuid(2)
+    if req_data.len() == 6 && u16::from_le_bytes([req_data[4], req_data[5]]) == GATT_PRIMARY_SERVICE_UUID {
+        for (start, end, uuid) in db.services() {
+            if start < start_handle || start > end_handle {
+                continue;
+            }
+            if pdu.len() + 6 > ATT_DEFAULT_MTU {
+                break;
+            }
+            pdu.extend_from_slice(&start.to_le_bytes());
+            pdu.extend_from_slice(&end.to_le_bytes());
+            pdu.extend_from_slice(&uuid.to_le_bytes());
         }
     }
 
-    // Return one primary service: handles 1-5, UUID = 0x1234 (custom)
-    vec![
-        0x02,
-        (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-        0x0C, 0x00, // ACL length
-        0x08, 0x00, // L2CAP length
-        0x04, 0x00, // ATT CID
-        ATT_OP_READ_BY_GROUP_RSP,
-        0x06, // Length of each entry (2+2+2)
-        0x01, 0x00, // Start handle: 1
-        0x05, 0x00, // End handle: 5
-        0x34, 0x12, // Service UUID: 0x1234
-    ]
+    if pdu.len() == 2 {
+        // No more services - return Attribute Not Found
+        return build_error_response(conn_handle, ATT_OP_READ_BY_GROUP_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND);
+    }
+    build_att_packet(conn_handle, &pdu)
 }
 
-fn build_read_by_type_response(conn_handle: u16, req_data: &[u8]) -> Vec<u8> {
+fn build_read_by_type_response(conn_handle: u16, db: &GattDb, req_data: &[u8]) -> Vec<u8> {
     // Request format: start_handle(2) + end_handle(2) + uuid(2 or 16)
-    if req_data.len() >= 6 {
-        let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
-        let _end_handle = u16::from_le_bytes([req_data[2], req_data[3]]);
-        let uuid = u16::from_le_bytes([req_data[4], req_data[5]]);
-
-        eprintln!("  [GATT] Read By Type from handle {} UUID 0x{:04X}", start_handle, uuid);
-
-        // Characteristic declaration (0x2803)
-        if uuid == 0x2803 {
-            // Our characteristics:
-            // - Handle 2: Read characteristic, value at handle 3, UUID 0x1235
-            // - Handle 4: Write characteristic, value at handle 5, UUID 0x1236
-
-            if start_handle <= 2 {
-                // Return first characteristic (handle 2)
-                // ATT payload: opcode(1) + length(1) + handle(2) + props(1) + value_handle(2) + uuid(2) = 9
-                return vec![
-                    0x02,
-                    (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                    0x0D, 0x00, // ACL length = 13 (L2CAP header 4 + ATT payload 9)
-                    0x09, 0x00, // L2CAP length = 9
-                    0x04, 0x00, // ATT CID
-                    ATT_OP_READ_BY_TYPE_RSP,
-                    0x07, // Length of each entry: handle(2) + props(1) + value_handle(2) + uuid(2) = 7
-                    0x02, 0x00, // Handle: 2
-                    0x02,       // Properties: Read
-                    0x03, 0x00, // Value handle: 3
-                    0x35, 0x12, // UUID: 0x1235
-                ];
-            } else if start_handle <= 4 {
-                // Return second characteristic (handle 4)
-                return vec![
-                    0x02,
-                    (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                    0x0D, 0x00, // ACL length = 13
-                    0x09, 0x00, // L2CAP length = 9
-                    0x04, 0x00, // ATT CID
-                    ATT_OP_READ_BY_TYPE_RSP,
-                    0x07, // Length of each entry
-                    0x04, 0x00, // Handle: 4
-                    0x0A,       // Properties: Write (0x08) + Write Without Response (0x02) = 0x0A
-                    0x05, 0x00, // Value handle: 5
-                    0x36, 0x12, // UUID: 0x1236
-                ];
+    if req_data.len() < 6 {
+        return build_error_response(conn_handle, ATT_OP_READ_BY_TYPE_REQ, 0x0001, ATT_ERR_ATTR_NOT_FOUND);
+    }
+    let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
+    let end_handle = u16::from_le_bytes([req_data[2], req_data[3]]);
+    let uuid = u16::from_le_bytes([req_data[4], req_data[5]]);
+    eprintln!("  [GATT] Read By Type from handle {} UUID 0x{:04X}", start_handle, uuid);
+
+    // Entry: handle(2) + value; all entries in one response have the same length
+    let mut pdu = vec![ATT_OP_READ_BY_TYPE_RSP, 0x00];
+    if req_data.len() == 6 {
+        let matches = db.attributes().iter()
+            .filter(|attr| attr.handle >= start_handle && attr.handle <= end_handle && attr.uuid == uuid);
+        for attr in matches {
+            // Only constant values (declarations, strings) are served here
+            let value = match &attr.value {
+                AttributeValue::Static(value) => value.clone(),
+                AttributeValue::Byte(level) => vec![level.load(Ordering::Relaxed)],
+                _ => break,
+            };
+            let value = &value[..value.len().min(ATT_DEFAULT_MTU - 4)];
+            let entry_len = 2 + value.len();
+            if (pdu[1] != 0 && pdu[1] as usize != entry_len) || pdu.len() + entry_len > ATT_DEFAULT_MTU {
+                break;
             }
-            // No more characteristics after handle 4
-            return build_error_response(conn_handle, ATT_OP_READ_BY_TYPE_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND);
+            pdu[1] = entry_len as u8;
+            pdu.extend_from_slice(&attr.handle.to_le_bytes());
+            pdu.extend_from_slice(value);
         }
     }
 
-    // Attribute not found for unknown UUID or invalid request
-    let start_handle = if req_data.len() >= 2 {
-        u16::from_le_bytes([req_data[0], req_data[1]])
-    } else {
-        0x0001
-    };
-    build_error_response(conn_handle, ATT_OP_READ_BY_TYPE_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND)
+    if pdu.len() == 2 {
+        return build_error_response(conn_handle, ATT_OP_READ_BY_TYPE_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND);
+    }
+    build_att_packet(conn_handle, &pdu)
 }
 
-fn build_find_info_response(conn_handle: u16, req_data: &[u8]) -> Vec<u8> {
+/// Find Information Response (ACL packet) listing handle/UUID pairs in range
+#[doc(hidden)] // pub for tests
+pub fn build_find_info_response(conn_handle: u16, db: &GattDb, req_data: &[u8]) -> Vec<u8> {
     // Request format: start_handle(2) + end_handle(2)
-    if req_data.len() >= 4 {
-        let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
-        let _end_handle = u16::from_le_bytes([req_data[2], req_data[3]]);
-
-        eprintln!("  [GATT] Find Info from handle {}", start_handle);
-
-        // Our attribute handles:
-        // 1: Primary Service
-        // 2: Characteristic Declaration (read)
-        // 3: Characteristic Value (read)
-        // 4: Characteristic Declaration (write)
-        // 5: Characteristic Value (write)
-
-        if start_handle == 1 {
-            return vec![
-                0x02,
-                (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                0x0A, 0x00, // ACL length
-                0x06, 0x00, // L2CAP length
-                0x04, 0x00, // ATT CID
-                ATT_OP_FIND_INFO_RSP,
-                0x01, // Format: 16-bit UUIDs
-                0x01, 0x00, // Handle: 1
-                0x00, 0x28, // UUID: 0x2800 (Primary Service)
-            ];
-        } else if start_handle == 2 {
-            return vec![
-                0x02,
-                (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                0x0A, 0x00, // ACL length
-                0x06, 0x00, // L2CAP length
-                0x04, 0x00, // ATT CID
-                ATT_OP_FIND_INFO_RSP,
-                0x01, // Format: 16-bit UUIDs
-                0x02, 0x00, // Handle: 2
-                0x03, 0x28, // UUID: 0x2803 (Characteristic Declaration)
-            ];
-        } else if start_handle == 3 {
-            return vec![
-                0x02,
-                (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                0x0A, 0x00, // ACL length
-                0x06, 0x00, // L2CAP length
-                0x04, 0x00, // ATT CID
-                ATT_OP_FIND_INFO_RSP,
-                0x01, // Format: 16-bit UUIDs
-                0x03, 0x00, // Handle: 3
-                0x35, 0x12, // UUID: 0x1235 (Characteristic Value)
-            ];
-        } else if start_handle == 4 {
-            return vec![
-                0x02,
-                (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                0x0A, 0x00, // ACL length
-                0x06, 0x00, // L2CAP length
-                0x04, 0x00, // ATT CID
-                ATT_OP_FIND_INFO_RSP,
-                0x01, // Format: 16-bit UUIDs
-                0x04, 0x00, // Handle: 4
-                0x03, 0x28, // UUID: 0x2803 (Characteristic Declaration)
-            ];
-        } else if start_handle == 5 {
-            return vec![
-                0x02,
-                (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
-                0x0A, 0x00, // ACL length
-                0x06, 0x00, // L2CAP length
-                0x04, 0x00, // ATT CID
-                ATT_OP_FIND_INFO_RSP,
-                0x01, // Format: 16-bit UUIDs
-                0x05, 0x00, // Handle: 5
-                0x36, 0x12, // UUID: 0x1236 (Characteristic Value)
-            ];
+    if req_data.len() < 4 {
+        return build_error_response(conn_handle, ATT_OP_FIND_INFO_REQ, 0x0001, ATT_ERR_ATTR_NOT_FOUND);
+    }
+    let start_handle = u16::from_le_bytes([req_data[0], req_data[1]]);
+    let end_handle = u16::from_le_bytes([req_data[2], req_data[3]]);
+    eprintln!("  [GATT] Find Info from handle {}", start_handle);
+
+    let mut pdu = vec![ATT_OP_FIND_INFO_RSP, 0x01]; // Format: 16-bit UUIDs
+    let in_range = db.attributes().iter()
+        .filter(|attr| attr.handle >= start_handle && attr.handle <= end_handle);
+    for attr in in_range {
+        if pdu.len() + 4 > ATT_DEFAULT_MTU {
+            break;
         }
+        pdu.extend_from_slice(&attr.handle.to_le_bytes());
+        pdu.extend_from_slice(&attr.uuid.to_le_bytes());
+    }
+
+    if pdu.len() == 2 {
         // Handle not found
         return build_error_response(conn_handle, ATT_OP_FIND_INFO_REQ, start_handle, ATT_ERR_ATTR_NOT_FOUND);
     }
-
-    // Invalid request
-    build_error_response(conn_handle, ATT_OP_FIND_INFO_REQ, 0x0001, ATT_ERR_ATTR_NOT_FOUND)
+    build_att_packet(conn_handle, &pdu)
 }
 
-fn build_read_response(conn_handle: u16, attr_handle: u16, hello_msg: &[u8], command_buf: &[u8]) -> Vec<u8> {
-    let data = match attr_handle {
-        3 => hello_msg, // Readable characteristic value
-        5 => command_buf, // Writable characteristic value
-        _ => b"Unknown",
+/// Current value of the attribute at `attr_handle`, or `None` if there is none
+fn read_attribute(
+    db: &GattDb,
+    handler: &mut dyn GattHandler,
+    cccd_values: &HashMap<u16, u16>,
+    attr_handle: u16,
+) -> Option<Vec<u8>> {
+    let value = match &db.get(attr_handle)?.value {
+        AttributeValue::Static(value) => value.clone(),
+        AttributeValue::Handler => handler.on_read(attr_handle),
+        AttributeValue::Byte(level) => vec![level.load(Ordering::Relaxed)],
+        AttributeValue::Stored(value) => value.lock().unwrap().clone(),
+        AttributeValue::Cccd => {
+            cccd_values.get(&attr_handle).copied().unwrap_or(0).to_le_bytes().to_vec()
+        }
     };
+    Some(value)
+}
+
+/// Wrap an ATT PDU in ACL and L2CAP headers
+fn build_att_packet(conn_handle: u16, pdu: &[u8]) -> Vec<u8> {
+    let l2cap_len = pdu.len();
+    let acl_len = l2cap_len + 4;
+
+    let mut pkt = vec![
+        HCI_ACLDATA_PKT,
+        (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
+        (acl_len & 0xFF) as u8, (acl_len >> 8) as u8,
+        (l2cap_len & 0xFF) as u8, (l2cap_len >> 8) as u8,
+        0x04, 0x00, // ATT CID
+    ];
+    pkt.extend_from_slice(pdu);
+    pkt
+}
+
+fn build_read_response(conn_handle: u16, value: &[u8]) -> Vec<u8> {
+    // Read Response carries at most MTU - 1 bytes of the value
+    let data = &value[..value.len().min(ATT_DEFAULT_MTU - 1)];
 
     let l2cap_len = 1 + data.len();
     let acl_len = l2cap_len + 4;
@@ -1045,6 +1558,42 @@ fn build_read_response(conn_handle: u16, attr_handle: u16, hello_msg: &[u8], com
     pkt
 }
 
+/// Read Blob Response with the part of `value` starting at `offset`
+///
+/// Clients read values longer than one Read Response with a Read Request
+/// followed by Read Blob Requests at increasing offsets, until a response
+/// is shorter than MTU - 1 bytes. An offset past the end of the value is
+/// an error; an offset equal to its length gives an empty response.
+fn build_read_blob_response(conn_handle: u16, attr_handle: u16, value: &[u8], offset: u16) -> Vec<u8> {
+    let Some(rest) = value.get(offset as usize..) else {
+        return build_error_response(conn_handle, ATT_OP_READ_BLOB_REQ, attr_handle, ATT_ERR_INVALID_OFFSET);
+    };
+
+    let mut pdu = vec![ATT_OP_READ_BLOB_RSP];
+    pdu.extend_from_slice(&rest[..rest.len().min(ATT_DEFAULT_MTU - 1)]);
+    build_att_packet(conn_handle, &pdu)
+}
+
+fn build_notification(conn_handle: u16, attr_handle: u16, value: &[u8]) -> Vec<u8> {
+    // Notification carries at most MTU - 3 bytes of the value
+    let data = &value[..value.len().min(ATT_DEFAULT_MTU - 3)];
+
+    let l2cap_len = 3 + data.len();
+    let acl_len = l2cap_len + 4;
+
+    let mut pkt = vec![
+        0x02,
+        (conn_handle & 0xFF) as u8, ((conn_handle >> 8) & 0x0F) as u8,
+        (acl_len & 0xFF) as u8, (acl_len >> 8) as u8,
+        (l2cap_len & 0xFF) as u8, (l2cap_len >> 8) as u8,
+        0x04, 0x00, // ATT CID
+        ATT_OP_HANDLE_VALUE_NOTIF,
+        (attr_handle & 0xFF) as u8, (attr_handle >> 8) as u8,
+    ];
+    pkt.extend_from_slice(data);
+    pkt
+}
+
 fn build_write_response(conn_handle: u16) -> Vec<u8> {
     vec![
         0x02,
@@ -1074,34 +1623,412 @@ fn send_acl_data(socket: &mut HciSocket, data: &[u8]) -> BleResult<()> {
     socket.write_all(data).map_err(|_| BleError::SocketError)
 }
 
-/// Connect to a BLE device
-pub fn ble_connect(address: &BleAddress, _timeout_ms: u32) -> BleResult<ConnectionHandle> {
-    let state = STATE.lock().map_err(|_| BleError::SocketError)?;
+/// Connect to a BLE device (central role)
+///
+/// Initiates an LE connection and waits up to `timeout_ms` for it to be
+/// established. The peer address type is taken from the last scan results
+/// (public if the device wasn't seen). Only one connection is supported.
+pub fn ble_connect(address: &BleAddress, timeout_ms: u32) -> BleResult<ConnectionHandle> {
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
     }
 
-    let _ = address;
-    // TODO: Implement full L2CAP connection
-    Err(BleError::NotSupported)
+    if state.connection.is_some() {
+        return Err(BleError::ConnectionError);
+    }
+
+    let peer_addr_type = match state.scan_results.iter().find(|r| r.address == *address) {
+        Some(r) if r.address_type == AddressType::Random => LE_RANDOM_ADDRESS,
+        _ => LE_PUBLIC_ADDRESS,
+    };
+
+    // HCI uses little-endian address byte order
+    let mut peer_addr = address.bytes;
+    peer_addr.reverse();
+
+    let mut params = [0u8; 25];
+    params[0..2].copy_from_slice(&0x0060u16.to_le_bytes()); // Scan interval: 96 * 0.625ms = 60ms
+    params[2..4].copy_from_slice(&0x0030u16.to_le_bytes()); // Scan window: 48 * 0.625ms = 30ms
+    params[4] = 0x00; // Initiator filter policy: use peer address
+    params[5] = peer_addr_type;
+    params[6..12].copy_from_slice(&peer_addr);
+    params[12] = LE_PUBLIC_ADDRESS; // Own address type
+    params[13..15].copy_from_slice(&0x0018u16.to_le_bytes()); // Min interval: 24 * 1.25ms = 30ms
+    params[15..17].copy_from_slice(&0x0028u16.to_le_bytes()); // Max interval: 40 * 1.25ms = 50ms
+    params[17..19].copy_from_slice(&0x0000u16.to_le_bytes()); // Peripheral latency
+    params[19..21].copy_from_slice(&0x00C8u16.to_le_bytes()); // Supervision timeout: 2s
+    params[21..23].copy_from_slice(&0x0000u16.to_le_bytes()); // Min CE length
+    params[23..25].copy_from_slice(&0x0000u16.to_le_bytes()); // Max CE length
+
+    let socket = state.socket.as_mut().unwrap();
+
+    // LE Create Connection answers with Command Status, not Command Complete
+    send_hci_cmd(socket, HCI_OP_LE_CREATE_CONN, &params)?;
+
+    socket.set_read_timeout(Duration::from_millis(100))?;
+    let start = std::time::Instant::now();
+    let timeout = Duration::from_millis(timeout_ms as u64);
+    let mut buf = [0u8; 258];
+
+    let handle = loop {
+        if start.elapsed() >= timeout {
+            // Abort the pending connection attempt
+            let _ = socket.send_cmd_wait(HCI_OP_LE_CREATE_CONN_CANCEL, &[]);
+            return Err(BleError::Timeout);
+        }
+
+        let len = match socket.read(&mut buf) {
+            Ok(len) if len >= 7 && buf[0] == HCI_EVENT_PKT => len,
+            _ => continue,
+        };
+
+        // Command Status: status(1) + num_cmds(1) + opcode(2)
+        if buf[1] == HCI_EV_CMD_STATUS
+            && u16::from_le_bytes([buf[5], buf[6]]) == HCI_OP_LE_CREATE_CONN
+            && buf[3] != 0
+        {
+            eprintln!("  [DEBUG] LE Create Connection failed with status 0x{:02X}", buf[3]);
+            return Err(BleError::ConnectionError);
+        }
+
+        // LE Connection Complete: subevent(1) + status(1) + handle(2) + role(1)
+        // + peer type(1) + peer addr(6)
+        if buf[1] == HCI_EV_LE_META && buf[3] == HCI_EV_LE_CONN_COMPLETE && len >= 15 {
+            if buf[4] != 0 {
+                eprintln!("  [DEBUG] Connection failed with status 0x{:02X}", buf[4]);
+                return Err(BleError::ConnectionError);
+            }
+            if buf[9..15] == peer_addr {
+                break ConnectionHandle::from_acl_header(buf[5], buf[6]);
+            }
+        }
+    };
+
+    eprintln!("  [DEBUG] Connected to {} (handle {})", address, handle);
+    state.connection = Some(handle);
+
+    Ok(handle)
 }
 
 /// Disconnect from a BLE device
 pub fn ble_disconnect(handle: ConnectionHandle) -> BleResult<()> {
-    let state = STATE.lock().map_err(|_| BleError::SocketError)?;
+    let mut state = lock_state()?;
 
     if state.socket.is_none() {
         return Err(BleError::NotInitialized);
     }
 
-    let _ = handle;
-    Ok(())
+    if !handle.is_valid() {
+        return Err(BleError::InvalidParameter);
+    }
+
+    let socket = state.socket.as_mut().unwrap();
+    let result = send_disconnect(socket, handle);
+
+    if state.connection == Some(handle) {
+        state.connection = None;
+    }
+
+    result
 }
 
-/// Discover GATT services
-pub fn gatt_discover_services(_handle: ConnectionHandle) -> BleResult<Vec<Uuid>> {
-    Err(BleError::NotSupported)
+/// Send HCI_Disconnect and wait for the matching Disconnection Complete event
+fn send_disconnect(socket: &mut HciSocket, handle: ConnectionHandle) -> BleResult<()> {
+    let mut params = [0u8; 3];
+    params[0..2].copy_from_slice(&handle.0.to_le_bytes());
+    params[2] = HCI_ERR_REMOTE_USER_TERM;
+    send_hci_cmd(socket, HCI_OP_DISCONNECT, &params)?;
+
+    socket.set_read_timeout(Duration::from_millis(100))?;
+    let start = std::time::Instant::now();
+    let mut buf = [0u8; 258];
+
+    while start.elapsed() < DISCONNECT_TIMEOUT {
+        let len = match socket.read(&mut buf) {
+            Ok(len) if len >= 7 && buf[0] == HCI_EVENT_PKT => len,
+            _ => continue,
+        };
+
+        // Command Status with an error (e.g. unknown connection handle)
+        if buf[1] == HCI_EV_CMD_STATUS
+            && u16::from_le_bytes([buf[5], buf[6]]) == HCI_OP_DISCONNECT
+            && buf[3] != 0
+        {
+            return Err(BleError::DisconnectionError);
+        }
+
+        // Disconnection Complete: status(1) + handle(2) + reason(1)
+        if buf[1] == HCI_EV_DISCONN_COMPLETE
+            && len >= 7
+            && ConnectionHandle::from_acl_header(buf[4], buf[5]) == handle
+        {
+            return if buf[3] == 0 {
+                Ok(())
+            } else {
+                Err(BleError::DisconnectionError)
+            };
+        }
+    }
+
+    Err(BleError::Timeout)
+}
+
+/// Primary service found by discovery
+#[doc(hidden)] // pub for tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceRange {
+    pub start_handle: u16,
+    pub end_handle: u16,
+    pub uuid: Uuid,
+}
+
+/// Read the RSSI of a connection in dBm
+///
+/// Returns `DeviceNotFound` unless `handle` is the connection made by `ble_connect`.
+pub fn ble_get_rssi(handle: ConnectionHandle) -> BleResult<i8> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    if state.connection != Some(handle) {
+        return Err(BleError::DeviceNotFound);
+    }
+
+    let socket = state.socket.as_mut().unwrap();
+    let ret = socket.send_cmd_return(HCI_OP_READ_RSSI, &handle.0.to_le_bytes())?;
+    parse_handle_i8_response(&ret)
+}
+
+/// Read the current transmit power level of the active connection in dBm
+///
+/// Returns `DeviceNotFound` if there is no connection made by `ble_connect`.
+pub fn ble_get_tx_power() -> BleResult<i8> {
+    let mut state = lock_state()?;
+
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+
+    let handle = state.connection.ok_or(BleError::DeviceNotFound)?;
+
+    // Parameters: handle(2) + type (0x00 = current level)
+    let mut params = [0u8; 3];
+    params[0..2].copy_from_slice(&handle.0.to_le_bytes());
+
+    let socket = state.socket.as_mut().unwrap();
+    let ret = socket.send_cmd_return(HCI_OP_READ_TX_POWER_LEVEL, &params)?;
+    parse_handle_i8_response(&ret)
+}
+
+/// Parse return parameters of the form handle(2) + value(1, two's complement)
+#[doc(hidden)] // pub for tests
+pub fn parse_handle_i8_response(ret: &[u8]) -> BleResult<i8> {
+    ret.get(2).map(|&v| v as i8).ok_or(BleError::SocketError)
+}
+
+/// Discover GATT services (central role)
+///
+/// Returns the peer's primary services in handle order.
+pub fn gatt_discover_services(handle: ConnectionHandle) -> BleResult<Vec<Uuid>> {
+    let mut state = lock_state()?;
+    let socket = connected_socket(&mut state, handle)?;
+
+    let services = discover_service_ranges(socket, handle)?;
+    Ok(services.into_iter().map(|s| s.uuid).collect())
+}
+
+/// Discover the characteristics of a service (central role)
+///
+/// Returns `GattError` if the peer has no primary service with `service_uuid`.
+pub fn gatt_discover_characteristics(
+    conn: ConnectionHandle,
+    service_uuid: Uuid,
+) -> BleResult<Vec<CharacteristicHandle>> {
+    let mut state = lock_state()?;
+    let socket = connected_socket(&mut state, conn)?;
+
+    let service = discover_service_ranges(socket, conn)?
+        .into_iter()
+        .find(|s| s.uuid == service_uuid)
+        .ok_or(BleError::GattError)?;
+
+    let mut characteristics = Vec::new();
+    let mut start = service.start_handle;
+
+    while start <= service.end_handle {
+        let mut req = [0u8; 7];
+        req[0] = ATT_OP_READ_BY_TYPE_REQ;
+        req[1..3].copy_from_slice(&start.to_le_bytes());
+        req[3..5].copy_from_slice(&service.end_handle.to_le_bytes());
+        req[5..7].copy_from_slice(&GATT_CHARACTERISTIC_UUID.to_le_bytes());
+
+        let rsp = att_request(socket, conn, &req)?;
+        if is_attr_not_found(&rsp) {
+            break;
+        }
+        if rsp.first() != Some(&ATT_OP_READ_BY_TYPE_RSP) {
+            return Err(BleError::GattError);
+        }
+
+        let entries = parse_characteristic_entries(&rsp)?;
+        let last = match entries.last() {
+            Some(&(decl_handle, _)) => decl_handle,
+            None => break,
+        };
+        characteristics.extend(entries.into_iter().map(|(handle, value_handle)| {
+            CharacteristicHandle { connection: conn, handle, value_handle }
+        }));
+
+        if last == 0xFFFF {
+            break;
+        }
+        start = last + 1;
+    }
+
+    Ok(characteristics)
+}
+
+/// Get the HCI socket if `handle` is the active central-role connection
+fn connected_socket(state: &mut BleState, handle: ConnectionHandle) -> BleResult<&mut HciSocket> {
+    if state.socket.is_none() {
+        return Err(BleError::NotInitialized);
+    }
+    if state.connection != Some(handle) {
+        return Err(BleError::ConnectionError);
+    }
+    Ok(state.socket.as_mut().unwrap())
+}
+
+/// Find all primary services with repeated Read By Group Type requests
+fn discover_service_ranges(
+    socket: &mut HciSocket,
+    conn: ConnectionHandle,
+) -> BleResult<Vec<ServiceRange>> {
+    let mut services: Vec<ServiceRange> = Vec::new();
+    let mut start: u16 = 0x0001;
+
+    loop {
+        let mut req = [0u8; 7];
+        req[0] = ATT_OP_READ_BY_GROUP_REQ;
+        req[1..3].copy_from_slice(&start.to_le_bytes());
+        req[3..5].copy_from_slice(&0xFFFFu16.to_le_bytes());
+        req[5..7].copy_from_slice(&GATT_PRIMARY_SERVICE_UUID.to_le_bytes());
+
+        let rsp = att_request(socket, conn, &req)?;
+        if is_attr_not_found(&rsp) {
+            break;
+        }
+        if rsp.first() != Some(&ATT_OP_READ_BY_GROUP_RSP) {
+            return Err(BleError::GattError);
+        }
+
+        let entries = parse_service_entries(&rsp)?;
+        let last_end = match entries.last() {
+            Some(entry) => entry.end_handle,
+            None => break,
+        };
+        services.extend(entries);
+
+        if last_end == 0xFFFF {
+            break;
+        }
+        start = last_end + 1;
+    }
+
+    services.sort_by_key(|s| s.start_handle);
+    Ok(services)
+}
+
+/// Parse a Read By Group Type Response: length(1) + [start(2) + end(2) + uuid(2 or 16)]...
+#[doc(hidden)] // pub for tests
+pub fn parse_service_entries(rsp: &[u8]) -> BleResult<Vec<ServiceRange>> {
+    let entry_len = *rsp.get(1).ok_or(BleError::GattError)? as usize;
+    if entry_len != 6 && entry_len != 20 {
+        return Err(BleError::GattError);
+    }
+
+    Ok(rsp[2..]
+        .chunks_exact(entry_len)
+        .map(|entry| ServiceRange {
+            start_handle: u16::from_le_bytes([entry[0], entry[1]]),
+            end_handle: u16::from_le_bytes([entry[2], entry[3]]),
+            uuid: uuid_from_att(&entry[4..]),
+        })
+        .collect())
+}
+
+/// Parse a Read By Type Response for characteristic declarations:
+/// length(1) + [handle(2) + properties(1) + value handle(2) + uuid(2 or 16)]...
+///
+/// Returns (declaration handle, value handle) pairs.
+#[doc(hidden)] // pub for tests
+pub fn parse_characteristic_entries(rsp: &[u8]) -> BleResult<Vec<(u16, u16)>> {
+    let entry_len = *rsp.get(1).ok_or(BleError::GattError)? as usize;
+    if entry_len != 7 && entry_len != 21 {
+        return Err(BleError::GattError);
+    }
+
+    Ok(rsp[2..]
+        .chunks_exact(entry_len)
+        .map(|entry| {
+            let handle = u16::from_le_bytes([entry[0], entry[1]]);
+            let value_handle = u16::from_le_bytes([entry[3], entry[4]]);
+            (handle, value_handle)
+        })
+        .collect())
+}
+
+/// Convert a little-endian UUID from an ATT PDU (2 or 16 bytes)
+fn uuid_from_att(bytes: &[u8]) -> Uuid {
+    if bytes.len() == 2 {
+        Uuid::from_u16(u16::from_le_bytes([bytes[0], bytes[1]]))
+    } else {
+        let mut be = [0u8; 16];
+        be.copy_from_slice(&bytes[..16]);
+        be.reverse();
+        Uuid::from_bytes(be)
+    }
+}
+
+/// Check for an Error Response carrying Attribute Not Found (end of discovery)
+fn is_attr_not_found(rsp: &[u8]) -> bool {
+    rsp.len() >= 5 && rsp[0] == ATT_OP_ERROR_RSP && rsp[4] == ATT_ERR_ATTR_NOT_FOUND
+}
+
+/// Send an ATT request on the connection and return the response PDU
+fn att_request(socket: &mut HciSocket, conn: ConnectionHandle, req: &[u8]) -> BleResult<Vec<u8>> {
+    send_acl_data(socket, &build_att_packet(conn.0, req))?;
+
+    socket.set_read_timeout(Duration::from_millis(100))?;
+    let start = std::time::Instant::now();
+    let mut buf = [0u8; 512];
+
+    while start.elapsed() < ATT_RSP_TIMEOUT {
+        let len = match socket.read(&mut buf) {
+            Ok(len) if len >= 10 => len,
+            _ => continue,
+        };
+
+        if buf[0] == HCI_EVENT_PKT && buf[1] == HCI_EV_DISCONN_COMPLETE {
+            return Err(BleError::ConnectionError);
+        }
+
+        // Skip notifications and requests from the peer; a response opcode
+        // is the request opcode + 1
+        if buf[0] == HCI_ACLDATA_PKT
+            && ConnectionHandle::from_acl_header(buf[1], buf[2]) == conn
+            && u16::from_le_bytes([buf[7], buf[8]]) == L2CAP_CID_ATT
+            && (buf[9] == ATT_OP_ERROR_RSP || buf[9] == req[0] + 1)
+        {
+            return Ok(buf[9..len].to_vec());
+        }
+    }
+
+    Err(BleError::Timeout)
 }
 
 /// Read a GATT characteristic
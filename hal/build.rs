@@ -0,0 +1,97 @@
+//! Build script: check the V4L2 struct layouts against the kernel headers
+//!
+//! When the Linux camera backend is built natively, compiles a small C
+//! program against `linux/videodev2.h` and compares the struct sizes with
+//! the ones `camera/linux.rs` asserts. Emits `cfg(v4l2_verified)` when they
+//! match and a build warning when they don't. Skipped silently if no C
+//! compiler or kernel headers are available.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Struct names and sizes as asserted in `camera/linux.rs` (64-bit)
+const EXPECTED_SIZES: [(&str, usize); 6] = [
+    ("v4l2_capability", 104),
+    ("v4l2_pix_format", 48),
+    ("v4l2_requestbuffers", 20),
+    ("v4l2_streamparm", 204),
+    ("v4l2_format", 208),
+    ("v4l2_buffer", 88),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=CC");
+    println!("cargo:rustc-check-cfg=cfg(v4l2_verified)");
+
+    let enabled = |feature: &str| env::var_os(format!("CARGO_FEATURE_{}", feature)).is_some();
+    if !enabled("PLATFORM_LINUX") || !enabled("CAMERA") {
+        return;
+    }
+
+    // The probe runs on the host, so it only describes a native 64-bit Linux target
+    let native = env::var("HOST").ok() == env::var("TARGET").ok();
+    let target_linux = env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux");
+    let target_64 = env::var("CARGO_CFG_TARGET_POINTER_WIDTH").as_deref() == Ok("64");
+    if !(native && target_linux && target_64) {
+        return;
+    }
+
+    let Some(sizes) = kernel_sizes().filter(|s| s.len() == EXPECTED_SIZES.len()) else {
+        return;
+    };
+    let mismatches: Vec<String> = EXPECTED_SIZES
+        .iter()
+        .zip(&sizes)
+        .filter(|((_, expected), actual)| expected != *actual)
+        .map(|((name, expected), actual)| {
+            format!("{} is {} bytes, expected {}", name, actual, expected)
+        })
+        .collect();
+
+    if mismatches.is_empty() {
+        println!("cargo:rustc-cfg=v4l2_verified");
+    } else {
+        for mismatch in mismatches {
+            println!("cargo:warning=V4L2 ABI mismatch: {}", mismatch);
+        }
+    }
+}
+
+/// Compile and run a probe printing the kernel's struct sizes, one per line
+fn kernel_sizes() -> Option<Vec<usize>> {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR")?);
+    let source = out_dir.join("v4l2_sizes.c");
+    let binary = out_dir.join("v4l2_sizes");
+
+    let mut program =
+        String::from("#include <stdio.h>\n#include <linux/videodev2.h>\nint main(void) {\n");
+    for (name, _) in EXPECTED_SIZES {
+        program.push_str(&format!(
+            "    printf(\"%zu\\n\", sizeof(struct {}));\n",
+            name
+        ));
+    }
+    program.push_str("    return 0;\n}\n");
+    fs::write(&source, program).ok()?;
+
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let compiled = Command::new(cc)
+        .arg(&source)
+        .arg("-o")
+        .arg(&binary)
+        .output()
+        .ok()?;
+    if !compiled.status.success() {
+        return None;
+    }
+
+    let output = Command::new(&binary).output().ok()?;
+    String::from_utf8(output.stdout)
+        .ok()?
+        .lines()
+        .map(|line| line.trim().parse().ok())
+        .collect()
+}
@@ -4,7 +4,8 @@
 //! efficient webcam capture on Linux systems.
 
 use super::{
-    CameraConfig, CameraError, CameraResult, CameraSettings, FrameBuffer, PixelFormat,
+    CameraConfig, CameraError, CameraHandle, CameraResult, CameraSettings, CameraStats,
+    FrameBuffer, PixelFormat, Resolution,
 };
 use std::fs::{File, OpenOptions};
 use std::os::unix::fs::OpenOptionsExt;
@@ -17,6 +18,7 @@ use std::sync::Mutex;
 
 // V4L2 ioctl commands
 const VIDIOC_QUERYCAP: libc::c_ulong = 0x80685600;
+const VIDIOC_ENUM_FMT: libc::c_ulong = 0xC0405602;
 const VIDIOC_S_FMT: libc::c_ulong = 0xC0D05605;
 const VIDIOC_G_FMT: libc::c_ulong = 0xC0D05604;
 const VIDIOC_REQBUFS: libc::c_ulong = 0xC0145608;
@@ -27,6 +29,8 @@ const VIDIOC_STREAMON: libc::c_ulong = 0x40045612;
 const VIDIOC_STREAMOFF: libc::c_ulong = 0x40045613;
 const VIDIOC_G_CTRL: libc::c_ulong = 0xC008561B;
 const VIDIOC_S_CTRL: libc::c_ulong = 0xC008561C;
+const VIDIOC_S_PARM: libc::c_ulong = 0xC0CC5616;
+const VIDIOC_ENUM_FRAMESIZES: libc::c_ulong = 0xC02C564A;
 
 // V4L2 pixel formats
 const V4L2_PIX_FMT_MJPEG: u32 = 0x47504A4D; // 'MJPG'
@@ -42,6 +46,9 @@ const V4L2_BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
 const V4L2_MEMORY_MMAP: u32 = 1;
 const V4L2_FIELD_ANY: u32 = 0;
 
+// V4L2 frame size types
+const V4L2_FRMSIZE_TYPE_DISCRETE: u32 = 1;
+
 // V4L2 control IDs
 const V4L2_CID_BRIGHTNESS: u32 = 0x00980900;
 const V4L2_CID_CONTRAST: u32 = 0x00980901;
@@ -89,10 +96,13 @@ struct V4l2Format {
     fmt: V4l2FormatUnion,
 }
 
+/// The kernel union also holds `v4l2_window`, whose pointers give it
+/// pointer alignment (`fmt` sits at offset 8 on 64-bit)
 #[repr(C)]
 union V4l2FormatUnion {
     pix: std::mem::ManuallyDrop<V4l2PixFormat>,
     raw_data: [u8; 200],
+    _align: *mut libc::c_void,
 }
 
 #[repr(C)]
@@ -123,18 +133,27 @@ struct V4l2Buffer {
     bytesused: u32,    // offset 8
     flags: u32,        // offset 12
     field: u32,        // offset 16
-    _pad1: u32,        // offset 20 (padding for alignment)
-    timestamp: libc::timeval, // offset 24 (16 bytes)
+    timestamp: libc::timeval, // offset 24 on 64-bit (repr(C) pads after field)
     timecode: V4l2Timecode,   // offset 40 (16 bytes)
     sequence: u32,     // offset 56
     memory: u32,       // offset 60
     m: V4l2BufferUnion, // offset 64 (8 bytes)
     length: u32,       // offset 72
     reserved2: u32,    // offset 76
-    request_fd: i32,   // offset 80
-    reserved: u32,     // offset 84 (to make total 88 bytes)
+    request_fd: i32,   // offset 80 (union with `reserved`; tail padding makes 88)
 }
 
+// Kernel ABI sizes (linux/videodev2.h); the ioctl numbers above encode the
+// same sizes, so a layout mistake here would corrupt every call
+const _: () = assert!(std::mem::size_of::<V4l2Capability>() == 104);
+const _: () = assert!(std::mem::size_of::<V4l2PixFormat>() == 48);
+const _: () = assert!(std::mem::size_of::<V4l2RequestBuffers>() == 20);
+const _: () = assert!(std::mem::size_of::<V4l2StreamParm>() == 204);
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<V4l2Format>() == 208);
+#[cfg(target_pointer_width = "64")]
+const _: () = assert!(std::mem::size_of::<V4l2Buffer>() == 88);
+
 #[repr(C)]
 union V4l2BufferUnion {
     offset: u32,
@@ -143,6 +162,43 @@ union V4l2BufferUnion {
     fd: i32,
 }
 
+#[repr(C)]
+struct V4l2FmtDesc {
+    index: u32,
+    type_: u32,
+    flags: u32,
+    description: [u8; 32],
+    pixelformat: u32,
+    mbus_code: u32,
+    reserved: [u32; 3],
+}
+
+/// Stream parameters with the capture member of the `parm` union inlined;
+/// `reserved` pads the union to its 200 bytes
+#[repr(C)]
+struct V4l2StreamParm {
+    type_: u32,
+    capability: u32,
+    capturemode: u32,
+    /// Frame interval in seconds as numerator / denominator
+    timeperframe: [u32; 2],
+    extendedmode: u32,
+    readbuffers: u32,
+    reserved: [u8; 176],
+}
+
+/// Frame size enumeration; `sizes` holds the discrete width/height or
+/// stepwise min_width, max_width, step_width, min_height, max_height,
+/// step_height
+#[repr(C)]
+struct V4l2FrmSizeEnum {
+    index: u32,
+    pixel_format: u32,
+    type_: u32,
+    sizes: [u32; 6],
+    reserved: [u32; 2],
+}
+
 #[repr(C)]
 struct V4l2Control {
     id: u32,
@@ -168,10 +224,11 @@ struct CameraState {
     width: u32,
     height: u32,
     format: PixelFormat,
+    stats: CameraStats,
 }
 
-impl Default for CameraState {
-    fn default() -> Self {
+impl CameraState {
+    const fn new() -> Self {
         Self {
             file: None,
             buffers: Vec::new(),
@@ -179,18 +236,29 @@ impl Default for CameraState {
             width: 640,
             height: 480,
             format: PixelFormat::Jpeg,
+            stats: CameraStats::new(),
         }
     }
 }
 
-static CAMERA_STATE: Mutex<CameraState> = Mutex::new(CameraState {
-    file: None,
-    buffers: Vec::new(),
-    streaming: false,
-    width: 640,
-    height: 480,
-    format: PixelFormat::Jpeg,
-});
+impl Default for CameraState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One state slot per camera (`MAX_CAMERAS`)
+static CAMERAS: [Mutex<CameraState>; super::MAX_CAMERAS] = [
+    Mutex::new(CameraState::new()),
+    Mutex::new(CameraState::new()),
+];
+
+fn camera_state(handle: CameraHandle) -> &'static Mutex<CameraState> {
+    &CAMERAS[handle.index()]
+}
+
+/// Device used for the first camera instead of auto-detection, if set
+static CAMERA_DEVICE_PATH: Mutex<Option<String>> = Mutex::new(None);
 
 // ============================================================================
 // Helper Functions
@@ -203,25 +271,43 @@ fn pixel_format_to_v4l2(fmt: PixelFormat) -> u32 {
         PixelFormat::Rgb888 => V4L2_PIX_FMT_RGB24,
         PixelFormat::Yuv422 => V4L2_PIX_FMT_YUYV,
         PixelFormat::Grayscale => V4L2_PIX_FMT_GREY,
+        PixelFormat::Nv12 => V4L2_PIX_FMT_NV12,
     }
 }
 
-fn v4l2_to_pixel_format(v4l2_fmt: u32) -> PixelFormat {
+fn v4l2_to_known_format(v4l2_fmt: u32) -> Option<PixelFormat> {
     match v4l2_fmt {
-        V4L2_PIX_FMT_MJPEG | V4L2_PIX_FMT_JPEG => PixelFormat::Jpeg,
-        V4L2_PIX_FMT_RGB565 => PixelFormat::Rgb565,
-        V4L2_PIX_FMT_RGB24 => PixelFormat::Rgb888,
-        V4L2_PIX_FMT_YUYV | V4L2_PIX_FMT_NV12 => PixelFormat::Yuv422,
-        V4L2_PIX_FMT_GREY => PixelFormat::Grayscale,
-        _ => PixelFormat::Yuv422, // Default to YUV for unknown formats
+        V4L2_PIX_FMT_MJPEG | V4L2_PIX_FMT_JPEG => Some(PixelFormat::Jpeg),
+        V4L2_PIX_FMT_RGB565 => Some(PixelFormat::Rgb565),
+        V4L2_PIX_FMT_RGB24 => Some(PixelFormat::Rgb888),
+        V4L2_PIX_FMT_YUYV => Some(PixelFormat::Yuv422),
+        V4L2_PIX_FMT_NV12 => Some(PixelFormat::Nv12),
+        V4L2_PIX_FMT_GREY => Some(PixelFormat::Grayscale),
+        _ => None,
     }
 }
 
+fn v4l2_to_pixel_format(v4l2_fmt: u32) -> PixelFormat {
+    // Default to YUV for unknown formats
+    v4l2_to_known_format(v4l2_fmt).unwrap_or(PixelFormat::Yuv422)
+}
+
 unsafe fn ioctl<T>(fd: i32, request: libc::c_ulong, arg: *mut T) -> i32 {
     libc::ioctl(fd, request, arg)
 }
 
-fn find_camera_device() -> Option<String> {
+/// Find the `index`-th video capture device (0 for the first)
+///
+/// The first camera comes from `camera_set_device_path` if one was set.
+fn find_camera_device(index: usize) -> Option<String> {
+    if index == 0 {
+        if let Some(path) = CAMERA_DEVICE_PATH.lock().unwrap().clone() {
+            return std::path::Path::new(&path).exists().then_some(path);
+        }
+    }
+
+    let mut remaining = index;
+
     // Try video devices, checking if they support capture
     for i in 0..10 {
         let path = format!("/dev/video{}", i);
@@ -233,7 +319,10 @@ fn find_camera_device() -> Option<String> {
                 if unsafe { ioctl(fd, VIDIOC_QUERYCAP, &mut cap) } >= 0 {
                     // Check if device supports video capture (capability bit 0x1)
                     if cap.capabilities & 0x1 != 0 {
-                        return Some(path);
+                        if remaining == 0 {
+                            return Some(path);
+                        }
+                        remaining -= 1;
                     }
                 }
             }
@@ -252,20 +341,199 @@ fn unmap_buffers(buffers: &mut Vec<MappedBuffer>) {
     }
 }
 
+/// Open the camera device read-only for queries, without streaming
+fn open_device_for_query() -> CameraResult<File> {
+    let device_path = find_camera_device(0).ok_or(CameraError::DeviceNotFound)?;
+    OpenOptions::new()
+        .read(true)
+        .open(&device_path)
+        .map_err(|_| CameraError::OpenFailed)
+}
+
+/// Request driver buffers and mmap them
+fn map_buffers(fd: i32) -> CameraResult<Vec<MappedBuffer>> {
+    let mut req: V4l2RequestBuffers = unsafe { std::mem::zeroed() };
+    req.count = BUFFER_COUNT as u32;
+    req.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    req.memory = V4L2_MEMORY_MMAP;
+
+    if unsafe { ioctl(fd, VIDIOC_REQBUFS, &mut req) } < 0 {
+        return Err(CameraError::BufferAllocationFailed);
+    }
+
+    let mut buffers = Vec::with_capacity(req.count as usize);
+    for i in 0..req.count {
+        let mut buf: V4l2Buffer = unsafe { std::mem::zeroed() };
+        buf.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+        buf.memory = V4L2_MEMORY_MMAP;
+        buf.index = i;
+
+        if unsafe { ioctl(fd, VIDIOC_QUERYBUF, &mut buf) } < 0 {
+            unmap_buffers(&mut buffers);
+            return Err(CameraError::BufferAllocationFailed);
+        }
+
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                buf.length as usize,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd,
+                buf.m.offset as libc::off_t,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            unmap_buffers(&mut buffers);
+            return Err(CameraError::BufferAllocationFailed);
+        }
+
+        buffers.push(MappedBuffer {
+            ptr,
+            length: buf.length as usize,
+        });
+    }
+
+    Ok(buffers)
+}
+
+/// Unmap buffers and release them in the driver
+fn release_buffers(fd: i32, buffers: &mut Vec<MappedBuffer>) {
+    unmap_buffers(buffers);
+
+    let mut req: V4l2RequestBuffers = unsafe { std::mem::zeroed() };
+    req.count = 0;
+    req.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    req.memory = V4L2_MEMORY_MMAP;
+    unsafe { ioctl(fd, VIDIOC_REQBUFS, &mut req) };
+}
+
+/// Queue all buffers and start streaming
+fn start_streaming(fd: i32, buffer_count: usize) -> CameraResult<()> {
+    for i in 0..buffer_count {
+        let mut buf: V4l2Buffer = unsafe { std::mem::zeroed() };
+        buf.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+        buf.memory = V4L2_MEMORY_MMAP;
+        buf.index = i as u32;
+
+        if unsafe { ioctl(fd, VIDIOC_QBUF, &mut buf) } < 0 {
+            return Err(CameraError::ConfigurationFailed);
+        }
+    }
+
+    let buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    if unsafe { ioctl(fd, VIDIOC_STREAMON, &buf_type as *const u32 as *mut u32) } < 0 {
+        return Err(CameraError::ConfigurationFailed);
+    }
+
+    Ok(())
+}
+
+/// Apply a new frame size on a stopped stream
+///
+/// Updates the state with the size the driver actually chose. Buffers are
+/// released if the driver needs that to change format, or if their size
+/// no longer matches; `restart_streaming` remaps them.
+fn apply_frame_size(
+    fd: i32,
+    state: &mut CameraState,
+    (width, height): (u32, u32),
+) -> CameraResult<()> {
+    let mut fmt: V4l2Format = unsafe { std::mem::zeroed() };
+    fmt.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+    if unsafe { ioctl(fd, VIDIOC_G_FMT, &mut fmt) } < 0 {
+        return Err(CameraError::ConfigurationFailed);
+    }
+
+    let pix = unsafe { &mut *fmt.fmt.pix };
+    pix.width = width;
+    pix.height = height;
+    pix.bytesperline = 0;
+    pix.sizeimage = 0;
+
+    if unsafe { ioctl(fd, VIDIOC_S_FMT, &mut fmt) } < 0 {
+        let errno = unsafe { *libc::__errno_location() };
+        if errno != libc::EBUSY {
+            return Err(CameraError::ConfigurationFailed);
+        }
+
+        // Most drivers refuse format changes while buffers are allocated
+        release_buffers(fd, &mut state.buffers);
+        if unsafe { ioctl(fd, VIDIOC_S_FMT, &mut fmt) } < 0 {
+            return Err(CameraError::ConfigurationFailed);
+        }
+    }
+
+    if unsafe { ioctl(fd, VIDIOC_G_FMT, &mut fmt) } < 0 {
+        return Err(CameraError::ConfigurationFailed);
+    }
+    let (width, height) = unsafe { (fmt.fmt.pix.width, fmt.fmt.pix.height) };
+    state.width = width;
+    state.height = height;
+
+    // Remap only if the driver's buffer sizes changed
+    for (i, mapped) in state.buffers.iter().enumerate() {
+        let mut buf: V4l2Buffer = unsafe { std::mem::zeroed() };
+        buf.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+        buf.memory = V4L2_MEMORY_MMAP;
+        buf.index = i as u32;
+
+        if unsafe { ioctl(fd, VIDIOC_QUERYBUF, &mut buf) } < 0
+            || buf.length as usize != mapped.length
+        {
+            release_buffers(fd, &mut state.buffers);
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Map buffers if none are mapped, queue them and start streaming
+fn restart_streaming(fd: i32, state: &mut CameraState) -> CameraResult<()> {
+    if state.buffers.is_empty() {
+        state.buffers = map_buffers(fd)?;
+    }
+    start_streaming(fd, state.buffers.len())?;
+    state.streaming = true;
+    Ok(())
+}
+
 // ============================================================================
 // Public API Implementation
 // ============================================================================
 
-/// Initialize the camera with the given configuration
+/// Initialize the camera selected by `config.device_index`
+///
+/// Whichever device is opened, it becomes the camera the handle-less
+/// functions operate on.
 pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
-    let mut state = CAMERA_STATE.lock().unwrap();
+    open_camera(CameraHandle::DEFAULT, config.device_index as usize, config)
+}
+
+/// Initialize the `index`-th camera (0 for the first) with the given
+/// configuration
+///
+/// Each camera has its own state and can be captured from independently,
+/// but simultaneous captures from cameras on the same USB bus compete for
+/// its bandwidth.
+pub fn camera_initialize_at(index: u8, config: CameraConfig) -> CameraResult<CameraHandle> {
+    let handle = CameraHandle::new(index).ok_or(CameraError::DeviceNotFound)?;
+    open_camera(handle, handle.index(), config)?;
+    Ok(handle)
+}
+
+/// Open the `device`-th capture device into the state slot of `handle`
+fn open_camera(handle: CameraHandle, device: usize, config: CameraConfig) -> CameraResult<()> {
+    let mut state = camera_state(handle).lock().unwrap();
 
     if state.file.is_some() {
         return Err(CameraError::AlreadyInitialized);
     }
 
     // Find and open camera device
-    let device_path = find_camera_device().ok_or(CameraError::DeviceNotFound)?;
+    let device_path = find_camera_device(device).ok_or(CameraError::DeviceNotFound)?;
 
     // Open with O_NONBLOCK for proper select() support
     let file = OpenOptions::new()
@@ -344,69 +612,30 @@ pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
         (fmt.fmt.pix.width, fmt.fmt.pix.height, fmt.fmt.pix.pixelformat)
     };
 
-    // Request buffers
-    let mut req: V4l2RequestBuffers = unsafe { std::mem::zeroed() };
-    req.count = BUFFER_COUNT as u32;
-    req.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
-    req.memory = V4L2_MEMORY_MMAP;
-
-    if unsafe { ioctl(fd, VIDIOC_REQBUFS, &mut req) } < 0 {
-        return Err(CameraError::BufferAllocationFailed);
-    }
-
-    // Map buffers
-    let mut buffers = Vec::with_capacity(req.count as usize);
-    for i in 0..req.count {
-        let mut buf: V4l2Buffer = unsafe { std::mem::zeroed() };
-        buf.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
-        buf.memory = V4L2_MEMORY_MMAP;
-        buf.index = i;
-
-        if unsafe { ioctl(fd, VIDIOC_QUERYBUF, &mut buf) } < 0 {
-            unmap_buffers(&mut buffers);
-            return Err(CameraError::BufferAllocationFailed);
-        }
-
-        let ptr = unsafe {
-            libc::mmap(
-                std::ptr::null_mut(),
-                buf.length as usize,
-                libc::PROT_READ | libc::PROT_WRITE,
-                libc::MAP_SHARED,
-                fd,
-                buf.m.offset as libc::off_t,
-            )
-        };
-
-        if ptr == libc::MAP_FAILED {
-            unmap_buffers(&mut buffers);
-            return Err(CameraError::BufferAllocationFailed);
-        }
+    // Apply initial orientation before streaming starts (best effort, like
+    // camera_set_settings - not every device has flip controls)
+    let mut ctrl = V4l2Control {
+        id: V4L2_CID_HFLIP,
+        value: config.hmirror as i32,
+    };
+    unsafe { ioctl(fd, VIDIOC_S_CTRL, &mut ctrl) };
+    ctrl.id = V4L2_CID_VFLIP;
+    ctrl.value = config.vflip as i32;
+    unsafe { ioctl(fd, VIDIOC_S_CTRL, &mut ctrl) };
 
-        buffers.push(MappedBuffer {
-            ptr,
-            length: buf.length as usize,
-        });
+    // Request the frame rate (best effort - the driver picks the closest
+    // interval it supports, and some ignore it entirely)
+    if config.fps > 0 {
+        let mut parm: V4l2StreamParm = unsafe { std::mem::zeroed() };
+        parm.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+        parm.timeperframe = [1, config.fps as u32];
+        unsafe { ioctl(fd, VIDIOC_S_PARM, &mut parm) };
     }
 
-    // Queue all buffers
-    for i in 0..buffers.len() {
-        let mut buf: V4l2Buffer = unsafe { std::mem::zeroed() };
-        buf.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
-        buf.memory = V4L2_MEMORY_MMAP;
-        buf.index = i as u32;
-
-        if unsafe { ioctl(fd, VIDIOC_QBUF, &mut buf) } < 0 {
-            unmap_buffers(&mut buffers);
-            return Err(CameraError::ConfigurationFailed);
-        }
-    }
-
-    // Start streaming
-    let buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE;
-    if unsafe { ioctl(fd, VIDIOC_STREAMON, &buf_type as *const u32 as *mut u32) } < 0 {
+    let mut buffers = map_buffers(fd)?;
+    if let Err(e) = start_streaming(fd, buffers.len()) {
         unmap_buffers(&mut buffers);
-        return Err(CameraError::ConfigurationFailed);
+        return Err(e);
     }
 
     state.file = Some(file);
@@ -415,13 +644,19 @@ pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
     state.width = actual_width;
     state.height = actual_height;
     state.format = v4l2_to_pixel_format(actual_pixfmt);
+    state.stats = CameraStats::new();
 
     Ok(())
 }
 
 /// Deinitialize the camera
 pub fn camera_deinitialize() -> CameraResult<()> {
-    let mut state = CAMERA_STATE.lock().unwrap();
+    camera_deinitialize_at(CameraHandle::DEFAULT)
+}
+
+/// Deinitialize a camera opened with `camera_initialize_at`
+pub fn camera_deinitialize_at(handle: CameraHandle) -> CameraResult<()> {
+    let mut state = camera_state(handle).lock().unwrap();
 
     if state.file.is_none() {
         return Err(CameraError::NotInitialized);
@@ -445,9 +680,129 @@ pub fn camera_deinitialize() -> CameraResult<()> {
     Ok(())
 }
 
+/// Change the capture resolution without reopening the device
+///
+/// Stops the stream, applies the new frame size and restarts streaming.
+/// Buffers are only remapped if their size changes. The driver may pick
+/// the closest supported size; captured frames report the actual size.
+///
+/// On failure the previous size is restored and the camera keeps
+/// streaming. If even that fails, the camera is deinitialized.
+pub fn camera_set_resolution(resolution: Resolution) -> CameraResult<()> {
+    let mut state = camera_state(CameraHandle::DEFAULT).lock().unwrap();
+
+    let fd = state
+        .file
+        .as_ref()
+        .ok_or(CameraError::NotInitialized)?
+        .as_raw_fd();
+
+    if state.streaming {
+        let buf_type = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+        unsafe { ioctl(fd, VIDIOC_STREAMOFF, &buf_type as *const u32 as *mut u32) };
+        state.streaming = false;
+    }
+
+    let previous = (state.width, state.height);
+    let result = apply_frame_size(fd, &mut state, (resolution.width(), resolution.height()))
+        .and_then(|()| restart_streaming(fd, &mut state));
+
+    if result.is_err() {
+        // Start over from unqueued, unmapped buffers at the old size
+        release_buffers(fd, &mut state.buffers);
+        let restored = apply_frame_size(fd, &mut state, previous)
+            .and_then(|()| restart_streaming(fd, &mut state));
+        if restored.is_err() {
+            // Neither size works; close rather than leave a stopped camera
+            unmap_buffers(&mut state.buffers);
+            state.streaming = false;
+            state.file = None;
+        }
+    }
+
+    result
+}
+
+/// List the pixel formats supported by the camera
+///
+/// Formats without a `PixelFormat` equivalent are skipped. Does not require
+/// (or affect) an initialized camera.
+pub fn camera_enumerate_formats() -> CameraResult<Vec<PixelFormat>> {
+    let file = open_device_for_query()?;
+    let fd = file.as_raw_fd();
+
+    let mut formats = Vec::new();
+    for index in 0.. {
+        let mut desc: V4l2FmtDesc = unsafe { std::mem::zeroed() };
+        desc.index = index;
+        desc.type_ = V4L2_BUF_TYPE_VIDEO_CAPTURE;
+
+        // The driver returns EINVAL past the last format
+        if unsafe { ioctl(fd, VIDIOC_ENUM_FMT, &mut desc) } < 0 {
+            break;
+        }
+
+        if let Some(format) = v4l2_to_known_format(desc.pixelformat) {
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+    }
+
+    Ok(formats)
+}
+
+/// List the frame sizes supported for a pixel format
+///
+/// Sizes matching a `Resolution` preset come first. For drivers reporting
+/// a continuous or stepwise range, the presets within the range are
+/// returned along with the maximum size.
+pub fn camera_enumerate_resolutions(format: PixelFormat) -> CameraResult<Vec<(u32, u32)>> {
+    let file = open_device_for_query()?;
+    let fd = file.as_raw_fd();
+
+    let mut sizes = Vec::new();
+    for index in 0.. {
+        let mut frmsize: V4l2FrmSizeEnum = unsafe { std::mem::zeroed() };
+        frmsize.index = index;
+        frmsize.pixel_format = pixel_format_to_v4l2(format);
+
+        if unsafe { ioctl(fd, VIDIOC_ENUM_FRAMESIZES, &mut frmsize) } < 0 {
+            break;
+        }
+
+        if frmsize.type_ == V4L2_FRMSIZE_TYPE_DISCRETE {
+            sizes.push((frmsize.sizes[0], frmsize.sizes[1]));
+        } else {
+            // Continuous/stepwise: a single entry describing the range
+            let [min_w, max_w, _, min_h, max_h, _] = frmsize.sizes;
+            for res in Resolution::ALL {
+                let (w, h) = (res.width(), res.height());
+                if (min_w..=max_w).contains(&w) && (min_h..=max_h).contains(&h) {
+                    sizes.push((w, h));
+                }
+            }
+            sizes.push((max_w, max_h));
+            break;
+        }
+    }
+
+    let is_preset = |&(w, h): &(u32, u32)| Resolution::from_dimensions(w, h).is_some();
+    sizes.dedup();
+    let (mut presets, others): (Vec<_>, Vec<_>) = sizes.into_iter().partition(is_preset);
+    presets.extend(others);
+
+    Ok(presets)
+}
+
 /// Capture a single frame
 pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
-    let state = CAMERA_STATE.lock().unwrap();
+    camera_capture_frame_at(CameraHandle::DEFAULT)
+}
+
+/// Capture a single frame from a camera opened with `camera_initialize_at`
+pub fn camera_capture_frame_at(handle: CameraHandle) -> CameraResult<FrameBuffer> {
+    let mut state = camera_state(handle).lock().unwrap();
 
     let file = state.file.as_ref().ok_or(CameraError::NotInitialized)?;
     let fd = file.as_raw_fd();
@@ -521,6 +876,8 @@ pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
         // Log but don't fail - we already have the frame
     }
 
+    state.stats.record_frame(data_vec.len());
+
     Ok(FrameBuffer {
         width: state.width,
         height: state.height,
@@ -530,9 +887,33 @@ pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
     })
 }
 
+/// Get a copy of the capture statistics
+pub fn camera_get_stats() -> CameraResult<CameraStats> {
+    let state = camera_state(CameraHandle::DEFAULT).lock().unwrap();
+    if state.file.is_none() {
+        return Err(CameraError::NotInitialized);
+    }
+    Ok(state.stats)
+}
+
+/// Reset the capture statistics
+pub fn camera_reset_stats() -> CameraResult<()> {
+    let mut state = camera_state(CameraHandle::DEFAULT).lock().unwrap();
+    if state.file.is_none() {
+        return Err(CameraError::NotInitialized);
+    }
+    state.stats = CameraStats::new();
+    Ok(())
+}
+
 /// Get current camera settings
 pub fn camera_get_settings() -> CameraResult<CameraSettings> {
-    let state = CAMERA_STATE.lock().unwrap();
+    camera_get_settings_at(CameraHandle::DEFAULT)
+}
+
+/// Get current settings of a camera opened with `camera_initialize_at`
+pub fn camera_get_settings_at(handle: CameraHandle) -> CameraResult<CameraSettings> {
+    let state = camera_state(handle).lock().unwrap();
 
     let file = state.file.as_ref().ok_or(CameraError::NotInitialized)?;
     let fd = file.as_raw_fd();
@@ -581,7 +962,12 @@ pub fn camera_get_settings() -> CameraResult<CameraSettings> {
 
 /// Set camera settings
 pub fn camera_set_settings(settings: CameraSettings) -> CameraResult<()> {
-    let state = CAMERA_STATE.lock().unwrap();
+    camera_set_settings_at(CameraHandle::DEFAULT, settings)
+}
+
+/// Set settings of a camera opened with `camera_initialize_at`
+pub fn camera_set_settings_at(handle: CameraHandle, settings: CameraSettings) -> CameraResult<()> {
+    let state = camera_state(handle).lock().unwrap();
 
     let file = state.file.as_ref().ok_or(CameraError::NotInitialized)?;
     let fd = file.as_raw_fd();
@@ -616,8 +1002,27 @@ pub fn camera_set_settings(settings: CameraSettings) -> CameraResult<()> {
     Ok(())
 }
 
+/// Use `path` for the first camera instead of scanning `/dev/video0`-`9`
+///
+/// Useful for higher-numbered nodes or udev symlinks like `/dev/camera`.
+/// Takes effect at the next initialization; an empty path restores
+/// auto-detection. A missing device makes initialization fail with
+/// `DeviceNotFound`.
+pub fn camera_set_device_path(path: &str) {
+    *CAMERA_DEVICE_PATH.lock().unwrap() = (!path.is_empty()).then(|| path.to_string());
+}
+
+/// Device path of the first camera, either set with `camera_set_device_path`
+/// or auto-detected
+///
+/// Returns `None` if no path is set and no capture device is found.
+pub fn camera_get_device_path() -> Option<String> {
+    let path = CAMERA_DEVICE_PATH.lock().unwrap().clone();
+    path.or_else(|| find_camera_device(0))
+}
+
 /// Check if camera is initialized
 pub fn camera_is_initialized() -> bool {
-    let state = CAMERA_STATE.lock().unwrap();
+    let state = camera_state(CameraHandle::DEFAULT).lock().unwrap();
     state.file.is_some()
 }
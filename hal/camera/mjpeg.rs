@@ -0,0 +1,278 @@
+//! MJPEG-over-HTTP streaming server on raw libc sockets
+//!
+//! Lets a browser (or VLC, ffplay, ...) show the live camera feed during
+//! development: each client gets a `multipart/x-mixed-replace` response
+//! with one JPEG part per captured frame.
+
+use super::{
+    camera_capture_frame, camera_deinitialize, camera_initialize, camera_is_initialized,
+    CameraConfig, CameraError, CameraResult, PixelFormat,
+};
+use std::io;
+use std::os::fd::RawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// First bytes sent to every MJPEG client
+const MJPEG_RESPONSE_HEADER: &[u8] =
+    b"HTTP/1.1 200 OK\r\nContent-Type: multipart/x-mixed-replace; boundary=frame\r\n\r\n";
+
+/// How often the streaming threads check the stop flag
+const MJPEG_POLL_MS: i32 = 200;
+
+/// A client that accepts no data for this long is dropped
+const MJPEG_SEND_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Running MJPEG server started by [`camera_stream_mjpeg`]
+///
+/// Pass it to [`stream_stop`] to shut the server down.
+#[derive(Debug)]
+pub struct StreamHandle {
+    thread: thread::JoinHandle<()>,
+    stop: Arc<AtomicBool>,
+    owns_camera: bool,
+    stats: Arc<Mutex<StreamStats>>,
+}
+
+impl StreamHandle {
+    /// Totals over all clients so far
+    pub fn stats(&self) -> StreamStats {
+        *lock_stats(&self.stats)
+    }
+}
+
+/// Data sent by an MJPEG server, summed over all clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamStats {
+    /// JPEG frames sent in full
+    pub frames_sent: u64,
+    /// Bytes sent, including HTTP and part headers
+    pub bytes_sent: u64,
+}
+
+fn lock_stats(stats: &Mutex<StreamStats>) -> MutexGuard<'_, StreamStats> {
+    stats.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Serve the camera as an MJPEG stream over HTTP on `0.0.0.0:port`
+///
+/// Initializes the camera with `config` unless it is already initialized;
+/// `config.format` must be `PixelFormat::Jpeg`. Every client gets a
+/// `multipart/x-mixed-replace` response that any browser can display, fed
+/// from its own thread; clients share the camera, so each one sees a subset
+/// of the captured frames.
+pub fn camera_stream_mjpeg(port: u16, config: CameraConfig) -> CameraResult<StreamHandle> {
+    let owns_camera = !camera_is_initialized();
+    if owns_camera {
+        if config.format != PixelFormat::Jpeg {
+            return Err(CameraError::InvalidFormat);
+        }
+        camera_initialize(config)?;
+    }
+
+    let listener = match MjpegSocket::listen(port) {
+        Ok(listener) => listener,
+        Err(e) => {
+            if owns_camera {
+                let _ = camera_deinitialize();
+            }
+            return Err(e);
+        }
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stats = Arc::new(Mutex::new(StreamStats::default()));
+    let flag = stop.clone();
+    let totals = stats.clone();
+    let thread = thread::Builder::new()
+        .name("mjpeg-listen".into())
+        .spawn(move || mjpeg_listen(listener, flag, totals))
+        .map_err(|e| {
+            if owns_camera {
+                let _ = camera_deinitialize();
+            }
+            CameraError::SystemError(e.raw_os_error().unwrap_or(0))
+        })?;
+
+    Ok(StreamHandle {
+        thread,
+        stop,
+        owns_camera,
+        stats,
+    })
+}
+
+/// Stop an MJPEG server and wait for its threads to exit
+///
+/// Connected clients are closed. The camera is deinitialized if
+/// [`camera_stream_mjpeg`] initialized it. Returns the final totals.
+pub fn stream_stop(handle: StreamHandle) -> StreamStats {
+    handle.stop.store(true, Ordering::Relaxed);
+    let _ = handle.thread.join();
+    if handle.owns_camera {
+        let _ = camera_deinitialize();
+    }
+    *lock_stats(&handle.stats)
+}
+
+/// Accept clients until stopped, then wait for their threads
+fn mjpeg_listen(listener: MjpegSocket, stop: Arc<AtomicBool>, stats: Arc<Mutex<StreamStats>>) {
+    let mut clients = Vec::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        let client = match listener.accept() {
+            Some(client) => client,
+            None => continue,
+        };
+        let flag = stop.clone();
+        let totals = stats.clone();
+        let spawned = thread::Builder::new()
+            .name("mjpeg-client".into())
+            .spawn(move || mjpeg_serve(client, &flag, &totals));
+        if let Ok(handle) = spawned {
+            clients.push(handle);
+        }
+        clients.retain(|handle| !handle.is_finished());
+    }
+
+    for handle in clients {
+        let _ = handle.join();
+    }
+}
+
+/// Send frames to one client until it disconnects or the server stops
+fn mjpeg_serve(client: MjpegSocket, stop: &AtomicBool, stats: &Mutex<StreamStats>) {
+    if client.send_all(MJPEG_RESPONSE_HEADER).is_err() {
+        return;
+    }
+    lock_stats(stats).bytes_sent += MJPEG_RESPONSE_HEADER.len() as u64;
+
+    while !stop.load(Ordering::Relaxed) {
+        let frame = match camera_capture_frame() {
+            Ok(frame) if frame.format == PixelFormat::Jpeg => frame,
+            // Reinitialized in another format, or gone entirely
+            Ok(_) | Err(CameraError::NotInitialized) => return,
+            Err(_) => {
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+        };
+
+        let header = format!(
+            "--frame\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+            frame.data.len()
+        );
+        if client.send_all(header.as_bytes()).is_err()
+            || client.send_all(&frame.data).is_err()
+            || client.send_all(b"\r\n").is_err()
+        {
+            return;
+        }
+
+        let mut totals = lock_stats(stats);
+        totals.frames_sent += 1;
+        totals.bytes_sent += (header.len() + frame.data.len() + 2) as u64;
+    }
+}
+
+/// TCP socket for the MJPEG server, closed on drop
+struct MjpegSocket(RawFd);
+
+impl MjpegSocket {
+    /// Listen on `0.0.0.0:port`
+    fn listen(port: u16) -> CameraResult<MjpegSocket> {
+        let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
+        if fd < 0 {
+            return Err(CameraError::from(last_errno()));
+        }
+        let socket = MjpegSocket(fd);
+
+        // Allow a quick restart while old connections sit in TIME_WAIT
+        let reuse: libc::c_int = 1;
+        socket.setsockopt(libc::SO_REUSEADDR, &reuse)?;
+
+        let mut sin: libc::sockaddr_in = unsafe { core::mem::zeroed() };
+        sin.sin_family = libc::AF_INET as libc::sa_family_t;
+        sin.sin_port = port.to_be();
+        sin.sin_addr.s_addr = libc::INADDR_ANY;
+        let rc = unsafe {
+            libc::bind(
+                fd,
+                &sin as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 || unsafe { libc::listen(fd, 4) } < 0 {
+            return Err(CameraError::from(last_errno()));
+        }
+        Ok(socket)
+    }
+
+    /// Wait up to `MJPEG_POLL_MS` for a client
+    fn accept(&self) -> Option<MjpegSocket> {
+        let mut pfd = libc::pollfd {
+            fd: self.0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        if unsafe { libc::poll(&mut pfd, 1, MJPEG_POLL_MS) } <= 0 {
+            return None;
+        }
+
+        let fd = unsafe { libc::accept(self.0, core::ptr::null_mut(), core::ptr::null_mut()) };
+        if fd < 0 {
+            return None;
+        }
+        let client = MjpegSocket(fd);
+
+        // Bound each send so a stalled client cannot hold up stream_stop
+        let timeout = libc::timeval {
+            tv_sec: MJPEG_SEND_TIMEOUT.as_secs() as libc::time_t,
+            tv_usec: 0,
+        };
+        client.setsockopt(libc::SO_SNDTIMEO, &timeout).ok()?;
+        Some(client)
+    }
+
+    fn send_all(&self, mut buf: &[u8]) -> CameraResult<()> {
+        while !buf.is_empty() {
+            let n =
+                unsafe { libc::send(self.0, buf.as_ptr() as *const libc::c_void, buf.len(), 0) };
+            if n <= 0 {
+                return Err(CameraError::from(last_errno()));
+            }
+            buf = &buf[n as usize..];
+        }
+        Ok(())
+    }
+
+    fn setsockopt<T>(&self, name: libc::c_int, value: &T) -> CameraResult<()> {
+        let rc = unsafe {
+            libc::setsockopt(
+                self.0,
+                libc::SOL_SOCKET,
+                name,
+                value as *const T as *const libc::c_void,
+                core::mem::size_of::<T>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            return Err(CameraError::from(last_errno()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MjpegSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+fn last_errno() -> i32 {
+    io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
@@ -0,0 +1,200 @@
+//! Mock camera backend for testing without hardware
+//!
+//! Replaces the unsupported-platform stub (including the `test-pattern`
+//! source) when the `mock` feature is enabled. Captures return an RGB888
+//! checkerboard at the configured resolution, unless a test injects a
+//! frame with `camera_mock_set_next_frame` or forces a failure with
+//! `camera_mock_fail_next_capture`.
+
+use super::{
+    CameraConfig, CameraError, CameraHandle, CameraResult, CameraSettings, CameraStats,
+    FrameBuffer, PixelFormat, Resolution, MAX_CAMERAS,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Checkerboard colors for even and odd pixels
+const EVEN_PIXEL: [u8; 3] = [200, 200, 200];
+const ODD_PIXEL: [u8; 3] = [50, 50, 50];
+
+/// Simulated frame interval in microseconds (~30 fps)
+const FRAME_INTERVAL_US: u64 = 33_333;
+
+struct MockState {
+    config: CameraConfig,
+    frame_count: u64,
+    settings: CameraSettings,
+    stats: CameraStats,
+}
+
+/// One mock camera per camera slot
+static MOCK_STATES: [Mutex<Option<MockState>>; MAX_CAMERAS] = [Mutex::new(None), Mutex::new(None)];
+
+/// Frame returned by the next capture from any camera
+static NEXT_FRAME: Mutex<Option<FrameBuffer>> = Mutex::new(None);
+
+/// Whether the next capture from any camera fails
+static FAIL_NEXT_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Lock a slot, recovering from a panic in another test thread
+fn mock_state(handle: CameraHandle) -> MutexGuard<'static, Option<MockState>> {
+    MOCK_STATES[handle.index()]
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Render an RGB888 checkerboard of single-pixel squares
+fn render_checkerboard(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = if (x + y) % 2 == 0 { EVEN_PIXEL } else { ODD_PIXEL };
+            data.extend_from_slice(&pixel);
+        }
+    }
+    data
+}
+
+/// Return `frame` from the next capture instead of the checkerboard
+pub fn camera_mock_set_next_frame(frame: FrameBuffer) {
+    *NEXT_FRAME.lock().unwrap_or_else(|e| e.into_inner()) = Some(frame);
+}
+
+/// Make the next capture fail with `CaptureFailed`
+pub fn camera_mock_fail_next_capture() {
+    FAIL_NEXT_CAPTURE.store(true, Ordering::Relaxed);
+}
+
+/// Initialize the mock camera (always succeeds, whatever the `device_index`)
+pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
+    camera_initialize_at(0, config).map(|_| ())
+}
+
+/// Initialize the mock camera in slot `index`, replacing any previous one
+pub fn camera_initialize_at(index: u8, config: CameraConfig) -> CameraResult<CameraHandle> {
+    let handle = CameraHandle::new(index).ok_or(CameraError::DeviceNotFound)?;
+    *mock_state(handle) = Some(MockState {
+        config,
+        frame_count: 0,
+        settings: CameraSettings {
+            hmirror: config.hmirror,
+            vflip: config.vflip,
+            ..CameraSettings::auto()
+        },
+        stats: CameraStats::new(),
+    });
+    Ok(handle)
+}
+
+/// Deinitialize the mock camera
+pub fn camera_deinitialize() -> CameraResult<()> {
+    camera_deinitialize_at(CameraHandle::DEFAULT)
+}
+
+/// Deinitialize the mock camera in a slot
+pub fn camera_deinitialize_at(handle: CameraHandle) -> CameraResult<()> {
+    if mock_state(handle).take().is_none() {
+        return Err(CameraError::NotInitialized);
+    }
+    Ok(())
+}
+
+/// Capture a checkerboard frame, or the injected frame or failure
+pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
+    camera_capture_frame_at(CameraHandle::DEFAULT)
+}
+
+/// Capture from the mock camera in a slot
+pub fn camera_capture_frame_at(handle: CameraHandle) -> CameraResult<FrameBuffer> {
+    let mut state = mock_state(handle);
+    let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+
+    if FAIL_NEXT_CAPTURE.swap(false, Ordering::Relaxed) {
+        return Err(CameraError::CaptureFailed);
+    }
+
+    let injected = NEXT_FRAME.lock().unwrap_or_else(|e| e.into_inner()).take();
+    let frame = injected.unwrap_or_else(|| {
+        let (width, height) = state.config.resolution.dimensions();
+        let mut frame = FrameBuffer::new(
+            width,
+            height,
+            PixelFormat::Rgb888,
+            render_checkerboard(width, height),
+        );
+        frame.timestamp = state.frame_count * FRAME_INTERVAL_US;
+        frame
+    });
+    state.frame_count += 1;
+    state.stats.record_frame(frame.len());
+
+    Ok(frame)
+}
+
+/// Change the generated frame size
+pub fn camera_set_resolution(resolution: Resolution) -> CameraResult<()> {
+    let mut state = mock_state(CameraHandle::DEFAULT);
+    let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+    state.config.resolution = resolution;
+    Ok(())
+}
+
+/// The mock always generates RGB888
+pub fn camera_enumerate_formats() -> CameraResult<Vec<PixelFormat>> {
+    Ok(vec![PixelFormat::Rgb888])
+}
+
+/// Every preset is available for RGB888, nothing for other formats
+pub fn camera_enumerate_resolutions(format: PixelFormat) -> CameraResult<Vec<(u32, u32)>> {
+    if format != PixelFormat::Rgb888 {
+        return Ok(Vec::new());
+    }
+    Ok(Resolution::ALL.iter().map(Resolution::dimensions).collect())
+}
+
+/// Get a copy of the capture statistics
+pub fn camera_get_stats() -> CameraResult<CameraStats> {
+    mock_state(CameraHandle::DEFAULT)
+        .as_ref()
+        .map(|s| s.stats)
+        .ok_or(CameraError::NotInitialized)
+}
+
+/// Reset the capture statistics
+pub fn camera_reset_stats() -> CameraResult<()> {
+    let mut state = mock_state(CameraHandle::DEFAULT);
+    let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+    state.stats = CameraStats::new();
+    Ok(())
+}
+
+/// Get the stored camera settings (`CameraSettings::auto()` until changed)
+pub fn camera_get_settings() -> CameraResult<CameraSettings> {
+    camera_get_settings_at(CameraHandle::DEFAULT)
+}
+
+/// Get the stored settings of the mock camera in a slot
+pub fn camera_get_settings_at(handle: CameraHandle) -> CameraResult<CameraSettings> {
+    mock_state(handle)
+        .as_ref()
+        .map(|s| s.settings)
+        .ok_or(CameraError::NotInitialized)
+}
+
+/// Store camera settings (they do not affect the generated frames)
+pub fn camera_set_settings(settings: CameraSettings) -> CameraResult<()> {
+    camera_set_settings_at(CameraHandle::DEFAULT, settings)
+}
+
+/// Store settings for the mock camera in a slot
+pub fn camera_set_settings_at(handle: CameraHandle, settings: CameraSettings) -> CameraResult<()> {
+    let mut state = mock_state(handle);
+    let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+    state.settings = settings;
+    Ok(())
+}
+
+/// Check if the mock camera is initialized
+pub fn camera_is_initialized() -> bool {
+    mock_state(CameraHandle::DEFAULT).is_some()
+}
@@ -5,6 +5,11 @@
 //!
 //! - Linux: Uses V4L2 API for webcam capture
 //! - NuttX ESP32S3: Uses ESP-IDF esp_camera library via C wrapper
+//! - Other: Stub returning NotSupported, or synthetic color bars with the
+//!   `test-pattern` feature
+//! - No platform + `mock`: Checkerboard frames and injectable results for tests
+//!
+//! On Linux and NuttX, `camera_stream_mjpeg` serves the live feed to a browser.
 
 // Platform-specific implementations
 
@@ -21,12 +26,30 @@ mod linux;
 pub use linux::*;
 
 // Fallback stub for other platforms
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 mod none;
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 pub use none::*;
 
+// Checkerboard frames and injectable results for tests, in place of the stub
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+mod mock;
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+pub use mock::*;
+
+// MJPEG-over-HTTP server on libc sockets
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod mjpeg;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use mjpeg::{camera_stream_mjpeg, stream_stop, StreamHandle, StreamStats};
+
 use core::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Camera operation errors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,7 +91,70 @@ impl fmt::Display for CameraError {
             CameraError::BufferAllocationFailed => write!(f, "Buffer allocation failed"),
             CameraError::Timeout => write!(f, "Timeout waiting for frame"),
             CameraError::NotSupported => write!(f, "Not supported on this platform"),
+            #[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+            CameraError::SystemError(e) => {
+                write!(f, "System error: {} ({})", e, crate::errno_to_str(*e))
+            }
+            #[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
             CameraError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl CameraError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            CameraError::DeviceNotFound => Some("check /dev/videoN exists and is readable"),
+            CameraError::OpenFailed if cfg!(feature = "platform-linux") => {
+                Some("check permissions and that no other program is using the camera")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for CameraError {}
+
+/// Map an errno value; negated values (as returned by NuttX drivers) are
+/// accepted too
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+impl From<i32> for CameraError {
+    fn from(errno: i32) -> Self {
+        match errno.wrapping_abs() {
+            libc::ENOENT | libc::ENODEV | libc::ENXIO => CameraError::DeviceNotFound,
+            libc::EPERM | libc::EACCES | libc::EBUSY => CameraError::OpenFailed,
+            libc::EINVAL => CameraError::InvalidFormat,
+            libc::ENOMEM => CameraError::BufferAllocationFailed,
+            libc::EIO => CameraError::CaptureFailed,
+            libc::ETIMEDOUT => CameraError::Timeout,
+            libc::ENOTTY | libc::EOPNOTSUPP => CameraError::NotSupported,
+            errno => CameraError::SystemError(errno),
+        }
+    }
+}
+
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+impl CameraError {
+    /// Map an errno value (either sign) from a camera driver
+    ///
+    /// Unlike `From<i32>`, which classifies errors from opening and
+    /// configuring a V4L2 device, this reads a busy device as already in
+    /// use by this process. Unrecognised values become `SystemError` with
+    /// the negated errno.
+    pub fn from_errno(e: i32) -> CameraError {
+        match e.wrapping_abs() {
+            libc::ENOENT => CameraError::DeviceNotFound,
+            libc::EBUSY => CameraError::AlreadyInitialized,
+            libc::ETIMEDOUT => CameraError::Timeout,
+            libc::ENOMEM => CameraError::BufferAllocationFailed,
+            errno => CameraError::SystemError(-errno),
         }
     }
 }
@@ -91,6 +177,8 @@ pub enum PixelFormat {
     Yuv422 = 3,
     /// Grayscale (8-bit)
     Grayscale = 4,
+    /// NV12 (Y plane followed by interleaved half-resolution UV plane)
+    Nv12 = 5,
 }
 
 impl fmt::Display for PixelFormat {
@@ -101,6 +189,7 @@ impl fmt::Display for PixelFormat {
             PixelFormat::Rgb888 => write!(f, "RGB888"),
             PixelFormat::Yuv422 => write!(f, "YUV422"),
             PixelFormat::Grayscale => write!(f, "Grayscale"),
+            PixelFormat::Nv12 => write!(f, "NV12"),
         }
     }
 }
@@ -137,6 +226,22 @@ pub enum Resolution {
 }
 
 impl Resolution {
+    /// All presets, smallest to largest
+    pub const ALL: [Resolution; 12] = [
+        Resolution::Qqvga,
+        Resolution::Qcif,
+        Resolution::Hqvga,
+        Resolution::Qvga,
+        Resolution::Cif,
+        Resolution::Hvga,
+        Resolution::Vga,
+        Resolution::Svga,
+        Resolution::Xga,
+        Resolution::Hd,
+        Resolution::Sxga,
+        Resolution::Uxga,
+    ];
+
     /// Get width for this resolution
     pub fn width(&self) -> u32 {
         match self {
@@ -172,6 +277,45 @@ impl Resolution {
             Resolution::Uxga => 1200,
         }
     }
+
+    /// Get width and height for this resolution
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width(), self.height())
+    }
+
+    /// Find the preset with exactly these dimensions
+    pub fn from_dimensions(w: u32, h: u32) -> Option<Resolution> {
+        Self::ALL.into_iter().find(|r| r.dimensions() == (w, h))
+    }
+
+    /// Find the preset whose pixel count is nearest to `w * h`
+    ///
+    /// Ties go to the smaller preset.
+    pub fn closest(w: u32, h: u32) -> Resolution {
+        let target = w as u64 * h as u64;
+        Self::ALL
+            .into_iter()
+            .min_by_key(|r| (r.width() as u64 * r.height() as u64).abs_diff(target))
+            .unwrap_or_default()
+    }
+
+    /// Preset name as used by `FromStr`
+    fn name(&self) -> &'static str {
+        match self {
+            Resolution::Qqvga => "qqvga",
+            Resolution::Qcif => "qcif",
+            Resolution::Hqvga => "hqvga",
+            Resolution::Qvga => "qvga",
+            Resolution::Cif => "cif",
+            Resolution::Hvga => "hvga",
+            Resolution::Vga => "vga",
+            Resolution::Svga => "svga",
+            Resolution::Xga => "xga",
+            Resolution::Hd => "hd",
+            Resolution::Sxga => "sxga",
+            Resolution::Uxga => "uxga",
+        }
+    }
 }
 
 impl fmt::Display for Resolution {
@@ -180,8 +324,52 @@ impl fmt::Display for Resolution {
     }
 }
 
+impl core::str::FromStr for Resolution {
+    type Err = CameraError;
+
+    /// Parse a preset name (case-insensitive, e.g. "VGA") or exact
+    /// dimensions of a preset (e.g. "640x480")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(r) = Self::ALL.into_iter().find(|r| r.name().eq_ignore_ascii_case(s)) {
+            return Ok(r);
+        }
+
+        let (w, h) = s
+            .split_once(['x', 'X'])
+            .ok_or(CameraError::InvalidFormat)?;
+        let w = w.trim().parse().map_err(|_| CameraError::InvalidFormat)?;
+        let h = h.trim().parse().map_err(|_| CameraError::InvalidFormat)?;
+        Self::from_dimensions(w, h).ok_or(CameraError::InvalidFormat)
+    }
+}
+
+/// Maximum number of cameras that can be open at once
+pub const MAX_CAMERAS: usize = 2;
+
+/// Handle to an initialized camera, returned by `camera_initialize_at`
+///
+/// The functions without a handle argument operate on camera 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraHandle(u8);
+
+impl CameraHandle {
+    /// The camera used by the handle-less API
+    pub const DEFAULT: CameraHandle = CameraHandle(0);
+
+    /// Create a handle for a camera slot, if the index is in range
+    pub fn new(index: u8) -> Option<Self> {
+        ((index as usize) < MAX_CAMERAS).then_some(CameraHandle(index))
+    }
+
+    /// Camera slot index
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
 /// Camera configuration
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CameraConfig {
     /// Pixel format
     pub format: PixelFormat,
@@ -191,28 +379,34 @@ pub struct CameraConfig {
     pub jpeg_quality: u8,
     /// Frame buffer count (for double/triple buffering)
     pub fb_count: u8,
+    /// Horizontal mirror applied at initialization
+    pub hmirror: bool,
+    /// Vertical flip applied at initialization
+    pub vflip: bool,
+    /// Camera opened by `camera_initialize` (0 for the first); it is always
+    /// the one the handle-less functions use. Mock and test-pattern sources
+    /// accept any index.
+    pub device_index: u8,
+    /// Requested frame rate on Linux (0 keeps the driver default)
+    pub fps: u8,
 }
 
 impl Default for CameraConfig {
     fn default() -> Self {
-        Self {
-            format: PixelFormat::Jpeg,
-            resolution: Resolution::Vga,
-            jpeg_quality: 12,  // ESP32-CAM default
-            fb_count: 1,
-        }
+        Self::builder().build()
     }
 }
 
 impl CameraConfig {
+    /// Start building a configuration from the defaults
+    pub fn builder() -> CameraConfigBuilder {
+        CameraConfigBuilder::new()
+    }
+
     /// Create a new camera configuration
+    #[deprecated(note = "use `CameraConfig::builder()` instead")]
     pub fn new(format: PixelFormat, resolution: Resolution) -> Self {
-        Self {
-            format,
-            resolution,
-            jpeg_quality: 12,
-            fb_count: 1,
-        }
+        Self::builder().format(format).resolution(resolution).build()
     }
 
     /// Set JPEG quality (1-100, lower = higher compression)
@@ -226,6 +420,108 @@ impl CameraConfig {
         self.fb_count = count.clamp(1, 3);
         self
     }
+
+    /// Mirror the image horizontally from the first frame
+    pub fn with_hmirror(mut self, hmirror: bool) -> Self {
+        self.hmirror = hmirror;
+        self
+    }
+
+    /// Flip the image vertically from the first frame
+    pub fn with_vflip(mut self, vflip: bool) -> Self {
+        self.vflip = vflip;
+        self
+    }
+}
+
+/// Builder for `CameraConfig`
+///
+/// Starts from JPEG at VGA, quality 12, one frame buffer, no mirroring,
+/// the first camera and the driver's default frame rate.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraConfigBuilder {
+    config: CameraConfig,
+}
+
+impl CameraConfigBuilder {
+    fn new() -> Self {
+        Self {
+            config: CameraConfig {
+                format: PixelFormat::Jpeg,
+                resolution: Resolution::Vga,
+                jpeg_quality: 12,  // ESP32-CAM default
+                fb_count: 1,
+                hmirror: false,
+                vflip: false,
+                device_index: 0,
+                fps: 0,
+            },
+        }
+    }
+
+    /// Set pixel format
+    pub fn format(mut self, format: PixelFormat) -> Self {
+        self.config.format = format;
+        self
+    }
+
+    /// Set resolution
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.config.resolution = resolution;
+        self
+    }
+
+    /// Set JPEG quality (1-100, lower = higher compression)
+    pub fn jpeg_quality(mut self, quality: u8) -> Self {
+        self.config = self.config.with_jpeg_quality(quality);
+        self
+    }
+
+    /// Set frame buffer count (1-3)
+    pub fn fb_count(mut self, count: u8) -> Self {
+        self.config = self.config.with_fb_count(count);
+        self
+    }
+
+    /// Select which camera `camera_initialize` opens
+    pub fn device_index(mut self, index: u8) -> Self {
+        self.config.device_index = index;
+        self
+    }
+
+    /// Mirror the image horizontally from the first frame
+    pub fn hmirror(mut self, hmirror: bool) -> Self {
+        self.config.hmirror = hmirror;
+        self
+    }
+
+    /// Flip the image vertically from the first frame
+    pub fn vflip(mut self, vflip: bool) -> Self {
+        self.config.vflip = vflip;
+        self
+    }
+
+    /// Request a frame rate on Linux (0 keeps the driver default)
+    pub fn fps(mut self, fps: u8) -> Self {
+        self.config.fps = fps;
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> CameraConfig {
+        self.config
+    }
+}
+
+/// Clockwise rotation for `FrameBuffer::rotate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationAngle {
+    /// 90° clockwise (width and height swap)
+    Cw90,
+    /// 180° (upside down)
+    Cw180,
+    /// 270° clockwise, i.e. 90° counter-clockwise (width and height swap)
+    Cw270,
 }
 
 /// Captured frame buffer
@@ -264,6 +560,377 @@ impl FrameBuffer {
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
+
+    /// Copy the frame into an RGB565 display buffer at (`x`, `y`)
+    ///
+    /// `dst` is a row-major buffer `dst_width` pixels wide; its height is
+    /// implied by its length. Pixels outside the destination are clipped.
+    /// Frames in other raw formats are converted on the fly, JPEG frames
+    /// return `CameraError::InvalidFormat`.
+    pub fn blit_rgb565(&self, dst: &mut [u16], dst_width: u32, x: u32, y: u32) -> CameraResult<()> {
+        if !self.has_raw_data() {
+            return Err(CameraError::InvalidFormat);
+        }
+        let width = self.width as usize;
+        let height = self.height as usize;
+
+        let dst_width = dst_width as usize;
+        if dst_width == 0 {
+            return Ok(());
+        }
+        let dst_height = dst.len() / dst_width;
+        let (x, y) = (x as usize, y as usize);
+        if x >= dst_width || y >= dst_height {
+            return Ok(());
+        }
+
+        // Clip to the destination bounds
+        let cols = width.min(dst_width - x);
+        let rows = height.min(dst_height - y);
+
+        for row in 0..rows {
+            let out = &mut dst[(y + row) * dst_width + x..][..cols];
+            for (col, px) in out.iter_mut().enumerate() {
+                *px = if self.format == PixelFormat::Rgb565 {
+                    let i = (row * width + col) * 2;
+                    u16::from_le_bytes([self.data[i], self.data[i + 1]])
+                } else {
+                    let (r, g, b) = self.pixel_rgb(col, row);
+                    rgb_to_rgb565(r, g, b)
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convert the frame to packed RGB888
+    ///
+    /// Handles YUYV (`Yuv422`), NV12, RGB565 and grayscale frames. Returns
+    /// `None` for JPEG (needs decoding), for frames that are already RGB888,
+    /// or if the data is too short for the frame dimensions.
+    pub fn to_rgb888(&self) -> Option<FrameBuffer> {
+        if self.format == PixelFormat::Rgb888 || !self.has_raw_data() {
+            return None;
+        }
+
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let mut data = Vec::with_capacity(width * height * 3);
+        for row in 0..height {
+            for col in 0..width {
+                let (r, g, b) = self.pixel_rgb(col, row);
+                data.extend_from_slice(&[r, g, b]);
+            }
+        }
+
+        Some(FrameBuffer {
+            width: self.width,
+            height: self.height,
+            format: PixelFormat::Rgb888,
+            data,
+            timestamp: self.timestamp,
+        })
+    }
+
+    /// Downsample so the longer side is at most `max_dim`, nearest-neighbor
+    ///
+    /// Supports RGB888, RGB565 and grayscale frames. Other formats (JPEG
+    /// needs decoding first, YUV shares chroma between pixels) and frames
+    /// already within `max_dim` are returned unchanged.
+    pub fn thumbnail(&self, max_dim: u32) -> FrameBuffer {
+        let bpp = match self.format {
+            PixelFormat::Rgb888 => 3,
+            PixelFormat::Rgb565 => 2,
+            PixelFormat::Grayscale => 1,
+            _ => return self.clone(),
+        };
+
+        let longest = self.width.max(self.height);
+        if max_dim == 0 || longest <= max_dim || !self.has_raw_data() {
+            return self.clone();
+        }
+
+        let out_width = (self.width as u64 * max_dim as u64 / longest as u64).max(1) as u32;
+        let out_height = (self.height as u64 * max_dim as u64 / longest as u64).max(1) as u32;
+
+        let mut data = Vec::with_capacity((out_width * out_height) as usize * bpp);
+        for y in 0..out_height {
+            let src_y = (y as u64 * self.height as u64 / out_height as u64) as usize;
+            for x in 0..out_width {
+                let src_x = (x as u64 * self.width as u64 / out_width as u64) as usize;
+                let i = (src_y * self.width as usize + src_x) * bpp;
+                data.extend_from_slice(&self.data[i..i + bpp]);
+            }
+        }
+
+        FrameBuffer {
+            width: out_width,
+            height: out_height,
+            format: self.format,
+            data,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Rotate the frame clockwise by `angle`
+    ///
+    /// Supports RGB888, RGB565 and grayscale frames; other formats are
+    /// returned unchanged. For `Cw90` and `Cw270` the output is `height`
+    /// pixels wide and `width` pixels high.
+    pub fn rotate(&self, angle: RotationAngle) -> FrameBuffer {
+        let (w, h) = (self.width as usize, self.height as usize);
+        match angle {
+            // Output (row, col) comes from input (h-1-col, row)
+            RotationAngle::Cw90 => self.remap(self.height, self.width, |x, y| (y, h - 1 - x)),
+            RotationAngle::Cw180 => {
+                self.remap(self.width, self.height, |x, y| (w - 1 - x, h - 1 - y))
+            }
+            RotationAngle::Cw270 => self.remap(self.height, self.width, |x, y| (w - 1 - y, x)),
+        }
+    }
+
+    /// Mirror the frame left to right
+    ///
+    /// Same format support as `rotate`.
+    pub fn flip_horizontal(&self) -> FrameBuffer {
+        let w = self.width as usize;
+        self.remap(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirror the frame top to bottom
+    ///
+    /// Same format support as `rotate`.
+    pub fn flip_vertical(&self) -> FrameBuffer {
+        let h = self.height as usize;
+        self.remap(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+
+    /// Per-channel absolute difference between two frames
+    ///
+    /// Returns a frame with the same format and dimensions, or `None` if the
+    /// frames differ in either, are not grayscale or RGB888, or are too short
+    /// for their dimensions.
+    pub fn diff(&self, other: &FrameBuffer) -> Option<FrameBuffer> {
+        let (a, b) = self.diff_samples(other)?;
+        Some(FrameBuffer {
+            width: self.width,
+            height: self.height,
+            format: self.format,
+            data: a.iter().zip(b).map(|(&x, &y)| x.abs_diff(y)).collect(),
+            timestamp: self.timestamp,
+        })
+    }
+
+    /// Mean per-channel difference between two frames, from 0.0 (identical)
+    /// to 1.0
+    ///
+    /// Returns `None` in the same cases as `diff`.
+    pub fn diff_score(&self, other: &FrameBuffer) -> Option<f32> {
+        let (a, b) = self.diff_samples(other)?;
+        if a.is_empty() {
+            return Some(0.0);
+        }
+        let total: u64 = a.iter().zip(b).map(|(&x, &y)| x.abs_diff(y) as u64).sum();
+        Some(total as f32 / (a.len() as f32 * 255.0))
+    }
+
+    /// Matching sample slices of two comparable frames
+    fn diff_samples<'a>(&'a self, other: &'a FrameBuffer) -> Option<(&'a [u8], &'a [u8])> {
+        let same_size = self.width == other.width && self.height == other.height;
+        if self.format != other.format || !same_size {
+            return None;
+        }
+        if !matches!(self.format, PixelFormat::Grayscale | PixelFormat::Rgb888) {
+            return None;
+        }
+        if !self.has_raw_data() || !other.has_raw_data() {
+            return None;
+        }
+
+        let len = raw_frame_len(self.format, self.width as usize, self.height as usize)?;
+        Some((&self.data[..len], &other.data[..len]))
+    }
+
+    /// HMAC-SHA256 tag proving the frame came from a holder of `key`
+    ///
+    /// Covers the dimensions, pixel format and data, but not the timestamp.
+    #[cfg(feature = "crypto")]
+    pub fn sign(&self, key: &[u8]) -> [u8; 32] {
+        let mut message = Vec::with_capacity(9 + self.data.len());
+        message.extend_from_slice(&self.width.to_le_bytes());
+        message.extend_from_slice(&self.height.to_le_bytes());
+        message.push(self.format as u8);
+        message.extend_from_slice(&self.data);
+        crate::crypto::hw_hmac_sha256(key, &message)
+    }
+
+    /// Build an `out_width` x `out_height` frame whose pixel (x, y) is copied
+    /// from source pixel `src(x, y)`; packed formats only
+    fn remap(
+        &self,
+        out_width: u32,
+        out_height: u32,
+        src: impl Fn(usize, usize) -> (usize, usize),
+    ) -> FrameBuffer {
+        let bpp = match self.format {
+            PixelFormat::Rgb888 => 3,
+            PixelFormat::Rgb565 => 2,
+            PixelFormat::Grayscale => 1,
+            _ => return self.clone(),
+        };
+        if !self.has_raw_data() {
+            return self.clone();
+        }
+
+        let mut data = Vec::with_capacity((out_width * out_height) as usize * bpp);
+        for y in 0..out_height as usize {
+            for x in 0..out_width as usize {
+                let (src_x, src_y) = src(x, y);
+                let i = (src_y * self.width as usize + src_x) * bpp;
+                data.extend_from_slice(&self.data[i..i + bpp]);
+            }
+        }
+
+        FrameBuffer {
+            width: out_width,
+            height: out_height,
+            format: self.format,
+            data,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Count pixels per luminance level
+    ///
+    /// Grayscale bytes and YUV luma samples are counted directly, RGB is
+    /// converted with `Y = (77*R + 150*G + 29*B) >> 8`. Returns all zeros
+    /// for JPEG or frames with too little data.
+    pub fn compute_histogram(&self) -> [u32; 256] {
+        let mut histogram = [0u32; 256];
+        if !self.has_raw_data() {
+            return histogram;
+        }
+
+        for row in 0..self.height as usize {
+            for col in 0..self.width as usize {
+                histogram[self.luma(col, row) as usize] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Average luminance (0 for JPEG or empty frames)
+    pub fn mean_luminance(&self) -> u8 {
+        let histogram = self.compute_histogram();
+        let total: u64 = histogram.iter().map(|&n| n as u64).sum();
+        let sum: u64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(level, &n)| level as u64 * n as u64)
+            .sum();
+        sum.checked_div(total).unwrap_or(0) as u8
+    }
+
+    /// Check that the frame is in a raw format with enough data for its size
+    fn has_raw_data(&self) -> bool {
+        raw_frame_len(self.format, self.width as usize, self.height as usize)
+            .is_some_and(|len| self.data.len() >= len)
+    }
+
+    /// Luminance of the pixel at (`col`, `row`)
+    ///
+    /// Grayscale bytes and YUV luma samples are used directly, RGB is
+    /// converted with `Y = (77*R + 150*G + 29*B) >> 8`. The frame must be in
+    /// a raw format with enough data (checked by callers).
+    fn luma(&self, col: usize, row: usize) -> u8 {
+        let width = self.width as usize;
+        match self.format {
+            // NV12 starts with a full-resolution Y plane
+            PixelFormat::Grayscale | PixelFormat::Nv12 => self.data[row * width + col],
+            // YUYV: every other byte is a Y sample
+            PixelFormat::Yuv422 => self.data[(row * width + col) * 2],
+            _ => {
+                let (r, g, b) = self.pixel_rgb(col, row);
+                ((77 * r as u32 + 150 * g as u32 + 29 * b as u32) >> 8) as u8
+            }
+        }
+    }
+
+    /// Decode the pixel at (`col`, `row`) to 8-bit RGB
+    ///
+    /// The frame must be in a raw format with enough data (checked by callers).
+    fn pixel_rgb(&self, col: usize, row: usize) -> (u8, u8, u8) {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        let d = &self.data;
+        match self.format {
+            PixelFormat::Rgb565 => {
+                let i = (row * width + col) * 2;
+                let px = u16::from_le_bytes([d[i], d[i + 1]]);
+                let r = ((px >> 11) & 0x1F) as u8;
+                let g = ((px >> 5) & 0x3F) as u8;
+                let b = (px & 0x1F) as u8;
+                ((r << 3) | (r >> 2), (g << 2) | (g >> 4), (b << 3) | (b >> 2))
+            }
+            PixelFormat::Rgb888 => {
+                let i = (row * width + col) * 3;
+                (d[i], d[i + 1], d[i + 2])
+            }
+            PixelFormat::Yuv422 => {
+                // YUYV: each pixel pair shares U and V (Y0 U Y1 V)
+                let pair = (row * width + (col & !1)) * 2;
+                let luma = d[pair + (col & 1) * 2];
+                let u = d.get(pair + 1).copied().unwrap_or(128);
+                let v = d.get(pair + 3).copied().unwrap_or(128);
+                yuv_to_rgb(luma, u, v)
+            }
+            PixelFormat::Grayscale => {
+                let l = d[row * width + col];
+                (l, l, l)
+            }
+            PixelFormat::Nv12 => {
+                // One UV pair per 2x2 block, stored after the Y plane
+                let luma = d[row * width + col];
+                let uv = width * height + (row / 2) * width + (col & !1);
+                let u = d.get(uv).copied().unwrap_or(128);
+                let v = d.get(uv + 1).copied().unwrap_or(128);
+                yuv_to_rgb(luma, u, v)
+            }
+            PixelFormat::Jpeg => (0, 0, 0),
+        }
+    }
+}
+
+/// Data length of a raw frame (`None` for JPEG)
+fn raw_frame_len(format: PixelFormat, width: usize, height: usize) -> Option<usize> {
+    let pixels = width * height;
+    match format {
+        PixelFormat::Jpeg => None,
+        PixelFormat::Rgb565 | PixelFormat::Yuv422 => Some(pixels * 2),
+        PixelFormat::Rgb888 => Some(pixels * 3),
+        PixelFormat::Grayscale => Some(pixels),
+        PixelFormat::Nv12 => Some(pixels + pixels / 2),
+    }
+}
+
+/// Pack 8-bit RGB into RGB565
+pub fn rgb_to_rgb565(r: u8, g: u8, b: u8) -> u16 {
+    ((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3)
+}
+
+/// Convert a BT.601 YCbCr sample to 8-bit RGB
+///
+/// `R = Y + 1.402*Cr`, `G = Y - 0.344*Cb - 0.714*Cr`, `B = Y + 1.772*Cb`
+/// (with Cb/Cr centered on 128), in 8.8 fixed point.
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> (u8, u8, u8) {
+    let y = y as i32;
+    let cb = u as i32 - 128;
+    let cr = v as i32 - 128;
+    let r = y + ((359 * cr + 128) >> 8);
+    let g = y - ((88 * cb + 183 * cr + 128) >> 8);
+    let b = y + ((454 * cb + 128) >> 8);
+    (r.clamp(0, 255) as u8, g.clamp(0, 255) as u8, b.clamp(0, 255) as u8)
 }
 
 /// Camera sensor settings (adjustable parameters)
@@ -311,3 +978,316 @@ impl CameraSettings {
         }
     }
 }
+
+/// Capture statistics, updated on every successful frame capture
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CameraStats {
+    /// Frames captured since initialization or the last reset
+    pub frames_captured: u64,
+    /// Total frame data captured in bytes
+    pub bytes_total: u64,
+    /// Time of the most recent capture
+    pub last_frame_at: Option<Instant>,
+    /// Average frame rate in hundredths of a frame per second
+    pub avg_fps_x100: u32,
+    /// Moving average of the inter-frame interval in microseconds
+    avg_interval_us: u64,
+}
+
+impl CameraStats {
+    /// Number of intervals the frame rate average spans
+    const FPS_WINDOW: u64 = 8;
+
+    /// Create zeroed statistics
+    pub const fn new() -> Self {
+        Self {
+            frames_captured: 0,
+            bytes_total: 0,
+            last_frame_at: None,
+            avg_fps_x100: 0,
+            avg_interval_us: 0,
+        }
+    }
+
+    /// Record a captured frame of `len` bytes
+    ///
+    /// Called by the capture path; useful when frames come from elsewhere.
+    pub fn record_frame(&mut self, len: usize) {
+        let now = Instant::now();
+
+        if let Some(last) = self.last_frame_at {
+            let interval = now.duration_since(last).as_micros() as u64;
+            // Exponential moving average over roughly the last 8 intervals
+            self.avg_interval_us = if self.avg_interval_us == 0 {
+                interval
+            } else {
+                (self.avg_interval_us * (Self::FPS_WINDOW - 1) + interval) / Self::FPS_WINDOW
+            };
+            if let Some(fps_x100) = 100_000_000u64.checked_div(self.avg_interval_us) {
+                self.avg_fps_x100 = fps_x100 as u32;
+            }
+        }
+
+        self.frames_captured += 1;
+        self.bytes_total += len as u64;
+        self.last_frame_at = Some(now);
+    }
+}
+
+impl fmt::Display for CameraStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} frames, {} bytes, {}.{:02} fps",
+            self.frames_captured,
+            self.bytes_total,
+            self.avg_fps_x100 / 100,
+            self.avg_fps_x100 % 100
+        )
+    }
+}
+
+/// Initialize the camera, giving up if it does not complete within `timeout`
+///
+/// Initialization runs on a helper thread so a misbehaving device cannot
+/// block the caller indefinitely. Returns `CameraError::Timeout` if the
+/// deadline passes; if the abandoned initialization later succeeds, the
+/// helper thread deinitializes the camera again so no partial state is left.
+pub fn camera_initialize_timeout(config: CameraConfig, timeout: Duration) -> CameraResult<()> {
+    // Rendezvous channel: send() only succeeds if the caller is still waiting
+    let (tx, rx) = mpsc::sync_channel(0);
+
+    thread::Builder::new()
+        .name("camera-init".into())
+        .spawn(move || {
+            let result = camera_initialize(config);
+            if tx.send(result).is_err() && result.is_ok() {
+                // Caller timed out and left - undo the late initialization
+                let _ = camera_deinitialize();
+            }
+        })
+        .map_err(|e| CameraError::SystemError(e.raw_os_error().unwrap_or(0)))?;
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(CameraError::Timeout),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(CameraError::OpenFailed),
+    }
+}
+
+/// Maximum number of frames in one burst
+pub const MAX_BURST_FRAMES: usize = 64;
+
+/// Options for `camera_capture_burst_with`
+#[derive(Debug, Clone, Copy)]
+pub struct BurstOptions {
+    /// Number of frames to capture (1-`MAX_BURST_FRAMES`)
+    pub count: usize,
+    /// Delay between captures in milliseconds
+    pub interval_ms: u32,
+    /// Abort on the first failed capture instead of skipping it
+    pub stop_on_error: bool,
+}
+
+impl BurstOptions {
+    /// Capture `count` frames back to back, stopping on errors
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            interval_ms: 0,
+            stop_on_error: true,
+        }
+    }
+
+    /// Set the delay between captures
+    pub fn with_interval_ms(mut self, interval_ms: u32) -> Self {
+        self.interval_ms = interval_ms;
+        self
+    }
+
+    /// Set whether a failed capture aborts the burst
+    pub fn with_stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+}
+
+/// Capture a burst of frames
+///
+/// Returns `InvalidFormat` if the count is 0 or above `MAX_BURST_FRAMES`.
+/// With `stop_on_error` the first capture error is returned, otherwise
+/// failed captures are skipped and fewer frames may be returned.
+pub fn camera_capture_burst_with(options: BurstOptions) -> CameraResult<Vec<FrameBuffer>> {
+    if options.count == 0 || options.count > MAX_BURST_FRAMES {
+        return Err(CameraError::InvalidFormat);
+    }
+
+    let mut frames = Vec::with_capacity(options.count);
+    for i in 0..options.count {
+        if i > 0 && options.interval_ms > 0 {
+            thread::sleep(Duration::from_millis(options.interval_ms as u64));
+        }
+
+        match camera_capture_frame() {
+            Ok(frame) => frames.push(frame),
+            Err(e) if options.stop_on_error => return Err(e),
+            Err(_) => {}
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Capture `count` frames, `interval_ms` apart, failing on the first error
+pub fn camera_capture_burst(count: usize, interval_ms: u32) -> CameraResult<Vec<FrameBuffer>> {
+    camera_capture_burst_with(BurstOptions::new(count).with_interval_ms(interval_ms))
+}
+
+/// Capture `count` frames, `interval_secs` apart, failing on the first error
+pub fn camera_capture_timelapse(
+    count: usize,
+    interval_secs: u64,
+) -> CameraResult<Vec<FrameBuffer>> {
+    let interval_ms = interval_secs.saturating_mul(1000).min(u32::MAX as u64) as u32;
+    camera_capture_burst(count, interval_ms)
+}
+
+/// Motion detection by differencing consecutive frames
+///
+/// Frames are compared by luminance, so any raw pixel format works. A
+/// change of frame size or format restarts detection.
+#[derive(Debug, Clone)]
+pub struct MotionDetector {
+    /// Score above which `is_motion` reports motion (0.0-1.0)
+    pub threshold: f32,
+    /// Previous frame
+    prev: Option<FrameBuffer>,
+}
+
+impl MotionDetector {
+    /// Create a detector with the given motion threshold
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            prev: None,
+        }
+    }
+
+    /// Compare `frame` with the previous one and return a motion score
+    ///
+    /// The score is the mean absolute luminance difference per pixel,
+    /// normalized to 0.0 (identical) - 1.0 (every pixel flipped between
+    /// black and white). The first frame, and frames that cannot be
+    /// compared with the previous one (JPEG, different size or format),
+    /// score 0.0.
+    pub fn detect(&mut self, frame: &FrameBuffer) -> f32 {
+        let score = match &self.prev {
+            Some(prev)
+                if prev.width == frame.width
+                    && prev.height == frame.height
+                    && prev.format == frame.format
+                    && prev.has_raw_data()
+                    && frame.has_raw_data() =>
+            {
+                let mut total: u64 = 0;
+                for row in 0..frame.height as usize {
+                    for col in 0..frame.width as usize {
+                        total += prev.luma(col, row).abs_diff(frame.luma(col, row)) as u64;
+                    }
+                }
+                let pixels = frame.width as u64 * frame.height as u64;
+                total as f32 / (pixels.max(1) * 255) as f32
+            }
+            _ => 0.0,
+        };
+
+        self.prev = Some(frame.clone());
+        score
+    }
+
+    /// Check whether `frame` differs from the previous one by more than
+    /// the threshold
+    pub fn is_motion(&mut self, frame: &FrameBuffer) -> bool {
+        self.detect(frame) > self.threshold
+    }
+
+    /// Forget the previous frame
+    pub fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+/// Continuous frame stream over the initialized camera
+///
+/// Each call to `next()` waits for one frame; the backend keeps its capture
+/// buffers queued in between. Dropping the stream stops capture and
+/// deinitializes the camera. Created by [`camera_capture_stream`].
+#[derive(Debug)]
+pub struct CameraStream {
+    _private: (),
+}
+
+impl Iterator for CameraStream {
+    type Item = CameraResult<FrameBuffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(camera_capture_frame())
+    }
+}
+
+impl Drop for CameraStream {
+    fn drop(&mut self) {
+        let _ = camera_deinitialize();
+    }
+}
+
+/// Start streaming frames from the initialized camera
+///
+/// The returned iterator never ends on its own; capture errors are yielded
+/// as items so the caller can decide whether to skip or stop. The stream is
+/// `Send`, so it can be moved to a processing thread.
+pub fn camera_capture_stream() -> CameraResult<CameraStream> {
+    if !camera_is_initialized() {
+        return Err(CameraError::NotInitialized);
+    }
+    Ok(CameraStream { _private: () })
+}
+
+/// Write a JPEG frame to a file
+///
+/// Returns `InvalidInput` for frames in any other format.
+pub fn camera_save_jpeg(frame: &FrameBuffer, path: &Path) -> io::Result<()> {
+    if frame.format != PixelFormat::Jpeg {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Not a JPEG frame"));
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&frame.data)?;
+    writer.flush()
+}
+
+/// Write a frame to a binary PPM (P6) file
+///
+/// Other raw formats are converted to RGB888 first; JPEG frames (or frames
+/// with too little data) return `InvalidInput`.
+pub fn camera_save_ppm(frame: &FrameBuffer, path: &Path) -> io::Result<()> {
+    let converted;
+    let rgb = if frame.format == PixelFormat::Rgb888 {
+        frame
+    } else {
+        converted = frame
+            .to_rgb888()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Not a raw frame"))?;
+        &converted
+    };
+
+    let len = rgb.width as usize * rgb.height as usize * 3;
+    if rgb.data.len() < len {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame data too short"));
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    write!(writer, "P6\n{} {}\n255\n", rgb.width, rgb.height)?;
+    writer.write_all(&rgb.data[..len])?;
+    writer.flush()
+}
@@ -1,33 +1,322 @@
 //! Camera HAL stub for unsupported platforms
+//!
+//! With the `test-pattern` feature enabled, the stub generates synthetic
+//! RGB888 color-bar frames instead of returning NotSupported. This allows
+//! camera-consuming code to be exercised without hardware.
 
-use super::{CameraConfig, CameraError, CameraResult, CameraSettings, FrameBuffer};
+#[cfg(not(feature = "test-pattern"))]
+use super::{
+    CameraConfig, CameraError, CameraHandle, CameraResult, CameraSettings, CameraStats,
+    FrameBuffer, PixelFormat, Resolution,
+};
+
+#[cfg(feature = "test-pattern")]
+pub use test_pattern::*;
 
 /// Initialize the camera (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_initialize(_config: CameraConfig) -> CameraResult<()> {
     Err(CameraError::NotSupported)
 }
 
+/// Initialize the `index`-th camera (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_initialize_at(_index: u8, _config: CameraConfig) -> CameraResult<CameraHandle> {
+    Err(CameraError::NotSupported)
+}
+
 /// Deinitialize the camera (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_deinitialize() -> CameraResult<()> {
     Err(CameraError::NotSupported)
 }
 
+/// Deinitialize a camera by handle (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_deinitialize_at(_handle: CameraHandle) -> CameraResult<()> {
+    Err(CameraError::NotSupported)
+}
+
 /// Capture a frame (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
     Err(CameraError::NotSupported)
 }
 
+/// Capture a frame by handle (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_capture_frame_at(_handle: CameraHandle) -> CameraResult<FrameBuffer> {
+    Err(CameraError::NotSupported)
+}
+
+/// Change the capture resolution (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_set_resolution(_resolution: Resolution) -> CameraResult<()> {
+    Err(CameraError::NotSupported)
+}
+
+/// List supported pixel formats (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_enumerate_formats() -> CameraResult<Vec<PixelFormat>> {
+    Err(CameraError::NotSupported)
+}
+
+/// List supported frame sizes (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_enumerate_resolutions(_format: PixelFormat) -> CameraResult<Vec<(u32, u32)>> {
+    Err(CameraError::NotSupported)
+}
+
+/// Get capture statistics (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_get_stats() -> CameraResult<CameraStats> {
+    Err(CameraError::NotSupported)
+}
+
+/// Reset capture statistics (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_reset_stats() -> CameraResult<()> {
+    Err(CameraError::NotSupported)
+}
+
 /// Get current camera settings (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_get_settings() -> CameraResult<CameraSettings> {
     Err(CameraError::NotSupported)
 }
 
+/// Get camera settings by handle (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_get_settings_at(_handle: CameraHandle) -> CameraResult<CameraSettings> {
+    Err(CameraError::NotSupported)
+}
+
 /// Set camera settings (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_set_settings(_settings: CameraSettings) -> CameraResult<()> {
     Err(CameraError::NotSupported)
 }
 
+/// Set camera settings by handle (stub - returns NotSupported)
+#[cfg(not(feature = "test-pattern"))]
+pub fn camera_set_settings_at(
+    _handle: CameraHandle,
+    _settings: CameraSettings,
+) -> CameraResult<()> {
+    Err(CameraError::NotSupported)
+}
+
 /// Check if camera is initialized (stub - always returns false)
+#[cfg(not(feature = "test-pattern"))]
 pub fn camera_is_initialized() -> bool {
     false
 }
+
+// ============================================================================
+// Test pattern source
+// ============================================================================
+
+#[cfg(feature = "test-pattern")]
+mod test_pattern {
+    use super::super::{
+        CameraConfig, CameraError, CameraHandle, CameraResult, CameraSettings, CameraStats,
+        FrameBuffer, PixelFormat, Resolution, MAX_CAMERAS,
+    };
+    use std::sync::Mutex;
+
+    /// Color bars (white, yellow, cyan, green, magenta, red, blue, black)
+    const COLOR_BARS: [[u8; 3]; 8] = [
+        [0xFF, 0xFF, 0xFF],
+        [0xFF, 0xFF, 0x00],
+        [0x00, 0xFF, 0xFF],
+        [0x00, 0xFF, 0x00],
+        [0xFF, 0x00, 0xFF],
+        [0xFF, 0x00, 0x00],
+        [0x00, 0x00, 0xFF],
+        [0x00, 0x00, 0x00],
+    ];
+
+    /// Simulated frame interval in microseconds (~30 fps)
+    const FRAME_INTERVAL_US: u64 = 33_333;
+
+    /// Horizontal scroll per frame in pixels
+    const SCROLL_PER_FRAME: u32 = 4;
+
+    struct PatternState {
+        width: u32,
+        height: u32,
+        frame_count: u64,
+        settings: CameraSettings,
+        stats: CameraStats,
+    }
+
+    /// One pattern source per camera slot
+    static PATTERN_STATES: [Mutex<Option<PatternState>>; MAX_CAMERAS] =
+        [Mutex::new(None), Mutex::new(None)];
+
+    fn pattern_state(handle: CameraHandle) -> &'static Mutex<Option<PatternState>> {
+        &PATTERN_STATES[handle.index()]
+    }
+
+    /// Render one frame of scrolling color bars
+    fn render_color_bars(width: u32, height: u32, frame_count: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        let bar_width = (width / COLOR_BARS.len() as u32).max(1);
+        let offset = (frame_count * SCROLL_PER_FRAME as u64 % width.max(1) as u64) as u32;
+
+        for _ in 0..height {
+            for x in 0..width {
+                let bar = (((x + offset) % width) / bar_width) as usize;
+                data.extend_from_slice(&COLOR_BARS[bar.min(COLOR_BARS.len() - 1)]);
+            }
+        }
+        data
+    }
+
+    /// Initialize the test pattern source with the configured resolution
+    ///
+    /// There is no device to select, so `device_index` is ignored.
+    pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
+        camera_initialize_at(0, config).map(|_| ())
+    }
+
+    /// Initialize an independent test pattern source in slot `index`
+    pub fn camera_initialize_at(index: u8, config: CameraConfig) -> CameraResult<CameraHandle> {
+        let handle = CameraHandle::new(index).ok_or(CameraError::DeviceNotFound)?;
+        let mut state = pattern_state(handle).lock().unwrap();
+
+        if state.is_some() {
+            return Err(CameraError::AlreadyInitialized);
+        }
+
+        *state = Some(PatternState {
+            width: config.resolution.width(),
+            height: config.resolution.height(),
+            frame_count: 0,
+            settings: CameraSettings {
+                hmirror: config.hmirror,
+                vflip: config.vflip,
+                ..CameraSettings::auto()
+            },
+            stats: CameraStats::new(),
+        });
+        Ok(handle)
+    }
+
+    /// Deinitialize the test pattern source
+    pub fn camera_deinitialize() -> CameraResult<()> {
+        camera_deinitialize_at(CameraHandle::DEFAULT)
+    }
+
+    /// Deinitialize the test pattern source in a slot
+    pub fn camera_deinitialize_at(handle: CameraHandle) -> CameraResult<()> {
+        let mut state = pattern_state(handle).lock().unwrap();
+
+        if state.take().is_none() {
+            return Err(CameraError::NotInitialized);
+        }
+        Ok(())
+    }
+
+    /// Capture a synthetic RGB888 color-bar frame
+    ///
+    /// The bars scroll horizontally on every capture and the timestamp
+    /// advances by one frame interval.
+    pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
+        camera_capture_frame_at(CameraHandle::DEFAULT)
+    }
+
+    /// Capture a synthetic frame from the source in a slot
+    pub fn camera_capture_frame_at(handle: CameraHandle) -> CameraResult<FrameBuffer> {
+        let mut state = pattern_state(handle).lock().unwrap();
+        let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+
+        let data = render_color_bars(state.width, state.height, state.frame_count);
+        let mut frame = FrameBuffer::new(state.width, state.height, PixelFormat::Rgb888, data);
+        frame.timestamp = state.frame_count * FRAME_INTERVAL_US;
+        state.frame_count += 1;
+        state.stats.record_frame(frame.len());
+
+        Ok(frame)
+    }
+
+    /// Change the generated frame size
+    pub fn camera_set_resolution(resolution: Resolution) -> CameraResult<()> {
+        let mut state = pattern_state(CameraHandle::DEFAULT).lock().unwrap();
+        let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+        state.width = resolution.width();
+        state.height = resolution.height();
+        Ok(())
+    }
+
+    /// The test pattern is always generated as RGB888
+    pub fn camera_enumerate_formats() -> CameraResult<Vec<PixelFormat>> {
+        Ok(vec![PixelFormat::Rgb888])
+    }
+
+    /// Every preset is available for RGB888, nothing for other formats
+    pub fn camera_enumerate_resolutions(format: PixelFormat) -> CameraResult<Vec<(u32, u32)>> {
+        if format != PixelFormat::Rgb888 {
+            return Ok(Vec::new());
+        }
+        Ok(Resolution::ALL
+            .iter()
+            .map(|r| (r.width(), r.height()))
+            .collect())
+    }
+
+    /// Get a copy of the capture statistics
+    pub fn camera_get_stats() -> CameraResult<CameraStats> {
+        let state = pattern_state(CameraHandle::DEFAULT).lock().unwrap();
+        state
+            .as_ref()
+            .map(|s| s.stats)
+            .ok_or(CameraError::NotInitialized)
+    }
+
+    /// Reset the capture statistics
+    pub fn camera_reset_stats() -> CameraResult<()> {
+        let mut state = pattern_state(CameraHandle::DEFAULT).lock().unwrap();
+        let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+        state.stats = CameraStats::new();
+        Ok(())
+    }
+
+    /// Get the stored camera settings
+    pub fn camera_get_settings() -> CameraResult<CameraSettings> {
+        camera_get_settings_at(CameraHandle::DEFAULT)
+    }
+
+    /// Get the stored settings of the source in a slot
+    pub fn camera_get_settings_at(handle: CameraHandle) -> CameraResult<CameraSettings> {
+        let state = pattern_state(handle).lock().unwrap();
+        state
+            .as_ref()
+            .map(|s| s.settings)
+            .ok_or(CameraError::NotInitialized)
+    }
+
+    /// Store camera settings (they do not affect the generated pattern)
+    pub fn camera_set_settings(settings: CameraSettings) -> CameraResult<()> {
+        camera_set_settings_at(CameraHandle::DEFAULT, settings)
+    }
+
+    /// Store settings for the source in a slot
+    pub fn camera_set_settings_at(
+        handle: CameraHandle,
+        settings: CameraSettings,
+    ) -> CameraResult<()> {
+        let mut state = pattern_state(handle).lock().unwrap();
+        let state = state.as_mut().ok_or(CameraError::NotInitialized)?;
+        state.settings = settings;
+        Ok(())
+    }
+
+    /// Check if the test pattern source is initialized
+    pub fn camera_is_initialized() -> bool {
+        pattern_state(CameraHandle::DEFAULT)
+            .lock()
+            .unwrap()
+            .is_some()
+    }
+}
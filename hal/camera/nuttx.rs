@@ -5,17 +5,26 @@
 //! buffer management on the C side.
 
 use super::{
-    CameraConfig, CameraError, CameraResult, CameraSettings, FrameBuffer, PixelFormat, Resolution,
+    CameraConfig, CameraError, CameraHandle, CameraResult, CameraSettings, CameraStats,
+    FrameBuffer, PixelFormat, Resolution,
 };
 use core::ffi::c_int;
+use std::sync::Mutex;
 
 // ============================================================================
 // C Wrapper FFI Bindings
 // ============================================================================
 
 extern "C" {
-    /// Initialize camera subsystem
-    fn rust_camera_wrapper_init(format: c_int, resolution: c_int, quality: c_int) -> c_int;
+    /// Initialize camera subsystem on `/dev/video<device>`
+    fn rust_camera_wrapper_init(
+        device: c_int,
+        format: c_int,
+        resolution: c_int,
+        quality: c_int,
+        hmirror: c_int,
+        vflip: c_int,
+    ) -> c_int;
 
     /// Deinitialize camera subsystem
     fn rust_camera_wrapper_deinit() -> c_int;
@@ -32,6 +41,9 @@ extern "C" {
     /// Return frame buffer after processing
     fn rust_camera_wrapper_return_frame();
 
+    /// Change capture resolution
+    fn rust_camera_wrapper_set_resolution(resolution: c_int) -> c_int;
+
     /// Check if camera is initialized
     fn rust_camera_wrapper_is_initialized() -> c_int;
 
@@ -40,6 +52,8 @@ extern "C" {
         brightness: *mut i8,
         contrast: *mut i8,
         saturation: *mut i8,
+        hmirror: *mut c_int,
+        vflip: *mut c_int,
     ) -> c_int;
 
     /// Set sensor settings
@@ -52,10 +66,26 @@ extern "C" {
     ) -> c_int;
 }
 
+/// Capture statistics (the C wrapper keeps no per-frame bookkeeping)
+static CAMERA_STATS: Mutex<CameraStats> = Mutex::new(CameraStats::new());
+
+/// Video device number opened by the next initialization with a
+/// `device_index` of 0
+static CAMERA_DEVICE_INDEX: Mutex<u8> = Mutex::new(0);
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// The C wrapper drives a single camera; other handles are never initialized
+fn check_handle(handle: CameraHandle) -> CameraResult<()> {
+    if handle == CameraHandle::DEFAULT {
+        Ok(())
+    } else {
+        Err(CameraError::NotInitialized)
+    }
+}
+
 /// Convert Resolution enum to C integer
 fn resolution_to_int(res: Resolution) -> c_int {
     match res {
@@ -82,6 +112,7 @@ fn format_to_int(fmt: PixelFormat) -> c_int {
         PixelFormat::Rgb888 => 2,
         PixelFormat::Yuv422 => 3,
         PixelFormat::Grayscale => 4,
+        PixelFormat::Nv12 => 5,
     }
 }
 
@@ -93,6 +124,7 @@ fn int_to_format(val: c_int) -> PixelFormat {
         2 => PixelFormat::Rgb888,
         3 => PixelFormat::Yuv422,
         4 => PixelFormat::Grayscale,
+        5 => PixelFormat::Nv12,
         _ => PixelFormat::Jpeg,
     }
 }
@@ -101,15 +133,27 @@ fn int_to_format(val: c_int) -> PixelFormat {
 // Public API Implementation
 // ============================================================================
 
-/// Initialize the camera with the given configuration
+/// Initialize `/dev/video<config.device_index>` with the given configuration
+///
+/// A `device_index` of 0 opens the device set with `camera_set_device_index`
+/// instead, `/dev/video0` by default.
 pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
     let format = format_to_int(config.format);
     let resolution = resolution_to_int(config.resolution);
     let quality = config.jpeg_quality as c_int;
+    let hmirror = if config.hmirror { 1 } else { 0 };
+    let vflip = if config.vflip { 1 } else { 0 };
+
+    let device = match config.device_index {
+        0 => *CAMERA_DEVICE_INDEX.lock().unwrap(),
+        index => index,
+    } as c_int;
 
-    let rc = unsafe { rust_camera_wrapper_init(format, resolution, quality) };
+    let rc =
+        unsafe { rust_camera_wrapper_init(device, format, resolution, quality, hmirror, vflip) };
 
     if rc == 0 {
+        *CAMERA_STATS.lock().unwrap() = CameraStats::new();
         Ok(())
     } else if rc == -libc::EALREADY {
         Err(CameraError::AlreadyInitialized)
@@ -124,6 +168,26 @@ pub fn camera_initialize(config: CameraConfig) -> CameraResult<()> {
     }
 }
 
+/// Open `/dev/video<index>` instead of `/dev/video0` from the next
+/// initialization on, unless its config selects a `device_index`
+pub fn camera_set_device_index(index: u8) {
+    *CAMERA_DEVICE_INDEX.lock().unwrap() = index;
+}
+
+/// Initialize the `index`-th camera (only camera 0 is supported)
+pub fn camera_initialize_at(index: u8, config: CameraConfig) -> CameraResult<CameraHandle> {
+    if index != 0 {
+        return Err(CameraError::DeviceNotFound);
+    }
+    camera_initialize(config).map(|_| CameraHandle::DEFAULT)
+}
+
+/// Deinitialize a camera opened with `camera_initialize_at`
+pub fn camera_deinitialize_at(handle: CameraHandle) -> CameraResult<()> {
+    check_handle(handle)?;
+    camera_deinitialize()
+}
+
 /// Deinitialize the camera
 pub fn camera_deinitialize() -> CameraResult<()> {
     let rc = unsafe { rust_camera_wrapper_deinit() };
@@ -173,6 +237,8 @@ pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
     // Return the frame buffer to C side
     unsafe { rust_camera_wrapper_return_frame() };
 
+    CAMERA_STATS.lock().unwrap().record_frame(data.len());
+
     Ok(FrameBuffer {
         width,
         height,
@@ -182,14 +248,71 @@ pub fn camera_capture_frame() -> CameraResult<FrameBuffer> {
     })
 }
 
+/// Capture a single frame from a camera opened with `camera_initialize_at`
+pub fn camera_capture_frame_at(handle: CameraHandle) -> CameraResult<FrameBuffer> {
+    check_handle(handle)?;
+    camera_capture_frame()
+}
+
+/// Change the capture resolution without reinitializing
+pub fn camera_set_resolution(resolution: Resolution) -> CameraResult<()> {
+    let rc = unsafe { rust_camera_wrapper_set_resolution(resolution_to_int(resolution)) };
+
+    if rc == 0 {
+        Ok(())
+    } else if rc == -libc::ENODEV {
+        Err(CameraError::NotInitialized)
+    } else if rc == -libc::ENOMEM {
+        Err(CameraError::BufferAllocationFailed)
+    } else {
+        Err(CameraError::SystemError(-rc))
+    }
+}
+
+/// List supported pixel formats (not supported by the C wrapper yet)
+pub fn camera_enumerate_formats() -> CameraResult<Vec<PixelFormat>> {
+    Err(CameraError::NotSupported)
+}
+
+/// List supported frame sizes (not supported by the C wrapper yet)
+pub fn camera_enumerate_resolutions(_format: PixelFormat) -> CameraResult<Vec<(u32, u32)>> {
+    Err(CameraError::NotSupported)
+}
+
+/// Get a copy of the capture statistics
+pub fn camera_get_stats() -> CameraResult<CameraStats> {
+    if !camera_is_initialized() {
+        return Err(CameraError::NotInitialized);
+    }
+    Ok(*CAMERA_STATS.lock().unwrap())
+}
+
+/// Reset the capture statistics
+pub fn camera_reset_stats() -> CameraResult<()> {
+    if !camera_is_initialized() {
+        return Err(CameraError::NotInitialized);
+    }
+    *CAMERA_STATS.lock().unwrap() = CameraStats::new();
+    Ok(())
+}
+
 /// Get current camera settings
 pub fn camera_get_settings() -> CameraResult<CameraSettings> {
     let mut brightness: i8 = 0;
     let mut contrast: i8 = 0;
     let mut saturation: i8 = 0;
+    let mut hmirror: c_int = 0;
+    let mut vflip: c_int = 0;
 
-    let rc =
-        unsafe { rust_camera_wrapper_get_sensor(&mut brightness, &mut contrast, &mut saturation) };
+    let rc = unsafe {
+        rust_camera_wrapper_get_sensor(
+            &mut brightness,
+            &mut contrast,
+            &mut saturation,
+            &mut hmirror,
+            &mut vflip,
+        )
+    };
 
     if rc != 0 {
         return if rc == -libc::ENODEV {
@@ -209,11 +332,23 @@ pub fn camera_get_settings() -> CameraResult<CameraSettings> {
         ae_level: 0,
         agc: true,
         gainceiling: 0,
-        hmirror: false,
-        vflip: false,
+        hmirror: hmirror != 0,
+        vflip: vflip != 0,
     })
 }
 
+/// Get current settings of a camera opened with `camera_initialize_at`
+pub fn camera_get_settings_at(handle: CameraHandle) -> CameraResult<CameraSettings> {
+    check_handle(handle)?;
+    camera_get_settings()
+}
+
+/// Set settings of a camera opened with `camera_initialize_at`
+pub fn camera_set_settings_at(handle: CameraHandle, settings: CameraSettings) -> CameraResult<()> {
+    check_handle(handle)?;
+    camera_set_settings(settings)
+}
+
 /// Set camera settings
 pub fn camera_set_settings(settings: CameraSettings) -> CameraResult<()> {
     let rc = unsafe {
@@ -0,0 +1,114 @@
+//! Cryptographic primitives HAL
+//!
+//! SHA-256, HMAC-SHA256 and single-block AES-128 encryption.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the hardware accelerators through /dev/crypto, falling
+//!   back to software when the driver is missing or refuses the request
+//! - Other platforms: Portable software implementation
+//!
+//! Results are identical either way, so the functions cannot fail.
+
+// NuttX uses the cryptodev driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Software implementation, also the NuttX fallback
+mod soft;
+
+use core::fmt;
+
+/// Crypto driver errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoError {
+    /// /dev/crypto not found
+    DeviceNotFound,
+    /// Algorithm not supported by the driver, or no driver on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::DeviceNotFound => write!(f, "Crypto device not found"),
+            CryptoError::NotSupported => write!(f, "Not supported on this platform"),
+            CryptoError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl CryptoError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            CryptoError::DeviceNotFound => Some("enable CONFIG_CRYPTO_CRYPTODEV"),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for crypto driver operations
+pub type CryptoResult<T> = Result<T, CryptoError>;
+
+/// Check whether hashing and encryption run on a hardware accelerator
+///
+/// `NotSupported` on platforms without a crypto driver.
+pub fn hw_crypto_available() -> CryptoResult<()> {
+    #[cfg(feature = "platform-nuttx")]
+    return nuttx::probe();
+
+    #[cfg(not(feature = "platform-nuttx"))]
+    Err(CryptoError::NotSupported)
+}
+
+/// SHA-256 digest of `data`
+pub fn hw_sha256(data: &[u8]) -> [u8; 32] {
+    #[cfg(feature = "platform-nuttx")]
+    if let Ok(digest) = nuttx::sha256(data) {
+        return digest;
+    }
+
+    soft::sha256(data)
+}
+
+/// Encrypt one 16-byte block with AES-128 (ECB)
+pub fn hw_aes128_ecb_encrypt(key: &[u8; 16], block: &[u8; 16]) -> [u8; 16] {
+    #[cfg(feature = "platform-nuttx")]
+    if let Ok(out) = nuttx::aes128_encrypt_block(key, block) {
+        return out;
+    }
+
+    soft::aes128_encrypt_block(key, block)
+}
+
+/// HMAC-SHA256 (RFC 2104) of `data` under `key`
+///
+/// Keys longer than the 64-byte block are hashed first.
+pub fn hw_hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut block_key = [0u8; 64];
+    if key.len() > block_key.len() {
+        block_key[..32].copy_from_slice(&hw_sha256(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Vec::with_capacity(64 + data.len());
+    inner.extend(block_key.iter().map(|b| b ^ 0x36));
+    inner.extend_from_slice(data);
+    let inner_digest = hw_sha256(&inner);
+
+    let mut outer = [0u8; 64 + 32];
+    for (o, b) in outer.iter_mut().zip(block_key) {
+        *o = b ^ 0x5c;
+    }
+    outer[64..].copy_from_slice(&inner_digest);
+    hw_sha256(&outer)
+}
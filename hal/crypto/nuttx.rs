@@ -0,0 +1,161 @@
+//! Crypto HAL for NuttX
+//!
+//! Uses the cryptodev driver (/dev/crypto), which the ESP32-S3 port backs
+//! with its SHA and AES accelerators (CONFIG_CRYPTO_CRYPTODEV).
+
+use super::{CryptoError, CryptoResult};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+const CRYPTO_DEVICE: &str = "/dev/crypto";
+
+// Cryptodev ioctl commands (from nuttx/crypto/cryptodev.h)
+// _CRYPTOIOC(nr) = _IOC(_CRYPTOIOCBASE, nr), _CRYPTOIOCBASE = 0x3f00
+const CRIOGET: i32 = 0x3f64;
+const CIOCGSESSION: i32 = 0x3f65;
+const CIOCFSESSION: i32 = 0x3f66;
+const CIOCCRYPT: i32 = 0x3f67;
+
+// Algorithms
+const CRYPTO_AES_CBC: u32 = 11;
+const CRYPTO_SHA2_256: u32 = 25;
+
+const COP_ENCRYPT: u16 = 1;
+
+/// struct session_op
+#[repr(C)]
+struct SessionOp {
+    cipher: u32,
+    mac: u32,
+    keylen: u32,
+    key: *const libc::c_char,
+    mackeylen: libc::c_int,
+    mackey: *const libc::c_char,
+    /// Session id, set by the driver
+    ses: u32,
+}
+
+/// struct crypt_op
+#[repr(C)]
+struct CryptOp {
+    ses: u32,
+    op: u16,
+    flags: u16,
+    len: libc::c_uint,
+    src: *const libc::c_char,
+    dst: *mut libc::c_char,
+    mac: *mut libc::c_char,
+    iv: *const libc::c_char,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a crypto error
+fn map_errno(errno: i32) -> CryptoError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => CryptoError::DeviceNotFound,
+        libc::EINVAL | libc::EOPNOTSUPP => CryptoError::NotSupported,
+        _ => CryptoError::SystemError(errno),
+    }
+}
+
+fn last_error() -> CryptoError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+/// Driver session for one algorithm, freed on drop
+struct Session {
+    file: File,
+    ses: u32,
+}
+
+impl Session {
+    fn open(cipher: u32, mac: u32, key: &[u8]) -> CryptoResult<Self> {
+        let dev = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(CRYPTO_DEVICE)
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+
+        // Sessions live on a cloned descriptor, not the device node itself
+        let mut fd: libc::c_int = -1;
+        if unsafe { ioctl(dev.as_raw_fd(), CRIOGET, &mut fd as *mut libc::c_int) } < 0 {
+            return Err(last_error());
+        }
+        let file = unsafe { File::from_raw_fd(fd) };
+
+        let mut op = SessionOp {
+            cipher,
+            mac,
+            keylen: key.len() as u32,
+            key: key.as_ptr() as *const libc::c_char,
+            mackeylen: 0,
+            mackey: core::ptr::null(),
+            ses: 0,
+        };
+        if unsafe { ioctl(file.as_raw_fd(), CIOCGSESSION, &mut op as *mut SessionOp) } < 0 {
+            return Err(last_error());
+        }
+
+        Ok(Session { file, ses: op.ses })
+    }
+
+    fn crypt(&self, src: &[u8], dst: *mut u8, mac: *mut u8, iv: *const u8) -> CryptoResult<()> {
+        let mut op = CryptOp {
+            ses: self.ses,
+            op: COP_ENCRYPT,
+            flags: 0,
+            len: src.len() as libc::c_uint,
+            src: src.as_ptr() as *const libc::c_char,
+            dst: dst as *mut libc::c_char,
+            mac: mac as *mut libc::c_char,
+            iv: iv as *const libc::c_char,
+        };
+        if unsafe { ioctl(self.file.as_raw_fd(), CIOCCRYPT, &mut op as *mut CryptOp) } < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut ses = self.ses;
+        unsafe {
+            ioctl(self.file.as_raw_fd(), CIOCFSESSION, &mut ses as *mut u32);
+        }
+    }
+}
+
+/// Check that the driver accepts a SHA-256 session
+pub(super) fn probe() -> CryptoResult<()> {
+    Session::open(0, CRYPTO_SHA2_256, &[]).map(drop)
+}
+
+/// SHA-256 digest of `data` computed by the accelerator
+pub(super) fn sha256(data: &[u8]) -> CryptoResult<[u8; 32]> {
+    let session = Session::open(0, CRYPTO_SHA2_256, &[])?;
+    let mut digest = [0u8; 32];
+    session.crypt(
+        data,
+        core::ptr::null_mut(),
+        digest.as_mut_ptr(),
+        core::ptr::null(),
+    )?;
+    Ok(digest)
+}
+
+/// Encrypt one block with AES-128 on the accelerator
+///
+/// CBC with a zero IV over a single block is plain ECB.
+pub(super) fn aes128_encrypt_block(key: &[u8; 16], block: &[u8; 16]) -> CryptoResult<[u8; 16]> {
+    let session = Session::open(CRYPTO_AES_CBC, 0, key)?;
+    let iv = [0u8; 16];
+    let mut out = [0u8; 16];
+    session.crypt(block, out.as_mut_ptr(), core::ptr::null_mut(), iv.as_ptr())?;
+    Ok(out)
+}
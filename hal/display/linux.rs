@@ -0,0 +1,143 @@
+//! Linux display stub
+//!
+//! There is no panel to drive, so this emulates the controller's frame
+//! memory: window commands and pixel writes land in an in-memory buffer,
+//! and every displayed frame is written out as a binary PPM (P6) image,
+//! `frame_00000.ppm`, `frame_00001.ppm`, ... in the output directory.
+
+use super::{DisplayConfig, DisplayError, DisplayResult, CMD_CASET, CMD_RAMWR, CMD_RASET};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Directory set with `display_set_output_dir`
+static OUTPUT_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Directory used when none is set, under the system temp directory
+const DEFAULT_OUTPUT_DIR: &str = "rustcam-display";
+
+/// Write frames of displays initialized from now on to `path`
+///
+/// The directory is created if needed. An empty path restores the default,
+/// `rustcam-display` in the system temp directory.
+pub fn display_set_output_dir(path: &str) {
+    *OUTPUT_DIR.lock().unwrap() = (!path.is_empty()).then(|| PathBuf::from(path));
+}
+
+fn map_io_error(e: io::Error) -> DisplayError {
+    match e.kind() {
+        io::ErrorKind::NotFound => DisplayError::NotFound,
+        io::ErrorKind::PermissionDenied => DisplayError::PermissionDenied,
+        _ => DisplayError::SystemError(e.raw_os_error().unwrap_or(-1)),
+    }
+}
+
+/// Expand RGB565 to 8 bits per channel, replicating the high bits
+fn rgb565_to_rgb(color: u16) -> [u8; 3] {
+    let r = (color >> 11) as u8 & 0x1f;
+    let g = (color >> 5) as u8 & 0x3f;
+    let b = color as u8 & 0x1f;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+/// Emulated panel: frame memory plus the write window
+#[derive(Debug)]
+pub(super) struct Panel {
+    width: usize,
+    height: usize,
+    pixels: Vec<u16>,
+    /// Inclusive column range from CASET
+    columns: (usize, usize),
+    /// Inclusive row range from RASET
+    rows: (usize, usize),
+    /// Next pixel written, or `None` once the window is full
+    cursor: Option<(usize, usize)>,
+    dir: PathBuf,
+    frames: u32,
+}
+
+impl Panel {
+    pub(super) fn open(config: &DisplayConfig) -> DisplayResult<Panel> {
+        let dir = OUTPUT_DIR
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join(DEFAULT_OUTPUT_DIR));
+        fs::create_dir_all(&dir).map_err(map_io_error)?;
+
+        let (width, height) = (config.width as usize, config.height as usize);
+        Ok(Panel {
+            width,
+            height,
+            pixels: vec![0; width * height],
+            columns: (0, width - 1),
+            rows: (0, height - 1),
+            cursor: None,
+            dir,
+            frames: 0,
+        })
+    }
+
+    /// Handle the window commands; everything else has no visible effect
+    pub(super) fn command(&mut self, cmd: u8, params: &[u8]) -> DisplayResult<()> {
+        let range = |p: &[u8]| {
+            (
+                u16::from_be_bytes([p[0], p[1]]) as usize,
+                u16::from_be_bytes([p[2], p[3]]) as usize,
+            )
+        };
+        match cmd {
+            CMD_CASET if params.len() == 4 => self.columns = range(params),
+            CMD_RASET if params.len() == 4 => self.rows = range(params),
+            CMD_RAMWR => self.cursor = Some((self.columns.0, self.rows.0)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Store pixels at the cursor, wrapping at the end of each window row
+    ///
+    /// Like the real controller, pixels beyond the window are dropped.
+    pub(super) fn write_pixels(&mut self, pixels: &[u16]) -> DisplayResult<()> {
+        for &color in pixels {
+            let Some((x, y)) = self.cursor else {
+                break;
+            };
+            if x < self.width && y < self.height {
+                self.pixels[y * self.width + x] = color;
+            }
+            self.cursor = if x < self.columns.1 {
+                Some((x + 1, y))
+            } else if y < self.rows.1 {
+                Some((self.columns.0, y + 1))
+            } else {
+                None
+            };
+        }
+        Ok(())
+    }
+
+    /// No backlight to switch
+    pub(super) fn set_backlight(&mut self, _on: bool) -> DisplayResult<()> {
+        Ok(())
+    }
+
+    /// Write the frame memory to the next PPM file
+    pub(super) fn end_frame(&mut self) -> DisplayResult<()> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.reserve(self.pixels.len() * 3);
+        for &color in &self.pixels {
+            ppm.extend_from_slice(&rgb565_to_rgb(color));
+        }
+
+        let path = self.dir.join(format!("frame_{:05}.ppm", self.frames));
+        fs::write(path, ppm).map_err(map_io_error)?;
+        self.frames += 1;
+        Ok(())
+    }
+}
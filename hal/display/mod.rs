@@ -0,0 +1,267 @@
+//! LCD display HAL
+//!
+//! Drives an SPI TFT panel with an ST7789/ILI9341-style command set, e.g.
+//! to show camera frames. Implementation is selected at compile time based
+//! on platform feature.
+//!
+//! - NuttX: Uses the SPI character driver (/dev/spiN) with GPIOs for the
+//!   data/command, reset and backlight lines
+//! - Linux: Stub panel that writes each displayed frame to a PPM file
+
+// Platform-specific implementations
+
+// NuttX uses the SPI character driver and the GPIO HAL
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+#[cfg(feature = "platform-nuttx")]
+use nuttx::Panel;
+
+// Linux emulates the panel in memory
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::display_set_output_dir;
+#[cfg(feature = "platform-linux")]
+use linux::Panel;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+use none::Panel;
+
+use crate::camera::{FrameBuffer, PixelFormat};
+use core::fmt;
+use std::thread;
+use std::time::Duration;
+
+pub use crate::camera::rgb_to_rgb565;
+
+// Panel commands (MIPI DCS, shared by ST7789 and ILI9341)
+const CMD_SWRESET: u8 = 0x01;
+const CMD_SLPOUT: u8 = 0x11;
+const CMD_DISPON: u8 = 0x29;
+const CMD_CASET: u8 = 0x2a;
+const CMD_RASET: u8 = 0x2b;
+const CMD_RAMWR: u8 = 0x2c;
+const CMD_MADCTL: u8 = 0x36;
+const CMD_COLMOD: u8 = 0x3a;
+
+// COLMOD parameter: 16 bits per pixel (RGB565)
+const COLMOD_RGB565: u8 = 0x55;
+
+/// Time the controller needs after SWRESET before taking commands
+const SWRESET_DELAY: Duration = Duration::from_millis(150);
+
+/// Time the controller needs after SLPOUT before taking commands
+const SLPOUT_DELAY: Duration = Duration::from_millis(120);
+
+/// Pixels sent per write while filling the screen
+const FILL_CHUNK: usize = 1024;
+
+/// Display operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayError {
+    /// SPI bus, GPIO or output directory not found
+    NotFound,
+    /// Permission denied
+    PermissionDenied,
+    /// Window outside the screen, or pixel count not matching it
+    InvalidArgument,
+    /// Frame format cannot be shown (only RGB565 and RGB888 can)
+    UnsupportedFormat,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for DisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayError::NotFound => write!(f, "Display not found"),
+            DisplayError::PermissionDenied => write!(f, "Permission denied"),
+            DisplayError::InvalidArgument => write!(f, "Invalid argument"),
+            DisplayError::UnsupportedFormat => write!(f, "Unsupported pixel format"),
+            DisplayError::NotSupported => write!(f, "Not supported on this platform"),
+            DisplayError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl DisplayError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            DisplayError::NotFound if cfg!(feature = "platform-nuttx") => {
+                Some("enable CONFIG_SPI_DRIVER and check /dev/spiN exists")
+            }
+            DisplayError::UnsupportedFormat => Some("capture in RGB565 or RGB888"),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for display operations
+pub type DisplayResult<T> = Result<T, DisplayError>;
+
+/// Panel geometry and wiring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayConfig {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+    /// SPI bus number (/dev/spiN on NuttX)
+    pub spi_bus: u8,
+    /// Chip select, passed to the SPI driver as the device index
+    pub cs_pin: u8,
+    /// Data/command select GPIO
+    pub dc_pin: u8,
+    /// Reset GPIO (active low)
+    pub reset_pin: u8,
+    /// Backlight GPIO (active high)
+    pub backlight_pin: u8,
+}
+
+/// An initialized display
+///
+/// Created with `Display::initialize()`. Coordinates are in pixels from the
+/// top-left corner; colors are RGB565.
+#[derive(Debug)]
+pub struct Display {
+    config: DisplayConfig,
+    panel: Panel,
+}
+
+impl Display {
+    /// Reset the panel, run its init sequence and turn the backlight on
+    ///
+    /// Both dimensions must be between 1 and 65535.
+    pub fn initialize(config: DisplayConfig) -> DisplayResult<Display> {
+        let valid = |d: u32| (1..=u16::MAX as u32).contains(&d);
+        if !valid(config.width) || !valid(config.height) {
+            return Err(DisplayError::InvalidArgument);
+        }
+
+        let mut panel = Panel::open(&config)?;
+        panel.command(CMD_SWRESET, &[])?;
+        thread::sleep(SWRESET_DELAY);
+        panel.command(CMD_SLPOUT, &[])?;
+        thread::sleep(SLPOUT_DELAY);
+        panel.command(CMD_COLMOD, &[COLMOD_RGB565])?;
+        // Row/column order as wired: top-left origin, RGB subpixels
+        panel.command(CMD_MADCTL, &[0x00])?;
+        panel.command(CMD_DISPON, &[])?;
+        // Only now, so whatever was in panel RAM is never visible
+        panel.set_backlight(true)?;
+
+        Ok(Display { config, panel })
+    }
+
+    /// Configuration the display was initialized with
+    pub fn config(&self) -> &DisplayConfig {
+        &self.config
+    }
+
+    /// Set the drawing window and start a memory write
+    fn set_window(&mut self, x: u16, y: u16, w: u16, h: u16) -> DisplayResult<()> {
+        let (x1, y1) = (x + w - 1, y + h - 1);
+        let [x0_hi, x0_lo] = x.to_be_bytes();
+        let [x1_hi, x1_lo] = x1.to_be_bytes();
+        let [y0_hi, y0_lo] = y.to_be_bytes();
+        let [y1_hi, y1_lo] = y1.to_be_bytes();
+        self.panel
+            .command(CMD_CASET, &[x0_hi, x0_lo, x1_hi, x1_lo])?;
+        self.panel
+            .command(CMD_RASET, &[y0_hi, y0_lo, y1_hi, y1_lo])?;
+        self.panel.command(CMD_RAMWR, &[])
+    }
+
+    /// Draw a `w` x `h` block of row-major `pixels` at (`x`, `y`)
+    ///
+    /// Fails with `InvalidArgument` if the block is empty, does not fit on
+    /// the screen, or `pixels` does not hold exactly `w * h` pixels.
+    pub fn blit_rgb565(
+        &mut self,
+        x: u16,
+        y: u16,
+        w: u16,
+        h: u16,
+        pixels: &[u16],
+    ) -> DisplayResult<()> {
+        let fits = |start: u16, len: u16, max: u32| len > 0 && start as u32 + len as u32 <= max;
+        if !fits(x, w, self.config.width)
+            || !fits(y, h, self.config.height)
+            || pixels.len() != w as usize * h as usize
+        {
+            return Err(DisplayError::InvalidArgument);
+        }
+
+        self.set_window(x, y, w, h)?;
+        self.panel.write_pixels(pixels)
+    }
+
+    /// Fill the whole screen with `color`
+    pub fn clear(&mut self, color: u16) -> DisplayResult<()> {
+        let (w, h) = (self.config.width as u16, self.config.height as u16);
+        self.set_window(0, 0, w, h)?;
+
+        let chunk = [color; FILL_CHUNK];
+        let mut remaining = w as usize * h as usize;
+        while remaining > 0 {
+            let n = remaining.min(FILL_CHUNK);
+            self.panel.write_pixels(&chunk[..n])?;
+            remaining -= n;
+        }
+        Ok(())
+    }
+
+    /// Show a camera frame in the top-left corner
+    ///
+    /// RGB565 frames are sent as-is and RGB888 frames are converted; other
+    /// formats fail with `UnsupportedFormat`. Parts of the frame outside the
+    /// screen are clipped. On Linux each call writes one PPM file.
+    pub fn display_frame(&mut self, frame: &FrameBuffer) -> DisplayResult<()> {
+        let bpp = match frame.format {
+            PixelFormat::Rgb565 => 2,
+            PixelFormat::Rgb888 => 3,
+            _ => return Err(DisplayError::UnsupportedFormat),
+        };
+        let width = frame.width as usize;
+        if frame.data.len() < width * frame.height as usize * bpp {
+            return Err(DisplayError::InvalidArgument);
+        }
+
+        let w = frame.width.min(self.config.width) as u16;
+        let h = frame.height.min(self.config.height) as u16;
+        if w == 0 || h == 0 {
+            return Ok(());
+        }
+        self.set_window(0, 0, w, h)?;
+
+        // One converted row at a time keeps the buffer small
+        let mut row = Vec::with_capacity(w as usize);
+        for y in 0..h as usize {
+            let src = &frame.data[y * width * bpp..][..w as usize * bpp];
+            row.clear();
+            if frame.format == PixelFormat::Rgb565 {
+                row.extend(
+                    src.chunks_exact(2)
+                        .map(|p| u16::from_le_bytes([p[0], p[1]])),
+                );
+            } else {
+                row.extend(src.chunks_exact(3).map(|p| rgb_to_rgb565(p[0], p[1], p[2])));
+            }
+            self.panel.write_pixels(&row)?;
+        }
+
+        self.panel.end_frame()
+    }
+}
@@ -0,0 +1,30 @@
+//! Display HAL stub for unsupported platforms
+
+use super::{DisplayConfig, DisplayError, DisplayResult};
+
+/// Never constructed: opening always fails
+#[derive(Debug)]
+pub(super) struct Panel;
+
+impl Panel {
+    /// Open the panel (stub: returns NotSupported)
+    pub(super) fn open(_config: &DisplayConfig) -> DisplayResult<Panel> {
+        Err(DisplayError::NotSupported)
+    }
+
+    pub(super) fn command(&mut self, _cmd: u8, _params: &[u8]) -> DisplayResult<()> {
+        Err(DisplayError::NotSupported)
+    }
+
+    pub(super) fn write_pixels(&mut self, _pixels: &[u16]) -> DisplayResult<()> {
+        Err(DisplayError::NotSupported)
+    }
+
+    pub(super) fn set_backlight(&mut self, _on: bool) -> DisplayResult<()> {
+        Err(DisplayError::NotSupported)
+    }
+
+    pub(super) fn end_frame(&mut self) -> DisplayResult<()> {
+        Err(DisplayError::NotSupported)
+    }
+}
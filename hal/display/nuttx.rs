@@ -0,0 +1,190 @@
+//! Display HAL for NuttX
+//!
+//! Talks to the panel through the SPI character driver (CONFIG_SPI_DRIVER),
+//! one SPIIOC_TRANSFER per command or pixel chunk. The data/command line is
+//! a GPIO: low while a command byte is sent, high for parameters and pixel
+//! data. Assumes CONFIG_SPI_CMDDATA and CONFIG_SPI_HWFEATURES are off, as
+//! they change the layout of `struct spi_trans_s`.
+
+use super::{DisplayConfig, DisplayError, DisplayResult};
+use crate::gpio::{GpioDirection, GpioError, GpioPin};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::thread;
+use std::time::Duration;
+
+// SPI ioctl commands (from nuttx/spi/spi_transfer.h)
+// _SPIIOC(nr) = _IOC(_SPIBASE, nr), _SPIBASE = 0x2200
+const SPIIOC_TRANSFER: i32 = 0x2201;
+
+// enum spi_devtype_e; SPIDEV_ID(type, index) = type << 16 | index
+const SPIDEVTYPE_DISPLAY: u32 = 4;
+
+/// SPI mode 0 (CPOL=0, CPHA=0), as used by ST7789 and ILI9341
+const SPI_MODE: u8 = 0;
+
+/// SPI clock; both controllers accept writes up to about 60 MHz
+const SPI_FREQUENCY: u32 = 40_000_000;
+
+/// Largest transfer, in bytes
+const SPI_CHUNK: usize = 4096;
+
+/// Reset pulse width and recovery time before the controller takes commands
+const RESET_PULSE: Duration = Duration::from_millis(10);
+const RESET_RECOVERY: Duration = Duration::from_millis(120);
+
+/// struct spi_trans_s
+#[repr(C)]
+struct SpiTransS {
+    deselect: bool,
+    delay: u32,
+    nwords: usize,
+    txbuffer: *const u8,
+    rxbuffer: *mut u8,
+}
+
+/// struct spi_sequence_s
+#[repr(C)]
+struct SpiSequenceS {
+    dev: u32,
+    mode: u8,
+    nbits: u8,
+    ntrans: u8,
+    frequency: u32,
+    trans: *mut SpiTransS,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a display error
+fn map_errno(errno: i32) -> DisplayError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => DisplayError::NotFound,
+        libc::EPERM | libc::EACCES => DisplayError::PermissionDenied,
+        _ => DisplayError::SystemError(errno),
+    }
+}
+
+fn map_gpio_error(e: GpioError) -> DisplayError {
+    match e {
+        GpioError::NotFound => DisplayError::NotFound,
+        GpioError::PermissionDenied => DisplayError::PermissionDenied,
+        GpioError::NotSupported => DisplayError::NotSupported,
+        GpioError::SystemError(errno) => DisplayError::SystemError(errno),
+        GpioError::AlreadyExported => DisplayError::SystemError(libc::EBUSY),
+        GpioError::NotInitialized => DisplayError::SystemError(libc::EBADF),
+    }
+}
+
+/// Export a pin as an output driven to `high`
+fn output_pin(number: u8, high: bool) -> DisplayResult<GpioPin> {
+    let pin = GpioPin::export(number as u32).map_err(map_gpio_error)?;
+    pin.set_direction(GpioDirection::Output)
+        .map_err(map_gpio_error)?;
+    pin.set_value(high).map_err(map_gpio_error)?;
+    Ok(pin)
+}
+
+/// SPI device plus the control lines of the panel
+#[derive(Debug)]
+pub(super) struct Panel {
+    spi: File,
+    /// Chip select index, see `SPIDEVTYPE_DISPLAY`
+    device: u32,
+    dc: GpioPin,
+    // Held so the pin stays exported and high
+    _reset: GpioPin,
+    backlight: GpioPin,
+}
+
+impl Panel {
+    /// Open /dev/spiN, claim the control GPIOs and pulse reset
+    pub(super) fn open(config: &DisplayConfig) -> DisplayResult<Panel> {
+        let spi = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/spi{}", config.spi_bus))
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+
+        let backlight = output_pin(config.backlight_pin, false)?;
+        let dc = output_pin(config.dc_pin, true)?;
+        let reset = output_pin(config.reset_pin, false)?;
+        thread::sleep(RESET_PULSE);
+        reset.set_value(true).map_err(map_gpio_error)?;
+        thread::sleep(RESET_RECOVERY);
+
+        Ok(Panel {
+            spi,
+            device: SPIDEVTYPE_DISPLAY << 16 | config.cs_pin as u32,
+            dc,
+            _reset: reset,
+            backlight,
+        })
+    }
+
+    /// Send `data` in one chip-select cycle
+    fn transfer(&self, data: &[u8]) -> DisplayResult<()> {
+        let mut trans = SpiTransS {
+            deselect: true,
+            delay: 0,
+            nwords: data.len(),
+            txbuffer: data.as_ptr(),
+            rxbuffer: std::ptr::null_mut(),
+        };
+        let mut seq = SpiSequenceS {
+            dev: self.device,
+            mode: SPI_MODE,
+            nbits: 8,
+            ntrans: 1,
+            frequency: SPI_FREQUENCY,
+            trans: &mut trans,
+        };
+
+        let ret = unsafe { ioctl(self.spi.as_raw_fd(), SPIIOC_TRANSFER, &mut seq) };
+        if ret < 0 {
+            return Err(map_errno(
+                io::Error::last_os_error().raw_os_error().unwrap_or(0),
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_dc(&self, data: bool) -> DisplayResult<()> {
+        self.dc.set_value(data).map_err(map_gpio_error)
+    }
+
+    /// Send a command byte followed by its parameters
+    pub(super) fn command(&mut self, cmd: u8, params: &[u8]) -> DisplayResult<()> {
+        self.set_dc(false)?;
+        self.transfer(&[cmd])?;
+        self.set_dc(true)?;
+        if !params.is_empty() {
+            self.transfer(params)?;
+        }
+        Ok(())
+    }
+
+    /// Send pixel data, most significant byte first
+    pub(super) fn write_pixels(&mut self, pixels: &[u16]) -> DisplayResult<()> {
+        let mut buf = Vec::with_capacity(SPI_CHUNK.min(pixels.len() * 2));
+        for chunk in pixels.chunks(SPI_CHUNK / 2) {
+            buf.clear();
+            buf.extend(chunk.iter().flat_map(|p| p.to_be_bytes()));
+            self.transfer(&buf)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn set_backlight(&mut self, on: bool) -> DisplayResult<()> {
+        self.backlight.set_value(on).map_err(map_gpio_error)
+    }
+
+    /// Pixels are shown as they are written
+    pub(super) fn end_frame(&mut self) -> DisplayResult<()> {
+        Ok(())
+    }
+}
@@ -10,8 +10,6 @@ fn main() {
         Ok(()) => println!("  OK: WiFi initialized"),
         Err(e) => {
             println!("  FAIL: {:?}", e);
-            println!("\nNote: Scanning requires CAP_NET_ADMIN capability.");
-            println!("Try: sudo setcap cap_net_admin+ep <binary>");
             return;
         }
     }
@@ -30,6 +28,12 @@ fn main() {
     println!("\nStarting WiFi scan...");
     match hal::wifi::wifi_start_scan() {
         Ok(()) => println!("  Scan triggered"),
+        Err(hal::wifi::WifiError::PermissionDenied) => {
+            println!("  Scan trigger failed: permission denied");
+            println!("\nNote: Scanning requires CAP_NET_ADMIN capability.");
+            println!("Try: sudo setcap cap_net_admin+ep <binary>");
+            println!("  Trying to get cached results...");
+        }
         Err(e) => {
             println!("  Scan trigger failed: {:?}", e);
             println!("  Trying to get cached results...");
@@ -0,0 +1,115 @@
+//! Linux GPIO implementation using the sysfs interface
+//!
+//! Pins are exported through /sys/class/gpio/export and controlled through
+//! the per-pin direction, value and edge attribute files.
+
+use super::{GpioDirection, GpioEdge, GpioError, GpioPin, GpioResult};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+const SYSFS_GPIO: &str = "/sys/class/gpio";
+
+// udev may need a moment to fix up permissions of a freshly exported pin
+const EXPORT_RETRIES: u32 = 10;
+const EXPORT_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// Map an I/O error from a sysfs access to a GPIO error
+fn map_io_error(e: io::Error) -> GpioError {
+    match e.kind() {
+        io::ErrorKind::NotFound => GpioError::NotFound,
+        io::ErrorKind::PermissionDenied => GpioError::PermissionDenied,
+        _ => GpioError::SystemError(e.raw_os_error().unwrap_or(-1)),
+    }
+}
+
+/// Write a string to a sysfs attribute file
+fn write_attr(path: PathBuf, value: &str) -> GpioResult<()> {
+    fs::write(path, value).map_err(map_io_error)
+}
+
+impl GpioPin {
+    /// Export a GPIO and open its value file
+    pub fn export(number: u32) -> GpioResult<GpioPin> {
+        if let Err(e) = fs::write(format!("{}/export", SYSFS_GPIO), number.to_string()) {
+            return Err(match e.raw_os_error() {
+                Some(libc::EBUSY) => GpioError::AlreadyExported,
+                // The kernel rejects numbers it has no GPIO for
+                Some(libc::EINVAL) => GpioError::NotFound,
+                _ => map_io_error(e),
+            });
+        }
+
+        let value_path = format!("{}/gpio{}/value", SYSFS_GPIO, number);
+        let mut attempt = 0;
+        let file = loop {
+            match OpenOptions::new().read(true).write(true).open(&value_path) {
+                Ok(file) => break file,
+                Err(_) if attempt < EXPORT_RETRIES => {
+                    attempt += 1;
+                    thread::sleep(EXPORT_RETRY_DELAY);
+                }
+                Err(e) => {
+                    let _ = fs::write(format!("{}/unexport", SYSFS_GPIO), number.to_string());
+                    return Err(map_io_error(e));
+                }
+            }
+        };
+
+        Ok(GpioPin { number, fd: Some(file) })
+    }
+
+    fn attr_path(&self, attr: &str) -> PathBuf {
+        PathBuf::from(format!("{}/gpio{}/{}", SYSFS_GPIO, self.number, attr))
+    }
+
+    fn value_file(&self) -> GpioResult<&File> {
+        self.fd.as_ref().ok_or(GpioError::NotInitialized)
+    }
+
+    /// Configure the pin as input or output
+    pub fn set_direction(&self, dir: GpioDirection) -> GpioResult<()> {
+        let value = match dir {
+            GpioDirection::Input => "in",
+            GpioDirection::Output => "out",
+        };
+        write_attr(self.attr_path("direction"), value)
+    }
+
+    /// Drive an output pin high or low
+    pub fn set_value(&self, high: bool) -> GpioResult<()> {
+        let mut file = self.value_file()?;
+        file.seek(SeekFrom::Start(0)).map_err(map_io_error)?;
+        file.write_all(if high { b"1" } else { b"0" }).map_err(map_io_error)
+    }
+
+    /// Read the current pin level
+    pub fn get_value(&self) -> GpioResult<bool> {
+        let mut file = self.value_file()?;
+        file.seek(SeekFrom::Start(0)).map_err(map_io_error)?;
+        let mut buf = [0u8; 2];
+        let len = file.read(&mut buf).map_err(map_io_error)?;
+        Ok(len > 0 && buf[0] == b'1')
+    }
+
+    /// Select which transitions raise an interrupt (input pins only)
+    pub fn set_edge(&self, edge: GpioEdge) -> GpioResult<()> {
+        let value = match edge {
+            GpioEdge::None => "none",
+            GpioEdge::Rising => "rising",
+            GpioEdge::Falling => "falling",
+            GpioEdge::Both => "both",
+        };
+        write_attr(self.attr_path("edge"), value)
+    }
+}
+
+impl Drop for GpioPin {
+    fn drop(&mut self) {
+        // Close the value file before unexporting
+        self.fd = None;
+        let _ = fs::write(format!("{}/unexport", SYSFS_GPIO), self.number.to_string());
+    }
+}
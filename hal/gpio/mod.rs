@@ -0,0 +1,115 @@
+//! GPIO HAL
+//!
+//! Provides digital pin access for LEDs, buttons and sensor triggers.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - Linux: Uses the sysfs GPIO interface (/sys/class/gpio)
+//! - NuttX: Uses the GPIO character driver (/dev/gpioN) via ioctl
+
+// Platform-specific implementations
+
+// NuttX uses /dev/gpioN ioctls
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Linux uses sysfs
+#[cfg(feature = "platform-linux")]
+mod linux;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::fs::File;
+
+/// GPIO operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpioError {
+    /// Pin or GPIO interface not found
+    NotFound,
+    /// Permission denied
+    PermissionDenied,
+    /// Pin already exported by another user
+    AlreadyExported,
+    /// Pin not exported/opened
+    NotInitialized,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for GpioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpioError::NotFound => write!(f, "GPIO not found"),
+            GpioError::PermissionDenied => write!(f, "Permission denied"),
+            GpioError::AlreadyExported => write!(f, "GPIO already exported"),
+            GpioError::NotInitialized => write!(f, "GPIO not initialized"),
+            GpioError::NotSupported => write!(f, "Not supported on this platform"),
+            GpioError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl GpioError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            GpioError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("run as root or add the user to the gpio group")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for GPIO operations
+pub type GpioResult<T> = Result<T, GpioError>;
+
+/// Pin direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpioDirection {
+    /// Input pin
+    Input,
+    /// Output pin
+    Output,
+}
+
+/// Edge that triggers an interrupt on an input pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GpioEdge {
+    /// No interrupt
+    #[default]
+    None,
+    /// Low to high transition
+    Rising,
+    /// High to low transition
+    Falling,
+    /// Both transitions
+    Both,
+}
+
+/// An exported GPIO pin
+///
+/// Created with `GpioPin::export()`; the pin is released when dropped.
+#[derive(Debug)]
+pub struct GpioPin {
+    number: u32,
+    #[cfg_attr(not(any(feature = "platform-linux", feature = "platform-nuttx")), allow(dead_code))]
+    fd: Option<File>,
+}
+
+impl GpioPin {
+    /// GPIO number of this pin
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+}
@@ -0,0 +1,30 @@
+//! GPIO HAL stub for unsupported platforms
+
+use super::{GpioDirection, GpioEdge, GpioError, GpioPin, GpioResult};
+
+impl GpioPin {
+    /// Export a GPIO (stub: returns NotSupported)
+    pub fn export(_number: u32) -> GpioResult<GpioPin> {
+        Err(GpioError::NotSupported)
+    }
+
+    /// Set pin direction (stub: returns NotSupported)
+    pub fn set_direction(&self, _dir: GpioDirection) -> GpioResult<()> {
+        Err(GpioError::NotSupported)
+    }
+
+    /// Set pin level (stub: returns NotSupported)
+    pub fn set_value(&self, _high: bool) -> GpioResult<()> {
+        Err(GpioError::NotSupported)
+    }
+
+    /// Read pin level (stub: returns NotSupported)
+    pub fn get_value(&self) -> GpioResult<bool> {
+        Err(GpioError::NotSupported)
+    }
+
+    /// Set interrupt edge (stub: returns NotSupported)
+    pub fn set_edge(&self, _edge: GpioEdge) -> GpioResult<()> {
+        Err(GpioError::NotSupported)
+    }
+}
@@ -0,0 +1,114 @@
+//! GPIO HAL for NuttX
+//!
+//! Uses the GPIO lower-half character driver: each pin registered by the
+//! board is a /dev/gpioN device controlled through GPIOC_* ioctls.
+
+use super::{GpioDirection, GpioEdge, GpioError, GpioPin, GpioResult};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+// GPIO ioctl commands (from nuttx/ioexpander/gpio.h)
+// _GPIOC(nr) = _IOC(_GPIOBASE, nr), _GPIOBASE = 0x2300
+const GPIOC_WRITE: i32 = 0x2301;
+const GPIOC_READ: i32 = 0x2302;
+const GPIOC_SETPINTYPE: i32 = 0x2306;
+
+// enum gpio_pintype_e
+const GPIO_INPUT_PIN: usize = 0;
+const GPIO_OUTPUT_PIN: usize = 3;
+const GPIO_INTERRUPT_RISING_PIN: usize = 8;
+const GPIO_INTERRUPT_FALLING_PIN: usize = 9;
+const GPIO_INTERRUPT_BOTH_PIN: usize = 10;
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a GPIO error
+fn map_errno(errno: i32) -> GpioError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => GpioError::NotFound,
+        libc::EPERM | libc::EACCES => GpioError::PermissionDenied,
+        _ => GpioError::SystemError(errno),
+    }
+}
+
+fn last_error() -> GpioError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+impl GpioPin {
+    /// Open the /dev/gpioN device for a pin
+    pub fn export(number: u32) -> GpioResult<GpioPin> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/gpio{}", number))
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+        Ok(GpioPin { number, fd: Some(file) })
+    }
+
+    fn device(&self) -> GpioResult<&File> {
+        self.fd.as_ref().ok_or(GpioError::NotInitialized)
+    }
+
+    fn set_pintype(&self, pintype: usize) -> GpioResult<()> {
+        let fd = self.device()?.as_raw_fd();
+        let ret = unsafe { ioctl(fd, GPIOC_SETPINTYPE, pintype) };
+        if ret < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    /// Configure the pin as input or output
+    pub fn set_direction(&self, dir: GpioDirection) -> GpioResult<()> {
+        self.set_pintype(match dir {
+            GpioDirection::Input => GPIO_INPUT_PIN,
+            GpioDirection::Output => GPIO_OUTPUT_PIN,
+        })
+    }
+
+    /// Drive an output pin high or low
+    pub fn set_value(&self, high: bool) -> GpioResult<()> {
+        let fd = self.device()?.as_raw_fd();
+        let ret = unsafe { ioctl(fd, GPIOC_WRITE, high as usize) };
+        if ret < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    /// Read the current pin level
+    pub fn get_value(&self) -> GpioResult<bool> {
+        let fd = self.device()?.as_raw_fd();
+        let mut value = false;
+        let ret = unsafe { ioctl(fd, GPIOC_READ, &mut value as *mut bool) };
+        if ret < 0 {
+            return Err(last_error());
+        }
+        Ok(value)
+    }
+
+    /// Select which transitions raise an interrupt
+    ///
+    /// NuttX expresses this as an interrupt pin type; `GpioEdge::None`
+    /// turns the pin back into a plain input.
+    pub fn set_edge(&self, edge: GpioEdge) -> GpioResult<()> {
+        self.set_pintype(match edge {
+            GpioEdge::None => GPIO_INPUT_PIN,
+            GpioEdge::Rising => GPIO_INTERRUPT_RISING_PIN,
+            GpioEdge::Falling => GPIO_INTERRUPT_FALLING_PIN,
+            GpioEdge::Both => GPIO_INTERRUPT_BOTH_PIN,
+        })
+    }
+}
+
+impl Drop for GpioPin {
+    fn drop(&mut self) {
+        // Nothing to unexport; closing the device releases the pin
+        self.fd = None;
+    }
+}
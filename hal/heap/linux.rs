@@ -2,11 +2,13 @@
 //!
 //! Uses glibc's mallinfo() for heap statistics.
 
-use super::HeapStats;
+use super::{HeapStats, HEAP_PEAK};
+use std::sync::atomic::Ordering;
 
 /// Get current heap usage in bytes
 pub fn get_heap_used() -> i32 {
     let info = unsafe { libc::mallinfo() };
+    HEAP_PEAK.fetch_max(info.uordblks, Ordering::Relaxed);
     info.uordblks
 }
 
@@ -19,6 +19,28 @@ mod none;
 #[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
 pub use none::*;
 
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Highest `get_heap_used()` value seen so far
+static HEAP_PEAK: AtomicI32 = AtomicI32::new(0);
+
+/// Get the highest heap usage observed by `get_heap_used()` since program
+/// start (or the last `reset_heap_peak()`)
+///
+/// Usage is only sampled when `get_heap_used()` is called, so short-lived
+/// spikes between calls are not seen.
+pub fn get_heap_peak() -> i32 {
+    HEAP_PEAK.load(Ordering::Relaxed)
+}
+
+/// Reset the recorded heap peak
+pub fn reset_heap_peak() {
+    HEAP_PEAK.store(0, Ordering::Relaxed);
+}
+
 /// Heap statistics structure
 #[derive(Debug, Clone, Copy)]
 pub struct HeapStats {
@@ -33,3 +55,279 @@ pub struct HeapStats {
     /// Total free space in bytes
     pub fordblks: i32,
 }
+
+impl HeapStats {
+    /// Share of free space outside the largest free chunk (0.0-1.0)
+    ///
+    /// 0.0 means all free memory is contiguous; near 1.0 means a large
+    /// allocation can fail even though plenty of memory is free. Reads 0.0
+    /// when the largest chunk is unknown (Linux mallinfo() lacks it) or
+    /// nothing is free.
+    pub fn fragmentation_ratio(&self) -> f32 {
+        if self.mxordblk <= 0 || self.fordblks <= 0 {
+            return 0.0;
+        }
+        (1.0 - self.mxordblk as f32 / self.fordblks as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Called with the fragmentation ratio when it exceeds the threshold
+pub type FragmentationCallback = Box<dyn Fn(f32) + Send>;
+
+/// Alerts when heap fragmentation exceeds a threshold
+///
+/// Fragmentation is `HeapStats::fragmentation_ratio`. Platforms without
+/// heap statistics never alert.
+pub struct HeapFragmentationMonitor {
+    threshold: f32,
+    callback: Arc<Mutex<FragmentationCallback>>,
+}
+
+impl HeapFragmentationMonitor {
+    /// Create a monitor calling `callback` when the ratio exceeds `threshold`
+    pub fn new(threshold: f32, callback: FragmentationCallback) -> Self {
+        Self {
+            threshold,
+            callback: Arc::new(Mutex::new(callback)),
+        }
+    }
+
+    /// Check the current fragmentation once, calling back if it is too high
+    pub fn check(&self) {
+        check_fragmentation(self.threshold, &self.callback);
+    }
+
+    /// Check every `interval_ms` on a background thread
+    ///
+    /// Set the returned flag to stop the thread; it notices within one
+    /// short polling step rather than a full interval.
+    ///
+    /// The thread is detached: it only holds its own clone of the callback,
+    /// so there is nothing to hand back once it exits and callers need not
+    /// wait for it.
+    pub fn run_background(&self, interval_ms: u32) -> Arc<AtomicBool> {
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop_flag);
+        let threshold = self.threshold;
+        let callback = Arc::clone(&self.callback);
+        let interval = Duration::from_millis(interval_ms as u64);
+        let _ = thread::Builder::new()
+            .name("heap-fragmentation".to_string())
+            .spawn(move || loop {
+                check_fragmentation(threshold, &callback);
+                if !crate::sleep_unless_stopped(interval, &thread_stop) {
+                    break;
+                }
+            });
+        stop_flag
+    }
+}
+
+fn check_fragmentation(threshold: f32, callback: &Mutex<FragmentationCallback>) {
+    let Some(stats) = get_heap_stats() else {
+        return;
+    };
+    let ratio = stats.fragmentation_ratio();
+    if ratio > threshold {
+        if let Ok(callback) = callback.lock() {
+            callback(ratio);
+        }
+    }
+}
+
+/// RAII guard that panics if heap usage grew while it was alive
+///
+/// Captures `get_heap_used()` on construction and compares on drop; growth
+/// beyond `tolerance_bytes` is reported as a leak. On platforms without heap
+/// introspection usage always reads 0, so the check never fires.
+///
+/// Note: glibc keeps small freed chunks in a per-thread cache that mallinfo()
+/// still counts as used, so on Linux the tolerance must cover those.
+#[derive(Debug)]
+pub struct HeapCanary {
+    baseline: i32,
+    tolerance_bytes: i32,
+    name: &'static str,
+}
+
+impl HeapCanary {
+    /// Arm a canary at the current heap usage
+    pub fn new(name: &'static str, tolerance_bytes: i32) -> HeapCanary {
+        HeapCanary {
+            baseline: get_heap_used(),
+            tolerance_bytes,
+            name,
+        }
+    }
+
+    /// Disarm the canary without checking for leaks
+    pub fn release(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for HeapCanary {
+    fn drop(&mut self) {
+        // Don't turn an unwinding panic into an abort
+        if std::thread::panicking() {
+            return;
+        }
+        let leaked = get_heap_used() - self.baseline;
+        if leaked > self.tolerance_bytes {
+            panic!(
+                "HeapCanary '{}' leaked {} bytes (tolerance {})",
+                self.name, leaked, self.tolerance_bytes
+            );
+        }
+    }
+}
+
+/// Run a block under a `HeapCanary`, panicking if it leaks
+///
+/// ```ignore
+/// heap_assert_no_leak! { tolerance: 64, name: "vec", {
+///     let v = vec![0u8; 1000];
+///     drop(v);
+/// } }
+/// ```
+#[macro_export]
+macro_rules! heap_assert_no_leak {
+    (tolerance: $tolerance:expr, name: $name:expr, $body:block) => {{
+        let _canary = $crate::heap::HeapCanary::new($name, $tolerance);
+        $body
+    }};
+}
+
+/// Heap usage at a point in time
+#[derive(Debug, Clone, Copy)]
+pub struct HeapSample {
+    /// When the sample was taken
+    pub timestamp: Instant,
+    /// Heap usage in bytes (`get_heap_used()`)
+    pub heap_used: i32,
+}
+
+/// Bounded history of heap usage samples
+#[derive(Debug, Clone)]
+pub struct HeapTimeSeries {
+    samples: Vec<HeapSample>,
+    max_samples: usize,
+}
+
+// Eighth-block characters, from 1/8 to full height
+const PLOT_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+impl HeapTimeSeries {
+    /// Create an empty series keeping at most `max_samples` samples
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            samples: Vec::with_capacity(max_samples),
+            max_samples,
+        }
+    }
+
+    /// Record the current heap usage, dropping the oldest sample when full
+    pub fn record(&mut self) {
+        self.push(HeapSample {
+            timestamp: Instant::now(),
+            heap_used: get_heap_used(),
+        });
+    }
+
+    /// Add a sample taken elsewhere, dropping the oldest sample when full
+    pub fn push(&mut self, sample: HeapSample) {
+        if self.max_samples == 0 {
+            return;
+        }
+        if self.samples.len() >= self.max_samples {
+            self.samples.remove(0);
+        }
+        self.samples.push(sample);
+    }
+
+    /// Recorded samples, oldest first
+    pub fn samples(&self) -> &[HeapSample] {
+        &self.samples
+    }
+
+    /// Draw the most recent samples (one per column) as a bar chart of
+    /// `width` x `height` characters, labelled with min/max/current values
+    pub fn plot_ascii(&self, width: u32, height: u32) -> String {
+        let start = self.samples.len().saturating_sub(width as usize);
+        let samples = &self.samples[start..];
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return String::new();
+        };
+        if height == 0 {
+            return String::new();
+        }
+
+        let min = samples.iter().map(|s| s.heap_used).min().unwrap_or(first.heap_used);
+        let max = samples.iter().map(|s| s.heap_used).max().unwrap_or(first.heap_used);
+        let range = (max - min) as i64;
+        let levels = height as i64 * 8;
+
+        // Bar height in eighths of a row; the minimum still shows as a thin bar
+        let bar_levels: Vec<i64> = samples
+            .iter()
+            .map(|s| {
+                if range == 0 {
+                    1
+                } else {
+                    1 + (s.heap_used - min) as i64 * (levels - 1) / range
+                }
+            })
+            .collect();
+
+        let max_label = max.to_string();
+        let min_label = min.to_string();
+        let label_width = max_label.len().max(min_label.len());
+
+        let mut out = String::new();
+        for row in 0..height {
+            let label = if row == 0 {
+                max_label.as_str()
+            } else if row == height - 1 {
+                min_label.as_str()
+            } else {
+                ""
+            };
+            out.push_str(&format!("{:>w$} |", label, w = label_width));
+
+            let row_base = (height - 1 - row) as i64 * 8;
+            for level in &bar_levels {
+                let fill = (level - row_base).clamp(0, 8);
+                out.push(if fill == 0 { ' ' } else { PLOT_BLOCKS[fill as usize - 1] });
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!("{:>w$} +{}\n", "", "-".repeat(samples.len()), w = label_width));
+        out.push_str(&format!("current: {} bytes\n", last.heap_used));
+        out
+    }
+
+    /// Heap growth in bytes per second (least-squares slope over all samples)
+    pub fn growth_rate_bytes_per_sec(&self) -> f32 {
+        let Some(first) = self.samples.first() else {
+            return 0.0;
+        };
+        let n = self.samples.len() as f64;
+        let points: Vec<(f64, f64)> = self
+            .samples
+            .iter()
+            .map(|s| {
+                let t = s.timestamp.duration_since(first.timestamp).as_secs_f64();
+                (t, s.heap_used as f64)
+            })
+            .collect();
+
+        let mean_t = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_h = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let cov: f64 = points.iter().map(|p| (p.0 - mean_t) * (p.1 - mean_h)).sum();
+        let var: f64 = points.iter().map(|p| (p.0 - mean_t) * (p.0 - mean_t)).sum();
+        if var == 0.0 {
+            return 0.0;
+        }
+        (cov / var) as f32
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Uses NuttX's mallinfo() for heap statistics.
 
-use super::HeapStats;
+use super::{HeapStats, HEAP_PEAK};
+use std::sync::atomic::Ordering;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
@@ -21,7 +22,9 @@ extern "C" {
 /// Get current heap usage in bytes
 pub fn get_heap_used() -> i32 {
     // NuttX's fordblks tracks used space
-    unsafe { mallinfo().fordblks }
+    let used = unsafe { mallinfo().fordblks };
+    HEAP_PEAK.fetch_max(used, Ordering::Relaxed);
+    used
 }
 
 /// Get detailed heap statistics
@@ -0,0 +1,111 @@
+//! Linux I2C implementation using i2c-dev
+//!
+//! Plain reads and writes go through read()/write() after selecting the
+//! device with I2C_SLAVE; combined transfers use a single I2C_RDWR ioctl.
+
+use super::{I2cBus, I2cError, I2cResult};
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+
+// ioctl requests (from linux/i2c-dev.h)
+const I2C_SLAVE: libc::c_ulong = 0x0703;
+const I2C_RDWR: libc::c_ulong = 0x0707;
+
+// i2c_msg flags (from linux/i2c.h)
+const I2C_M_RD: u16 = 0x0001;
+
+/// struct i2c_msg
+#[repr(C)]
+struct I2cMsg {
+    addr: u16,
+    flags: u16,
+    len: u16,
+    buf: *mut u8,
+}
+
+/// struct i2c_rdwr_ioctl_data
+#[repr(C)]
+struct I2cRdwrIoctlData {
+    msgs: *mut I2cMsg,
+    nmsgs: u32,
+}
+
+/// Map an I/O error from a bus access to an I2C error
+fn map_io_error(e: io::Error) -> I2cError {
+    match e.raw_os_error() {
+        Some(libc::ENOENT) | Some(libc::ENODEV) => I2cError::NotFound,
+        Some(libc::EACCES) | Some(libc::EPERM) => I2cError::AccessDenied,
+        // No ACK from the device
+        Some(libc::ENXIO) | Some(libc::EREMOTEIO) => I2cError::TransferFailed,
+        Some(libc::EIO) | Some(libc::ETIMEDOUT) | Some(libc::EAGAIN) => I2cError::BusError,
+        Some(errno) => I2cError::SystemError(errno),
+        None => I2cError::SystemError(-1),
+    }
+}
+
+impl I2cBus {
+    /// Open /dev/i2c-`bus`
+    pub fn open(bus: u8) -> I2cResult<I2cBus> {
+        let fd = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/i2c-{}", bus))
+            .map_err(map_io_error)?;
+        Ok(I2cBus { fd })
+    }
+
+    fn set_address(&self, addr: u8) -> I2cResult<()> {
+        let ret = unsafe { libc::ioctl(self.fd.as_raw_fd(), I2C_SLAVE, addr as libc::c_ulong) };
+        if ret < 0 {
+            return Err(map_io_error(io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    /// Write `data` to the device at `addr`
+    pub fn write(&self, addr: u8, data: &[u8]) -> I2cResult<()> {
+        self.set_address(addr)?;
+        (&self.fd).write_all(data).map_err(map_io_error)
+    }
+
+    /// Read `buf.len()` bytes from the device at `addr`
+    pub fn read(&self, addr: u8, buf: &mut [u8]) -> I2cResult<()> {
+        self.set_address(addr)?;
+        (&self.fd).read_exact(buf).map_err(map_io_error)
+    }
+
+    /// Write `cmd` then read into `buf` with a repeated start, as one transfer
+    pub fn write_read(&self, addr: u8, cmd: &[u8], buf: &mut [u8]) -> I2cResult<()> {
+        if cmd.len() > u16::MAX as usize || buf.len() > u16::MAX as usize {
+            return Err(I2cError::SystemError(libc::EINVAL));
+        }
+
+        let mut msgs = [
+            I2cMsg {
+                addr: addr as u16,
+                flags: 0,
+                len: cmd.len() as u16,
+                // The kernel only reads from write buffers
+                buf: cmd.as_ptr() as *mut u8,
+            },
+            I2cMsg {
+                addr: addr as u16,
+                flags: I2C_M_RD,
+                len: buf.len() as u16,
+                buf: buf.as_mut_ptr(),
+            },
+        ];
+        let mut data = I2cRdwrIoctlData {
+            msgs: msgs.as_mut_ptr(),
+            nmsgs: msgs.len() as u32,
+        };
+
+        let fd = self.fd.as_raw_fd();
+        let ret = unsafe { libc::ioctl(fd, I2C_RDWR, &mut data as *mut I2cRdwrIoctlData) };
+        if ret < 0 {
+            return Err(map_io_error(io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+}
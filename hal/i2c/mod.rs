@@ -0,0 +1,95 @@
+//! I2C HAL
+//!
+//! Provides I2C bus master access for sensors and other peripherals.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - Linux: Uses the i2c-dev interface (/dev/i2c-N)
+//! - NuttX: Uses the I2C character driver (/dev/i2cN) with I2CIOC_TRANSFER
+
+// Platform-specific implementations
+
+// NuttX uses the I2C character driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Linux uses i2c-dev
+#[cfg(feature = "platform-linux")]
+mod linux;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::fs::File;
+
+/// I2C operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum I2cError {
+    /// Bus device not found
+    NotFound,
+    /// Permission denied opening the bus
+    AccessDenied,
+    /// Transfer not acknowledged by the device
+    TransferFailed,
+    /// Bus error (arbitration lost, timeout)
+    BusError,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for I2cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            I2cError::NotFound => write!(f, "I2C bus not found"),
+            I2cError::AccessDenied => write!(f, "Access denied"),
+            I2cError::TransferFailed => write!(f, "Transfer failed"),
+            I2cError::BusError => write!(f, "Bus error"),
+            I2cError::NotSupported => write!(f, "Not supported on this platform"),
+            I2cError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl I2cError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            I2cError::NotFound if cfg!(feature = "platform-linux") => {
+                Some("load the i2c-dev module and check /dev/i2c-N exists")
+            }
+            I2cError::AccessDenied if cfg!(feature = "platform-linux") => {
+                Some("add the user to the i2c group")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for I2C operations
+pub type I2cResult<T> = Result<T, I2cError>;
+
+/// An open I2C bus (master)
+///
+/// Devices are addressed per transfer with their 7-bit address.
+#[derive(Debug)]
+pub struct I2cBus {
+    #[cfg_attr(not(any(feature = "platform-linux", feature = "platform-nuttx")), allow(dead_code))]
+    fd: File,
+}
+
+impl I2cBus {
+    /// Wrap an already open bus device, e.g. a mock in tests
+    #[doc(hidden)] // pub for tests
+    pub fn from_file(fd: File) -> I2cBus {
+        I2cBus { fd }
+    }
+}
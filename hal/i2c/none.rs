@@ -0,0 +1,25 @@
+//! I2C HAL stub for unsupported platforms
+
+use super::{I2cBus, I2cError, I2cResult};
+
+impl I2cBus {
+    /// Open an I2C bus (stub: returns NotSupported)
+    pub fn open(_bus: u8) -> I2cResult<I2cBus> {
+        Err(I2cError::NotSupported)
+    }
+
+    /// Write to a device (stub: returns NotSupported)
+    pub fn write(&self, _addr: u8, _data: &[u8]) -> I2cResult<()> {
+        Err(I2cError::NotSupported)
+    }
+
+    /// Read from a device (stub: returns NotSupported)
+    pub fn read(&self, _addr: u8, _buf: &mut [u8]) -> I2cResult<()> {
+        Err(I2cError::NotSupported)
+    }
+
+    /// Combined write/read transfer (stub: returns NotSupported)
+    pub fn write_read(&self, _addr: u8, _cmd: &[u8], _buf: &mut [u8]) -> I2cResult<()> {
+        Err(I2cError::NotSupported)
+    }
+}
@@ -0,0 +1,106 @@
+//! I2C HAL for NuttX
+//!
+//! Uses the I2C character driver (CONFIG_I2C_DRIVER). Every operation is a
+//! single I2CIOC_TRANSFER ioctl, so write_read uses a repeated start.
+
+use super::{I2cBus, I2cError, I2cResult};
+use std::fs::OpenOptions;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+// I2C ioctl commands (from nuttx/i2c/i2c_master.h)
+// _I2CIOC(nr) = _IOC(_I2CBASE, nr), _I2CBASE = 0x2100
+const I2CIOC_TRANSFER: i32 = 0x2101;
+
+// i2c_msg_s flags
+const I2C_M_READ: u16 = 0x0001;
+
+/// Bus frequency used for all transfers
+const I2C_FREQUENCY: u32 = 100_000;
+
+/// struct i2c_msg_s
+#[repr(C)]
+struct I2cMsgS {
+    frequency: u32,
+    addr: u16,
+    flags: u16,
+    buffer: *mut u8,
+    length: isize,
+}
+
+/// struct i2c_transfer_s
+#[repr(C)]
+struct I2cTransferS {
+    msgv: *mut I2cMsgS,
+    msgc: usize,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to an I2C error
+fn map_errno(errno: i32) -> I2cError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => I2cError::NotFound,
+        libc::EACCES | libc::EPERM => I2cError::AccessDenied,
+        libc::ENXIO => I2cError::TransferFailed,
+        libc::EIO | libc::ETIMEDOUT | libc::EAGAIN => I2cError::BusError,
+        _ => I2cError::SystemError(errno),
+    }
+}
+
+impl I2cBus {
+    /// Open /dev/i2c`bus`
+    pub fn open(bus: u8) -> I2cResult<I2cBus> {
+        let fd = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/i2c{}", bus))
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+        Ok(I2cBus { fd })
+    }
+
+    fn transfer(&self, msgs: &mut [I2cMsgS]) -> I2cResult<()> {
+        let mut xfer = I2cTransferS {
+            msgv: msgs.as_mut_ptr(),
+            msgc: msgs.len(),
+        };
+        let fd = self.fd.as_raw_fd();
+        let ret = unsafe { ioctl(fd, I2CIOC_TRANSFER, &mut xfer as *mut I2cTransferS) };
+        if ret < 0 {
+            return Err(map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0)));
+        }
+        Ok(())
+    }
+
+    fn msg(addr: u8, flags: u16, buffer: *mut u8, length: usize) -> I2cMsgS {
+        I2cMsgS {
+            frequency: I2C_FREQUENCY,
+            addr: addr as u16,
+            flags,
+            buffer,
+            length: length as isize,
+        }
+    }
+
+    /// Write `data` to the device at `addr`
+    pub fn write(&self, addr: u8, data: &[u8]) -> I2cResult<()> {
+        // The driver only reads from write buffers
+        self.transfer(&mut [Self::msg(addr, 0, data.as_ptr() as *mut u8, data.len())])
+    }
+
+    /// Read `buf.len()` bytes from the device at `addr`
+    pub fn read(&self, addr: u8, buf: &mut [u8]) -> I2cResult<()> {
+        self.transfer(&mut [Self::msg(addr, I2C_M_READ, buf.as_mut_ptr(), buf.len())])
+    }
+
+    /// Write `cmd` then read into `buf` with a repeated start, as one transfer
+    pub fn write_read(&self, addr: u8, cmd: &[u8], buf: &mut [u8]) -> I2cResult<()> {
+        self.transfer(&mut [
+            Self::msg(addr, 0, cmd.as_ptr() as *mut u8, cmd.len()),
+            Self::msg(addr, I2C_M_READ, buf.as_mut_ptr(), buf.len()),
+        ])
+    }
+}
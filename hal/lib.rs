@@ -15,7 +15,10 @@
 pub mod heap;
 
 #[cfg(feature = "heap")]
-pub use heap::{get_heap_stats, get_heap_used};
+pub use heap::{
+    get_heap_peak, get_heap_stats, get_heap_used, reset_heap_peak, HeapCanary,
+    HeapFragmentationMonitor, HeapTimeSeries,
+};
 
 #[cfg(feature = "ble")]
 pub mod ble;
@@ -25,3 +28,86 @@ pub mod wifi;
 
 #[cfg(feature = "camera")]
 pub mod camera;
+
+#[cfg(feature = "gpio")]
+pub mod gpio;
+
+#[cfg(feature = "i2c")]
+pub mod i2c;
+
+#[cfg(feature = "uart")]
+pub mod uart;
+
+#[cfg(feature = "watchdog")]
+pub mod watchdog;
+
+#[cfg(feature = "rtc")]
+pub mod rtc;
+
+#[cfg(feature = "adc")]
+pub mod adc;
+
+#[cfg(feature = "nvs")]
+pub mod nvs;
+
+#[cfg(feature = "temp")]
+pub mod temp;
+
+#[cfg(feature = "pwm")]
+pub mod pwm;
+
+#[cfg(feature = "crypto")]
+pub mod crypto;
+
+#[cfg(feature = "display")]
+pub mod display;
+
+pub mod security;
+
+/// Description of an errno value (either sign), e.g. "Invalid argument"
+///
+/// Returns "unknown" for values the C library has no message for.
+#[cfg(all(
+    any(feature = "platform-linux", feature = "platform-nuttx"),
+    any(feature = "camera", feature = "wifi")
+))]
+pub(crate) fn errno_to_str(e: i32) -> &'static str {
+    // Known errnos point into the C library's constant message table; the
+    // "Unknown error N" fallback may live in a reused buffer, so skip it
+    let msg = unsafe { std::ffi::CStr::from_ptr(libc::strerror(e.wrapping_abs())) };
+    match msg.to_str() {
+        Ok(msg) if !msg.starts_with("Unknown error") => msg,
+        _ => "unknown",
+    }
+}
+
+/// Sleep for `duration`, returning false early once `stop` gets set
+///
+/// Background threads with long periods sleep through this so that
+/// stopping them only waits one short polling step.
+#[cfg(any(
+    feature = "wifi",
+    feature = "heap",
+    all(feature = "ble", feature = "platform-linux")
+))]
+pub(crate) fn sleep_unless_stopped(
+    duration: std::time::Duration,
+    stop: &std::sync::atomic::AtomicBool,
+) -> bool {
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    const STOP_POLL: Duration = Duration::from_millis(50);
+    let deadline = Instant::now() + duration;
+
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return true;
+        }
+        std::thread::sleep(remaining.min(STOP_POLL));
+    }
+}
@@ -0,0 +1,168 @@
+//! Linux NVS implementation
+//!
+//! Each namespace is a flat JSON object of string values stored in
+//! /tmp/rustcam_nvs_<name>.json. Updates are written to a temporary file
+//! and renamed into place so a crash never leaves a half-written file.
+
+use super::{is_valid_name, NvsError, NvsNamespace, NvsResult};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::iter::Peekable;
+use std::str::Chars;
+
+fn map_io_error(e: io::Error) -> NvsError {
+    NvsError::SystemError(e.raw_os_error().unwrap_or(-1))
+}
+
+fn namespace_path(ns: &NvsNamespace) -> String {
+    format!("/tmp/rustcam_nvs_{}.json", ns.name())
+}
+
+/// Load all entries of a namespace (empty if the file does not exist)
+fn load(ns: &NvsNamespace) -> NvsResult<BTreeMap<String, String>> {
+    match fs::read_to_string(namespace_path(ns)) {
+        Ok(text) => parse_object(&text).ok_or(NvsError::Corrupted),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(map_io_error(e)),
+    }
+}
+
+/// Replace the namespace file with the given entries
+fn store(ns: &NvsNamespace, entries: &BTreeMap<String, String>) -> NvsResult<()> {
+    let mut text = String::from("{\n");
+    for (i, (key, value)) in entries.iter().enumerate() {
+        let separator = if i + 1 < entries.len() { "," } else { "" };
+        let _ = writeln!(text, "  {}: {}{}", json_string(key), json_string(value), separator);
+    }
+    text.push_str("}\n");
+
+    let path = namespace_path(ns);
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, text).map_err(map_io_error)?;
+    fs::rename(&tmp_path, &path).map_err(map_io_error)
+}
+
+/// Quote and escape a string for JSON
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+/// Parse a JSON string, with the opening quote not yet consumed
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    if chars.next()? != '"' {
+        return None;
+    }
+    let mut out = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(out),
+            '\\' => match chars.next()? {
+                '"' => out.push('"'),
+                '\\' => out.push('\\'),
+                '/' => out.push('/'),
+                'b' => out.push('\u{8}'),
+                'f' => out.push('\u{c}'),
+                'n' => out.push('\n'),
+                'r' => out.push('\r'),
+                't' => out.push('\t'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next()).collect();
+                    let code = u32::from_str_radix(&hex, 16).ok()?;
+                    // Surrogate pairs are never written by `json_string`
+                    out.push(char::from_u32(code)?);
+                }
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+}
+
+/// Parse a flat JSON object whose values are all strings
+fn parse_object(text: &str) -> Option<BTreeMap<String, String>> {
+    let mut entries = BTreeMap::new();
+    let mut chars = text.chars().peekable();
+
+    skip_whitespace(&mut chars);
+    if chars.next()? != '{' {
+        return None;
+    }
+    skip_whitespace(&mut chars);
+    if chars.next_if_eq(&'}').is_none() {
+        loop {
+            skip_whitespace(&mut chars);
+            let key = parse_string(&mut chars)?;
+            skip_whitespace(&mut chars);
+            if chars.next()? != ':' {
+                return None;
+            }
+            skip_whitespace(&mut chars);
+            let value = parse_string(&mut chars)?;
+            entries.insert(key, value);
+            skip_whitespace(&mut chars);
+            match chars.next()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+    }
+
+    skip_whitespace(&mut chars);
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(entries)
+}
+
+/// Store a string
+pub fn nvs_set_str(ns: &NvsNamespace, key: &str, value: &str) -> NvsResult<()> {
+    if !is_valid_name(key) {
+        return Err(NvsError::InvalidName);
+    }
+    let mut entries = load(ns)?;
+    entries.insert(key.to_string(), value.to_string());
+    store(ns, &entries)
+}
+
+/// Load a string, or `None` if the key is not set
+pub fn nvs_get_str(ns: &NvsNamespace, key: &str) -> NvsResult<Option<String>> {
+    if !is_valid_name(key) {
+        return Err(NvsError::InvalidName);
+    }
+    Ok(load(ns)?.remove(key))
+}
+
+/// Remove a key (no-op if it is not set)
+pub fn nvs_delete(ns: &NvsNamespace, key: &str) -> NvsResult<()> {
+    if !is_valid_name(key) {
+        return Err(NvsError::InvalidName);
+    }
+    let mut entries = load(ns)?;
+    if entries.remove(key).is_some() {
+        store(ns, &entries)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Non-volatile key-value storage HAL
+//!
+//! Persists small settings (WiFi credentials, calibration data) across
+//! reboots, grouped into namespaces.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the system settings storage (`settings_*_str` API)
+//! - Linux: Uses one JSON file per namespace under /tmp
+
+// Platform-specific implementations
+
+// NuttX uses the settings storage
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+#[cfg(feature = "platform-nuttx")]
+pub use nuttx::*;
+
+// Linux uses JSON files
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::*;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+pub use none::*;
+
+use core::fmt;
+
+/// NVS operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NvsError {
+    /// Namespace or key name is empty or contains invalid characters
+    InvalidName,
+    /// Stored data could not be parsed
+    Corrupted,
+    /// Stored value has a different type than requested
+    TypeMismatch,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for NvsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NvsError::InvalidName => write!(f, "Invalid namespace or key name"),
+            NvsError::Corrupted => write!(f, "Storage corrupted"),
+            NvsError::TypeMismatch => write!(f, "Type mismatch"),
+            NvsError::NotSupported => write!(f, "Not supported on this platform"),
+            NvsError::SystemError(e) => write!(f, "System error: {}", e),
+        }
+    }
+}
+
+/// Result type for NVS operations
+pub type NvsResult<T> = Result<T, NvsError>;
+
+/// A group of related keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NvsNamespace {
+    name: String,
+}
+
+/// Names may only use ASCII letters, digits, '_' and '-', since they end
+/// up in file names and settings keys
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+impl NvsNamespace {
+    /// Create a namespace handle
+    pub fn new(name: &str) -> NvsResult<NvsNamespace> {
+        if !is_valid_name(name) {
+            return Err(NvsError::InvalidName);
+        }
+        Ok(NvsNamespace {
+            name: name.to_string(),
+        })
+    }
+
+    /// Namespace name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Store an unsigned integer (saved as its decimal string)
+pub fn nvs_set_u32(ns: &NvsNamespace, key: &str, v: u32) -> NvsResult<()> {
+    nvs_set_str(ns, key, &v.to_string())
+}
+
+/// Load an unsigned integer, or `None` if the key is not set
+pub fn nvs_get_u32(ns: &NvsNamespace, key: &str) -> NvsResult<Option<u32>> {
+    match nvs_get_str(ns, key)? {
+        Some(value) => value.parse().map(Some).map_err(|_| NvsError::TypeMismatch),
+        None => Ok(None),
+    }
+}
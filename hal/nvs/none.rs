@@ -0,0 +1,18 @@
+//! Stub NVS implementation for unsupported platforms
+
+use super::{NvsError, NvsNamespace, NvsResult};
+
+/// Store a string (stub: returns NotSupported)
+pub fn nvs_set_str(_ns: &NvsNamespace, _key: &str, _value: &str) -> NvsResult<()> {
+    Err(NvsError::NotSupported)
+}
+
+/// Load a string (stub: returns NotSupported)
+pub fn nvs_get_str(_ns: &NvsNamespace, _key: &str) -> NvsResult<Option<String>> {
+    Err(NvsError::NotSupported)
+}
+
+/// Remove a key (stub: returns NotSupported)
+pub fn nvs_delete(_ns: &NvsNamespace, _key: &str) -> NvsResult<()> {
+    Err(NvsError::NotSupported)
+}
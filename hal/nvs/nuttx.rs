@@ -0,0 +1,74 @@
+//! NVS HAL for NuttX
+//!
+//! Uses the system settings storage. Keys are stored as `<namespace>.<key>`.
+//! The storage has no way to remove a key, so deleting stores an empty
+//! string and empty values read back as unset.
+
+use super::{is_valid_name, NvsError, NvsNamespace, NvsResult};
+use std::ffi::{CStr, CString};
+
+/// Largest value read back, including the terminator
+const SETTINGS_VALUE_SIZE: usize = 256;
+
+// Settings storage string accessors (return negated errno on failure)
+extern "C" {
+    fn settings_set_str(key: *const libc::c_char, value: *const libc::c_char) -> libc::c_int;
+    fn settings_get_str(
+        key: *const libc::c_char,
+        buffer: *mut libc::c_char,
+        size: libc::size_t,
+    ) -> libc::c_int;
+}
+
+/// Build the settings key for a namespaced key
+fn settings_key(ns: &NvsNamespace, key: &str) -> NvsResult<CString> {
+    if !is_valid_name(key) {
+        return Err(NvsError::InvalidName);
+    }
+    CString::new(format!("{}.{}", ns.name(), key)).map_err(|_| NvsError::InvalidName)
+}
+
+/// Store a string
+pub fn nvs_set_str(ns: &NvsNamespace, key: &str, value: &str) -> NvsResult<()> {
+    let key = settings_key(ns, key)?;
+    // Interior NULs cannot be stored in a C string
+    let value = CString::new(value).map_err(|_| NvsError::TypeMismatch)?;
+    let ret = unsafe { settings_set_str(key.as_ptr(), value.as_ptr()) };
+    if ret < 0 {
+        return Err(NvsError::SystemError(-ret));
+    }
+    Ok(())
+}
+
+/// Load a string, or `None` if the key is not set
+pub fn nvs_get_str(ns: &NvsNamespace, key: &str) -> NvsResult<Option<String>> {
+    let key = settings_key(ns, key)?;
+    let mut buffer = [0 as libc::c_char; SETTINGS_VALUE_SIZE];
+    let ret = unsafe { settings_get_str(key.as_ptr(), buffer.as_mut_ptr(), buffer.len()) };
+    if ret == -libc::ENOENT {
+        return Ok(None);
+    }
+    if ret < 0 {
+        return Err(NvsError::SystemError(-ret));
+    }
+
+    // Guarantee termination even if the driver filled the whole buffer
+    buffer[SETTINGS_VALUE_SIZE - 1] = 0;
+    let value = unsafe { CStr::from_ptr(buffer.as_ptr()) }
+        .to_str()
+        .map_err(|_| NvsError::Corrupted)?;
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(value.to_string()))
+}
+
+/// Remove a key by storing an empty value
+pub fn nvs_delete(ns: &NvsNamespace, key: &str) -> NvsResult<()> {
+    let key = settings_key(ns, key)?;
+    let ret = unsafe { settings_set_str(key.as_ptr(), c"".as_ptr()) };
+    if ret < 0 && ret != -libc::ENOENT {
+        return Err(NvsError::SystemError(-ret));
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Linux PWM implementation using the sysfs interface
+//!
+//! Channels are exported through /sys/class/pwm/pwmchipN/export and
+//! configured through the per-channel period, duty_cycle and enable files.
+
+use super::{PwmChannel, PwmError, PwmResult, DEFAULT_PERIOD_NS};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+const SYSFS_PWM: &str = "/sys/class/pwm";
+
+/// PWM class directory set with `pwm_set_sysfs_path`
+static SYSFS_PWM_PATH: Mutex<Option<String>> = Mutex::new(None);
+
+/// Use `path` instead of /sys/class/pwm
+///
+/// Useful for a chroot or container with sysfs mounted elsewhere. Set it
+/// before opening channels; an empty path restores the default.
+pub fn pwm_set_sysfs_path(path: &str) {
+    *SYSFS_PWM_PATH.lock().unwrap_or_else(|e| e.into_inner()) =
+        (!path.is_empty()).then(|| path.to_string());
+}
+
+/// Path of the chip directory, e.g. /sys/class/pwm/pwmchip0
+fn chip_path(chip: u8) -> String {
+    let sysfs = SYSFS_PWM_PATH.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    format!("{}/pwmchip{}", sysfs.as_deref().unwrap_or(SYSFS_PWM), chip)
+}
+
+// udev may need a moment to fix up permissions of a freshly exported channel
+const EXPORT_RETRIES: u32 = 10;
+const EXPORT_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+/// Map an I/O error from a sysfs access to a PWM error
+fn map_io_error(e: io::Error) -> PwmError {
+    match e.kind() {
+        io::ErrorKind::NotFound => PwmError::NotFound,
+        io::ErrorKind::PermissionDenied => PwmError::PermissionDenied,
+        _ => PwmError::SystemError(e.raw_os_error().unwrap_or(-1)),
+    }
+}
+
+impl PwmChannel {
+    pub(super) fn open(chip: u8, channel: u8) -> PwmResult<PwmChannel> {
+        if let Err(e) = fs::write(format!("{}/export", chip_path(chip)), channel.to_string()) {
+            match e.raw_os_error() {
+                // Already exported (e.g. by a previous run)
+                Some(libc::EBUSY) => {}
+                // The kernel rejects channels the chip does not have
+                Some(libc::EINVAL) => return Err(PwmError::NotFound),
+                _ => return Err(map_io_error(e)),
+            }
+        }
+
+        let pwm = PwmChannel {
+            chip,
+            channel,
+            period_ns: DEFAULT_PERIOD_NS,
+            duty: 0.0,
+            fd: None,
+        };
+
+        // Writable once udev has processed the export
+        let mut attempt = 0;
+        loop {
+            match pwm.write_attr("enable", "0") {
+                Ok(()) => break,
+                Err(_) if attempt < EXPORT_RETRIES => {
+                    attempt += 1;
+                    thread::sleep(EXPORT_RETRY_DELAY);
+                }
+                // Dropping `pwm` unexports the channel
+                Err(e) => return Err(e),
+            }
+        }
+
+        pwm.apply()?;
+        Ok(pwm)
+    }
+
+    /// Write a sysfs attribute of this channel
+    fn write_attr(&self, attr: &str, value: &str) -> PwmResult<()> {
+        let path = format!("{}/pwm{}/{}", chip_path(self.chip), self.channel, attr);
+        fs::write(Path::new(&path), value).map_err(map_io_error)
+    }
+
+    /// Write the current period and duty cycle to the hardware
+    pub(super) fn apply(&self) -> PwmResult<()> {
+        let period = self.period_ns.to_string();
+        let duty = self.duty_cycle_ns().to_string();
+        // The kernel rejects a duty cycle longer than the period, so when the
+        // period shrinks below the old duty cycle, the duty has to go first
+        if self.write_attr("period", &period).is_err() {
+            self.write_attr("duty_cycle", &duty)?;
+            return self.write_attr("period", &period);
+        }
+        self.write_attr("duty_cycle", &duty)
+    }
+
+    /// Start driving the output
+    pub fn enable(&mut self) -> PwmResult<()> {
+        self.write_attr("enable", "1")
+    }
+
+    /// Stop driving the output
+    pub fn disable(&mut self) -> PwmResult<()> {
+        self.write_attr("enable", "0")
+    }
+}
+
+impl Drop for PwmChannel {
+    fn drop(&mut self) {
+        let _ = self.disable();
+        let _ = fs::write(
+            format!("{}/unexport", chip_path(self.chip)),
+            self.channel.to_string(),
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! PWM output HAL
+//!
+//! Drives PWM outputs for buzzer tones, servo control and LED dimming.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the PWM driver (/dev/pwmN) via PWMIOC_* ioctls
+//! - Linux: Uses the sysfs interface (/sys/class/pwm/pwmchipN/pwmM)
+
+// Platform-specific implementations
+
+// NuttX uses the PWM upper-half driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Linux uses sysfs
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::pwm_set_sysfs_path;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::fs::File;
+
+/// Period used until `set_frequency()` is called (1 kHz)
+#[cfg_attr(not(any(feature = "platform-linux", feature = "platform-nuttx")), allow(dead_code))]
+const DEFAULT_PERIOD_NS: u64 = 1_000_000;
+
+/// PWM operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmError {
+    /// PWM chip or channel not found
+    NotFound,
+    /// Permission denied
+    PermissionDenied,
+    /// Frequency is zero or too high to represent
+    InvalidFrequency,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for PwmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PwmError::NotFound => write!(f, "PWM channel not found"),
+            PwmError::PermissionDenied => write!(f, "Permission denied"),
+            PwmError::InvalidFrequency => write!(f, "Invalid frequency"),
+            PwmError::NotSupported => write!(f, "Not supported on this platform"),
+            PwmError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl PwmError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            PwmError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("run as root or grant access to /sys/class/pwm")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for PWM operations
+pub type PwmResult<T> = Result<T, PwmError>;
+
+/// An opened PWM output
+///
+/// Starts disabled at 1 kHz with 0% duty cycle. The output is disabled
+/// and released when dropped.
+#[derive(Debug)]
+pub struct PwmChannel {
+    chip: u8,
+    channel: u8,
+    period_ns: u64,
+    /// Fraction of the period the output is high (0.0 to 1.0)
+    duty: f32,
+    #[cfg_attr(not(feature = "platform-nuttx"), allow(dead_code))]
+    fd: Option<File>,
+}
+
+/// Open a PWM channel
+pub fn pwm_open(chip: u8, channel: u8) -> PwmResult<PwmChannel> {
+    PwmChannel::open(chip, channel)
+}
+
+impl PwmChannel {
+    /// PWM chip number
+    pub fn chip(&self) -> u8 {
+        self.chip
+    }
+
+    /// Channel number within the chip
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Current period in nanoseconds
+    pub fn period_ns(&self) -> u64 {
+        self.period_ns
+    }
+
+    /// Current high time in nanoseconds
+    pub fn duty_cycle_ns(&self) -> u64 {
+        (self.period_ns as f64 * self.duty as f64).round() as u64
+    }
+
+    /// Set the output frequency, keeping the duty cycle fraction
+    pub fn set_frequency(&mut self, hz: u32) -> PwmResult<()> {
+        if hz == 0 || hz > 1_000_000_000 {
+            return Err(PwmError::InvalidFrequency);
+        }
+        self.period_ns = 1_000_000_000 / hz as u64;
+        self.apply()
+    }
+
+    /// Set the duty cycle as a fraction of the period, clamped to [0.0, 1.0]
+    pub fn set_duty_cycle(&mut self, pct: f32) -> PwmResult<()> {
+        // NaN compares false against both bounds, so treat it as off
+        self.duty = if pct.is_nan() { 0.0 } else { pct.clamp(0.0, 1.0) };
+        self.apply()
+    }
+}
@@ -0,0 +1,23 @@
+//! Stub PWM implementation for unsupported platforms
+
+use super::{PwmChannel, PwmError, PwmResult};
+
+impl PwmChannel {
+    pub(super) fn open(_chip: u8, _channel: u8) -> PwmResult<PwmChannel> {
+        Err(PwmError::NotSupported)
+    }
+
+    pub(super) fn apply(&self) -> PwmResult<()> {
+        Err(PwmError::NotSupported)
+    }
+
+    /// Start driving the output (stub: returns NotSupported)
+    pub fn enable(&mut self) -> PwmResult<()> {
+        Err(PwmError::NotSupported)
+    }
+
+    /// Stop driving the output (stub: returns NotSupported)
+    pub fn disable(&mut self) -> PwmResult<()> {
+        Err(PwmError::NotSupported)
+    }
+}
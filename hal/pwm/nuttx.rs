@@ -0,0 +1,114 @@
+//! PWM HAL for NuttX
+//!
+//! Uses the PWM upper-half character driver. Assumes a build without
+//! CONFIG_PWM_MULTICHAN, where each /dev/pwmN drives a single output, so
+//! only channel 0 exists for each chip. The device is held open because the
+//! driver stops the output when the last handle is closed.
+
+use super::{PwmChannel, PwmError, PwmResult, DEFAULT_PERIOD_NS};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+// PWM ioctl commands (from nuttx/timers/pwm.h)
+// _PWMIOC(nr) = _IOC(_PWMIOCBASE, nr), _PWMIOCBASE = 0x0c00
+const PWMIOC_SETCHARACTERISTICS: i32 = 0x0c01;
+const PWMIOC_START: i32 = 0x0c03;
+const PWMIOC_STOP: i32 = 0x0c04;
+
+/// struct pwm_info_s (single channel)
+#[repr(C)]
+struct PwmInfo {
+    /// Frequency of the pulse train in Hz
+    frequency: u32,
+    /// High time as a ub16_t fraction of the period
+    duty: u32,
+    /// Pulse count with CONFIG_PWM_PULSECOUNT (0 = run continuously)
+    count: u32,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a PWM error
+fn map_errno(errno: i32) -> PwmError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => PwmError::NotFound,
+        libc::EPERM | libc::EACCES => PwmError::PermissionDenied,
+        _ => PwmError::SystemError(errno),
+    }
+}
+
+fn last_error() -> PwmError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+impl PwmChannel {
+    pub(super) fn open(chip: u8, channel: u8) -> PwmResult<PwmChannel> {
+        if channel != 0 {
+            return Err(PwmError::NotFound);
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/dev/pwm{}", chip))
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+
+        let pwm = PwmChannel {
+            chip,
+            channel,
+            period_ns: DEFAULT_PERIOD_NS,
+            duty: 0.0,
+            fd: Some(file),
+        };
+        pwm.apply()?;
+        Ok(pwm)
+    }
+
+    fn device(&self) -> PwmResult<&File> {
+        self.fd.as_ref().ok_or(PwmError::NotFound)
+    }
+
+    fn command(&self, request: i32, arg: libc::c_ulong) -> PwmResult<()> {
+        let fd = self.device()?.as_raw_fd();
+        if unsafe { ioctl(fd, request, arg) } < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    /// Write the current period and duty cycle to the hardware
+    ///
+    /// Takes effect immediately if the output is running.
+    pub(super) fn apply(&self) -> PwmResult<()> {
+        let info = PwmInfo {
+            frequency: (1_000_000_000 / self.period_ns.max(1)) as u32,
+            // 100% cannot be represented in ub16; use the largest fraction
+            duty: ((self.duty as f64 * 65536.0) as u32).min(0xFFFF),
+            count: 0,
+        };
+        let fd = self.device()?.as_raw_fd();
+        if unsafe { ioctl(fd, PWMIOC_SETCHARACTERISTICS, &info as *const PwmInfo) } < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    /// Start driving the output
+    pub fn enable(&mut self) -> PwmResult<()> {
+        self.command(PWMIOC_START, 0)
+    }
+
+    /// Stop driving the output
+    pub fn disable(&mut self) -> PwmResult<()> {
+        self.command(PWMIOC_STOP, 0)
+    }
+}
+
+impl Drop for PwmChannel {
+    fn drop(&mut self) {
+        let _ = self.disable();
+    }
+}
@@ -0,0 +1,43 @@
+//! Linux RTC implementation
+//!
+//! Reads and sets the system realtime clock rather than /dev/rtc0, so the
+//! time matches what the rest of the system sees.
+
+use super::{rtc_from_unix_timestamp, rtc_to_unix_timestamp, RtcError, RtcResult, RtcTime};
+use std::io;
+
+fn last_error() -> RtcError {
+    match io::Error::last_os_error().raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => RtcError::PermissionDenied,
+        Some(libc::EINVAL) => RtcError::InvalidTime,
+        Some(errno) => RtcError::SystemError(errno),
+        None => RtcError::SystemError(-1),
+    }
+}
+
+/// Get the current time (UTC)
+pub fn rtc_get_time() -> RtcResult<RtcTime> {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    if unsafe { libc::clock_gettime(libc::CLOCK_REALTIME, &mut ts) } < 0 {
+        return Err(last_error());
+    }
+    if ts.tv_sec < 0 {
+        return Err(RtcError::InvalidTime);
+    }
+    Ok(rtc_from_unix_timestamp(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+/// Set the current time (UTC); requires CAP_SYS_TIME
+pub fn rtc_set_time(t: &RtcTime) -> RtcResult<()> {
+    if !t.is_valid() {
+        return Err(RtcError::InvalidTime);
+    }
+    let ts = libc::timespec {
+        tv_sec: rtc_to_unix_timestamp(t) as libc::time_t,
+        tv_nsec: t.subsec_ns as libc::c_long,
+    };
+    if unsafe { libc::clock_settime(libc::CLOCK_REALTIME, &ts) } < 0 {
+        return Err(last_error());
+    }
+    Ok(())
+}
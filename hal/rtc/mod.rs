@@ -0,0 +1,190 @@
+//! Real-time clock HAL
+//!
+//! Provides wall-clock time for timestamps and scheduling.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the RTC driver (/dev/rtc0) via RTC_* ioctls
+//! - Linux: Uses the system realtime clock (CLOCK_REALTIME)
+
+// Platform-specific implementations
+
+// NuttX uses the RTC lower-half driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+#[cfg(feature = "platform-nuttx")]
+pub use nuttx::*;
+
+// Linux uses clock_gettime/clock_settime
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::*;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+pub use none::*;
+
+use core::fmt;
+
+/// RTC operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtcError {
+    /// RTC device not found
+    NotFound,
+    /// Time fields out of range
+    InvalidTime,
+    /// Permission denied (setting the clock usually requires root)
+    PermissionDenied,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for RtcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RtcError::NotFound => write!(f, "RTC device not found"),
+            RtcError::InvalidTime => write!(f, "Invalid time"),
+            RtcError::PermissionDenied => write!(f, "Permission denied"),
+            RtcError::NotSupported => write!(f, "Not supported on this platform"),
+            RtcError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl RtcError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            RtcError::NotFound => Some("check /dev/rtc0 exists"),
+            RtcError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("setting the clock needs root or CAP_SYS_TIME")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for RTC operations
+pub type RtcResult<T> = Result<T, RtcError>;
+
+/// Calendar time in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RtcTime {
+    /// Full year (e.g. 2024)
+    pub year: u16,
+    /// Month (1-12)
+    pub month: u8,
+    /// Day of month (1-31)
+    pub day: u8,
+    /// Hour (0-23)
+    pub hour: u8,
+    /// Minute (0-59)
+    pub min: u8,
+    /// Second (0-59)
+    pub sec: u8,
+    /// Nanoseconds within the second
+    pub subsec_ns: u32,
+}
+
+impl RtcTime {
+    /// Check that all fields are in range and the time is not before 1970
+    pub fn is_valid(&self) -> bool {
+        self.year >= 1970
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.min < 60
+            && self.sec < 60
+            && self.subsec_ns < 1_000_000_000
+    }
+}
+
+impl fmt::Display for RtcTime {
+    /// ISO 8601 in UTC with millisecond precision, e.g. `2024-02-29T12:34:56.789Z`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.min,
+            self.sec,
+            self.subsec_ns / 1_000_000
+        )
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+///
+/// Counts years from March so the leap day falls at the end of the year.
+fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Convert an RTC time to seconds since the Unix epoch
+///
+/// Times before 1970 saturate to 0.
+pub fn rtc_to_unix_timestamp(t: &RtcTime) -> u64 {
+    let days = days_from_civil(t.year, t.month, t.day);
+    let secs = days * 86_400 + t.hour as i64 * 3600 + t.min as i64 * 60 + t.sec as i64;
+    secs.max(0) as u64
+}
+
+/// Convert seconds since the Unix epoch to an RTC time (inverse of `days_from_civil`)
+#[cfg_attr(not(feature = "platform-linux"), allow(dead_code))]
+fn rtc_from_unix_timestamp(secs: u64, subsec_ns: u32) -> RtcTime {
+    let days = (secs / 86_400) as i64 + 719_468;
+    let secs_of_day = secs % 86_400;
+
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    RtcTime {
+        year: year as u16,
+        month: month as u8,
+        day: day as u8,
+        hour: (secs_of_day / 3600) as u8,
+        min: (secs_of_day / 60 % 60) as u8,
+        sec: (secs_of_day % 60) as u8,
+        subsec_ns,
+    }
+}
@@ -0,0 +1,13 @@
+//! Stub RTC implementation for unsupported platforms
+
+use super::{RtcError, RtcResult, RtcTime};
+
+/// Get the current time (stub: returns NotSupported)
+pub fn rtc_get_time() -> RtcResult<RtcTime> {
+    Err(RtcError::NotSupported)
+}
+
+/// Set the current time (stub: returns NotSupported)
+pub fn rtc_set_time(_t: &RtcTime) -> RtcResult<()> {
+    Err(RtcError::NotSupported)
+}
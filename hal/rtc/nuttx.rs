@@ -0,0 +1,107 @@
+//! RTC HAL for NuttX
+//!
+//! Uses the RTC lower-half character driver (/dev/rtc0).
+
+use super::{RtcError, RtcResult, RtcTime};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+const RTC_DEVICE: &str = "/dev/rtc0";
+
+// RTC ioctl commands (from nuttx/timers/rtc.h)
+// _RTCIOC(nr) = _IOC(_RTCBASE, nr), _RTCBASE = 0x1900
+const RTC_RD_TIME: i32 = 0x1901;
+const RTC_SET_TIME: i32 = 0x1902;
+
+/// struct rtc_time (same layout as struct tm)
+#[repr(C)]
+#[derive(Default)]
+struct NuttxRtcTime {
+    tm_sec: libc::c_int,
+    tm_min: libc::c_int,
+    tm_hour: libc::c_int,
+    tm_mday: libc::c_int,
+    /// Months since January (0-11)
+    tm_mon: libc::c_int,
+    /// Years since 1900
+    tm_year: libc::c_int,
+    tm_wday: libc::c_int,
+    tm_yday: libc::c_int,
+    tm_isdst: libc::c_int,
+    /// Only present with CONFIG_RTC_HIRES; always reserved so the driver
+    /// never writes past the end of the struct
+    tm_nsec: libc::c_long,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to an RTC error
+fn map_errno(errno: i32) -> RtcError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => RtcError::NotFound,
+        libc::EPERM | libc::EACCES => RtcError::PermissionDenied,
+        libc::EINVAL => RtcError::InvalidTime,
+        _ => RtcError::SystemError(errno),
+    }
+}
+
+fn last_error() -> RtcError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+fn open_rtc() -> RtcResult<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(RTC_DEVICE)
+        .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))
+}
+
+/// Get the current time (UTC)
+pub fn rtc_get_time() -> RtcResult<RtcTime> {
+    let file = open_rtc()?;
+    let mut tm = NuttxRtcTime::default();
+    if unsafe { ioctl(file.as_raw_fd(), RTC_RD_TIME, &mut tm as *mut NuttxRtcTime) } < 0 {
+        return Err(last_error());
+    }
+
+    let time = RtcTime {
+        year: (tm.tm_year + 1900) as u16,
+        month: (tm.tm_mon + 1) as u8,
+        day: tm.tm_mday as u8,
+        hour: tm.tm_hour as u8,
+        min: tm.tm_min as u8,
+        sec: tm.tm_sec as u8,
+        subsec_ns: tm.tm_nsec as u32,
+    };
+    if !time.is_valid() {
+        return Err(RtcError::InvalidTime);
+    }
+    Ok(time)
+}
+
+/// Set the current time (UTC)
+pub fn rtc_set_time(t: &RtcTime) -> RtcResult<()> {
+    if !t.is_valid() {
+        return Err(RtcError::InvalidTime);
+    }
+    let file = open_rtc()?;
+    let tm = NuttxRtcTime {
+        tm_sec: t.sec as libc::c_int,
+        tm_min: t.min as libc::c_int,
+        tm_hour: t.hour as libc::c_int,
+        tm_mday: t.day as libc::c_int,
+        tm_mon: t.month as libc::c_int - 1,
+        tm_year: t.year as libc::c_int - 1900,
+        tm_nsec: t.subsec_ns as libc::c_long,
+        ..Default::default()
+    };
+    if unsafe { ioctl(file.as_raw_fd(), RTC_SET_TIME, &tm as *const NuttxRtcTime) } < 0 {
+        return Err(last_error());
+    }
+    Ok(())
+}
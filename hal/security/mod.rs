@@ -0,0 +1,8 @@
+//! Handling of secrets in memory
+//!
+//! Passwords and keys should not outlive their use in freed heap or stack
+//! memory, where a heap dump or a later out-of-bounds read could find them.
+
+mod password_zeroize;
+
+pub use password_zeroize::{zeroize, ZeroOnDrop};
@@ -0,0 +1,63 @@
+//! Wiping secrets when they are dropped
+//!
+//! Plain writes to memory that is about to be freed are dead stores the
+//! compiler may remove, so every byte is cleared with a volatile write
+//! followed by a compiler fence.
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrite `bytes` with zeros in a way the optimizer keeps
+pub fn zeroize(bytes: &mut [u8]) {
+    for b in bytes.iter_mut() {
+        unsafe { core::ptr::write_volatile(b, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Byte buffer that is zeroed when dropped, e.g. for keys
+///
+/// Dereferences to the wrapped value. `Debug` does not show the contents.
+/// Only the final location is wiped: copies left behind by moves of a
+/// by-value buffer like `[u8; N]` are not, so box large secrets first.
+pub struct ZeroOnDrop<T: AsMut<[u8]>>(T);
+
+impl<T: AsMut<[u8]>> ZeroOnDrop<T> {
+    /// Wrap `secret`
+    pub fn new(secret: T) -> Self {
+        ZeroOnDrop(secret)
+    }
+}
+
+impl<T: AsMut<[u8]>> Deref for ZeroOnDrop<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: AsMut<[u8]>> DerefMut for ZeroOnDrop<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsMut<[u8]> + Clone> Clone for ZeroOnDrop<T> {
+    fn clone(&self) -> Self {
+        ZeroOnDrop(self.0.clone())
+    }
+}
+
+impl<T: AsMut<[u8]>> fmt::Debug for ZeroOnDrop<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZeroOnDrop(..)")
+    }
+}
+
+impl<T: AsMut<[u8]>> Drop for ZeroOnDrop<T> {
+    fn drop(&mut self) {
+        zeroize(self.0.as_mut());
+    }
+}
@@ -0,0 +1,53 @@
+//! Linux temperature implementation using thermal zones
+//!
+//! Zone 0 is normally the CPU package; zone 1, if present, is used as the
+//! ambient (board) temperature. Values are in millidegrees Celsius.
+
+use super::{TempError, TempResult};
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+
+const SYSFS_THERMAL: &str = "/sys/class/thermal";
+
+/// Thermal class directory set with `temp_set_thermal_path`
+static THERMAL_PATH: Mutex<Option<String>> = Mutex::new(None);
+
+/// Read thermal zones from `path` instead of /sys/class/thermal
+///
+/// Useful for a chroot or container with sysfs mounted elsewhere. An empty
+/// path restores the default.
+pub fn temp_set_thermal_path(path: &str) {
+    *THERMAL_PATH.lock().unwrap_or_else(|e| e.into_inner()) =
+        (!path.is_empty()).then(|| path.to_string());
+}
+
+/// Parse a thermal zone `temp` attribute into degrees Celsius
+fn parse_millidegrees(text: &str) -> TempResult<f32> {
+    text.trim()
+        .parse::<i32>()
+        .map(|millidegrees| millidegrees as f32 / 1000.0)
+        .map_err(|_| TempError::ReadFailed)
+}
+
+fn read_zone(zone: u32) -> TempResult<f32> {
+    let thermal = THERMAL_PATH.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let thermal = thermal.as_deref().unwrap_or(SYSFS_THERMAL);
+    let path = format!("{}/thermal_zone{}/temp", thermal, zone);
+    let text = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => TempError::NotFound,
+        // Some zones report an error while the sensor is powered down
+        _ => TempError::SystemError(e.raw_os_error().unwrap_or(-1)),
+    })?;
+    parse_millidegrees(&text)
+}
+
+/// Read the CPU temperature in degrees Celsius
+pub fn temp_read_cpu() -> TempResult<f32> {
+    read_zone(0)
+}
+
+/// Read the ambient temperature in degrees Celsius
+pub fn temp_read_ambient() -> TempResult<f32> {
+    read_zone(1)
+}
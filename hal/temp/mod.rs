@@ -0,0 +1,82 @@
+//! Temperature sensor HAL
+//!
+//! Reads CPU and ambient temperature for thermal monitoring.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: CPU sensor via /dev/temp0, ambient sensor on I2C bus 0
+//! - Linux: Uses the thermal sysfs interface (/sys/class/thermal)
+
+// Platform-specific implementations
+
+// NuttX uses the sensor driver and the I2C HAL
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+#[cfg(feature = "platform-nuttx")]
+pub use nuttx::*;
+
+// Linux uses thermal zones
+#[cfg(feature = "platform-linux")]
+mod linux;
+#[cfg(feature = "platform-linux")]
+pub use linux::*;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+pub use none::*;
+
+use core::fmt;
+
+/// CPU temperature above which the chip is considered to be throttling
+pub const THROTTLE_TEMP_C: f32 = 85.0;
+
+/// Temperature sensor errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempError {
+    /// Sensor not found
+    NotFound,
+    /// Sensor returned an invalid reading
+    ReadFailed,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for TempError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TempError::NotFound => write!(f, "Temperature sensor not found"),
+            TempError::ReadFailed => write!(f, "Temperature read failed"),
+            TempError::NotSupported => write!(f, "Not supported on this platform"),
+            TempError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl TempError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            TempError::NotFound if cfg!(feature = "platform-linux") => {
+                Some("no thermal zone found under /sys/class/thermal")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for temperature operations
+pub type TempResult<T> = Result<T, TempError>;
+
+/// Check whether the CPU is hot enough to be throttled
+pub fn temp_is_throttling() -> TempResult<bool> {
+    Ok(temp_read_cpu()? > THROTTLE_TEMP_C)
+}
@@ -0,0 +1,13 @@
+//! Stub temperature implementation for unsupported platforms
+
+use super::{TempError, TempResult};
+
+/// Read the CPU temperature (stub: returns NotSupported)
+pub fn temp_read_cpu() -> TempResult<f32> {
+    Err(TempError::NotSupported)
+}
+
+/// Read the ambient temperature (stub: returns NotSupported)
+pub fn temp_read_ambient() -> TempResult<f32> {
+    Err(TempError::NotSupported)
+}
@@ -0,0 +1,69 @@
+//! Temperature HAL for NuttX
+//!
+//! The CPU temperature comes from the internal sensor driver (/dev/temp0),
+//! which reports a b16_t (16.16 fixed-point) value in degrees Celsius.
+//! The ambient temperature is read from a TMP102-compatible sensor on
+//! I2C bus 0.
+
+use super::{TempError, TempResult};
+use crate::i2c::{I2cBus, I2cError};
+use std::fs::OpenOptions;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+const CPU_TEMP_DEVICE: &str = "/dev/temp0";
+
+// Sensor ioctl commands (from nuttx/sensors/ioctl.h)
+// _SNIOC(nr) = _IOC(_SNIOCBASE, nr), _SNIOCBASE = 0x0a00
+const SNIOC_READTEMP: i32 = 0x0a19;
+
+// Ambient sensor: TMP102 at its default address, temperature register,
+// 12-bit left-justified result with 0.0625 degrees per count
+const AMBIENT_I2C_BUS: u8 = 0;
+const AMBIENT_I2C_ADDR: u8 = 0x48;
+const AMBIENT_TEMP_REG: u8 = 0x00;
+const AMBIENT_DEGREES_PER_COUNT: f32 = 0.0625;
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a temperature error
+fn map_errno(errno: i32) -> TempError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => TempError::NotFound,
+        _ => TempError::SystemError(errno),
+    }
+}
+
+/// Read the CPU temperature in degrees Celsius
+pub fn temp_read_cpu() -> TempResult<f32> {
+    let file = OpenOptions::new()
+        .read(true)
+        .open(CPU_TEMP_DEVICE)
+        .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+
+    let mut temp_b16: i32 = 0;
+    if unsafe { ioctl(file.as_raw_fd(), SNIOC_READTEMP, &mut temp_b16 as *mut i32) } < 0 {
+        return Err(map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0)));
+    }
+    Ok(temp_b16 as f32 / 65536.0)
+}
+
+/// Read the ambient temperature in degrees Celsius
+pub fn temp_read_ambient() -> TempResult<f32> {
+    let map_i2c_error = |e: I2cError| match e {
+        I2cError::NotFound => TempError::NotFound,
+        _ => TempError::ReadFailed,
+    };
+
+    let bus = I2cBus::open(AMBIENT_I2C_BUS).map_err(map_i2c_error)?;
+    let mut raw = [0u8; 2];
+    bus.write_read(AMBIENT_I2C_ADDR, &[AMBIENT_TEMP_REG], &mut raw)
+        .map_err(map_i2c_error)?;
+
+    // Arithmetic shift keeps the sign of sub-zero readings
+    let counts = i16::from_be_bytes(raw) >> 4;
+    Ok(counts as f32 * AMBIENT_DEGREES_PER_COUNT)
+}
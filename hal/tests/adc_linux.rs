@@ -0,0 +1,78 @@
+//! Tests for the Linux ADC backend against a mock IIO device
+//!
+//! A temporary directory with in_voltageN_raw/scale files stands in for
+//! /sys/bus/iio/devices/iio:device0.
+//!
+//! ```text
+//! cargo test -p hal --features adc --test adc_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "adc"))]
+
+use hal::adc::*;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+/// The device path is global, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Mock IIO device selected for the ADC, removed on drop
+struct MockDevice(PathBuf);
+
+impl MockDevice {
+    fn new(name: &str) -> MockDevice {
+        let dir =
+            std::env::temp_dir().join(format!("rustcam-adc-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        adc_set_device_path(dir.to_str().unwrap());
+        MockDevice(dir)
+    }
+
+    fn set(&self, channel: u8, attr: &str, value: &str) {
+        let path = self.0.join(format!("in_voltage{}_{}", channel, attr));
+        fs::write(path, format!("{}\n", value)).unwrap();
+    }
+}
+
+impl Drop for MockDevice {
+    fn drop(&mut self) {
+        adc_set_device_path("");
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn test_read_millivolts() {
+    let _guard = serial();
+    let device = MockDevice::new("millivolts");
+    device.set(0, "raw", "2048");
+    device.set(0, "scale", "0.805664062");
+
+    let channel = adc_open(0).unwrap();
+    assert_eq!(channel.read_raw(), Ok(2048));
+    assert_eq!(channel.read_millivolts(3300), Ok(1650));
+    assert_eq!(channel.read_average_mv(4, 0, 3300), Ok(1650));
+    assert!((channel.read_scale_mv().unwrap() - 0.805_664).abs() < 1e-6);
+}
+
+#[test]
+fn test_missing_and_bad_channel() {
+    let _guard = serial();
+    let device = MockDevice::new("errors");
+    device.set(1, "raw", "not a number");
+
+    assert_eq!(adc_open(0), Err(AdcError::NotFound));
+    assert_eq!(adc_open(1).unwrap().read_raw(), Err(AdcError::ReadFailed));
+}
+
+#[test]
+fn test_raw_to_millivolts() {
+    assert_eq!(adc_raw_to_millivolts(0, 3300), 0);
+    assert_eq!(adc_raw_to_millivolts(2048, 3300), 1650);
+    assert_eq!(adc_raw_to_millivolts(4095, 3300), 3299);
+}
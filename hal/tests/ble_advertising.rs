@@ -0,0 +1,53 @@
+//! Advertising payload encoding
+
+#![cfg(all(test, feature = "ble"))]
+
+use hal::ble::*;
+
+#[test]
+fn test_flags_and_complete_name() {
+    let data = AdvertisingData::new()
+        .add_flags(0x06)
+        .unwrap()
+        .add_complete_name("RustCam")
+        .unwrap();
+
+    assert_eq!(
+        data.as_bytes(),
+        [0x02, 0x01, 0x06, 0x08, 0x09, b'R', b'u', b's', b't', b'C', b'a', b'm']
+    );
+    assert_eq!(data.len(), 12);
+}
+
+#[test]
+fn test_payload_overflow() {
+    // 2 header bytes + 29 name bytes fill the payload exactly
+    let full = AdvertisingData::new()
+        .add_complete_name(&"x".repeat(29))
+        .unwrap();
+    assert_eq!(full.len(), AdvertisingData::MAX_LEN);
+    assert_eq!(full.add_tx_power(0), Err(BleError::InvalidParameter));
+
+    assert_eq!(
+        AdvertisingData::new().add_complete_name(&"x".repeat(30)),
+        Err(BleError::InvalidParameter)
+    );
+}
+
+#[test]
+fn test_ibeacon_reference_vector() {
+    let expected: [u8; 30] = [
+        0x02, 0x01, 0x06, // flags
+        0x1A, 0xFF, 0x4C, 0x00, // manufacturer data, Apple
+        0x02, 0x15, // iBeacon, 21 bytes follow
+        0xE2, 0xC5, 0x6D, 0xB5, 0xDF, 0xFB, 0x48, 0xD2, // proximity UUID
+        0xB0, 0x60, 0xD0, 0xF5, 0xA7, 0x10, 0x96, 0xE0, // (Apple's AirLocate)
+        0x00, 0x01, // major
+        0x00, 0x02, // minor
+        0xC5, // measured power (-59 dBm)
+    ];
+    let uuid = expected[9..25].try_into().unwrap();
+    let beacon = IBeacon::new(uuid, 1, 2, -59);
+
+    assert_eq!(beacon.advertising_data().unwrap().as_bytes(), expected);
+}
@@ -0,0 +1,106 @@
+//! ATT PDU encoding and parsing in the Linux GATT server and client
+//!
+//! Packets are built from attribute tables in memory, so no Bluetooth
+//! adapter is needed.
+
+#![cfg(all(test, feature = "platform-linux", feature = "ble"))]
+
+use hal::ble::*;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// HCI ACL header (5 bytes) plus L2CAP header (4 bytes)
+const ACL_HEADER_LEN: usize = 9;
+
+/// ATT PDU carried by an ACL packet
+fn att_pdu(packet: &[u8]) -> &[u8] {
+    &packet[ACL_HEADER_LEN..]
+}
+
+#[test]
+fn test_find_info_lists_cccd() {
+    let db = GattDb::with_custom_service();
+    // start handle 6, end handle 6
+    let packet = build_find_info_response(0x0040, &db, &[0x06, 0x00, 0x06, 0x00]);
+
+    // Find Information Response, 16-bit UUIDs, handle 6 = 0x2902
+    assert_eq!(att_pdu(&packet), [0x05, 0x01, 0x06, 0x00, 0x02, 0x29]);
+    assert_eq!(GATT_CCCD_HANDLE, 6);
+}
+
+#[test]
+fn test_parse_service_entries() {
+    // Read By Group Type Response with two 16-bit services
+    let rsp = [
+        0x11, 0x06, // opcode, entry length
+        0x01, 0x00, 0x05, 0x00, 0x0F, 0x18, // 1-5: Battery Service
+        0x06, 0x00, 0x0B, 0x00, 0x0A, 0x18, // 6-11: Device Information
+    ];
+    let services = parse_service_entries(&rsp).unwrap();
+
+    assert_eq!(services.len(), 2);
+    assert_eq!((services[0].start_handle, services[0].end_handle), (1, 5));
+    assert_eq!(services[0].uuid, 0x180F);
+    assert_eq!((services[1].start_handle, services[1].end_handle), (6, 11));
+    assert_eq!(services[1].uuid, 0x180A);
+
+    // 128-bit UUIDs are sent little-endian
+    let mut rsp = vec![0x11, 0x14, 0x10, 0x00, 0xFF, 0xFF];
+    rsp.extend((0..16u8).rev());
+    let services = parse_service_entries(&rsp).unwrap();
+    assert_eq!(
+        services[0].uuid,
+        Uuid::from_bytes(core::array::from_fn(|i| i as u8))
+    );
+
+    // Only 16- and 128-bit entries are valid
+    assert_eq!(
+        parse_service_entries(&[0x11, 0x05, 0x01, 0x00, 0x02, 0x00, 0x00]),
+        Err(BleError::GattError)
+    );
+    assert_eq!(parse_service_entries(&[0x11]), Err(BleError::GattError));
+}
+
+#[test]
+fn test_parse_characteristic_entries() {
+    // Read By Type Response with two characteristic declarations
+    let rsp = [
+        0x09, 0x07, // opcode, entry length
+        0x02, 0x00, 0x12, 0x03, 0x00, 0x35, 0x12, // 2: read/notify 0x1235 at 3
+        0x04, 0x00, 0x0A, 0x05, 0x00, 0x36, 0x12, // 4: read/write 0x1236 at 5
+    ];
+    assert_eq!(parse_characteristic_entries(&rsp), Ok(vec![(2, 3), (4, 5)]));
+}
+
+#[test]
+fn test_read_by_group_lists_battery_service() {
+    let mut db = GattDb::new();
+    gatt_add_battery_service(&mut db, Arc::new(AtomicU8::new(87)));
+    let info = DeviceInfoService {
+        manufacturer: "RustCam",
+        model: "rc-1",
+        firmware_rev: "0.1.0",
+    };
+    gatt_add_device_info_service(&mut db, &info);
+
+    // Primary services (0x2800) from handle 1 to 0xFFFF
+    let packet = build_read_by_group_response(0x0040, &db, &[0x01, 0x00, 0xFF, 0xFF, 0x00, 0x28]);
+    assert_eq!(
+        att_pdu(&packet),
+        [
+            0x11, 0x06, // opcode, entry length
+            0x01, 0x00, 0x04, 0x00, 0x0F, 0x18, // 1-4: Battery Service
+            0x05, 0x00, 0x0B, 0x00, 0x0A, 0x18, // 5-11: Device Information
+        ]
+    );
+
+    // Battery Level is a single byte read from the shared level
+    assert!(matches!(
+        &db.get(3).unwrap().value,
+        AttributeValue::Byte(level) if level.load(Ordering::Relaxed) == 87
+    ));
+
+    // Past the last service
+    let packet = build_read_by_group_response(0x0040, &db, &[0x0C, 0x00, 0xFF, 0xFF, 0x00, 0x28]);
+    assert_eq!(att_pdu(&packet)[0], 0x01); // Error Response
+}
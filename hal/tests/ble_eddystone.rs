@@ -0,0 +1,60 @@
+//! Eddystone-URL encoding and advertising payload
+
+#![cfg(all(test, feature = "ble"))]
+
+use hal::ble::*;
+
+#[test]
+fn test_encode_https_url() {
+    let mut expected = vec![0x03];
+    expected.extend_from_slice(b"rustcam.local");
+    assert_eq!(
+        encode_eddystone_url("https://rustcam.local"),
+        Some(expected)
+    );
+}
+
+#[test]
+fn test_encode_prefix_and_expansions() {
+    // "https://www." wins over "https://", ".com/" over ".com"
+    assert_eq!(
+        encode_eddystone_url("https://www.example.com/cam"),
+        Some([&[0x01][..], b"example", &[0x00], b"cam"].concat())
+    );
+    assert_eq!(
+        encode_eddystone_url("http://go.org"),
+        Some([&[0x02][..], b"go", &[0x08]].concat())
+    );
+}
+
+#[test]
+fn test_encode_rejects_invalid_urls() {
+    assert_eq!(encode_eddystone_url("ftp://example.com"), None);
+    assert_eq!(encode_eddystone_url("https://my cam"), None);
+}
+
+#[test]
+fn test_advertising_data_layout() {
+    let data = eddystone_url_advertising_data("https://rustcam.local", -20).unwrap();
+    let bytes = data.as_bytes();
+
+    // Flags, then the complete 16-bit UUID list with 0xFEAA
+    assert_eq!(&bytes[..7], &[0x02, 0x01, 0x06, 0x03, 0x03, 0xAA, 0xFE]);
+    // Service data: length, type, UUID, frame type, TX power, URL
+    assert_eq!(
+        &bytes[7..13],
+        &[3 + 2 + 14, 0x16, 0xAA, 0xFE, 0x10, -20i8 as u8]
+    );
+    assert_eq!(bytes[13], 0x03);
+    assert_eq!(&bytes[14..], b"rustcam.local");
+}
+
+#[test]
+fn test_advertising_data_rejects_long_url() {
+    // 1 prefix byte + 17 characters
+    assert_eq!(
+        eddystone_url_advertising_data("https://rustcam-frontdoor", 0),
+        Err(BleError::InvalidParameter)
+    );
+    assert!(eddystone_url_advertising_data("https://rustcam-frontdoo", 0).is_ok());
+}
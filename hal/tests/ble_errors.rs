@@ -0,0 +1,15 @@
+//! Tests for the remediation hints in BLE error messages
+
+#![cfg(all(test, feature = "platform-linux", feature = "ble"))]
+
+use hal::ble::BleError;
+
+#[test]
+fn test_permission_denied_hint() {
+    let message = BleError::PermissionDenied.to_string();
+    assert!(message.contains("setcap"), "{message}");
+
+    let help = BleError::PermissionDenied.help_text().unwrap();
+    assert!(message.ends_with(&format!("({help})")), "{message}");
+    assert_eq!(BleError::Timeout.help_text(), None);
+}
@@ -0,0 +1,82 @@
+//! Tests for building GATT attribute tables from a `GattServerConfig`
+//!
+//! Only the table layout is checked, so no Bluetooth adapter is needed.
+
+#![cfg(all(test, feature = "ble"))]
+
+use hal::ble::*;
+
+fn characteristic(uuid: u16, writable: bool, notify: bool) -> GattCharacteristic {
+    GattCharacteristic {
+        uuid,
+        properties: CHR_PROP_READ,
+        initial_value: vec![uuid as u8],
+        writable,
+        notify,
+    }
+}
+
+#[test]
+fn test_two_service_handles() {
+    let config = GattServerConfig::builder()
+        .name("test")
+        .add_service(GattService {
+            uuid: 0x1234,
+            characteristics: vec![
+                characteristic(0x1235, false, true),
+                characteristic(0x1236, true, false),
+            ],
+        })
+        .add_service(GattService {
+            uuid: 0x5678,
+            characteristics: vec![characteristic(0x5679, true, true)],
+        })
+        .build();
+    let db = config.build_db();
+
+    // Handles run from 1 with no gaps
+    let handles: Vec<u16> = db.attributes().iter().map(|attr| attr.handle).collect();
+    assert_eq!(handles, (1..=10).collect::<Vec<u16>>());
+
+    let uuids: Vec<u16> = db.attributes().iter().map(|attr| attr.uuid).collect();
+    assert_eq!(
+        uuids,
+        [
+            0x2800, 0x2803, 0x1235, 0x2902, 0x2803, 0x1236, // first service
+            0x2800, 0x2803, 0x5679, 0x2902, // second service
+        ]
+    );
+    assert_eq!(db.services(), vec![(1, 6, 0x1234), (7, 10, 0x5678)]);
+
+    // Declarations point at the value handle right after them
+    let decl = |handle: u16| match &db.get(handle).unwrap().value {
+        AttributeValue::Static(value) => value.clone(),
+        other => panic!("declaration at {handle} is {other:?}"),
+    };
+    assert_eq!(decl(2), [CHR_PROP_READ | CHR_PROP_NOTIFY, 3, 0, 0x35, 0x12]);
+    assert_eq!(decl(5), [CHR_PROP_READ | CHR_PROP_WRITE, 6, 0, 0x36, 0x12]);
+    assert_eq!(
+        decl(8),
+        [
+            CHR_PROP_READ | CHR_PROP_WRITE | CHR_PROP_NOTIFY,
+            9,
+            0,
+            0x79,
+            0x56
+        ]
+    );
+
+    // Read-only values are constant, writable ones stored by the server
+    assert!(matches!(
+        db.get(3).unwrap().value,
+        AttributeValue::Static(_)
+    ));
+    assert!(matches!(
+        db.get(6).unwrap().value,
+        AttributeValue::Stored(_)
+    ));
+    assert!(matches!(
+        db.get(9).unwrap().value,
+        AttributeValue::Stored(_)
+    ));
+}
@@ -0,0 +1,69 @@
+//! HCI event parsing: advertising reports and command responses
+//!
+//! Events are synthesized in memory, so no Bluetooth adapter is needed.
+
+#![cfg(all(test, feature = "platform-linux", feature = "ble"))]
+
+use hal::ble::*;
+
+/// LE advertising report parameters (from the report count on) for one
+/// device named `name`
+fn advertising_report(last_addr_byte: u8, name: &str, rssi: i8) -> Vec<u8> {
+    // One ADV_IND report from a public address (sent little-endian)
+    let mut report = vec![0x01, 0x00, 0x00];
+    report.extend_from_slice(&[last_addr_byte, 0x00, 0x00, 0x00, 0x00, 0xAA]);
+    let mut ad = vec![0x02, 0x01, 0x06, name.len() as u8 + 1, 0x09];
+    ad.extend_from_slice(name.as_bytes());
+    report.push(ad.len() as u8);
+    report.extend(ad);
+    report.push(rssi as u8);
+    report
+}
+
+#[test]
+fn test_min_rssi_filters_weaker_reports() {
+    let reports: Vec<Vec<u8>> = (1..=3)
+        .map(|i| advertising_report(i, "RustCam", -70))
+        .collect();
+
+    let unfiltered = BleScanner::new();
+    let accepted: Vec<ScanResult> = reports
+        .iter()
+        .filter_map(|r| parse_filtered_report(r, &unfiltered))
+        .collect();
+    assert_eq!(accepted.len(), 3);
+    assert_eq!(accepted[0].rssi, -70);
+    assert_eq!(accepted[0].name_str(), Some("RustCam"));
+
+    let scanner = BleScanner::new().with_min_rssi(0);
+    let accepted: Vec<ScanResult> = reports
+        .iter()
+        .filter_map(|r| parse_filtered_report(r, &scanner))
+        .collect();
+    assert!(accepted.is_empty());
+}
+
+#[test]
+fn test_name_prefix_filter() {
+    let scanner = BleScanner::new().with_name_prefix("Rust");
+    assert!(parse_filtered_report(&advertising_report(1, "RustCam", -40), &scanner).is_some());
+    assert!(parse_filtered_report(&advertising_report(2, "Other", -40), &scanner).is_none());
+}
+
+#[test]
+fn test_read_rssi_response() {
+    // Command Complete for Read RSSI (0x1405): status 0, handle 0x0040, RSSI 0xC8
+    let event = [0x04, 0x0E, 0x07, 0x01, 0x05, 0x14, 0x00, 0x40, 0x00, 0xC8];
+
+    let params = parse_command_complete(&event, 0x1405).unwrap().unwrap();
+    assert_eq!(parse_handle_i8_response(params), Ok(-56));
+
+    // Another command's completion is skipped, a failure reported
+    assert!(parse_command_complete(&event, 0x0C2D).is_none());
+    let mut failed = event;
+    failed[6] = 0x02; // Unknown Connection Identifier
+    assert_eq!(
+        parse_command_complete(&failed, 0x1405),
+        Some(Err(BleError::SocketError))
+    );
+}
@@ -0,0 +1,92 @@
+//! Integration tests for the Linux BLE backend (raw HCI sockets)
+//!
+//! Opening the HCI socket needs a Bluetooth adapter and `CAP_NET_RAW`, so
+//! those tests are ignored by default. Run them with:
+//!
+//! ```text
+//! cargo test -p hal --features ble --test ble_linux -- --include-ignored
+//! ```
+//!
+//! The connection test also needs a connectable peripheral in range, named
+//! by its address in `RUSTCAM_TEST_BLE_PEER` (e.g. `AA:BB:CC:DD:EE:FF`).
+
+#![cfg(all(test, feature = "platform-linux", feature = "ble"))]
+
+use hal::ble::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The backend keeps global state, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_deinitialize_before_initialize() {
+    let _guard = serial();
+    assert_eq!(ble_deinitialize(), Err(BleError::NotInitialized));
+    assert_eq!(ble_start_scan(100), Err(BleError::NotInitialized));
+}
+
+#[test]
+#[ignore = "needs a Bluetooth adapter and CAP_NET_RAW"]
+fn test_double_initialize() {
+    let _guard = serial();
+    assert_eq!(ble_initialize(), Ok(()));
+    assert_eq!(ble_initialize(), Err(BleError::AlreadyInitialized));
+    assert_eq!(ble_deinitialize(), Ok(()));
+
+    // The state outlives deinitialization, so this starts over cleanly
+    assert_eq!(ble_initialize(), Ok(()));
+    assert_eq!(ble_deinitialize(), Ok(()));
+}
+
+#[test]
+fn test_generated_addresses_differ() {
+    // HCI byte order: the most significant byte is last
+    let first = generate_static_random_address().unwrap();
+    let second = generate_static_random_address().unwrap();
+    assert_ne!(first, second);
+    assert_eq!(first[5] & 0xC0, 0xC0);
+    assert_eq!(second[5] & 0xC0, 0xC0);
+}
+
+#[test]
+#[ignore = "needs a Bluetooth adapter and CAP_NET_RAW"]
+fn test_address_rotation() {
+    let _guard = serial();
+    ble_initialize().unwrap();
+    let before = ble_get_random_address().unwrap();
+
+    ble_rotate_random_address(1).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2500));
+    let after = ble_get_random_address();
+    ble_stop_address_rotation().unwrap();
+    ble_deinitialize().unwrap();
+
+    let after = after.unwrap();
+    assert_ne!(before, after);
+    // Static random addresses have the two top bits set
+    assert_eq!(after.bytes[0] & 0xC0, 0xC0);
+}
+
+#[test]
+#[ignore = "needs a Bluetooth adapter, CAP_NET_RAW and a peripheral in range"]
+fn test_connect_disconnect() {
+    let peer = std::env::var("RUSTCAM_TEST_BLE_PEER").expect("RUSTCAM_TEST_BLE_PEER not set");
+    let address = BleAddress::from_str(&peer).expect("RUSTCAM_TEST_BLE_PEER is not an address");
+
+    let _guard = serial();
+    ble_initialize().unwrap();
+    // Scanning first tells ble_connect whether the peer uses a random address
+    let scanned = ble_start_scan(2000);
+    let connected = ble_connect(&address, 5000);
+    let disconnected = connected.map(ble_disconnect);
+    ble_deinitialize().unwrap();
+
+    scanned.unwrap();
+    let handle = connected.unwrap();
+    assert!(handle.is_valid());
+    assert_eq!(disconnected, Ok(Ok(())));
+}
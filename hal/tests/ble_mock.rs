@@ -0,0 +1,131 @@
+//! Tests against the mock BLE backend
+//!
+//! The mock backend only replaces the stub when no platform is selected:
+//!
+//! ```text
+//! cargo test -p hal --no-default-features --features ble,mock --test ble_mock
+//! ```
+
+#![cfg(all(
+    test,
+    feature = "mock",
+    not(any(feature = "platform-linux", feature = "platform-nuttx"))
+))]
+
+use hal::ble::*;
+use std::sync::mpsc;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// The mock keeps global state, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn device(last: u8) -> ScanResult {
+    ScanResult {
+        address: BleAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, last]),
+        address_type: AddressType::Public,
+        rssi: -60,
+        name: None,
+        name_len: 0,
+    }
+}
+
+#[test]
+fn test_continuous_scan_reports_each_device() {
+    let _guard = serial();
+    ble_mock_set_scan_results(vec![device(1), device(2)]);
+
+    let (tx, rx) = mpsc::channel();
+    let handle = ble_start_scan_continuous(Box::new(move |result| {
+        let _ = tx.send(result.address);
+    }))
+    .unwrap();
+    assert!(handle.is_running());
+
+    let timeout = Duration::from_secs(1);
+    let mut found = vec![
+        rx.recv_timeout(timeout).unwrap(),
+        rx.recv_timeout(timeout).unwrap(),
+    ];
+
+    // Devices appearing later are reported too, known ones only once
+    ble_mock_set_scan_results(vec![device(1), device(2), device(3)]);
+    found.push(rx.recv_timeout(timeout).unwrap());
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+    found.sort();
+    assert_eq!(
+        found,
+        [device(1).address, device(2).address, device(3).address]
+    );
+
+    handle.stop();
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn test_advertised_name() {
+    let _guard = serial();
+    ble_initialize().unwrap();
+    assert_eq!(ble_mock_get_advertised_name(), None);
+
+    ble_start_advertising("RustCam").unwrap();
+    assert_eq!(ble_mock_get_advertised_name().as_deref(), Some("RustCam"));
+
+    // Beacons carry no name
+    ble_advertise_eddystone_url("https://example.com", -20).unwrap();
+    assert_eq!(ble_mock_get_advertised_name(), None);
+
+    ble_start_advertising("RustCam").unwrap();
+    ble_stop_advertising().unwrap();
+    assert_eq!(ble_mock_get_advertised_name(), None);
+    ble_deinitialize().unwrap();
+}
+
+#[test]
+fn test_scan_returns_preset_devices() {
+    let _guard = serial();
+    ble_initialize().unwrap();
+    ble_mock_set_scan_results(vec![device(7)]);
+
+    ble_start_scan(1000).unwrap();
+    let results = ble_get_scan_results().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].address, device(7).address);
+
+    ble_mock_set_scan_results(Vec::new());
+    ble_deinitialize().unwrap();
+    assert!(ble_get_scan_results().unwrap().is_empty());
+}
+
+/// Records the writes it receives
+#[derive(Default)]
+struct Recorder {
+    writes: Vec<(u16, Vec<u8>)>,
+}
+
+impl GattHandler for Recorder {
+    fn on_read(&mut self, _attr_handle: u16) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn on_write(&mut self, attr_handle: u16, data: &[u8]) {
+        self.writes.push((attr_handle, data.to_vec()));
+    }
+}
+
+#[test]
+fn test_gatt_server_delivers_injected_writes() {
+    let _guard = serial();
+    ble_mock_inject_write(0x0003, vec![0x01, 0x00]);
+
+    let mut recorder = Recorder::default();
+    let start = Instant::now();
+    ble_run_gatt_server_with_handler("RustCam", 50, &mut recorder).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(recorder.writes, [(0x0003, vec![0x01, 0x00])]);
+}
@@ -0,0 +1,97 @@
+//! Parsing and formatting of BLE identifiers
+
+#![cfg(all(test, feature = "ble"))]
+
+use hal::ble::*;
+use std::collections::HashSet;
+
+#[test]
+fn test_uuid_from_short_form() {
+    let uuid = Uuid::from_str("180F").unwrap();
+    assert!(uuid.is_16bit);
+    assert_eq!(uuid, 0x180F);
+    assert_eq!(uuid, Uuid::from_u16(0x180F));
+    assert!(uuid.is_bluetooth_base());
+    assert_eq!(uuid.to_string(), "0x180F");
+
+    // Hex digits in either case
+    assert_eq!(Uuid::from_str("2a19"), Some(Uuid::from_u16(0x2A19)));
+}
+
+#[test]
+fn test_uuid_from_full_form() {
+    // On the Bluetooth Base UUID: same as the short form
+    assert_eq!(
+        Uuid::from_str("0000180F-0000-1000-8000-00805F9B34FB"),
+        Some(Uuid::from_u16(0x180F))
+    );
+
+    let text = "E2C56DB5-DFFB-48D2-B060-D0F5A71096E0";
+    let uuid = Uuid::from_str(text).unwrap();
+    assert!(!uuid.is_16bit);
+    assert!(!uuid.is_bluetooth_base());
+    assert_eq!(uuid.bytes[..4], [0xE2, 0xC5, 0x6D, 0xB5]);
+    assert_eq!(uuid.to_string(), text);
+    assert_ne!(uuid, 0x180F);
+}
+
+#[test]
+fn test_uuid_invalid() {
+    for text in [
+        "",
+        "180",
+        "180F0",
+        "+180",
+        "18 F",
+        "GGGG",
+        "0000180F00001000800000805F9B34FB",
+        "0000180F-0000-1000-8000-00805F9B34F",
+        "0000180F-0000-1000-8000-00805F9B34FBA",
+        "0000180F-00001-000-8000-00805F9B34FB",
+        "0000180F-0000-1000-8000-00805F9B34FX",
+    ] {
+        assert_eq!(Uuid::from_str(text), None, "{text:?}");
+    }
+}
+
+#[test]
+fn test_address_try_from_str() {
+    let address = BleAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    assert_eq!(BleAddress::try_from("AA:BB:CC:DD:EE:FF"), Ok(address));
+    assert_eq!(BleAddress::try_from("aa:bb:cc:dd:ee:ff"), Ok(address));
+    assert_eq!(address.to_string(), "AA:BB:CC:DD:EE:FF");
+
+    for text in [
+        "",
+        "AA:BB:CC:DD:EE",
+        "AA:BB:CC:DD:EE:FF:00",
+        "AA:BB:CC:DD:EE:GG",
+    ] {
+        assert!(BleAddress::try_from(text).is_err(), "{text:?}");
+    }
+}
+
+#[test]
+fn test_address_array_conversions() {
+    let bytes = [0x00, 0x1A, 0x7D, 0xDA, 0x71, 0x13];
+    let address = BleAddress::from(bytes);
+    assert_eq!(address, BleAddress::new(bytes));
+    assert_eq!(<[u8; 6]>::from(address), bytes);
+}
+
+#[test]
+fn test_address_order_and_hash() {
+    let mut addresses = vec![
+        BleAddress::broadcast(),
+        BleAddress::new([0x00, 0x00, 0x00, 0x00, 0x01, 0x00]),
+        BleAddress::new([0x00, 0x00, 0x00, 0x00, 0x00, 0xFF]),
+    ];
+    addresses.sort();
+    assert_eq!(addresses[0].bytes[5], 0xFF);
+    assert_eq!(addresses[1].bytes[4], 0x01);
+    assert!(addresses[2].is_broadcast());
+    assert!(!addresses[0].is_broadcast());
+
+    let unique: HashSet<BleAddress> = addresses.iter().chain(&addresses).copied().collect();
+    assert_eq!(unique.len(), 3);
+}
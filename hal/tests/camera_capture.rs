@@ -0,0 +1,43 @@
+//! Capture tests for the Linux camera backend
+//!
+//! These capture from a real V4L2 device that can deliver YUYV at VGA and
+//! QVGA (most USB webcams), so they are ignored by default. Run them on a
+//! machine with a camera at `/dev/video0` with:
+//!
+//! ```text
+//! cargo test -p hal --features camera --test camera_capture -- --include-ignored
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "camera"))]
+
+use hal::camera::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The camera is a global resource, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+#[ignore = "needs a YUYV-capable camera"]
+fn test_set_resolution_between_captures() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Yuv422)
+        .resolution(Resolution::Vga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    let frame = camera_capture_frame().unwrap();
+    assert_eq!((frame.width, frame.height), (640, 480));
+    assert_eq!(frame.len(), 640 * 480 * 2);
+
+    camera_set_resolution(Resolution::Qvga).unwrap();
+    let frame = camera_capture_frame().unwrap();
+    assert_eq!((frame.width, frame.height), (320, 240));
+    assert_eq!(frame.len(), 320 * 240 * 2);
+
+    camera_deinitialize().unwrap();
+}
@@ -0,0 +1,88 @@
+//! Camera configuration: builder, defaults and resolution lookup
+
+#![cfg(all(test, feature = "camera"))]
+
+use hal::camera::*;
+
+#[test]
+fn test_builder_sets_fields() {
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Jpeg)
+        .resolution(Resolution::Vga)
+        .fps(30)
+        .build();
+
+    assert_eq!(
+        config,
+        CameraConfig {
+            format: PixelFormat::Jpeg,
+            resolution: Resolution::Vga,
+            jpeg_quality: 12,
+            fb_count: 1,
+            hmirror: false,
+            vflip: false,
+            device_index: 0,
+            fps: 30,
+        }
+    );
+}
+
+#[test]
+fn test_builder_clamps_like_with_methods() {
+    let config = CameraConfig::builder().jpeg_quality(0).fb_count(9).build();
+    assert_eq!((config.jpeg_quality, config.fb_count), (1, 3));
+
+    let config = CameraConfig::default()
+        .with_jpeg_quality(200)
+        .with_fb_count(0);
+    assert_eq!((config.jpeg_quality, config.fb_count), (100, 1));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_new_matches_builder() {
+    assert_eq!(
+        CameraConfig::new(PixelFormat::Rgb565, Resolution::Qvga),
+        CameraConfig::builder()
+            .format(PixelFormat::Rgb565)
+            .resolution(Resolution::Qvga)
+            .build()
+    );
+    assert_eq!(CameraConfig::default(), CameraConfig::builder().build());
+}
+
+#[test]
+fn test_resolution_from_dimensions() {
+    assert_eq!(Resolution::from_dimensions(640, 480), Some(Resolution::Vga));
+    assert_eq!(Resolution::from_dimensions(641, 480), None);
+    assert_eq!(Resolution::from_dimensions(480, 640), None);
+
+    for r in Resolution::ALL {
+        assert_eq!(Resolution::from_dimensions(r.width(), r.height()), Some(r));
+    }
+}
+
+#[test]
+fn test_resolution_closest() {
+    assert_eq!(Resolution::closest(640, 480), Resolution::Vga);
+    assert_eq!(Resolution::closest(700, 500), Resolution::Vga);
+    assert_eq!(Resolution::closest(0, 0), Resolution::Qqvga);
+    assert_eq!(Resolution::closest(4000, 3000), Resolution::Uxga);
+}
+
+#[test]
+fn test_resolution_from_str() {
+    assert_eq!("VGA".parse(), Ok(Resolution::Vga));
+    assert_eq!("qvga".parse(), Ok(Resolution::Qvga));
+    assert_eq!(" 1280x720 ".parse(), Ok(Resolution::Hd));
+    assert_eq!("1280X1024".parse(), Ok(Resolution::Sxga));
+    assert_eq!(Resolution::Svga.to_string().parse(), Ok(Resolution::Svga));
+
+    for text in ["", "vga2", "641x480", "640x", "x480"] {
+        assert_eq!(
+            text.parse::<Resolution>(),
+            Err(CameraError::InvalidFormat),
+            "{text:?}"
+        );
+    }
+}
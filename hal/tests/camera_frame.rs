@@ -0,0 +1,255 @@
+//! Frame buffer helpers: conversion, blitting and comparison
+//!
+//! These work on synthetic frames, so no camera is needed.
+
+#![cfg(all(test, feature = "camera"))]
+
+use hal::camera::*;
+
+#[test]
+fn test_blit_rgb565_places_and_clips() {
+    // 2x2 RGB565 frame: red, green / blue, white
+    let pixels: [u16; 4] = [0xF800, 0x07E0, 0x001F, 0xFFFF];
+    let data = pixels.iter().flat_map(|p| p.to_le_bytes()).collect();
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb565, data);
+
+    // 4x3 destination, frame placed at (1, 1)
+    let mut dst = [0x1234u16; 12];
+    frame.blit_rgb565(&mut dst, 4, 1, 1).unwrap();
+    assert_eq!(dst[..4], [0x1234; 4]);
+    assert_eq!(dst[4..8], [0x1234, 0xF800, 0x07E0, 0x1234]);
+    assert_eq!(dst[8..], [0x1234, 0x001F, 0xFFFF, 0x1234]);
+
+    // At (3, 2) only the top-left pixel fits
+    let mut dst = [0x1234u16; 12];
+    frame.blit_rgb565(&mut dst, 4, 3, 2).unwrap();
+    assert_eq!(dst[11], 0xF800);
+    assert!(dst[..11].iter().all(|&p| p == 0x1234));
+
+    // Entirely outside: nothing is written
+    let mut dst = [0x1234u16; 12];
+    frame.blit_rgb565(&mut dst, 4, 4, 0).unwrap();
+    assert!(dst.iter().all(|&p| p == 0x1234));
+}
+
+#[test]
+fn test_blit_rgb565_converts_and_rejects_jpeg() {
+    let frame = FrameBuffer::new(1, 1, PixelFormat::Rgb888, vec![255, 0, 0]);
+    let mut dst = [0u16; 1];
+    frame.blit_rgb565(&mut dst, 1, 0, 0).unwrap();
+    assert_eq!(dst[0], 0xF800);
+
+    let jpeg = FrameBuffer::new(1, 1, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    assert_eq!(
+        jpeg.blit_rgb565(&mut dst, 1, 0, 0),
+        Err(CameraError::InvalidFormat)
+    );
+}
+
+#[test]
+fn test_motion_detector_scores() {
+    let black = FrameBuffer::new(4, 4, PixelFormat::Grayscale, vec![0; 16]);
+    let white = FrameBuffer::new(4, 4, PixelFormat::Grayscale, vec![255; 16]);
+    let mut detector = MotionDetector::new(0.5);
+
+    // The first frame has nothing to compare with
+    assert_eq!(detector.detect(&black), 0.0);
+    assert!(detector.detect(&black).abs() < 1e-6);
+    assert!((detector.detect(&white) - 1.0).abs() < 1e-6);
+    assert!(detector.is_motion(&black));
+    assert!(!detector.is_motion(&black));
+}
+
+#[test]
+fn test_motion_detector_restarts_on_format_change() {
+    let gray = FrameBuffer::new(2, 1, PixelFormat::Grayscale, vec![0, 0]);
+    let white = FrameBuffer::new(2, 1, PixelFormat::Rgb888, vec![255; 6]);
+    let black = FrameBuffer::new(2, 1, PixelFormat::Rgb888, vec![0; 6]);
+    let mut detector = MotionDetector::new(0.5);
+
+    detector.detect(&gray);
+    assert_eq!(detector.detect(&white), 0.0);
+    assert!((detector.detect(&black) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_rotate_cw180_reverses_rgb888_pixels() {
+    let pixels = [[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]];
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb888, pixels.concat());
+
+    let rotated = frame.rotate(RotationAngle::Cw180);
+    assert_eq!((rotated.width, rotated.height), (2, 2));
+    assert_eq!(rotated.format, PixelFormat::Rgb888);
+    let reversed: Vec<_> = pixels.iter().rev().flatten().copied().collect();
+    assert_eq!(rotated.data, reversed);
+}
+
+#[test]
+fn test_rotate_quarter_turns_swap_dimensions() {
+    // 3x2 grayscale:  0 1 2
+    //                 3 4 5
+    let frame = FrameBuffer::new(3, 2, PixelFormat::Grayscale, (0..6).collect());
+
+    let cw90 = frame.rotate(RotationAngle::Cw90);
+    assert_eq!((cw90.width, cw90.height), (2, 3));
+    assert_eq!(cw90.data, [3, 0, 4, 1, 5, 2]);
+
+    let cw270 = frame.rotate(RotationAngle::Cw270);
+    assert_eq!((cw270.width, cw270.height), (2, 3));
+    assert_eq!(cw270.data, [2, 5, 1, 4, 0, 3]);
+
+    // Four quarter turns are the identity
+    let back = cw90
+        .rotate(RotationAngle::Cw90)
+        .rotate(RotationAngle::Cw90)
+        .rotate(RotationAngle::Cw90);
+    assert_eq!((back.width, back.height), (3, 2));
+    assert_eq!(back.data, frame.data);
+}
+
+#[test]
+fn test_flip_rgb565_keeps_pixel_bytes_together() {
+    // 2x2 RGB565: pixels A B / C D, two bytes each
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb565, (0..8).collect());
+
+    assert_eq!(frame.flip_horizontal().data, [2, 3, 0, 1, 6, 7, 4, 5]);
+    assert_eq!(frame.flip_vertical().data, [4, 5, 6, 7, 0, 1, 2, 3]);
+}
+
+#[test]
+fn test_rotate_leaves_other_formats_unchanged() {
+    let jpeg = FrameBuffer::new(2, 1, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    let rotated = jpeg.rotate(RotationAngle::Cw90);
+    assert_eq!((rotated.width, rotated.height), (2, 1));
+    assert_eq!(rotated.data, jpeg.data);
+}
+
+#[test]
+fn test_diff_with_itself_is_zero() {
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb888, (0..12).map(|i| i * 20).collect());
+
+    let diff = frame.diff(&frame).unwrap();
+    assert_eq!((diff.width, diff.height), (2, 2));
+    assert_eq!(diff.format, PixelFormat::Rgb888);
+    assert_eq!(diff.data, [0; 12]);
+    assert_eq!(frame.diff_score(&frame), Some(0.0));
+}
+
+#[test]
+fn test_diff_score_of_uniform_offset() {
+    let a = FrameBuffer::new(4, 4, PixelFormat::Grayscale, vec![50; 16]);
+    let b = FrameBuffer::new(4, 4, PixelFormat::Grayscale, vec![150; 16]);
+
+    // Absolute difference, whichever frame comes first
+    assert_eq!(a.diff(&b).unwrap().data, [100; 16]);
+    assert_eq!(b.diff(&a).unwrap().data, [100; 16]);
+    let score = a.diff_score(&b).unwrap();
+    assert!((score - 100.0 / 255.0).abs() < 1e-6, "{score}");
+
+    // RGB888 differs per channel
+    let a = FrameBuffer::new(1, 1, PixelFormat::Rgb888, vec![0, 200, 100]);
+    let b = FrameBuffer::new(1, 1, PixelFormat::Rgb888, vec![100, 100, 200]);
+    assert_eq!(a.diff(&b).unwrap().data, [100, 100, 100]);
+    let score = a.diff_score(&b).unwrap();
+    assert!((score - 100.0 / 255.0).abs() < 1e-6, "{score}");
+}
+
+#[test]
+fn test_diff_rejects_mismatched_frames() {
+    let gray = FrameBuffer::new(2, 2, PixelFormat::Grayscale, vec![0; 4]);
+    let wide = FrameBuffer::new(4, 1, PixelFormat::Grayscale, vec![0; 4]);
+    let rgb = FrameBuffer::new(2, 2, PixelFormat::Rgb888, vec![0; 12]);
+    let rgb565 = FrameBuffer::new(2, 2, PixelFormat::Rgb565, vec![0; 8]);
+
+    assert!(gray.diff(&wide).is_none());
+    assert!(gray.diff(&rgb).is_none());
+    assert!(rgb565.diff(&rgb565).is_none());
+    assert_eq!(gray.diff_score(&wide), None);
+}
+
+#[test]
+fn test_yuyv_to_rgb888_known_values() {
+    // Two YUYV rows (Y0 U Y1 V): gray and white, then two reds sharing chroma
+    let yuyv = vec![128, 128, 255, 128, 82, 90, 82, 240];
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Yuv422, yuyv);
+
+    let rgb = frame.to_rgb888().unwrap();
+    assert_eq!(rgb.format, PixelFormat::Rgb888);
+    assert_eq!((rgb.width, rgb.height), (2, 2));
+    assert_eq!(rgb.len(), 2 * 2 * 3);
+    // BT.601: R = 82 + 1.402 * 112, G = 82 + 0.344 * 38 - 0.714 * 112,
+    // B = 82 - 1.772 * 38
+    let expected = [
+        [128, 128, 128],
+        [255, 255, 255],
+        [239, 15, 15],
+        [239, 15, 15],
+    ];
+    assert_eq!(rgb.data, expected.concat());
+}
+
+#[test]
+fn test_to_rgb888_rejects_rgb888_and_jpeg() {
+    let rgb = FrameBuffer::new(1, 1, PixelFormat::Rgb888, vec![1, 2, 3]);
+    assert!(rgb.to_rgb888().is_none());
+    let jpeg = FrameBuffer::new(1, 1, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    assert!(jpeg.to_rgb888().is_none());
+    // Too short for 2x2 YUYV
+    let short = FrameBuffer::new(2, 2, PixelFormat::Yuv422, vec![0; 4]);
+    assert!(short.to_rgb888().is_none());
+}
+
+#[test]
+fn test_thumbnail_vga_rgb888() {
+    let frame = FrameBuffer::new(640, 480, PixelFormat::Rgb888, vec![7; 640 * 480 * 3]);
+
+    let thumb = frame.thumbnail(160);
+    assert_eq!((thumb.width, thumb.height), (160, 120));
+    assert_eq!(thumb.format, PixelFormat::Rgb888);
+    assert_eq!(thumb.len(), 160 * 120 * 3);
+}
+
+#[test]
+fn test_thumbnail_samples_nearest_pixel() {
+    // 4x2 grayscale halves to 2x1, taking every other pixel of the top row
+    let frame = FrameBuffer::new(4, 2, PixelFormat::Grayscale, (0..8).collect());
+    let thumb = frame.thumbnail(2);
+    assert_eq!((thumb.width, thumb.height), (2, 1));
+    assert_eq!(thumb.data, [0, 2]);
+
+    // Already small enough, or JPEG: unchanged
+    assert_eq!(frame.thumbnail(4).data, frame.data);
+    let jpeg = FrameBuffer::new(640, 480, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    let thumb = jpeg.thumbnail(160);
+    assert_eq!((thumb.width, thumb.height), (640, 480));
+}
+
+#[test]
+fn test_histogram_and_mean_luminance() {
+    let frame = FrameBuffer::new(4, 1, PixelFormat::Grayscale, vec![0, 64, 128, 255]);
+
+    let histogram = frame.compute_histogram();
+    for level in [0, 64, 128, 255] {
+        assert_eq!(histogram[level], 1, "level {level}");
+    }
+    assert_eq!(histogram.iter().sum::<u32>(), 4);
+    // (0 + 64 + 128 + 255) / 4, rounded down
+    assert_eq!(frame.mean_luminance(), 111);
+}
+
+#[test]
+fn test_histogram_of_rgb_yuyv_and_jpeg() {
+    // Y = (77*R + 150*G + 29*B) >> 8
+    let rgb = FrameBuffer::new(2, 1, PixelFormat::Rgb888, vec![255, 0, 0, 255, 255, 255]);
+    let histogram = rgb.compute_histogram();
+    assert_eq!((histogram[76], histogram[255]), (1, 1));
+
+    // YUYV luma is every other byte
+    let yuyv = FrameBuffer::new(2, 1, PixelFormat::Yuv422, vec![10, 128, 20, 128]);
+    let histogram = yuyv.compute_histogram();
+    assert_eq!((histogram[10], histogram[20], histogram[128]), (1, 1, 0));
+
+    let jpeg = FrameBuffer::new(2, 1, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    assert_eq!(jpeg.compute_histogram(), [0; 256]);
+    assert_eq!(jpeg.mean_luminance(), 0);
+}
@@ -0,0 +1,73 @@
+//! Integration tests for the Linux camera backend (V4L2)
+//!
+//! These only touch device lookup and error reporting, so unlike `camera_stream` they run
+//! without a camera attached.
+
+#![cfg(all(test, feature = "platform-linux", feature = "camera"))]
+
+use hal::camera::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The camera state and device path override are global
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_device_path_override() {
+    let _guard = serial();
+    camera_set_device_path("/dev/nonexistent");
+    assert_eq!(
+        camera_get_device_path().as_deref(),
+        Some("/dev/nonexistent")
+    );
+    assert_eq!(
+        camera_initialize(CameraConfig::default()),
+        Err(CameraError::DeviceNotFound)
+    );
+    assert!(!camera_is_initialized());
+
+    // An empty path goes back to scanning /dev/video*
+    camera_set_device_path("");
+    assert_ne!(
+        camera_get_device_path().as_deref(),
+        Some("/dev/nonexistent")
+    );
+}
+
+#[test]
+fn test_device_index_opens_default_camera() {
+    let _guard = serial();
+    // Past the number of camera slots, so it only works as a device index
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Yuv422)
+        .resolution(Resolution::Qvga)
+        .device_index(2)
+        .build();
+    match camera_initialize(config) {
+        Ok(()) => {}
+        Err(CameraError::DeviceNotFound) => {
+            eprintln!("skipping: fewer than 3 capture devices");
+            return;
+        }
+        Err(e) => panic!("initialize failed: {e}"),
+    }
+
+    assert!(camera_is_initialized());
+    assert!(!camera_capture_frame().unwrap().is_empty());
+    camera_get_settings().unwrap();
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_system_error_display() {
+    let message = CameraError::SystemError(-22).to_string();
+    assert!(message.contains("Invalid"), "{message}");
+    assert_eq!(
+        CameraError::from_errno(-16),
+        CameraError::AlreadyInitialized
+    );
+    assert_eq!(CameraError::from_errno(5), CameraError::SystemError(-5));
+}
@@ -0,0 +1,178 @@
+//! Tests against the mock camera backend
+//!
+//! The mock backend only replaces the stub when no platform is selected:
+//!
+//! ```text
+//! cargo test -p hal --no-default-features --features camera,mock --test camera_mock
+//! ```
+
+#![cfg(all(
+    test,
+    feature = "camera",
+    feature = "mock",
+    not(any(feature = "platform-linux", feature = "platform-nuttx"))
+))]
+
+use hal::camera::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The mock keeps global state, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_captures_match_resolution() {
+    let _guard = serial();
+    let config = CameraConfig::builder().resolution(Resolution::Qvga).build();
+    camera_initialize(config).unwrap();
+
+    let mut timestamps = Vec::new();
+    for _ in 0..3 {
+        let frame = camera_capture_frame().unwrap();
+        assert_eq!(frame.format, PixelFormat::Rgb888);
+        assert_eq!((frame.width, frame.height), (320, 240));
+        assert_eq!(frame.len(), 320 * 240 * 3);
+        timestamps.push(frame.timestamp);
+    }
+    assert!(timestamps.windows(2).all(|w| w[0] < w[1]), "{timestamps:?}");
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_checkerboard_pixels() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .resolution(Resolution::Qqvga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    let frame = camera_capture_frame().unwrap();
+    let width = frame.width as usize;
+    let pixel = |x: usize, y: usize| &frame.data[(y * width + x) * 3..][..3];
+    assert_eq!(pixel(0, 0), [200, 200, 200]);
+    assert_eq!(pixel(1, 0), [50, 50, 50]);
+    assert_eq!(pixel(0, 1), [50, 50, 50]);
+    assert_eq!(pixel(1, 1), [200, 200, 200]);
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_injected_frame_and_failure() {
+    let _guard = serial();
+    camera_initialize(CameraConfig::default()).unwrap();
+
+    let injected = FrameBuffer::new(2, 1, PixelFormat::Rgb888, vec![1, 2, 3, 4, 5, 6]);
+    camera_mock_set_next_frame(injected);
+    assert_eq!(camera_capture_frame().unwrap().data, [1, 2, 3, 4, 5, 6]);
+
+    camera_mock_fail_next_capture();
+    assert_eq!(
+        camera_capture_frame().unwrap_err(),
+        CameraError::CaptureFailed
+    );
+
+    // Both only apply once
+    assert_eq!(camera_capture_frame().unwrap().len(), 640 * 480 * 3);
+    assert_eq!(
+        format!("{:?}", camera_get_settings().unwrap()),
+        format!("{:?}", CameraSettings::auto())
+    );
+
+    camera_deinitialize().unwrap();
+    assert_eq!(
+        camera_capture_frame().unwrap_err(),
+        CameraError::NotInitialized
+    );
+}
+
+#[test]
+fn test_device_index_opens_default_camera() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .resolution(Resolution::Qqvga)
+        .device_index(3)
+        .build();
+    camera_initialize(config).unwrap();
+
+    // The handle-less API sees the camera whatever device it came from
+    assert!(camera_is_initialized());
+    let frame = camera_capture_frame().unwrap();
+    assert_eq!((frame.width, frame.height), (160, 120));
+    camera_get_settings().unwrap();
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_initial_orientation_in_settings() {
+    let _guard = serial();
+    let config = CameraConfig::default().with_hmirror(true);
+    camera_initialize(config).unwrap();
+
+    let settings = camera_get_settings().unwrap();
+    assert!(settings.hmirror);
+    assert!(!settings.vflip);
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_capture_burst() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .resolution(Resolution::Qqvga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    let frames = camera_capture_burst(3, 0).unwrap();
+    assert_eq!(frames.len(), 3);
+    assert!(frames
+        .iter()
+        .all(|f| (f.width, f.height) == (frames[0].width, frames[0].height)));
+    assert_eq!((frames[0].width, frames[0].height), (160, 120));
+
+    // A failed capture ends the burst, unless it is told to carry on
+    camera_mock_fail_next_capture();
+    assert_eq!(
+        camera_capture_burst(3, 0).unwrap_err(),
+        CameraError::CaptureFailed
+    );
+    camera_mock_fail_next_capture();
+    let options = BurstOptions::new(3).with_stop_on_error(false);
+    assert_eq!(camera_capture_burst_with(options).unwrap().len(), 2);
+
+    assert_eq!(
+        camera_capture_burst(0, 0).unwrap_err(),
+        CameraError::InvalidFormat
+    );
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_capture_stats() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .resolution(Resolution::Qqvga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    for _ in 0..5 {
+        camera_capture_frame().unwrap();
+    }
+    let stats = camera_get_stats().unwrap();
+    assert_eq!(stats.frames_captured, 5);
+    assert_eq!(stats.bytes_total, 5 * 160 * 120 * 3);
+    assert!(stats.last_frame_at.is_some());
+
+    camera_reset_stats().unwrap();
+    assert_eq!(camera_get_stats().unwrap().frames_captured, 0);
+
+    camera_deinitialize().unwrap();
+    assert_eq!(camera_get_stats().unwrap_err(), CameraError::NotInitialized);
+}
@@ -0,0 +1,80 @@
+//! Saving frames to PPM and JPEG files
+//!
+//! Uses synthetic frames and temporary files, so no camera is needed.
+
+#![cfg(all(test, feature = "camera"))]
+
+use hal::camera::*;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Temporary file removed when the test ends
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str) -> TempFile {
+        TempFile(std::env::temp_dir().join(format!(
+            "rustcam-save-test-{}-{}",
+            name,
+            std::process::id()
+        )))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn test_ppm_round_trip() {
+    let pixels = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]].concat();
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb888, pixels.clone());
+    let file = TempFile::new("round-trip.ppm");
+
+    camera_save_ppm(&frame, file.path()).unwrap();
+
+    let saved = fs::read(file.path()).unwrap();
+    let header = b"P6\n2 2\n255\n";
+    assert_eq!(&saved[..header.len()], header);
+    assert_eq!(&saved[header.len()..], &pixels[..]);
+}
+
+#[test]
+fn test_ppm_converts_grayscale_and_rejects_jpeg() {
+    let gray = FrameBuffer::new(2, 1, PixelFormat::Grayscale, vec![10, 200]);
+    let file = TempFile::new("gray.ppm");
+    camera_save_ppm(&gray, file.path()).unwrap();
+    assert_eq!(
+        fs::read(file.path()).unwrap(),
+        b"P6\n2 1\n255\n\x0a\x0a\x0a\xc8\xc8\xc8"
+    );
+
+    let jpeg = FrameBuffer::new(2, 1, PixelFormat::Jpeg, vec![0xFF, 0xD8, 0xFF, 0xD9]);
+    let file = TempFile::new("jpeg.ppm");
+    let err = camera_save_ppm(&jpeg, file.path()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!file.path().exists());
+}
+
+#[test]
+fn test_save_jpeg() {
+    let data = vec![0xFF, 0xD8, 0x01, 0x02, 0xFF, 0xD9];
+    let jpeg = FrameBuffer::new(2, 1, PixelFormat::Jpeg, data.clone());
+    let file = TempFile::new("frame.jpg");
+    camera_save_jpeg(&jpeg, file.path()).unwrap();
+    assert_eq!(fs::read(file.path()).unwrap(), data);
+
+    // Raw frames are not JPEG and nothing is written
+    let rgb = FrameBuffer::new(1, 1, PixelFormat::Rgb888, vec![1, 2, 3]);
+    let file = TempFile::new("raw.jpg");
+    let err = camera_save_jpeg(&rgb, file.path()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!file.path().exists());
+}
@@ -0,0 +1,91 @@
+//! Integration tests for the MJPEG streaming server
+//!
+//! These capture from a real V4L2 device that can deliver MJPEG (most USB
+//! webcams), so they are ignored by default. Run them on a machine with a
+//! camera at `/dev/video0` with:
+//!
+//! ```text
+//! cargo test -p hal --features camera --test camera_stream -- --include-ignored
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "camera"))]
+
+use hal::camera::*;
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpStream;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+/// The camera is a global resource, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Read one CRLF-terminated line, without the terminator
+fn read_line(reader: &mut impl BufRead) -> String {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert!(line.ends_with("\r\n"), "line not CRLF-terminated: {line:?}");
+    line.truncate(line.len() - 2);
+    line
+}
+
+#[test]
+#[ignore = "needs an MJPEG-capable camera"]
+fn test_response_header() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Jpeg)
+        .resolution(Resolution::Vga)
+        .build();
+    let handle = camera_stream_mjpeg(18081, config).unwrap();
+
+    let stream = TcpStream::connect(("127.0.0.1", 18081)).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream);
+
+    assert_eq!(read_line(&mut reader), "HTTP/1.1 200 OK");
+    assert_eq!(
+        read_line(&mut reader),
+        "Content-Type: multipart/x-mixed-replace; boundary=frame"
+    );
+    assert_eq!(read_line(&mut reader), "");
+
+    // First part: boundary, part headers, then a JPEG of the stated length
+    assert_eq!(read_line(&mut reader), "--frame");
+    assert_eq!(read_line(&mut reader), "Content-Type: image/jpeg");
+    let length = read_line(&mut reader);
+    let length: usize = length
+        .strip_prefix("Content-Length: ")
+        .expect("missing Content-Length")
+        .parse()
+        .unwrap();
+    assert_eq!(read_line(&mut reader), "");
+
+    let mut jpeg = vec![0u8; length];
+    reader.read_exact(&mut jpeg).unwrap();
+    assert_eq!(&jpeg[..2], &[0xFF, 0xD8], "missing JPEG SOI marker");
+
+    drop(reader);
+    stream_stop(handle);
+    assert!(!camera_is_initialized());
+}
+
+#[test]
+#[ignore = "needs an MJPEG-capable camera"]
+fn test_rejects_raw_format() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Yuv422)
+        .resolution(Resolution::Vga)
+        .build();
+    assert_eq!(
+        camera_stream_mjpeg(18082, config).err(),
+        Some(CameraError::InvalidFormat)
+    );
+    assert!(!camera_is_initialized());
+}
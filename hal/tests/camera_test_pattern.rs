@@ -0,0 +1,85 @@
+//! Frame streaming against the synthetic test pattern source
+//!
+//! The pattern source replaces the stub backend, so this needs the
+//! `test-pattern` feature without a platform:
+//!
+//! ```text
+//! cargo test -p hal --no-default-features --features test-pattern --test camera_test_pattern
+//! ```
+
+#![cfg(all(
+    test,
+    feature = "test-pattern",
+    not(any(
+        feature = "platform-linux",
+        feature = "platform-nuttx",
+        feature = "mock"
+    ))
+))]
+
+use hal::camera::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The pattern source is global, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_stream_30_frames() {
+    let _guard = serial();
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Rgb888)
+        .resolution(Resolution::Qvga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    let stream = camera_capture_stream().unwrap();
+    let frames: Vec<FrameBuffer> = stream
+        .take(30)
+        .collect::<CameraResult<_>>()
+        .expect("capture failed");
+
+    assert_eq!(frames.len(), 30);
+    for frame in &frames {
+        assert_eq!((frame.width, frame.height), (320, 240));
+        assert_eq!(frame.len(), 320 * 240 * 3);
+    }
+    // The bars scroll, so consecutive frames differ
+    assert_ne!(frames[0].data, frames[1].data);
+
+    // Dropping the stream deinitializes the camera
+    assert!(!camera_is_initialized());
+}
+
+#[test]
+fn test_initial_orientation_in_settings() {
+    let _guard = serial();
+    let config = CameraConfig::default().with_hmirror(true).with_vflip(true);
+    camera_initialize(config).unwrap();
+
+    let settings = camera_get_settings().unwrap();
+    assert!(settings.hmirror);
+    assert!(settings.vflip);
+
+    camera_deinitialize().unwrap();
+}
+
+#[test]
+fn test_capture_stats() {
+    let _guard = serial();
+    camera_initialize(CameraConfig::default()).unwrap();
+
+    for _ in 0..5 {
+        camera_capture_frame().unwrap();
+    }
+    let stats = camera_get_stats().unwrap();
+    assert!(
+        stats.frames_captured == 5 && stats.bytes_total > 0,
+        "{stats:?}"
+    );
+
+    camera_deinitialize().unwrap();
+}
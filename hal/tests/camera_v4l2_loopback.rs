@@ -0,0 +1,90 @@
+//! Integration tests for the V4L2 backend against a v4l2loopback device
+//!
+//! These need a loopback device with a producer writing YUYV frames into
+//! it, so they are skipped unless `RUSTCAM_TEST_V4L2_LOOPBACK` names the
+//! device:
+//!
+//! ```text
+//! sudo modprobe v4l2loopback video_nr=10 exclusive_caps=1
+//! ffmpeg -re -f lavfi -i testsrc=size=640x480:rate=30 -pix_fmt yuyv422 -f v4l2 /dev/video10 &
+//! RUSTCAM_TEST_V4L2_LOOPBACK=/dev/video10 cargo test -p hal --features camera --test camera_v4l2_loopback
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "camera"))]
+
+use hal::camera::*;
+use std::sync::{Mutex, MutexGuard};
+
+/// The camera and its device path override are global
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Loopback device selected as the first camera until dropped
+struct Loopback;
+
+impl Loopback {
+    /// Select the device in `RUSTCAM_TEST_V4L2_LOOPBACK`, `None` if unset
+    fn open() -> Option<Loopback> {
+        let Ok(path) = std::env::var("RUSTCAM_TEST_V4L2_LOOPBACK") else {
+            eprintln!("skipping: RUSTCAM_TEST_V4L2_LOOPBACK not set");
+            return None;
+        };
+        assert!(
+            std::path::Path::new(&path).exists(),
+            "{path} does not exist"
+        );
+        camera_set_device_path(&path);
+        Some(Loopback)
+    }
+}
+
+impl Drop for Loopback {
+    fn drop(&mut self) {
+        camera_set_device_path("");
+    }
+}
+
+#[test]
+fn test_enumerate_formats_lists_yuyv() {
+    let _guard = serial();
+    let Some(_loopback) = Loopback::open() else {
+        return;
+    };
+
+    let formats = camera_enumerate_formats().unwrap();
+    assert!(formats.contains(&PixelFormat::Yuv422), "{formats:?}");
+    assert!(!camera_is_initialized());
+}
+
+#[test]
+fn test_stream_30_frames() {
+    let _guard = serial();
+    let Some(_loopback) = Loopback::open() else {
+        return;
+    };
+
+    let config = CameraConfig::builder()
+        .format(PixelFormat::Yuv422)
+        .resolution(Resolution::Vga)
+        .build();
+    camera_initialize(config).unwrap();
+
+    let stream = camera_capture_stream().unwrap();
+    let frames: Vec<FrameBuffer> = stream
+        .take(30)
+        .collect::<CameraResult<_>>()
+        .expect("capture failed");
+
+    assert_eq!(frames.len(), 30);
+    for frame in &frames {
+        assert_eq!(frame.format, PixelFormat::Yuv422);
+        assert_eq!((frame.width, frame.height), (640, 480));
+        assert_eq!(frame.len(), 640 * 480 * 2);
+    }
+
+    // Dropping the stream stops capture and deinitializes the camera
+    assert!(!camera_is_initialized());
+}
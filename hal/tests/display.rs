@@ -0,0 +1,66 @@
+//! Display HAL: color conversion and the Linux PPM stub
+//!
+//! ```text
+//! cargo test -p hal --features display --test display
+//! ```
+
+#![cfg(all(test, feature = "display"))]
+
+use hal::display::rgb_to_rgb565;
+#[cfg(feature = "platform-linux")]
+use hal::{
+    camera::{FrameBuffer, PixelFormat},
+    display::{Display, DisplayConfig, DisplayError},
+};
+
+#[test]
+fn test_rgb888_to_rgb565() {
+    assert_eq!(rgb_to_rgb565(255, 0, 0), 0xF800);
+    assert_eq!(rgb_to_rgb565(0, 255, 0), 0x07E0);
+    assert_eq!(rgb_to_rgb565(0, 0, 255), 0x001F);
+    assert_eq!(rgb_to_rgb565(255, 255, 255), 0xFFFF);
+    assert_eq!(rgb_to_rgb565(0, 0, 0), 0x0000);
+}
+
+#[cfg(feature = "platform-linux")]
+#[test]
+fn test_linux_writes_ppm_per_frame() {
+    let dir = std::env::temp_dir().join(format!("rustcam-display-test-{}", std::process::id()));
+    hal::display::display_set_output_dir(dir.to_str().unwrap());
+
+    let config = DisplayConfig {
+        width: 4,
+        height: 2,
+        spi_bus: 2,
+        cs_pin: 0,
+        dc_pin: 4,
+        reset_pin: 5,
+        backlight_pin: 6,
+    };
+    let mut display = Display::initialize(config).unwrap();
+    hal::display::display_set_output_dir("");
+
+    // Red 2x2 RGB888 frame over a blue background, then a green pixel
+    display.clear(0x001F).unwrap();
+    let frame = FrameBuffer::new(2, 2, PixelFormat::Rgb888, [255, 0, 0].repeat(4));
+    display.blit_rgb565(3, 1, 1, 1, &[0x07E0]).unwrap();
+    display.display_frame(&frame).unwrap();
+
+    let ppm = std::fs::read(dir.join("frame_00000.ppm")).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    let header = b"P6\n4 2\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    let (red, green, blue) = ([255, 0, 0], [0, 255, 0], [0, 0, 255]);
+    let expected = [red, red, blue, blue, red, red, blue, green].concat();
+    assert_eq!(&ppm[header.len()..], &expected[..]);
+
+    assert_eq!(
+        display.blit_rgb565(3, 0, 2, 1, &[0, 0]),
+        Err(DisplayError::InvalidArgument)
+    );
+    let jpeg = FrameBuffer::new(2, 2, PixelFormat::Jpeg, vec![0xff, 0xd8]);
+    assert_eq!(
+        display.display_frame(&jpeg),
+        Err(DisplayError::UnsupportedFormat)
+    );
+}
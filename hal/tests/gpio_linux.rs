@@ -0,0 +1,46 @@
+//! Integration tests for the Linux GPIO backend (sysfs)
+//!
+//! The round-trip test needs a real or simulated GPIO (e.g. from the
+//! `gpio-mockup` module) and write access to /sys/class/gpio, so it is
+//! ignored by default. Run it with the pin number in `RUSTCAM_TEST_GPIO`:
+//!
+//! ```text
+//! RUSTCAM_TEST_GPIO=496 cargo test -p hal --features gpio --test gpio_linux -- --include-ignored
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "gpio"))]
+
+use hal::gpio::*;
+
+#[test]
+fn test_export_invalid_pin() {
+    // No controller has this many lines; without sysfs GPIO or root the
+    // export fails earlier, but never with a generic system error
+    let err = GpioPin::export(u32::MAX).unwrap_err();
+    assert!(
+        matches!(err, GpioError::NotFound | GpioError::PermissionDenied),
+        "{err}"
+    );
+}
+
+#[test]
+#[ignore = "needs a GPIO and write access to /sys/class/gpio"]
+fn test_output_round_trip() {
+    let number: u32 = std::env::var("RUSTCAM_TEST_GPIO")
+        .expect("RUSTCAM_TEST_GPIO not set")
+        .parse()
+        .expect("RUSTCAM_TEST_GPIO is not a number");
+
+    let pin = GpioPin::export(number).unwrap();
+    assert_eq!(pin.number(), number);
+    pin.set_direction(GpioDirection::Output).unwrap();
+
+    pin.set_value(true).unwrap();
+    assert!(pin.get_value().unwrap());
+    pin.set_value(false).unwrap();
+    assert!(!pin.get_value().unwrap());
+
+    // Dropping the pin unexports it, so it can be exported again
+    drop(pin);
+    drop(GpioPin::export(number).unwrap());
+}
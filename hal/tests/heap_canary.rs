@@ -0,0 +1,42 @@
+//! Leak checks with `HeapCanary`
+
+#![cfg(all(test, feature = "heap"))]
+
+use hal::heap::HeapCanary;
+use std::sync::{Mutex, MutexGuard};
+
+/// Heap usage is process-wide, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_vec_freed_within_tolerance() {
+    let _guard = serial();
+    // glibc caches a freed chunk of this size per thread and still counts
+    // it as used, so let one land in the cache before arming the canary.
+    // Non-zero fill: zeroed vectors come from calloc, which skips the cache.
+    drop(std::hint::black_box(vec![1u8; 1000]));
+
+    let canary = HeapCanary::new("Vec test", 128);
+    let v = vec![1u8; 1000];
+    assert_eq!(v.len(), 1000);
+    drop(v);
+    drop(canary);
+
+    hal::heap_assert_no_leak! { tolerance: 128, name: "macro", {
+        let v = vec![1u8; 1000];
+        assert_eq!(v.len(), 1000);
+    } }
+}
+
+#[test]
+#[cfg(feature = "platform-linux")]
+#[should_panic(expected = "HeapCanary 'leak' leaked")]
+fn test_leak_panics() {
+    let _guard = serial();
+    let _canary = HeapCanary::new("leak", 64);
+    std::mem::forget(vec![1u8; 4096]);
+}
@@ -0,0 +1,41 @@
+//! Tests for the heap fragmentation ratio and monitor
+
+#![cfg(all(test, feature = "heap"))]
+
+use hal::heap::HeapStats;
+use hal::HeapFragmentationMonitor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+fn stats(mxordblk: i32, fordblks: i32) -> HeapStats {
+    HeapStats {
+        arena: 4096,
+        ordblks: 4,
+        mxordblk,
+        uordblks: 4096 - fordblks,
+        fordblks,
+    }
+}
+
+#[test]
+fn test_fragmentation_ratio() {
+    assert!((stats(100, 1000).fragmentation_ratio() - 0.9).abs() < 1e-6);
+    assert_eq!(stats(1000, 1000).fragmentation_ratio(), 0.0);
+    assert_eq!(stats(0, 0).fragmentation_ratio(), 0.0);
+    // Largest chunk unknown
+    assert_eq!(stats(0, 1000).fragmentation_ratio(), 0.0);
+}
+
+#[test]
+fn test_monitor_threshold_of_one_never_fires() {
+    // A threshold of 1.0 can never be exceeded
+    let fired = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&fired);
+    let monitor =
+        HeapFragmentationMonitor::new(1.0, Box::new(move |_| flag.store(true, Ordering::Relaxed)));
+    monitor.check();
+    let stop = monitor.run_background(10);
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    stop.store(true, Ordering::Relaxed);
+    assert!(!fired.load(Ordering::Relaxed));
+}
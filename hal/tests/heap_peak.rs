@@ -0,0 +1,25 @@
+//! Peak heap tracking
+//!
+//! The peak is process-wide, so this file holds a single test.
+
+#![cfg(all(test, feature = "heap", feature = "platform-linux"))]
+
+use hal::{get_heap_peak, get_heap_used, reset_heap_peak};
+
+#[test]
+fn test_peak_survives_free() {
+    reset_heap_peak();
+    let before = get_heap_used();
+
+    let v = std::hint::black_box(vec![1u8; 1000]);
+    let during = get_heap_used();
+    drop(v);
+    let after = get_heap_used();
+
+    assert!(get_heap_peak() >= 1000, "{}", get_heap_peak());
+    assert!(get_heap_peak() >= during && during >= before + 1000);
+    assert!(get_heap_peak() >= after);
+
+    reset_heap_peak();
+    assert_eq!(get_heap_peak(), 0);
+}
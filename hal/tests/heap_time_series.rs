@@ -0,0 +1,51 @@
+//! Heap usage history: plotting and growth rate
+
+#![cfg(all(test, feature = "heap"))]
+
+use hal::heap::{HeapSample, HeapTimeSeries};
+use std::time::{Duration, Instant};
+
+/// Series with one sample per second
+fn series(values: &[i32]) -> HeapTimeSeries {
+    let start = Instant::now();
+    let mut series = HeapTimeSeries::new(values.len());
+    for (i, &heap_used) in values.iter().enumerate() {
+        series.push(HeapSample {
+            timestamp: start + Duration::from_secs(i as u64),
+            heap_used,
+        });
+    }
+    series
+}
+
+#[test]
+fn test_uniform_samples_plot_flat() {
+    let plot = series(&[4096; 10]).plot_ascii(40, 4);
+    let lines: Vec<&str> = plot.lines().collect();
+
+    // Only the bottom row has bars, all the same height
+    assert_eq!(lines.len(), 6);
+    for line in &lines[..3] {
+        assert!(line.ends_with(&" ".repeat(10)), "{line:?}");
+    }
+    assert!(lines[3].ends_with(&"▁".repeat(10)), "{:?}", lines[3]);
+    assert_eq!(lines[5], "current: 4096 bytes");
+}
+
+#[test]
+fn test_growth_rate() {
+    assert_eq!(series(&[4096; 10]).growth_rate_bytes_per_sec(), 0.0);
+    let rate = series(&[1000, 1100, 1200, 1300]).growth_rate_bytes_per_sec();
+    assert!((rate - 100.0).abs() < 1e-3, "{rate}");
+}
+
+#[test]
+fn test_oldest_sample_dropped() {
+    let mut series = series(&[1, 2, 3]);
+    series.push(HeapSample {
+        timestamp: Instant::now(),
+        heap_used: 4,
+    });
+    let values: Vec<i32> = series.samples().iter().map(|s| s.heap_used).collect();
+    assert_eq!(values, [2, 3, 4]);
+}
@@ -0,0 +1,84 @@
+//! Tests for the Linux I2C backend (i2c-dev) against a mock bus device
+//!
+//! A plain file stands in for /dev/i2c-N: reads and writes work on it,
+//! but it rejects the i2c-dev ioctls like a non-I2C character device.
+//!
+//! ```text
+//! cargo test -p hal --features i2c --test i2c_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "i2c"))]
+
+use hal::i2c::*;
+use std::fs::{self, OpenOptions};
+use std::path::PathBuf;
+
+/// Mock bus device file, removed on drop
+struct MockDevice(PathBuf);
+
+impl MockDevice {
+    fn new(name: &str) -> (MockDevice, I2cBus) {
+        let path =
+            std::env::temp_dir().join(format!("rustcam-i2c-test-{}-{}", name, std::process::id()));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        (MockDevice(path), I2cBus::from_file(file))
+    }
+
+    fn contents(&self) -> Vec<u8> {
+        fs::read(&self.0).unwrap()
+    }
+}
+
+impl Drop for MockDevice {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn test_open_missing_bus() {
+    assert_eq!(I2cBus::open(255).unwrap_err(), I2cError::NotFound);
+}
+
+#[test]
+fn test_write_needs_address() {
+    let (device, bus) = MockDevice::new("write");
+
+    // Selecting the device fails, so nothing reaches the bus
+    assert_eq!(
+        bus.write(0x48, &[0x01, 0x60]),
+        Err(I2cError::SystemError(libc::ENOTTY))
+    );
+    assert!(device.contents().is_empty());
+}
+
+#[test]
+fn test_write_read_is_one_transfer() {
+    let (device, bus) = MockDevice::new("write-read");
+
+    // The combined transfer is a single ioctl, never a write() then read()
+    let mut buf = [0xAAu8; 2];
+    assert_eq!(
+        bus.write_read(0x48, &[0x00], &mut buf),
+        Err(I2cError::SystemError(libc::ENOTTY))
+    );
+    assert_eq!(buf, [0xAA; 2]);
+    assert!(device.contents().is_empty());
+}
+
+#[test]
+fn test_write_read_too_long() {
+    let (_device, bus) = MockDevice::new("too-long");
+
+    let cmd = vec![0u8; u16::MAX as usize + 1];
+    assert_eq!(
+        bus.write_read(0x48, &cmd, &mut [0u8; 1]),
+        Err(I2cError::SystemError(libc::EINVAL))
+    );
+}
@@ -0,0 +1,102 @@
+//! Tests for the Linux NVS backend (JSON files in /tmp)
+//!
+//! Each test uses its own namespace, suffixed with the process ID, and
+//! removes the file afterwards.
+//!
+//! ```text
+//! cargo test -p hal --features nvs --test nvs_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "nvs"))]
+
+use hal::nvs::*;
+use std::fs;
+
+/// Namespace unique to this test run, whose file is removed on drop
+struct TestNamespace(NvsNamespace);
+
+impl TestNamespace {
+    fn new(name: &str) -> TestNamespace {
+        let ns = NvsNamespace::new(&format!("test-{}-{}", name, std::process::id())).unwrap();
+        let namespace = TestNamespace(ns);
+        let _ = fs::remove_file(namespace.path());
+        namespace
+    }
+
+    fn path(&self) -> String {
+        format!("/tmp/rustcam_nvs_{}.json", self.0.name())
+    }
+}
+
+impl Drop for TestNamespace {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(self.path());
+    }
+}
+
+#[test]
+fn test_str_round_trip() {
+    let ns = TestNamespace::new("str");
+    let ns = &ns.0;
+
+    assert_eq!(nvs_get_str(ns, "ssid"), Ok(None));
+    nvs_set_str(ns, "ssid", "Home \"5G\"\n\\").unwrap();
+    nvs_set_str(ns, "psk", "hunter2").unwrap();
+    assert_eq!(
+        nvs_get_str(ns, "ssid"),
+        Ok(Some("Home \"5G\"\n\\".to_string()))
+    );
+
+    // Overwrite, then delete
+    nvs_set_str(ns, "ssid", "Office").unwrap();
+    assert_eq!(nvs_get_str(ns, "ssid"), Ok(Some("Office".to_string())));
+    nvs_delete(ns, "ssid").unwrap();
+    assert_eq!(nvs_get_str(ns, "ssid"), Ok(None));
+    assert_eq!(nvs_get_str(ns, "psk"), Ok(Some("hunter2".to_string())));
+
+    // Deleting a missing key is not an error
+    nvs_delete(ns, "ssid").unwrap();
+}
+
+#[test]
+fn test_u32_round_trip() {
+    let ns = TestNamespace::new("u32");
+    let ns = &ns.0;
+
+    nvs_set_u32(ns, "boot_count", u32::MAX).unwrap();
+    assert_eq!(nvs_get_u32(ns, "boot_count"), Ok(Some(u32::MAX)));
+    nvs_set_str(ns, "boot_count", "many").unwrap();
+    assert_eq!(nvs_get_u32(ns, "boot_count"), Err(NvsError::TypeMismatch));
+    nvs_delete(ns, "boot_count").unwrap();
+    assert_eq!(nvs_get_u32(ns, "boot_count"), Ok(None));
+}
+
+#[test]
+fn test_persists_across_handles() {
+    let ns = TestNamespace::new("persist");
+    nvs_set_str(&ns.0, "calibration", "1.25").unwrap();
+
+    let reopened = NvsNamespace::new(ns.0.name()).unwrap();
+    assert_eq!(
+        nvs_get_str(&reopened, "calibration"),
+        Ok(Some("1.25".to_string()))
+    );
+}
+
+#[test]
+fn test_corrupted_file() {
+    let ns = TestNamespace::new("corrupted");
+    fs::write(ns.path(), "{\"key\": ").unwrap();
+    assert_eq!(nvs_get_str(&ns.0, "key"), Err(NvsError::Corrupted));
+}
+
+#[test]
+fn test_invalid_names() {
+    assert_eq!(
+        NvsNamespace::new("../etc").unwrap_err(),
+        NvsError::InvalidName
+    );
+    let ns = TestNamespace::new("names");
+    assert_eq!(nvs_set_str(&ns.0, "", "x"), Err(NvsError::InvalidName));
+    assert_eq!(nvs_get_str(&ns.0, "a/b"), Err(NvsError::InvalidName));
+}
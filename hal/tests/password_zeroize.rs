@@ -0,0 +1,77 @@
+//! Secrets are wiped when dropped
+
+use hal::security::ZeroOnDrop;
+use std::mem::MaybeUninit;
+
+#[cfg(feature = "wifi")]
+#[test]
+fn test_station_config_password_zeroed_on_drop() {
+    use hal::wifi::StationConfig;
+
+    // The storage outlives the config, so reading it after the drop is sound
+    let mut slot = MaybeUninit::new(StationConfig::new("Test-AP", "hunter22"));
+    let config = slot.as_mut_ptr();
+    let (password, password_len) = unsafe {
+        (
+            core::ptr::addr_of!((*config).password),
+            core::ptr::addr_of!((*config).password_len),
+        )
+    };
+    let before = unsafe { *password };
+    assert_eq!(&before[..8], b"hunter22");
+
+    unsafe { core::ptr::drop_in_place(config) };
+
+    assert_eq!(unsafe { *password }, [0u8; 64]);
+    assert_eq!(unsafe { *password_len }, 0);
+}
+
+#[cfg(feature = "wifi")]
+#[test]
+fn test_ap_config_password_zeroed_on_drop() {
+    use hal::wifi::ApConfig;
+
+    let mut slot = MaybeUninit::new(ApConfig::new("RustCam", "hunter22", 6));
+    let config = slot.as_mut_ptr();
+    let password = unsafe { &*(*config).password } as *const [u8; 64];
+    let before = unsafe { *password };
+    assert_eq!(&before[..8], b"hunter22");
+
+    unsafe { core::ptr::drop_in_place(config) };
+
+    assert_eq!(unsafe { *password }, [0u8; 64]);
+}
+
+#[cfg(feature = "wifi")]
+#[test]
+fn test_wifi_config_debug_hides_password() {
+    use hal::wifi::{ApConfig, StationConfig};
+
+    let station = format!("{:?}", StationConfig::new("Test-AP", "hunter22"));
+    assert!(station.contains("Test-AP"), "{station}");
+    assert!(!station.contains("hunter22"), "{station}");
+    // Nor as the bytes the derived impl would have printed
+    assert!(!station.contains("104, 117"), "{station}");
+
+    let ap = format!("{:?}", ApConfig::new("RustCam", "hunter22", 6));
+    assert!(!ap.contains("104, 117"), "{ap}");
+}
+
+#[test]
+fn test_zero_on_drop_wipes_buffer() {
+    let mut slot = MaybeUninit::new(ZeroOnDrop::new([0x5au8; 16]));
+    let key = unsafe { &**slot.as_ptr() } as *const [u8; 16];
+    assert_eq!(unsafe { *key }, [0x5a; 16]);
+
+    unsafe { slot.assume_init_drop() };
+
+    assert_eq!(unsafe { *key }, [0; 16]);
+}
+
+#[test]
+fn test_zero_on_drop_derefs_and_hides_contents() {
+    let mut key = ZeroOnDrop::new(vec![1u8, 2, 3]);
+    key.push(4);
+    assert_eq!(&key[..], &[1, 2, 3, 4]);
+    assert_eq!(format!("{:?}", key), "ZeroOnDrop(..)");
+}
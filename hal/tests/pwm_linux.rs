@@ -0,0 +1,99 @@
+//! Tests for the Linux PWM backend against a mock sysfs tree
+//!
+//! A temporary directory with pwmchipN/export and pwmN attribute files
+//! stands in for /sys/class/pwm.
+//!
+//! ```text
+//! cargo test -p hal --features pwm --test pwm_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "pwm"))]
+
+use hal::pwm::*;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+/// The sysfs path is global, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Mock PWM class directory with chip 0, channel 0, removed on drop
+struct MockPwm(PathBuf);
+
+impl MockPwm {
+    fn new(name: &str) -> MockPwm {
+        let dir =
+            std::env::temp_dir().join(format!("rustcam-pwm-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("pwmchip0/pwm0")).unwrap();
+        pwm_set_sysfs_path(dir.to_str().unwrap());
+        MockPwm(dir)
+    }
+
+    fn attr(&self, attr: &str) -> String {
+        fs::read_to_string(self.0.join("pwmchip0/pwm0").join(attr)).unwrap()
+    }
+}
+
+impl Drop for MockPwm {
+    fn drop(&mut self) {
+        pwm_set_sysfs_path("");
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn test_duty_cycle_half() {
+    let _guard = serial();
+    let mock = MockPwm::new("duty");
+
+    let mut pwm = pwm_open(0, 0).unwrap();
+    assert_eq!(pwm.period_ns(), 1_000_000);
+    assert_eq!(mock.attr("enable"), "0");
+
+    pwm.set_duty_cycle(0.5).unwrap();
+    assert_eq!(pwm.duty_cycle_ns(), 500_000);
+    assert_eq!(mock.attr("period"), "1000000");
+    assert_eq!(mock.attr("duty_cycle"), "500000");
+
+    // The duty fraction is kept when the frequency changes
+    pwm.set_frequency(2000).unwrap();
+    assert_eq!(mock.attr("period"), "500000");
+    assert_eq!(mock.attr("duty_cycle"), "250000");
+
+    pwm.enable().unwrap();
+    assert_eq!(mock.attr("enable"), "1");
+
+    drop(pwm);
+    assert_eq!(mock.attr("enable"), "0");
+    assert_eq!(
+        fs::read_to_string(mock.0.join("pwmchip0/unexport")).unwrap(),
+        "0"
+    );
+}
+
+#[test]
+fn test_duty_cycle_clamped() {
+    let _guard = serial();
+    let _mock = MockPwm::new("clamp");
+
+    let mut pwm = pwm_open(0, 0).unwrap();
+    pwm.set_duty_cycle(1.5).unwrap();
+    assert_eq!(pwm.duty_cycle_ns(), 1_000_000);
+    pwm.set_duty_cycle(-0.1).unwrap();
+    assert_eq!(pwm.duty_cycle_ns(), 0);
+    pwm.set_duty_cycle(f32::NAN).unwrap();
+    assert_eq!(pwm.duty_cycle_ns(), 0);
+
+    assert_eq!(pwm.set_frequency(0), Err(PwmError::InvalidFrequency));
+}
+
+#[test]
+fn test_missing_chip() {
+    let _guard = serial();
+    let _mock = MockPwm::new("missing");
+    assert_eq!(pwm_open(1, 0).unwrap_err(), PwmError::NotFound);
+}
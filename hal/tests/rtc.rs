@@ -0,0 +1,75 @@
+//! Tests for RTC calendar conversion
+//!
+//! ```text
+//! cargo test -p hal --features rtc --test rtc
+//! ```
+
+#![cfg(all(test, feature = "rtc"))]
+
+use hal::rtc::*;
+
+fn time(year: u16, month: u8, day: u8, hour: u8, min: u8, sec: u8) -> RtcTime {
+    RtcTime {
+        year,
+        month,
+        day,
+        hour,
+        min,
+        sec,
+        subsec_ns: 0,
+    }
+}
+
+#[test]
+fn test_unix_timestamp_known_dates() {
+    let cases = [
+        (time(1970, 1, 1, 0, 0, 0), 0),
+        (time(2000, 1, 1, 0, 0, 0), 946_684_800),
+        // Leap day in a year divisible by 400
+        (time(2000, 2, 29, 12, 0, 0), 951_825_600),
+        (time(2024, 1, 1, 0, 0, 0), 1_704_067_200),
+        // Last second representable in a signed 32-bit time_t
+        (time(2038, 1, 19, 3, 14, 7), 2_147_483_647),
+        // 2100 is not a leap year
+        (time(2100, 3, 1, 0, 0, 0), 4_107_542_400),
+    ];
+    for (t, expected) in cases {
+        assert_eq!(rtc_to_unix_timestamp(&t), expected, "{t}");
+    }
+}
+
+#[test]
+fn test_unix_timestamp_before_epoch() {
+    assert_eq!(rtc_to_unix_timestamp(&time(1969, 12, 31, 23, 59, 59)), 0);
+}
+
+#[test]
+fn test_is_valid() {
+    assert!(time(2024, 2, 29, 23, 59, 59).is_valid());
+    assert!(!time(2023, 2, 29, 0, 0, 0).is_valid());
+    assert!(!time(2100, 2, 29, 0, 0, 0).is_valid());
+    assert!(!time(2024, 13, 1, 0, 0, 0).is_valid());
+    assert!(!time(2024, 1, 1, 24, 0, 0).is_valid());
+    assert!(!time(1969, 12, 31, 0, 0, 0).is_valid());
+}
+
+#[test]
+fn test_display_iso8601() {
+    let t = RtcTime {
+        subsec_ns: 789_000_000,
+        ..time(2024, 2, 29, 12, 34, 56)
+    };
+    assert_eq!(t.to_string(), "2024-02-29T12:34:56.789Z");
+}
+
+#[cfg(feature = "platform-linux")]
+#[test]
+fn test_get_time_matches_system_clock() {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let t = rtc_get_time().unwrap();
+    assert!(t.is_valid(), "{t}");
+    assert!(rtc_to_unix_timestamp(&t).abs_diff(now) <= 1, "{t}");
+}
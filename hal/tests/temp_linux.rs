@@ -0,0 +1,73 @@
+//! Tests for the Linux temperature backend against mock thermal zones
+//!
+//! A temporary directory with thermal_zoneN/temp files stands in for
+//! /sys/class/thermal.
+//!
+//! ```text
+//! cargo test -p hal --features temp --test temp_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "temp"))]
+
+use hal::temp::*;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+/// The thermal path is global, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Mock thermal class directory selected for reads, removed on drop
+struct MockThermal(PathBuf);
+
+impl MockThermal {
+    fn new(name: &str) -> MockThermal {
+        let dir =
+            std::env::temp_dir().join(format!("rustcam-temp-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        temp_set_thermal_path(dir.to_str().unwrap());
+        MockThermal(dir)
+    }
+
+    fn set_zone(&self, zone: u32, value: &str) {
+        let zone_dir = self.0.join(format!("thermal_zone{}", zone));
+        fs::create_dir_all(&zone_dir).unwrap();
+        fs::write(zone_dir.join("temp"), value).unwrap();
+    }
+}
+
+impl Drop for MockThermal {
+    fn drop(&mut self) {
+        temp_set_thermal_path("");
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn test_read_cpu_millidegrees() {
+    let _guard = serial();
+    let thermal = MockThermal::new("cpu");
+    thermal.set_zone(0, "47250\n");
+    thermal.set_zone(1, "-5000\n");
+
+    assert_eq!(temp_read_cpu(), Ok(47.25));
+    assert_eq!(temp_read_ambient(), Ok(-5.0));
+    assert_eq!(temp_is_throttling(), Ok(false));
+
+    thermal.set_zone(0, "85001\n");
+    assert_eq!(temp_is_throttling(), Ok(true));
+}
+
+#[test]
+fn test_read_errors() {
+    let _guard = serial();
+    let thermal = MockThermal::new("errors");
+
+    assert_eq!(temp_read_cpu(), Err(TempError::NotFound));
+    thermal.set_zone(0, "hot\n");
+    assert_eq!(temp_read_cpu(), Err(TempError::ReadFailed));
+}
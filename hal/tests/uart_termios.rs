@@ -0,0 +1,96 @@
+//! Tests for the termios UART backend over a pseudo-terminal pair
+//!
+//! The `Uart` opens the pty's terminal side; the test plays the peer device
+//! on the controlling side. Line settings are applied to the pty like to a
+//! real port, but no actual serial timing is involved.
+//!
+//! ```text
+//! cargo test -p hal --features uart --test uart_termios
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "uart"))]
+
+use hal::uart::*;
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::FromRawFd;
+use std::time::{Duration, Instant};
+
+/// Open a pty and return its controlling side and the terminal's path
+fn open_pty() -> (File, String) {
+    unsafe {
+        let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+        assert!(fd >= 0, "posix_openpt failed");
+        let peer = File::from_raw_fd(fd);
+        assert_eq!(libc::grantpt(fd), 0);
+        assert_eq!(libc::unlockpt(fd), 0);
+
+        let mut name = [0 as libc::c_char; 64];
+        assert_eq!(libc::ptsname_r(fd, name.as_mut_ptr(), name.len()), 0);
+        let path = CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned();
+        (peer, path)
+    }
+}
+
+fn config_9600() -> UartConfig {
+    UartConfig {
+        baud: 9600,
+        ..UartConfig::default()
+    }
+}
+
+#[test]
+fn test_loopback_byte() {
+    let (mut peer, path) = open_pty();
+    let mut uart = Uart::open(&path, &config_9600()).unwrap();
+
+    // Raw mode: CR and non-ASCII bytes pass through untranslated
+    for byte in [0x55u8, b'\r', 0xFF] {
+        peer.write_all(&[byte]).unwrap();
+        let mut received = [0u8; 1];
+        uart.read_exact(&mut received).unwrap();
+        assert_eq!(received[0], byte);
+
+        uart.write_all(&[byte]).unwrap();
+        uart.flush().unwrap();
+        peer.read_exact(&mut received).unwrap();
+        assert_eq!(received[0], byte);
+    }
+}
+
+#[test]
+fn test_read_timeout() {
+    let (_peer, path) = open_pty();
+    let mut uart = Uart::open(&path, &config_9600()).unwrap();
+
+    uart.set_read_timeout(Some(Duration::from_millis(100)))
+        .unwrap();
+    let start = Instant::now();
+    assert_eq!(uart.read(&mut [0u8; 1]).unwrap(), 0);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[test]
+fn test_invalid_config() {
+    let (_peer, path) = open_pty();
+    for config in [
+        UartConfig {
+            baud: 12345,
+            ..config_9600()
+        },
+        UartConfig {
+            data_bits: 9,
+            ..config_9600()
+        },
+        UartConfig {
+            stop_bits: 3,
+            ..config_9600()
+        },
+    ] {
+        assert_eq!(
+            Uart::open(&path, &config).unwrap_err(),
+            UartError::InvalidConfig
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Tests for the Linux watchdog backend
+//!
+//! Arming a real watchdog would reset the machine if the test died, so
+//! these only cover the handle before `start()` and a missing device.
+//!
+//! ```text
+//! cargo test -p hal --features watchdog --test watchdog_linux
+//! ```
+
+#![cfg(all(test, feature = "platform-linux", feature = "watchdog"))]
+
+use hal::watchdog::*;
+use std::path::Path;
+
+#[test]
+fn test_open_is_noop() {
+    let mut watchdog = Watchdog::open().unwrap();
+    assert!(!watchdog.is_enabled());
+    assert_eq!(watchdog.pet(), Err(WatchdogError::NotStarted));
+    assert_eq!(watchdog.stop(), Err(WatchdogError::NotStarted));
+}
+
+#[test]
+fn test_start_without_device() {
+    if Path::new("/dev/watchdog0").exists() {
+        return;
+    }
+
+    let mut watchdog = Watchdog::open().unwrap();
+    assert_eq!(watchdog.start(5000), Err(WatchdogError::NotFound));
+    assert!(!watchdog.is_enabled());
+}
@@ -0,0 +1,35 @@
+//! Loading station credentials from the environment
+//!
+//! Kept in its own test binary since it changes process-wide environment
+//! variables.
+
+#![cfg(all(test, feature = "wifi"))]
+
+use hal::wifi::*;
+use std::env;
+
+#[test]
+fn test_from_env_default() {
+    env::remove_var(WIFI_SSID_ENV);
+    env::remove_var(WIFI_PASS_ENV);
+    assert!(StationConfig::from_env_default().is_none());
+
+    // Both variables are required
+    env::set_var(WIFI_SSID_ENV, "ci-network");
+    assert!(StationConfig::from_env_default().is_none());
+
+    env::set_var(WIFI_PASS_ENV, "ci-password");
+    let config = StationConfig::from_env_default().unwrap();
+    assert_eq!(config.ssid_str(), Some("ci-network"));
+    assert_eq!(&config.password[..config.password_len], b"ci-password");
+
+    env::remove_var(WIFI_SSID_ENV);
+    env::remove_var(WIFI_PASS_ENV);
+}
+
+#[test]
+fn test_from_env_custom_names() {
+    assert!(
+        StationConfig::from_env("RUSTCAM_TEST_UNSET_SSID", "RUSTCAM_TEST_UNSET_PASS").is_none()
+    );
+}
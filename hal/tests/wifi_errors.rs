@@ -0,0 +1,50 @@
+//! WiFi results and errors can cross threads and work as `std::error::Error`
+
+#![cfg(all(test, feature = "wifi"))]
+
+use hal::wifi::*;
+use std::error::Error;
+
+fn assert_send<T: Send>() {}
+
+fn assert_error<T: Error + Send + Sync + 'static>() {}
+
+// Fails to compile if `WifiError` loses any of these bounds
+const _: fn() = assert_error::<WifiError>;
+
+#[test]
+fn wifi_init_is_send_sync() {
+    assert_send::<WifiResult<()>>();
+
+    // Initialization state is shared, so any thread can query it
+    let initialized = std::thread::spawn(wifi_is_initialized).join().unwrap();
+    assert_eq!(initialized, wifi_is_initialized());
+}
+
+#[test]
+fn test_boxed_error() {
+    fn fails() -> Result<(), Box<dyn Error + Send + Sync>> {
+        Err(WifiError::Timeout)?;
+        Ok(())
+    }
+
+    let err = fails().unwrap_err();
+    assert_eq!(err.to_string(), WifiError::Timeout.to_string());
+    assert_eq!(err.downcast_ref::<WifiError>(), Some(&WifiError::Timeout));
+}
+
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+#[test]
+fn test_from_errno() {
+    assert_eq!(WifiError::from(libc::EPERM), WifiError::PermissionDenied);
+    assert_eq!(WifiError::from(libc::ENODEV), WifiError::InterfaceNotFound);
+    // NuttX drivers return negated errno values
+    assert_eq!(
+        WifiError::from(-libc::EINVAL),
+        WifiError::ConfigurationError
+    );
+    assert_eq!(
+        WifiError::from(libc::EIO),
+        WifiError::SystemError(libc::EIO)
+    );
+}
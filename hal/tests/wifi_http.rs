@@ -0,0 +1,100 @@
+//! Tests for the minimal HTTP client against a local test server
+//!
+//! The server is a std `TcpListener` on the loopback interface that
+//! records the request and answers with a canned response, so no network
+//! or WiFi interface is needed.
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Serve one connection with `response`, returning the request it received
+fn serve_once(response: &'static [u8]) -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 512];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "client closed before the end of the request");
+            request.extend_from_slice(&buf[..n]);
+        }
+        stream.write_all(response).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (port, server)
+}
+
+#[test]
+fn test_get_content_length() {
+    let (port, server) = serve_once(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\n\r\nhello",
+    );
+
+    let body = wifi_http_get(&format!("http://127.0.0.1:{}/status?v=1", port), 2000).unwrap();
+    assert_eq!(body, b"hello");
+
+    let request = server.join().unwrap();
+    assert_eq!(
+        request,
+        format!(
+            "GET /status?v=1 HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nConnection: close\r\n\r\n",
+            port
+        )
+    );
+}
+
+#[test]
+fn test_get_chunked() {
+    let (port, server) = serve_once(
+        b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+          4\r\nWiki\r\n6;ext=1\r\npedia \r\nE\r\nin \r\n\r\nchunks.\r\n0\r\n\r\n",
+    );
+
+    let body = wifi_http_get(&format!("http://127.0.0.1:{}", port), 2000).unwrap();
+    assert_eq!(body, b"Wikipedia in \r\n\r\nchunks.");
+    assert!(server.join().unwrap().starts_with("GET / HTTP/1.1\r\n"));
+}
+
+#[test]
+fn test_get_malformed_response() {
+    let (port, server) = serve_once(b"SSH-2.0-OpenSSH_9.6\r\n\r\n");
+
+    let result = wifi_http_get(&format!("http://127.0.0.1:{}/", port), 2000);
+    assert_eq!(result, Err(WifiError::ConnectionFailed));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_get_timeout() {
+    // Accepts the connection but never answers
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let start = Instant::now();
+    let result = wifi_http_get(&format!("http://127.0.0.1:{}/", port), 200);
+    assert_eq!(result, Err(WifiError::Timeout));
+    assert!(start.elapsed() < Duration::from_secs(2));
+    drop(listener);
+}
+
+#[test]
+fn test_get_invalid_url() {
+    assert_eq!(
+        wifi_http_get("https://example.com/", 100),
+        Err(WifiError::NotSupported)
+    );
+    for url in ["example.com", "http://", "http://host:port/"] {
+        assert_eq!(
+            wifi_http_get(url, 100),
+            Err(WifiError::ConfigurationError),
+            "{url}"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! JSON encoding of WiFi scan results
+
+#![cfg(all(test, feature = "wifi"))]
+
+use hal::wifi::*;
+
+fn test_ap() -> ScanResult {
+    let mut result = ScanResult {
+        ssid_len: 7,
+        bssid: [0xAA, 0xBB, 0xCC, 0x01, 0x02, 0x03],
+        channel: 6,
+        rssi: -72,
+        auth_mode: AuthMode::Wpa2Psk,
+        ..ScanResult::default()
+    };
+    result.ssid[..7].copy_from_slice(b"Test-AP");
+    result
+}
+
+#[test]
+fn test_to_json_format() {
+    assert_eq!(
+        wifi_scan_results_to_json(&[test_ap()]),
+        "[{\"ssid\":\"Test-AP\",\"bssid\":\"AA:BB:CC:01:02:03\",\"channel\":6,\
+         \"rssi\":-72,\"auth_mode\":\"WPA2PSK\"}]"
+    );
+    assert_eq!(wifi_scan_results_to_json(&[]), "[]");
+}
+
+#[test]
+fn test_round_trip() {
+    let json = wifi_scan_results_to_json(&[test_ap()]);
+    let object = json.trim_start_matches('[').trim_end_matches(']');
+    let parsed = wifi_scan_result_from_json(object).unwrap();
+
+    assert_eq!(parsed.ssid_str(), Some("Test-AP"));
+    assert_eq!(parsed.bssid, test_ap().bssid);
+    assert_eq!(parsed.channel, 6);
+    assert_eq!(parsed.rssi, -72);
+    assert_eq!(parsed.auth_mode, AuthMode::Wpa2Psk);
+}
+
+#[test]
+fn test_non_ascii_ssid_is_escaped() {
+    let mut ap = test_ap();
+    ap.ssid[..4].copy_from_slice(&[b'"', 0xC3, 0xA9, 0x01]);
+    ap.ssid_len = 4;
+
+    let json = wifi_scan_results_to_json(&[ap]);
+    assert!(json.contains("\"ssid\":\"\\\"\\u00c3\\u00a9\\u0001\""));
+
+    let object = json.trim_start_matches('[').trim_end_matches(']');
+    let parsed = wifi_scan_result_from_json(object).unwrap();
+    assert_eq!(&parsed.ssid[..parsed.ssid_len], &[b'"', 0xC3, 0xA9, 0x01]);
+}
+
+#[test]
+fn test_from_json_rejects_incomplete() {
+    let json = "{ \"ssid\": \"Test-AP\", \"bssid\": \"AA:BB:CC:01:02:03\", \"channel\": 6 }";
+    assert!(wifi_scan_result_from_json(json).is_none());
+    assert!(wifi_scan_result_from_json("").is_none());
+    assert!(wifi_scan_result_from_json("{\"rssi\":-300}").is_none());
+}
@@ -0,0 +1,220 @@
+//! Integration tests for the Linux WiFi backend (nl80211)
+//!
+//! These talk to the real kernel and need a wireless interface plus
+//! `CAP_NET_ADMIN` (scan triggers are refused without it), so they are
+//! ignored by default. Run them on a capable CI runner with:
+//!
+//! ```text
+//! cargo test -p hal --features wifi --test wifi_linux -- --include-ignored
+//! ```
+//!
+//! No access point needs to be in range: an empty scan passes.
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// The backend keeps global state, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+#[ignore = "needs a wireless interface"]
+fn test_initialize_deinitialize() {
+    let _guard = serial();
+    assert_eq!(wifi_initialize(), Ok(()));
+    assert!(wifi_is_initialized());
+    assert_eq!(wifi_deinitialize(), Ok(()));
+    assert!(!wifi_is_initialized());
+}
+
+#[test]
+#[ignore = "needs a wireless interface"]
+fn test_get_mac_address() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    let mac = wifi_get_mac_address().unwrap();
+    wifi_deinitialize().unwrap();
+    assert_ne!(mac, [0u8; 6]);
+}
+
+#[test]
+#[ignore = "needs a wireless interface and CAP_NET_ADMIN"]
+fn test_start_scan_and_get_results() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    wifi_start_scan().unwrap();
+    thread::sleep(Duration::from_secs(3));
+    assert!(wifi_scan_is_complete().is_ok());
+    let results = wifi_get_scan_results();
+    wifi_deinitialize().unwrap();
+    assert!(results.is_ok());
+}
+
+#[test]
+fn test_scan_on_channels_does_not_panic() {
+    let _guard = serial();
+    // Without a wireless interface this fails cleanly instead
+    let _ = wifi_start_scan_on_channels(&[6]);
+    let _ = wifi_start_scan_for_ssid("rustcam");
+}
+
+#[test]
+fn test_scan_on_invalid_channels() {
+    assert_eq!(
+        wifi_start_scan_on_channels(&[]),
+        Err(WifiError::ConfigurationError)
+    );
+    assert_eq!(
+        wifi_start_scan_on_channels(&[15]),
+        Err(WifiError::ConfigurationError)
+    );
+    assert_eq!(
+        wifi_start_scan_for_ssid(""),
+        Err(WifiError::ConfigurationError)
+    );
+}
+
+#[test]
+#[ignore = "needs a wireless interface and CAP_NET_ADMIN"]
+fn test_directed_scan() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    wifi_start_scan_on_channels(&[1, 6, 11]).unwrap();
+    thread::sleep(Duration::from_secs(3));
+    let results = wifi_get_scan_results();
+    wifi_deinitialize().unwrap();
+    let (results, count) = results.unwrap();
+    assert!(results[..count]
+        .iter()
+        .all(|r| [1, 6, 11].contains(&r.channel)));
+}
+
+#[test]
+#[ignore = "needs a wireless interface"]
+fn test_get_connection_status() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    let status = wifi_get_connection_status();
+    wifi_deinitialize().unwrap();
+    assert!(status.is_ok());
+}
+
+#[test]
+#[ignore = "needs a wireless interface and CAP_NET_ADMIN"]
+fn test_static_ip_round_trip() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    let config = IpConfig {
+        ip: [192, 168, 4, 20],
+        netmask: [255, 255, 255, 0],
+        gateway: [192, 168, 4, 1],
+        dns: [1, 1, 1, 1],
+    };
+    let set = wifi_set_static_ip(&config);
+    let got = wifi_get_ip_config();
+    wifi_deinitialize().unwrap();
+    assert_eq!(set, Ok(()));
+    assert_eq!(got, Ok(config));
+}
+
+#[test]
+fn test_dhcp_client_not_found_names_udhcpc() {
+    let message = WifiError::DhcpClientNotFound.to_string();
+    assert!(message.contains("udhcpc"), "{message}");
+}
+
+#[test]
+#[ignore = "needs CAP_NET_RAW or net.ipv4.ping_group_range"]
+fn test_ping_loopback() {
+    let rtts = wifi_ping([127, 0, 0, 1], 3, 1000).unwrap();
+    assert_eq!(rtts.len(), 3);
+    assert!(rtts.iter().all(|&rtt| rtt != u32::MAX), "{rtts:?}");
+
+    let stats = PingStats::from_rtts(&rtts);
+    assert_eq!((stats.sent, stats.received), (3, 3));
+    assert!(stats.min_rtt_us <= stats.avg_rtt_us && stats.avg_rtt_us <= stats.max_rtt_us);
+}
+
+#[test]
+fn test_resolve_needs_initialization() {
+    let _guard = serial();
+    if !wifi_is_initialized() {
+        assert_eq!(
+            wifi_resolve_hostname("localhost"),
+            Err(WifiError::NotInitialized)
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs a wireless interface"]
+fn test_resolve_localhost() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    let ip = wifi_resolve_hostname("localhost");
+    let all = wifi_resolve_hostname_all("localhost");
+    wifi_deinitialize().unwrap();
+    assert_eq!(ip, Ok([127, 0, 0, 1]));
+    assert!(all.unwrap().contains(&[127, 0, 0, 1]));
+}
+
+/// /proc/net/dev as printed by Linux 6.x (header lines included)
+const PROC_NET_DEV: &str = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  123456     789    0    0    0     0          0         0   123456     789    0    0    0     0       0          0
+ wlan0: 98765432  65432    3   10    0     0          0       120  1234567    8901    2    0    0     0       0          0
+";
+
+#[test]
+fn test_parse_proc_net_dev() {
+    assert_eq!(
+        parse_proc_net_dev(PROC_NET_DEV, "wlan0"),
+        Some(WifiStats {
+            rx_bytes: 98_765_432,
+            rx_packets: 65_432,
+            rx_errors: 3,
+            tx_bytes: 1_234_567,
+            tx_packets: 8_901,
+            tx_errors: 2,
+        })
+    );
+    assert_eq!(
+        parse_proc_net_dev(PROC_NET_DEV, "lo").map(|s| s.tx_packets),
+        Some(789)
+    );
+}
+
+#[test]
+fn test_parse_proc_net_dev_rejects_bad_input() {
+    // Unknown interface, or a prefix of a listed one
+    assert_eq!(parse_proc_net_dev(PROC_NET_DEV, "wlan1"), None);
+    assert_eq!(parse_proc_net_dev(PROC_NET_DEV, "wlan"), None);
+    // Too few counters, or one that is not a number
+    assert_eq!(parse_proc_net_dev("wlan0: 1 2 3\n", "wlan0"), None);
+    let garbled = PROC_NET_DEV.replace("8901", "89x1");
+    assert_eq!(parse_proc_net_dev(&garbled, "wlan0"), None);
+    assert_eq!(parse_proc_net_dev("", "wlan0"), None);
+}
+
+#[test]
+#[ignore = "needs a wireless interface and CAP_NET_ADMIN"]
+fn test_stats_count_scan_traffic() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+    let before = wifi_get_stats();
+    wifi_start_scan().unwrap();
+    thread::sleep(Duration::from_secs(3));
+    let after = wifi_get_stats();
+    wifi_deinitialize().unwrap();
+
+    let (before, after) = (before.unwrap(), after.unwrap());
+    assert!(after.tx_packets > before.tx_packets, "{before:?} {after:?}");
+}
@@ -0,0 +1,129 @@
+//! Tests for the mDNS responder's query matching and DNS wire format
+//!
+//! Responses are built without a socket, so port 5353 is not needed.
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+
+const IP: [u8; 4] = [192, 168, 1, 50];
+
+// Names as length-prefixed labels
+const SERVICE: &[u8] = b"\x05_http\x04_tcp\x05local\x00";
+const INSTANCE: &[u8] = b"\x07rustcam\x05_http\x04_tcp\x05local\x00";
+const HOST: &[u8] = b"\x07rustcam\x05local\x00";
+
+/// Query with ID `id` and one question for `name`
+fn query(id: u16, name: &[u8], qtype: u16, qclass: u16) -> Vec<u8> {
+    let mut packet = Vec::new();
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    packet.extend_from_slice(name);
+    packet.extend_from_slice(&qtype.to_be_bytes());
+    packet.extend_from_slice(&qclass.to_be_bytes());
+    packet
+}
+
+fn respond(query: &[u8]) -> Option<Vec<u8>> {
+    mdns_response("rustcam", "_http._tcp", 80, query, Some(IP)).unwrap()
+}
+
+#[test]
+fn test_ptr_response_bytes() {
+    // Resource records laid out as in RFC 1035 section 4.1.3, uncompressed
+    let expected = [
+        // Header: ID 0, response + authoritative, 4 answers
+        &[
+            0x00, 0x00, 0x84, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00,
+        ][..],
+        // PTR _http._tcp.local -> rustcam._http._tcp.local, shared, TTL 4500
+        SERVICE,
+        &[0x00, 0x0C, 0x00, 0x01, 0x00, 0x00, 0x11, 0x94, 0x00, 0x1A],
+        INSTANCE,
+        // SRV priority 0, weight 0, port 80 -> rustcam.local, cache flush, TTL 120
+        INSTANCE,
+        &[0x00, 0x21, 0x80, 0x01, 0x00, 0x00, 0x00, 0x78, 0x00, 0x15],
+        &[0x00, 0x00, 0x00, 0x00, 0x00, 0x50],
+        HOST,
+        // Empty TXT, cache flush, TTL 4500
+        INSTANCE,
+        &[
+            0x00, 0x10, 0x80, 0x01, 0x00, 0x00, 0x11, 0x94, 0x00, 0x01, 0x00,
+        ],
+        // A rustcam.local -> 192.168.1.50, cache flush, TTL 120
+        HOST,
+        &[0x00, 0x01, 0x80, 0x01, 0x00, 0x00, 0x00, 0x78, 0x00, 0x04],
+        &IP,
+    ]
+    .concat();
+
+    let response = respond(&query(0, SERVICE, 12, 1)).unwrap();
+    assert_eq!(response, expected);
+}
+
+#[test]
+fn test_a_query_answers_host_only() {
+    let response = respond(&query(0x1234, HOST, 1, 1)).unwrap();
+    let expected = [
+        &[
+            0x12, 0x34, 0x84, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
+        ][..],
+        HOST,
+        &[0x00, 0x01, 0x80, 0x01, 0x00, 0x00, 0x00, 0x78, 0x00, 0x04],
+        &IP,
+    ]
+    .concat();
+    assert_eq!(response, expected);
+}
+
+#[test]
+fn test_names_match_case_insensitively() {
+    let upper = b"\x05_HTTP\x04_TCP\x05LOCAL\x00";
+    let response = respond(&query(0, upper, 12, 1));
+    assert!(response.is_some());
+    assert_eq!(response, respond(&query(0, SERVICE, 12, 1)));
+}
+
+#[test]
+fn test_unrelated_queries_are_ignored() {
+    // Another service type
+    assert_eq!(
+        respond(&query(0, b"\x04_ssh\x04_tcp\x05local\x00", 12, 1)),
+        None
+    );
+    // Right name, wrong type
+    assert_eq!(respond(&query(0, SERVICE, 1, 1)), None);
+    // A response rather than a query
+    let mut packet = query(0, SERVICE, 12, 1);
+    packet[2] = 0x84;
+    assert_eq!(respond(&packet), None);
+    // Truncated packets
+    assert_eq!(respond(&[0x00, 0x00, 0x00]), None);
+    assert_eq!(respond(&query(0, SERVICE, 12, 1)[..20]), None);
+}
+
+#[test]
+fn test_invalid_names_are_rejected() {
+    for (hostname, service) in [
+        ("rust.cam", "_http._tcp"),
+        ("", "_http._tcp"),
+        ("rustcam", "_http.._tcp"),
+    ] {
+        assert_eq!(
+            mdns_response(hostname, service, 80, &[], None),
+            Err(WifiError::ConfigurationError),
+            "{hostname} {service}"
+        );
+    }
+    // A trailing .local on the service type is optional
+    assert_eq!(
+        mdns_response(
+            "rustcam",
+            "_http._tcp.local.",
+            80,
+            &query(0, SERVICE, 12, 1),
+            Some(IP)
+        ),
+        Ok(respond(&query(0, SERVICE, 12, 1)))
+    );
+}
@@ -0,0 +1,270 @@
+//! Tests against the mock WiFi backend
+//!
+//! The mock backend only replaces the stub when no platform is selected:
+//!
+//! ```text
+//! cargo test -p hal --no-default-features --features wifi,mock --test wifi_mock
+//! ```
+
+#![cfg(all(
+    test,
+    feature = "mock",
+    not(any(feature = "platform-linux", feature = "platform-nuttx"))
+))]
+
+use hal::wifi::*;
+use std::sync::mpsc;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The mock keeps global state, so tests must not run concurrently
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[test]
+fn test_power_save_round_trip() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+
+    wifi_set_power_save(PowerSaveMode::Maximum).unwrap();
+    assert_eq!(wifi_get_power_save().unwrap(), PowerSaveMode::Maximum);
+
+    wifi_set_power_save(PowerSaveMode::None).unwrap();
+    assert_eq!(wifi_get_power_save().unwrap(), PowerSaveMode::None);
+}
+
+#[test]
+fn test_static_ip_round_trip() {
+    let _guard = serial();
+    wifi_initialize().unwrap();
+
+    let config = IpConfig {
+        ip: [192, 168, 4, 20],
+        netmask: [255, 255, 255, 0],
+        gateway: [192, 168, 4, 1],
+        dns: [1, 1, 1, 1],
+    };
+    wifi_set_static_ip(&config).unwrap();
+    assert_eq!(wifi_get_ip_config().unwrap(), config);
+
+    wifi_set_dhcp().unwrap();
+    assert_eq!(wifi_get_ip_config().unwrap(), IpConfig::default());
+}
+
+#[test]
+fn test_scan_complete_event() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    wifi_register_event_handler(
+        1,
+        Box::new(move |event| {
+            if let WifiEvent::ScanComplete { count } = event {
+                let _ = tx.lock().unwrap().send(count);
+            }
+        }),
+    )
+    .unwrap();
+
+    wifi_start_scan().unwrap();
+    while !wifi_scan_is_complete().unwrap() {
+        thread::sleep(Duration::from_millis(50));
+    }
+    // Sent once per scan, however often completion is polled
+    assert!(wifi_scan_is_complete().unwrap());
+    wifi_unregister_event_handler(1);
+
+    assert_eq!(rx.recv_timeout(Duration::from_secs(1)), Ok(3));
+    assert!(rx.try_recv().is_err());
+}
+
+fn network(ssid: &str, bssid: [u8; 6], channel: u8, rssi: i8) -> ScanResult {
+    let mut result = ScanResult {
+        ssid_len: ssid.len(),
+        bssid,
+        channel,
+        rssi,
+        auth_mode: AuthMode::Wpa3Psk,
+        ..ScanResult::default()
+    };
+    result.ssid[..ssid.len()].copy_from_slice(ssid.as_bytes());
+    result
+}
+
+/// Fields to compare, since `ScanResult` has no `PartialEq`
+fn summary(r: &ScanResult) -> (Option<&str>, [u8; 6], u8, i8, AuthMode) {
+    (r.ssid_str(), r.bssid, r.channel, r.rssi, r.auth_mode)
+}
+
+fn scan_and_wait() -> Vec<ScanResult> {
+    wifi_start_scan().unwrap();
+    assert_eq!(
+        wifi_get_scan_results().unwrap_err(),
+        WifiError::ScanInProgress
+    );
+    while !wifi_scan_is_complete().unwrap() {
+        thread::sleep(Duration::from_millis(50));
+    }
+    let (results, count) = wifi_get_scan_results().unwrap();
+    results[..count].to_vec()
+}
+
+#[test]
+fn test_scan_returns_preset_results() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+
+    let preset = vec![
+        network("Lab", [0x02, 0, 0, 0, 0x10, 0x01], 1, -40),
+        network("Lab-5G", [0x02, 0, 0, 0, 0x10, 0x02], 149, -62),
+    ];
+    wifi_mock_set_scan_results(preset.clone());
+
+    let results = scan_and_wait();
+    let expected: Vec<_> = preset.iter().map(summary).collect();
+    assert_eq!(results.iter().map(summary).collect::<Vec<_>>(), expected);
+
+    // Resetting restores the three default networks
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+    assert_eq!(scan_and_wait().len(), 3);
+}
+
+#[test]
+fn test_handler_can_unregister_itself() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    wifi_register_event_handler(
+        2,
+        Box::new(move |_| {
+            // Handlers run without the registry locked, so this returns
+            wifi_unregister_event_handler(2);
+            let _ = tx.lock().unwrap().send(());
+        }),
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        wifi_start_scan().unwrap();
+        while !wifi_scan_is_complete().unwrap() {
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    assert_eq!(rx.try_recv(), Ok(()));
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn test_keepalive_reconnects() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+    wifi_disconnect().unwrap();
+    assert_eq!(
+        wifi_get_connection_status().unwrap(),
+        ConnectionStatus::Disconnected
+    );
+
+    let interval_ms = 100;
+    let keepalive =
+        WifiKeepAlive::start(StationConfig::new("MockNet", "secret"), interval_ms).unwrap();
+
+    // The first check runs after one interval, so allow two
+    thread::sleep(Duration::from_millis(2 * interval_ms as u64));
+    assert!(keepalive.attempts_made() >= 1);
+    assert_ne!(
+        wifi_get_connection_status().unwrap(),
+        ConnectionStatus::Disconnected
+    );
+
+    keepalive.stop();
+}
+
+#[test]
+fn test_connect_simple_zero_timeout() {
+    let _guard = serial();
+    wifi_mock_reset();
+
+    // The mock takes a moment to associate, so a zero timeout expires
+    assert_eq!(
+        wifi_connect_simple("MockNet", "secret", 0).unwrap_err(),
+        WifiError::Timeout
+    );
+    assert!(wifi_is_initialized());
+
+    // Retrying with a real timeout succeeds
+    let ip = wifi_connect_simple("MockNet", "secret", 5000).unwrap();
+    assert_eq!(ip.ip, [192, 168, 1, 100]);
+
+    wifi_disconnect_simple().unwrap();
+    assert!(!wifi_is_initialized());
+}
+
+/// Retry options that keep the test fast
+fn retry_opts(max_retries: u8) -> WifiConnectOptions {
+    WifiConnectOptions {
+        max_retries,
+        initial_delay_ms: 10,
+        backoff_factor: 2.0,
+        timeout_per_attempt_ms: 1000,
+    }
+}
+
+#[test]
+fn test_connect_with_retry_no_retries() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+    let config = StationConfig::new("MockNet", "secret");
+
+    wifi_connect_with_retry(&config, &retry_opts(0)).unwrap();
+    assert_eq!(wifi_mock_connect_attempts(), 1);
+
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+    wifi_mock_set_connect_fails(true);
+    assert_eq!(
+        wifi_connect_with_retry(&config, &retry_opts(0)),
+        Err(WifiError::ConnectionFailed)
+    );
+    assert_eq!(wifi_mock_connect_attempts(), 1);
+}
+
+#[test]
+fn test_connect_with_retry_gives_up() {
+    let _guard = serial();
+    wifi_mock_reset();
+    wifi_initialize().unwrap();
+    wifi_mock_set_connect_fails(true);
+    let config = StationConfig::new("MockNet", "secret");
+
+    // Delays of 10, 20 and 40 ms before the three retries
+    let started = Instant::now();
+    assert_eq!(
+        wifi_connect_with_retry(&config, &retry_opts(3)),
+        Err(WifiError::ConnectionFailed)
+    );
+    assert_eq!(wifi_mock_connect_attempts(), 4);
+    assert!(started.elapsed() >= Duration::from_millis(70));
+
+    // Retrying cannot help when WiFi is down, so that fails at once
+    wifi_mock_reset();
+    assert_eq!(
+        wifi_connect_with_retry(&config, &retry_opts(3)),
+        Err(WifiError::NotInitialized)
+    );
+    assert_eq!(wifi_mock_connect_attempts(), 0);
+}
@@ -0,0 +1,127 @@
+//! Tests for the MQTT client against a loopback fake broker
+//!
+//! The broker is a std `TcpListener` that records what the client sends
+//! and answers with canned packets, so the bytes can be checked against
+//! the MQTT 3.1.1 specification.
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const LOCALHOST: [u8; 4] = [127, 0, 0, 1];
+
+/// CONNECT for client_id "rustcam", keep-alive 60 s, clean session
+const CONNECT_RUSTCAM: [u8; 21] = [
+    0x10, 19, // fixed header: CONNECT, remaining length
+    0x00, 0x04, b'M', b'Q', b'T', b'T', // protocol name
+    0x04, // protocol level 3.1.1
+    0x02, // connect flags: clean session
+    0x00, 0x3C, // keep-alive
+    0x00, 0x07, b'r', b'u', b's', b't', b'c', b'a', b'm', // client id
+];
+
+/// Spawn a broker that answers CONNECT with `connack` and then runs `session`
+fn broker<T, F>(connack: [u8; 4], session: F) -> (u16, JoinHandle<(Vec<u8>, T)>)
+where
+    T: Send + 'static,
+    F: FnOnce(&mut TcpStream) -> T + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let connect = read_packet(&mut stream);
+        stream.write_all(&connack).unwrap();
+        let result = session(&mut stream);
+        (connect, result)
+    });
+    (port, handle)
+}
+
+/// Read one packet whose remaining length fits in a single byte
+fn read_packet(stream: &mut TcpStream) -> Vec<u8> {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert!(header[1] < 0x80, "multi-byte remaining length");
+    let mut packet = vec![0u8; 2 + header[1] as usize];
+    packet[..2].copy_from_slice(&header);
+    stream.read_exact(&mut packet[2..]).unwrap();
+    packet
+}
+
+#[test]
+fn test_connect_packet_bytes() {
+    let (port, handle) = broker([0x20, 2, 0, 0], |_| ());
+    let client = MqttClient::connect(LOCALHOST, port, "rustcam", 60).unwrap();
+    assert_eq!(client.client_id(), "rustcam");
+    assert_eq!(client.keep_alive_secs(), 60);
+
+    let (connect, ()) = handle.join().unwrap();
+    assert_eq!(connect, CONNECT_RUSTCAM);
+}
+
+#[test]
+fn test_publish_ping_disconnect() {
+    let (port, handle) = broker([0x20, 2, 0, 0], |stream| {
+        let publish = read_packet(stream);
+        let pingreq = read_packet(stream);
+        stream.write_all(&[0xD0, 0]).unwrap();
+        let disconnect = read_packet(stream);
+        (publish, pingreq, disconnect)
+    });
+
+    let mut client = MqttClient::connect(LOCALHOST, port, "rustcam", 60).unwrap();
+    client.publish("a/b", b"23.5", 0).unwrap();
+    client.ping().unwrap();
+    client.disconnect().unwrap();
+
+    let (_, (publish, pingreq, disconnect)) = handle.join().unwrap();
+    assert_eq!(
+        publish,
+        [0x30, 9, 0x00, 0x03, b'a', b'/', b'b', b'2', b'3', b'.', b'5']
+    );
+    assert_eq!(pingreq, [0xC0, 0]);
+    assert_eq!(disconnect, [0xE0, 0]);
+}
+
+#[test]
+fn test_connect_refused_by_broker() {
+    // Return code 5: not authorized
+    let (port, handle) = broker([0x20, 2, 0, 5], |_| ());
+    assert_eq!(
+        MqttClient::connect(LOCALHOST, port, "rustcam", 60).err(),
+        Some(WifiError::AuthenticationFailed)
+    );
+    handle.join().unwrap();
+
+    // Return code 2: identifier rejected
+    let (port, handle) = broker([0x20, 2, 0, 2], |_| ());
+    assert_eq!(
+        MqttClient::connect(LOCALHOST, port, "rustcam", 60).err(),
+        Some(WifiError::ConnectionFailed)
+    );
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_publish_rejects_unsupported() {
+    let (port, handle) = broker([0x20, 2, 0, 0], |_| ());
+    let mut client = MqttClient::connect(LOCALHOST, port, "rustcam", 60).unwrap();
+    assert_eq!(client.publish("a/b", b"x", 1), Err(WifiError::NotSupported));
+    assert_eq!(
+        client.publish("a/#", b"x", 0),
+        Err(WifiError::ConfigurationError)
+    );
+    assert_eq!(
+        client.publish("", b"x", 0),
+        Err(WifiError::ConfigurationError)
+    );
+    handle.join().unwrap();
+}
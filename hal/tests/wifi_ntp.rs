@@ -0,0 +1,21 @@
+//! Tests for the NTP to Unix time conversion
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+
+#[test]
+fn test_ntp_epoch_offset() {
+    // 2024-01-11 00:00:00 UTC
+    assert_eq!(ntp_to_unix(3_913_900_800), 1_704_912_000);
+    // The Unix epoch itself
+    assert_eq!(ntp_to_unix(2_208_988_800), 0);
+    assert_eq!(ntp_to_unix(u32::MAX), u32::MAX as u64 - 2_208_988_800);
+}
+
+#[test]
+fn test_ntp_era_rollover() {
+    // NTP era 1 starts at 2036-02-07 06:28:16 UTC
+    assert_eq!(ntp_to_unix(0), 2_085_978_496);
+    assert_eq!(ntp_to_unix(2_208_988_799), u32::MAX as u64);
+}
@@ -0,0 +1,70 @@
+//! Scan result helpers: deduplication and table formatting
+
+#![cfg(all(test, feature = "wifi"))]
+
+use hal::wifi::*;
+
+fn network(ssid: &str, last_bssid_byte: u8, channel: u8, rssi: i8) -> ScanResult {
+    let mut result = ScanResult {
+        ssid_len: ssid.len(),
+        bssid: [0xAA, 0xBB, 0xCC, 0x00, 0x00, last_bssid_byte],
+        channel,
+        rssi,
+        auth_mode: AuthMode::Wpa2Psk,
+        ..ScanResult::default()
+    };
+    result.ssid[..ssid.len()].copy_from_slice(ssid.as_bytes());
+    result
+}
+
+#[test]
+fn test_table_columns_align() {
+    let table =
+        wifi_scan_results_to_table(&[network("Test-AP", 0x01, 6, -72), network("", 0x02, 149, -5)]);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(
+        lines,
+        [
+            "SSID                             BSSID              CH  RSSI  AUTH",
+            "Test-AP                          AA:BB:CC:00:00:01   6   -72  WPA2PSK",
+            "<hidden>                         AA:BB:CC:00:00:02 149    -5  WPA2PSK",
+        ]
+    );
+}
+
+#[test]
+fn test_table_empty() {
+    assert_eq!(wifi_scan_results_to_table(&[]).lines().count(), 1);
+}
+
+#[test]
+fn test_dedup_keeps_strongest_bssid() {
+    let results = dedup_scan_results(vec![
+        network("Weak", 0x01, 6, -80),
+        network("Other", 0x02, 11, -60),
+        network("Strong", 0x01, 6, -50),
+    ]);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].ssid_str(), Some("Strong"));
+    assert_eq!(results[0].rssi, -50);
+    assert_eq!(results[1].ssid_str(), Some("Other"));
+}
+
+#[test]
+fn test_dedup_in_place() {
+    let mut results = [
+        network("A", 0x01, 1, -70),
+        network("B", 0x02, 1, -40),
+        network("A", 0x01, 1, -90),
+        network("C", 0x03, 1, -70),
+    ];
+
+    let count = dedup_scan_results_in_place(&mut results);
+    let order: Vec<(&str, i8)> = results[..count]
+        .iter()
+        .map(|r| (r.ssid_str().unwrap(), r.rssi))
+        .collect();
+    assert_eq!(order, [("B", -40), ("A", -70), ("C", -70)]);
+}
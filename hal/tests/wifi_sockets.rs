@@ -0,0 +1,126 @@
+//! Tests for the libc socket wrappers over the loopback interface
+//!
+//! The peers are std sockets, so no WiFi interface is needed.
+
+#![cfg(all(test, feature = "platform-linux", feature = "wifi"))]
+
+use hal::wifi::*;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+const LOCALHOST: [u8; 4] = [127, 0, 0, 1];
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn test_tcp_hello_exchange() {
+    assert_send::<TcpStream>();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (mut stream, peer) = listener.accept().unwrap();
+        let mut buf = [0u8; 5];
+        stream.read_exact(&mut buf).unwrap();
+        stream.write_all(b"hello").unwrap();
+        (buf, peer.port())
+    });
+
+    let mut stream = TcpStream::connect(LOCALHOST, port, 1000).unwrap();
+    stream.set_nodelay(true).unwrap();
+    stream.write_all(b"hello").unwrap();
+    let mut reply = [0u8; 5];
+    stream.read_exact(&mut reply).unwrap();
+    assert_eq!(&reply, b"hello");
+
+    let (received, peer_port) = server.join().unwrap();
+    assert_eq!(&received, b"hello");
+    assert_eq!(stream.local_addr().unwrap(), (LOCALHOST, peer_port));
+}
+
+#[test]
+fn test_tcp_read_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let mut stream = TcpStream::connect(LOCALHOST, port, 1000).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .unwrap();
+    let err = stream.read(&mut [0u8; 1]).unwrap_err();
+    assert!(
+        matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut),
+        "{err}"
+    );
+
+    assert_eq!(
+        stream.set_read_timeout(Some(Duration::ZERO)).unwrap_err(),
+        WifiError::ConfigurationError
+    );
+    drop(listener);
+}
+
+#[test]
+fn test_tcp_connect_refused() {
+    // Bind then close to get a port nobody listens on
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    assert_eq!(
+        TcpStream::connect(LOCALHOST, port, 1000).err(),
+        Some(WifiError::ConnectionFailed)
+    );
+}
+
+#[test]
+fn test_udp_send_recv_loopback() {
+    let a = UdpSocket::bind_any().unwrap();
+    let b = UdpSocket::bind_any().unwrap();
+    let (_, a_port) = a.local_addr().unwrap();
+    let (_, b_port) = b.local_addr().unwrap();
+    b.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+    assert_eq!(a.send_to(b"ping", LOCALHOST, b_port).unwrap(), 4);
+    let mut buf = [0u8; 16];
+    let (addr, port, n) = b.recv_from(&mut buf).unwrap();
+    assert_eq!((addr, port, n), (LOCALHOST, a_port, 4));
+    assert_eq!(&buf[..n], b"ping");
+}
+
+#[test]
+fn test_udp_interop_with_std() {
+    let peer = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+    let peer_port = peer.local_addr().unwrap().port();
+
+    let socket = UdpSocket::bind_any().unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(1)))
+        .unwrap();
+    socket.send_to(b"hello", LOCALHOST, peer_port).unwrap();
+
+    let mut buf = [0u8; 16];
+    let (n, from) = peer.recv_from(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"hello");
+    peer.send_to(b"world", from).unwrap();
+
+    let (addr, port, n) = socket.recv_from(&mut buf).unwrap();
+    assert_eq!((addr, port), (LOCALHOST, peer_port));
+    assert_eq!(&buf[..n], b"world");
+}
+
+#[test]
+fn test_udp_read_timeout() {
+    let socket = UdpSocket::bind_any().unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .unwrap();
+    assert_eq!(
+        socket.recv_from(&mut [0u8; 1]).err(),
+        Some(WifiError::Timeout)
+    );
+}
@@ -0,0 +1,129 @@
+//! UART HAL
+//!
+//! Provides serial port access for peripherals such as GPS receivers and
+//! cellular modems. Implementation is selected at compile time based on
+//! platform feature.
+//!
+//! - Linux and NuttX: POSIX termios on the tty device (/dev/ttyS0, ...)
+
+// Platform-specific implementations
+
+// Linux and NuttX both provide POSIX termios
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod termios;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+/// UART operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UartError {
+    /// Serial device not found
+    NotFound,
+    /// Permission denied opening the device
+    PermissionDenied,
+    /// Unsupported baud rate, data bits or stop bits
+    InvalidConfig,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for UartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UartError::NotFound => write!(f, "Serial device not found"),
+            UartError::PermissionDenied => write!(f, "Permission denied"),
+            UartError::InvalidConfig => write!(f, "Invalid UART configuration"),
+            UartError::NotSupported => write!(f, "Not supported on this platform"),
+            UartError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl UartError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            UartError::NotFound => Some("check the device path, e.g. /dev/ttyS0 or /dev/ttyUSB0"),
+            UartError::PermissionDenied if cfg!(feature = "platform-linux") => {
+                Some("add the user to the dialout group")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Result type for UART operations
+pub type UartResult<T> = Result<T, UartError>;
+
+/// Parity bit setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Parity {
+    /// No parity bit
+    #[default]
+    None,
+    /// Odd parity
+    Odd,
+    /// Even parity
+    Even,
+}
+
+/// Serial line settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UartConfig {
+    /// Baud rate (standard rates from 1200 to 921600)
+    pub baud: u32,
+    /// Data bits per character (5-8)
+    pub data_bits: u8,
+    /// Stop bits (1 or 2)
+    pub stop_bits: u8,
+    /// Parity bit setting
+    pub parity: Parity,
+}
+
+impl Default for UartConfig {
+    /// 115200 baud, 8N1
+    fn default() -> Self {
+        Self {
+            baud: 115200,
+            data_bits: 8,
+            stop_bits: 1,
+            parity: Parity::None,
+        }
+    }
+}
+
+/// An open serial port in raw mode without flow control
+#[derive(Debug)]
+pub struct Uart {
+    file: File,
+}
+
+impl Read for Uart {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Write for Uart {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
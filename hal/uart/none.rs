@@ -0,0 +1,21 @@
+//! UART HAL stub for unsupported platforms
+
+use super::{Uart, UartConfig, UartError, UartResult};
+use std::time::Duration;
+
+impl Uart {
+    /// Open a serial device (stub: returns NotSupported)
+    pub fn open(_port: &str, _config: &UartConfig) -> UartResult<Uart> {
+        Err(UartError::NotSupported)
+    }
+
+    /// Set read timeout (stub: returns NotSupported)
+    pub fn set_read_timeout(&self, _timeout: Option<Duration>) -> UartResult<()> {
+        Err(UartError::NotSupported)
+    }
+
+    /// Wait for transmission (stub: returns NotSupported)
+    pub fn flush(&self) -> UartResult<()> {
+        Err(UartError::NotSupported)
+    }
+}
@@ -0,0 +1,158 @@
+//! POSIX termios UART implementation (Linux and NuttX)
+//!
+//! The port is put in raw mode: no line editing, echo, signals, character
+//! translation or software/hardware flow control.
+
+use super::{Parity, Uart, UartConfig, UartError, UartResult};
+use std::fs::OpenOptions;
+use std::io;
+use std::mem::MaybeUninit;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+
+/// Map an errno value to a UART error
+fn map_errno(errno: i32) -> UartError {
+    match errno {
+        libc::ENOENT | libc::ENODEV | libc::ENXIO => UartError::NotFound,
+        libc::EACCES | libc::EPERM => UartError::PermissionDenied,
+        _ => UartError::SystemError(errno),
+    }
+}
+
+fn last_error() -> UartError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+/// termios speed constant for a baud rate
+fn baud_to_speed(baud: u32) -> Option<libc::speed_t> {
+    let speed = match baud {
+        1200 => libc::B1200,
+        2400 => libc::B2400,
+        4800 => libc::B4800,
+        9600 => libc::B9600,
+        19200 => libc::B19200,
+        38400 => libc::B38400,
+        57600 => libc::B57600,
+        115200 => libc::B115200,
+        230400 => libc::B230400,
+        460800 => libc::B460800,
+        921600 => libc::B921600,
+        _ => return None,
+    };
+    Some(speed)
+}
+
+impl Uart {
+    /// Open a serial device (e.g. "/dev/ttyS0") and apply `config`
+    pub fn open(port: &str, config: &UartConfig) -> UartResult<Uart> {
+        let speed = baud_to_speed(config.baud).ok_or(UartError::InvalidConfig)?;
+        let char_size = match config.data_bits {
+            5 => libc::CS5,
+            6 => libc::CS6,
+            7 => libc::CS7,
+            8 => libc::CS8,
+            _ => return Err(UartError::InvalidConfig),
+        };
+        if config.stop_bits != 1 && config.stop_bits != 2 {
+            return Err(UartError::InvalidConfig);
+        }
+
+        // O_NOCTTY: don't become the controlling terminal of this process
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .custom_flags(libc::O_NOCTTY)
+            .open(port)
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+        let fd = file.as_raw_fd();
+
+        let mut tio = get_attr(fd)?;
+
+        // Raw mode
+        tio.c_iflag &= !(libc::IGNBRK | libc::BRKINT | libc::PARMRK | libc::ISTRIP
+            | libc::INLCR | libc::IGNCR | libc::ICRNL | libc::IXON | libc::IXOFF | libc::IXANY);
+        tio.c_oflag &= !libc::OPOST;
+        tio.c_lflag &= !(libc::ECHO | libc::ECHONL | libc::ICANON | libc::ISIG | libc::IEXTEN);
+
+        // Frame format, no hardware flow control, ignore modem lines
+        tio.c_cflag &= !(libc::CSIZE | libc::PARENB | libc::PARODD | libc::CSTOPB | libc::CRTSCTS);
+        tio.c_cflag |= char_size | libc::CREAD | libc::CLOCAL;
+        if config.stop_bits == 2 {
+            tio.c_cflag |= libc::CSTOPB;
+        }
+        match config.parity {
+            Parity::None => {}
+            Parity::Odd => {
+                tio.c_cflag |= libc::PARENB | libc::PARODD;
+                tio.c_iflag |= libc::INPCK;
+            }
+            Parity::Even => {
+                tio.c_cflag |= libc::PARENB;
+                tio.c_iflag |= libc::INPCK;
+            }
+        }
+
+        // Blocking reads returning as soon as one byte is available
+        tio.c_cc[libc::VMIN] = 1;
+        tio.c_cc[libc::VTIME] = 0;
+
+        unsafe {
+            if libc::cfsetispeed(&mut tio, speed) < 0 || libc::cfsetospeed(&mut tio, speed) < 0 {
+                return Err(UartError::InvalidConfig);
+            }
+        }
+        set_attr(fd, &tio)?;
+
+        // Drop anything received before the port was configured
+        unsafe { libc::tcflush(fd, libc::TCIOFLUSH) };
+
+        Ok(Uart { file })
+    }
+
+    /// Set how long a read waits for data
+    ///
+    /// `None` blocks until at least one byte arrives. Otherwise reads return
+    /// after `timeout` (rounded up to 0.1 s, at most 25.5 s) with whatever was
+    /// received, possibly nothing; a zero timeout makes reads non-blocking.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> UartResult<()> {
+        let fd = self.file.as_raw_fd();
+        let mut tio = get_attr(fd)?;
+        match timeout {
+            None => {
+                tio.c_cc[libc::VMIN] = 1;
+                tio.c_cc[libc::VTIME] = 0;
+            }
+            Some(timeout) => {
+                // VTIME is in tenths of a second
+                let tenths = timeout.as_millis().div_ceil(100).min(255);
+                tio.c_cc[libc::VMIN] = 0;
+                tio.c_cc[libc::VTIME] = tenths as libc::cc_t;
+            }
+        }
+        set_attr(fd, &tio)
+    }
+
+    /// Wait until all written data has been transmitted
+    pub fn flush(&self) -> UartResult<()> {
+        if unsafe { libc::tcdrain(self.file.as_raw_fd()) } < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+}
+
+fn get_attr(fd: libc::c_int) -> UartResult<libc::termios> {
+    let mut tio = MaybeUninit::<libc::termios>::uninit();
+    if unsafe { libc::tcgetattr(fd, tio.as_mut_ptr()) } < 0 {
+        return Err(last_error());
+    }
+    Ok(unsafe { tio.assume_init() })
+}
+
+fn set_attr(fd: libc::c_int, tio: &libc::termios) -> UartResult<()> {
+    if unsafe { libc::tcsetattr(fd, libc::TCSANOW, tio) } < 0 {
+        return Err(last_error());
+    }
+    Ok(())
+}
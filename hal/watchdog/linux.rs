@@ -0,0 +1,89 @@
+//! Linux watchdog implementation
+//!
+//! On Linux, opening /dev/watchdog0 arms the watchdog immediately, so the
+//! device is only opened by `start()`; until then the handle is a no-op.
+//! `stop()` uses the magic close ('V') to disarm it.
+
+use super::{Watchdog, WatchdogError, WdtResult};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::os::unix::io::AsRawFd;
+
+const WATCHDOG_DEVICE: &str = "/dev/watchdog0";
+
+// ioctl requests (from linux/watchdog.h)
+const WDIOC_KEEPALIVE: libc::c_ulong = 0x8004_5705; // _IOR('W', 5, int)
+const WDIOC_SETTIMEOUT: libc::c_ulong = 0xC004_5706; // _IOWR('W', 6, int)
+
+/// Map an I/O error to a watchdog error
+fn map_io_error(e: io::Error) -> WatchdogError {
+    match e.raw_os_error() {
+        Some(libc::ENOENT) | Some(libc::ENODEV) => WatchdogError::NotFound,
+        // Another process holds the device open
+        Some(libc::EBUSY) => WatchdogError::AlreadyStarted,
+        Some(errno) => WatchdogError::SystemError(errno),
+        None => WatchdogError::SystemError(-1),
+    }
+}
+
+impl Watchdog {
+    /// Create a watchdog handle (the device is not touched until `start()`)
+    pub fn open() -> WdtResult<Watchdog> {
+        Ok(Watchdog { fd: None })
+    }
+
+    /// Arm the watchdog with a timeout (rounded up to whole seconds)
+    pub fn start(&mut self, timeout_ms: u32) -> WdtResult<()> {
+        if self.fd.is_some() {
+            return Err(WatchdogError::AlreadyStarted);
+        }
+
+        let file = OpenOptions::new()
+            .write(true)
+            .open(WATCHDOG_DEVICE)
+            .map_err(map_io_error)?;
+
+        let mut timeout_s = timeout_ms.div_ceil(1000).max(1) as libc::c_int;
+        let ret = unsafe { libc::ioctl(file.as_raw_fd(), WDIOC_SETTIMEOUT, &mut timeout_s) };
+        // Keep the handle even if the timeout was rejected so it gets disarmed
+        self.fd = Some(file);
+        if ret < 0 {
+            let err = map_io_error(io::Error::last_os_error());
+            let _ = self.stop();
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Reset the watchdog countdown
+    pub fn pet(&self) -> WdtResult<()> {
+        let file = self.fd.as_ref().ok_or(WatchdogError::NotStarted)?;
+        let mut dummy: libc::c_int = 0;
+        if unsafe { libc::ioctl(file.as_raw_fd(), WDIOC_KEEPALIVE, &mut dummy) } < 0 {
+            return Err(map_io_error(io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    /// Disarm the watchdog
+    pub fn stop(&mut self) -> WdtResult<()> {
+        let file = self.fd.take().ok_or(WatchdogError::NotStarted)?;
+        // Magic close: drivers disarm on close only after a 'V' is written
+        (&file).write_all(b"V").map_err(map_io_error)
+    }
+
+    /// Check whether the watchdog is armed
+    ///
+    /// The device stays armed for as long as it is held open.
+    pub fn is_enabled(&self) -> bool {
+        self.fd.is_some()
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        if self.fd.is_some() {
+            let _ = self.stop();
+        }
+    }
+}
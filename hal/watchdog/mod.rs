@@ -0,0 +1,80 @@
+//! Hardware watchdog HAL
+//!
+//! Resets the system if the application stops petting the watchdog.
+//! Implementation is selected at compile time based on platform feature.
+//!
+//! - NuttX: Uses the watchdog driver (/dev/watchdog0) via WDIOC_* ioctls
+//! - Linux: No-op until started; `start()` arms /dev/watchdog0
+
+// Platform-specific implementations
+
+// NuttX uses the watchdog lower-half driver
+#[cfg(feature = "platform-nuttx")]
+mod nuttx;
+
+// Linux uses the watchdog device API
+#[cfg(feature = "platform-linux")]
+mod linux;
+
+// Fallback stub for other platforms
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+mod none;
+
+use core::fmt;
+use std::fs::File;
+
+/// Watchdog operation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogError {
+    /// Watchdog device not found
+    NotFound,
+    /// Watchdog already started
+    AlreadyStarted,
+    /// Watchdog not started
+    NotStarted,
+    /// Operation not supported on this platform
+    NotSupported,
+    /// System error with errno
+    SystemError(i32),
+}
+
+impl fmt::Display for WatchdogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchdogError::NotFound => write!(f, "Watchdog device not found"),
+            WatchdogError::AlreadyStarted => write!(f, "Watchdog already started"),
+            WatchdogError::NotStarted => write!(f, "Watchdog not started"),
+            WatchdogError::NotSupported => write!(f, "Not supported on this platform"),
+            WatchdogError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl WatchdogError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            WatchdogError::NotFound => Some("check /dev/watchdog0 exists"),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for watchdog operations
+pub type WdtResult<T> = Result<T, WatchdogError>;
+
+/// Handle to the system watchdog timer
+///
+/// The watchdog is stopped when the handle is dropped, so a clean exit
+/// never triggers a reset.
+#[derive(Debug)]
+pub struct Watchdog {
+    #[cfg_attr(not(any(feature = "platform-linux", feature = "platform-nuttx")), allow(dead_code))]
+    fd: Option<File>,
+}
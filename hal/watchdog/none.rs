@@ -0,0 +1,30 @@
+//! Stub watchdog implementation for unsupported platforms
+
+use super::{Watchdog, WatchdogError, WdtResult};
+
+impl Watchdog {
+    /// Open the watchdog device (stub: returns NotSupported)
+    pub fn open() -> WdtResult<Watchdog> {
+        Err(WatchdogError::NotSupported)
+    }
+
+    /// Arm the watchdog (stub: returns NotSupported)
+    pub fn start(&mut self, _timeout_ms: u32) -> WdtResult<()> {
+        Err(WatchdogError::NotSupported)
+    }
+
+    /// Reset the watchdog countdown (stub: returns NotSupported)
+    pub fn pet(&self) -> WdtResult<()> {
+        Err(WatchdogError::NotSupported)
+    }
+
+    /// Disarm the watchdog (stub: returns NotSupported)
+    pub fn stop(&mut self) -> WdtResult<()> {
+        Err(WatchdogError::NotSupported)
+    }
+
+    /// Check whether the watchdog is armed (stub: always false)
+    pub fn is_enabled(&self) -> bool {
+        false
+    }
+}
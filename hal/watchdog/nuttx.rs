@@ -0,0 +1,121 @@
+//! Watchdog HAL for NuttX
+//!
+//! Uses the watchdog lower-half character driver (/dev/watchdog0). Opening
+//! the device does not arm it; that is done explicitly with WDIOC_START.
+
+use super::{Watchdog, WatchdogError, WdtResult};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+const WATCHDOG_DEVICE: &str = "/dev/watchdog0";
+
+// Watchdog ioctl commands (from nuttx/timers/watchdog.h)
+// _WDIOC(nr) = _IOC(_WDIOCBASE, nr), _WDIOCBASE = 0x0200
+const WDIOC_START: i32 = 0x0201;
+const WDIOC_STOP: i32 = 0x0202;
+const WDIOC_GETSTATUS: i32 = 0x0203;
+const WDIOC_SETTIMEOUT: i32 = 0x0204;
+const WDIOC_KEEPALIVE: i32 = 0x0206;
+
+// watchdog_status_s.flags
+const WDFLAGS_ACTIVE: u32 = 1 << 0;
+
+/// struct watchdog_status_s
+#[repr(C)]
+#[derive(Default)]
+struct WatchdogStatus {
+    flags: u32,
+    timeout: u32,
+    timeleft: u32,
+}
+
+// NuttX ioctl uses int for request, not unsigned long like Linux
+extern "C" {
+    fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+}
+
+/// Map an errno value to a watchdog error
+fn map_errno(errno: i32) -> WatchdogError {
+    match errno {
+        libc::ENOENT | libc::ENODEV => WatchdogError::NotFound,
+        _ => WatchdogError::SystemError(errno),
+    }
+}
+
+fn last_error() -> WatchdogError {
+    map_errno(io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+impl Watchdog {
+    /// Open the watchdog device without arming it
+    pub fn open() -> WdtResult<Watchdog> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(WATCHDOG_DEVICE)
+            .map_err(|e| map_errno(e.raw_os_error().unwrap_or(0)))?;
+        Ok(Watchdog { fd: Some(file) })
+    }
+
+    fn device(&self) -> WdtResult<&File> {
+        self.fd.as_ref().ok_or(WatchdogError::NotFound)
+    }
+
+    fn command(&self, request: i32, arg: libc::c_ulong) -> WdtResult<()> {
+        let fd = self.device()?.as_raw_fd();
+        if unsafe { ioctl(fd, request, arg) } < 0 {
+            return Err(last_error());
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> WdtResult<WatchdogStatus> {
+        let fd = self.device()?.as_raw_fd();
+        let mut status = WatchdogStatus::default();
+        if unsafe { ioctl(fd, WDIOC_GETSTATUS, &mut status as *mut WatchdogStatus) } < 0 {
+            return Err(last_error());
+        }
+        Ok(status)
+    }
+
+    /// Arm the watchdog with a timeout in milliseconds
+    pub fn start(&mut self, timeout_ms: u32) -> WdtResult<()> {
+        if self.is_enabled() {
+            return Err(WatchdogError::AlreadyStarted);
+        }
+        self.command(WDIOC_SETTIMEOUT, timeout_ms as libc::c_ulong)?;
+        self.command(WDIOC_START, 0)
+    }
+
+    /// Reset the watchdog countdown
+    pub fn pet(&self) -> WdtResult<()> {
+        if !self.is_enabled() {
+            return Err(WatchdogError::NotStarted);
+        }
+        self.command(WDIOC_KEEPALIVE, 0)
+    }
+
+    /// Disarm the watchdog
+    pub fn stop(&mut self) -> WdtResult<()> {
+        if !self.is_enabled() {
+            return Err(WatchdogError::NotStarted);
+        }
+        self.command(WDIOC_STOP, 0)
+    }
+
+    /// Check whether the watchdog is armed
+    pub fn is_enabled(&self) -> bool {
+        self.status()
+            .map(|status| status.flags & WDFLAGS_ACTIVE != 0)
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        if self.is_enabled() {
+            let _ = self.stop();
+        }
+    }
+}
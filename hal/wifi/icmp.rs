@@ -0,0 +1,118 @@
+//! ICMP echo (ping) on libc sockets
+//!
+//! Shared by the Linux and NuttX `wifi_ping`, which only differ in the
+//! kind of socket they open.
+
+use std::time::{Duration, Instant};
+
+/// ICMP message types
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMP_ECHO_REQUEST: u8 = 8;
+
+/// Build an ICMP echo request (header plus 8 bytes of payload)
+fn icmp_echo_request(ident: u16, seq: u16) -> [u8; 16] {
+    let mut packet = [0u8; 16];
+    packet[0] = ICMP_ECHO_REQUEST;
+    packet[4..6].copy_from_slice(&ident.to_be_bytes());
+    packet[6..8].copy_from_slice(&seq.to_be_bytes());
+    packet[8..].copy_from_slice(b"rustcam\0");
+
+    // One's complement checksum over the whole message
+    let mut sum: u32 = packet
+        .chunks(2)
+        .map(|w| u16::from_be_bytes([w[0], w[1]]) as u32)
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    packet[2..4].copy_from_slice(&(!(sum as u16)).to_be_bytes());
+    packet
+}
+
+/// Wait for the echo reply matching `seq`, returning the RTT in
+/// microseconds or `u32::MAX` on timeout
+///
+/// Raw sockets deliver the IP header in front of the ICMP message and see
+/// every ICMP packet on the host, so the identifier is checked as well.
+fn icmp_wait_reply(
+    fd: i32,
+    raw: bool,
+    ident: u16,
+    seq: u16,
+    sent_at: Instant,
+    timeout_ms: u32,
+) -> u32 {
+    let deadline = sent_at + Duration::from_millis(timeout_ms as u64);
+    let mut buf = [0u8; 1500];
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return u32::MAX;
+        }
+
+        let mut pfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let wait_ms = remaining.as_micros().div_ceil(1000) as libc::c_int;
+        if unsafe { libc::poll(&mut pfd, 1, wait_ms) } <= 0 {
+            continue;
+        }
+
+        let len = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if len <= 0 {
+            continue;
+        }
+        let data = &buf[..len as usize];
+
+        let icmp = if raw {
+            let ihl = (data[0] & 0x0f) as usize * 4;
+            &data[ihl.min(data.len())..]
+        } else {
+            data
+        };
+        if icmp.len() < 8 || icmp[0] != ICMP_ECHO_REPLY {
+            continue;
+        }
+
+        let reply_ident = u16::from_be_bytes([icmp[4], icmp[5]]);
+        let reply_seq = u16::from_be_bytes([icmp[6], icmp[7]]);
+        if reply_seq == seq && (!raw || reply_ident == ident) {
+            return sent_at.elapsed().as_micros().min((u32::MAX - 1) as u128) as u32;
+        }
+    }
+}
+
+/// Send one echo request per sequence number and collect the RTTs
+///
+/// `raw` tells whether `fd` is a raw socket or an ICMP datagram socket.
+/// Missing replies are reported as `u32::MAX`.
+pub(super) fn icmp_ping(fd: i32, raw: bool, ip: [u8; 4], count: u8, timeout_ms: u32) -> Vec<u32> {
+    let ident = std::process::id() as u16;
+    let mut dest: libc::sockaddr_in = unsafe { core::mem::zeroed() };
+    dest.sin_family = libc::AF_INET as libc::sa_family_t;
+    dest.sin_addr.s_addr = u32::from_ne_bytes(ip);
+
+    (0..count as u16)
+        .map(|seq| {
+            let packet = icmp_echo_request(ident, seq);
+            let sent_at = Instant::now();
+            let ret = unsafe {
+                libc::sendto(
+                    fd,
+                    packet.as_ptr() as *const libc::c_void,
+                    packet.len(),
+                    0,
+                    &dest as *const libc::sockaddr_in as *const libc::sockaddr,
+                    core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                )
+            };
+            if ret < 0 {
+                return u32::MAX;
+            }
+            icmp_wait_reply(fd, raw, ident, seq, sent_at, timeout_ms)
+        })
+        .collect()
+}
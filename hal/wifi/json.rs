@@ -0,0 +1,236 @@
+//! JSON encoding of scan results, e.g. for a provisioning portal
+//!
+//! Hand-built to avoid a serde dependency:
+//!
+//! ```text
+//! [{"ssid":"Test-AP","bssid":"AA:BB:CC:DD:EE:FF","channel":6,"rssi":-65,"auth_mode":"WPA2PSK"}]
+//! ```
+//!
+//! SSIDs are raw bytes, so every byte outside printable ASCII is written as
+//! its own `\u00XX` escape. Parsing maps those back byte for byte.
+
+use super::{AuthMode, ScanResult};
+use core::fmt::Write;
+
+/// Roughly one encoded result, used to size the output up front
+const RESULT_JSON_CAPACITY: usize = 112;
+
+/// Upper-case name of an auth mode, as used in JSON and tables
+pub(super) fn auth_mode_name(mode: AuthMode) -> &'static str {
+    match mode {
+        AuthMode::Open => "OPEN",
+        AuthMode::Wep => "WEP",
+        AuthMode::WpaPsk => "WPAPSK",
+        AuthMode::Wpa2Psk => "WPA2PSK",
+        AuthMode::Wpa3Psk => "WPA3PSK",
+        AuthMode::WpaWpa2Psk => "WPAWPA2PSK",
+        AuthMode::Unknown => "UNKNOWN",
+    }
+}
+
+fn auth_mode_from_name(name: &[u8]) -> Option<AuthMode> {
+    Some(match name {
+        b"OPEN" => AuthMode::Open,
+        b"WEP" => AuthMode::Wep,
+        b"WPAPSK" => AuthMode::WpaPsk,
+        b"WPA2PSK" => AuthMode::Wpa2Psk,
+        b"WPA3PSK" => AuthMode::Wpa3Psk,
+        b"WPAWPA2PSK" => AuthMode::WpaWpa2Psk,
+        b"UNKNOWN" => AuthMode::Unknown,
+        _ => return None,
+    })
+}
+
+/// Append `bytes` as a JSON string
+fn write_json_bytes(out: &mut String, bytes: &[u8]) {
+    out.push('"');
+    for &b in bytes {
+        match b {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            0x20..=0x7e => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\u{:04x}", b);
+            }
+        }
+    }
+    out.push('"');
+}
+
+/// Serialize scan results as a JSON array
+pub fn wifi_scan_results_to_json(results: &[ScanResult]) -> String {
+    let mut out = String::with_capacity(2 + results.len() * RESULT_JSON_CAPACITY);
+    out.push('[');
+    for (i, r) in results.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"ssid\":");
+        write_json_bytes(&mut out, &r.ssid[..r.ssid_len.min(32)]);
+        out.push_str(",\"bssid\":");
+        write_json_bytes(&mut out, &r.bssid_str());
+        let _ = write!(
+            out,
+            ",\"channel\":{},\"rssi\":{},\"auth_mode\":\"{}\"}}",
+            r.channel,
+            r.rssi,
+            auth_mode_name(r.auth_mode)
+        );
+    }
+    out.push(']');
+    out
+}
+
+/// Cursor over the input of `wifi_scan_result_from_json`
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `byte` after optional whitespace
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) != Some(&byte) {
+            return None;
+        }
+        self.pos += 1;
+        Some(())
+    }
+
+    /// Consume `byte` if it comes next, after optional whitespace
+    fn accept(&mut self, byte: u8) -> bool {
+        self.expect(byte).is_some()
+    }
+
+    /// Parse a string into raw bytes; `\uXXXX` escapes must be below 0x100
+    fn string(&mut self) -> Option<Vec<u8>> {
+        self.expect(b'"')?;
+        let mut out = Vec::new();
+        loop {
+            let b = *self.bytes.get(self.pos)?;
+            self.pos += 1;
+            match b {
+                b'"' => return Some(out),
+                b'\\' => {
+                    let escape = *self.bytes.get(self.pos)?;
+                    self.pos += 1;
+                    out.push(match escape {
+                        b'"' | b'\\' | b'/' => escape,
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        b'u' => {
+                            let hex = self.bytes.get(self.pos..self.pos + 4)?;
+                            self.pos += 4;
+                            let hex = core::str::from_utf8(hex).ok()?;
+                            u8::try_from(u16::from_str_radix(hex, 16).ok()?).ok()?
+                        }
+                        _ => return None,
+                    });
+                }
+                _ => out.push(b),
+            }
+        }
+    }
+
+    /// Parse an optionally negative integer
+    fn integer(&mut self) -> Option<i64> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.bytes.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+}
+
+/// Parse "AA:BB:CC:DD:EE:FF"
+fn parse_bssid(text: &[u8]) -> Option<[u8; 6]> {
+    if text.len() != 17 {
+        return None;
+    }
+    let mut bssid = [0u8; 6];
+    for (i, byte) in bssid.iter_mut().enumerate() {
+        if i < 5 && text[i * 3 + 2] != b':' {
+            return None;
+        }
+        let hex = core::str::from_utf8(&text[i * 3..i * 3 + 2]).ok()?;
+        *byte = u8::from_str_radix(hex, 16).ok()?;
+    }
+    Some(bssid)
+}
+
+/// Parse one object in the format written by `wifi_scan_results_to_json`
+///
+/// All five fields are required, in any order. Returns `None` for anything
+/// else, including an SSID longer than 32 bytes.
+pub fn wifi_scan_result_from_json(json: &str) -> Option<ScanResult> {
+    let mut parser = Parser {
+        bytes: json.as_bytes(),
+        pos: 0,
+    };
+    let mut result = ScanResult::default();
+    // ssid, bssid, channel, rssi, auth_mode
+    let mut seen = [false; 5];
+
+    parser.expect(b'{')?;
+    loop {
+        let key = parser.string()?;
+        parser.expect(b':')?;
+        let field = match key.as_slice() {
+            b"ssid" => {
+                let ssid = parser.string()?;
+                if ssid.len() > result.ssid.len() {
+                    return None;
+                }
+                result.ssid[..ssid.len()].copy_from_slice(&ssid);
+                result.ssid_len = ssid.len();
+                0
+            }
+            b"bssid" => {
+                result.bssid = parse_bssid(&parser.string()?)?;
+                1
+            }
+            b"channel" => {
+                result.channel = u8::try_from(parser.integer()?).ok()?;
+                2
+            }
+            b"rssi" => {
+                result.rssi = i8::try_from(parser.integer()?).ok()?;
+                3
+            }
+            b"auth_mode" => {
+                result.auth_mode = auth_mode_from_name(&parser.string()?)?;
+                4
+            }
+            _ => return None,
+        };
+        seen[field] = true;
+
+        if !parser.accept(b',') {
+            break;
+        }
+    }
+    parser.expect(b'}')?;
+
+    parser.skip_whitespace();
+    let complete = parser.pos == parser.bytes.len() && seen.iter().all(|&s| s);
+    complete.then_some(result)
+}
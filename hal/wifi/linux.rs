@@ -4,12 +4,20 @@
 //! Requires CAP_NET_ADMIN capability for scanning.
 
 use super::{
-    AuthMode, ConnectionStatus, IpInfo, ScanResult, StationConfig, WifiError, WifiMode, WifiResult,
+    channel_to_freq, dispatch_event, ApConfig, AuthMode, ConnectionStatus, IpConfig, IpInfo,
+    PowerSaveMode, ScanResult, StationConfig, WifiError, WifiEvent, WifiMode, WifiResult,
+    WifiStats,
 };
+use super::icmp::icmp_ping;
 
 use std::collections::HashMap;
 use std::fs;
+use std::io;
 use std::os::unix::io::RawFd;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+use std::thread;
 
 // Netlink constants
 const NETLINK_GENERIC: i32 = 16;
@@ -56,6 +64,13 @@ const NL80211_IFTYPE_MONITOR: u32 = 6;
 const NLMSG_ERROR: u16 = 2;
 const NLMSG_DONE: u16 = 3;
 
+// rtnetlink address notifications
+const RTM_NEWADDR: u16 = 20;
+const RTM_DELADDR: u16 = 21;
+const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+
 // IE (Information Element) types
 const WLAN_EID_SSID: u8 = 0;
 const WLAN_EID_RSN: u8 = 48;
@@ -88,6 +103,17 @@ struct NlAttr {
     nla_type: u16,
 }
 
+/// Interface address message (rtnetlink)
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct IfAddrMsg {
+    ifa_family: u8,
+    ifa_prefixlen: u8,
+    ifa_flags: u8,
+    ifa_scope: u8,
+    ifa_index: u32,
+}
+
 /// Netlink error message
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
@@ -104,14 +130,40 @@ struct WifiInterface {
     iftype: u32,
 }
 
+/// Interface selected by `wifi_initialize`
+#[derive(Debug, Clone, Copy)]
+struct WifiIfaceInfo {
+    ifindex: i32,
+    ifname: [u8; 16],
+    mac: [u8; 6],
+}
+
+impl WifiIfaceInfo {
+    /// Interface name without the NUL padding
+    fn ifname_str(&self) -> &str {
+        std::str::from_utf8(&self.ifname)
+            .unwrap_or("")
+            .trim_end_matches('\0')
+    }
+}
+
 /// Global state
-static mut NL80211_FAMILY_ID: u16 = 0;
-static mut WIFI_IFINDEX: i32 = 0;
-static mut WIFI_IFNAME: [u8; 16] = [0u8; 16];
-static mut WIFI_MAC: [u8; 6] = [0u8; 6];
-static mut INITIALIZED: bool = false;
-static mut SCAN_IN_PROGRESS: bool = false;
-static mut CACHED_SCAN_RESULTS: Option<Vec<ScanResult>> = None;
+static NL80211_FAMILY_ID: AtomicU16 = AtomicU16::new(0);
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static WIFI_IFACE: Mutex<Option<WifiIfaceInfo>> = Mutex::new(None);
+static SCAN_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+static CACHED_SCAN_RESULTS: Mutex<Option<Vec<ScanResult>>> = Mutex::new(None);
+static STATIC_IP_CONFIG: Mutex<Option<IpConfig>> = Mutex::new(None);
+
+/// Lock a global; poisoning is ignored since the guarded state is plain data
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Get the interface selected at init
+fn current_iface() -> WifiResult<WifiIfaceInfo> {
+    lock(&WIFI_IFACE).ok_or(WifiError::NotInitialized)
+}
 
 /// Create netlink socket
 fn create_nl_socket() -> WifiResult<RawFd> {
@@ -287,15 +339,19 @@ fn resolve_nl80211_family(fd: RawFd) -> WifiResult<u16> {
 }
 
 /// Parse netlink attributes from buffer
-fn parse_attrs(data: &[u8]) -> HashMap<u16, Vec<u8>> {
+pub(crate) fn parse_attrs(data: &[u8]) -> HashMap<u16, Vec<u8>> {
     let mut attrs = HashMap::new();
     let mut offset = 0;
 
     while offset + std::mem::size_of::<NlAttr>() <= data.len() {
-        let attr = unsafe { &*(data[offset..].as_ptr() as *const NlAttr) };
+        // Read the header field by field: attributes need not be 4-byte aligned in memory
+        let attr = NlAttr {
+            nla_len: u16::from_ne_bytes([data[offset], data[offset + 1]]),
+            nla_type: u16::from_ne_bytes([data[offset + 2], data[offset + 3]]),
+        };
         let attr_len = attr.nla_len as usize;
 
-        if attr_len < std::mem::size_of::<NlAttr>() || offset + attr_len > data.len() {
+        if attr_len < std::mem::size_of::<NlAttr>() || attr_len > data.len() - offset {
             break;
         }
 
@@ -383,10 +439,45 @@ fn get_wifi_interfaces(fd: RawFd, family_id: u16) -> WifiResult<Vec<WifiInterfac
     Ok(interfaces)
 }
 
+/// Payload of a nested attribute holding `items`, numbered from 1
+fn nested_attr<T: AsRef<[u8]>>(items: &[T]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let data = item.as_ref();
+        let attr = NlAttr {
+            nla_len: (std::mem::size_of::<NlAttr>() + data.len()) as u16,
+            nla_type: i as u16 + 1,
+        };
+        payload.extend_from_slice(as_bytes(&attr));
+        payload.extend_from_slice(data);
+        payload.resize(align4(payload.len()), 0);
+    }
+    payload
+}
+
 /// Trigger WiFi scan
-fn trigger_scan(fd: RawFd, family_id: u16, ifindex: i32) -> WifiResult<()> {
+///
+/// Empty `freqs` scans every channel; each entry of `ssids` is probed for
+/// directly, which also finds hidden networks.
+fn trigger_scan(
+    fd: RawFd,
+    family_id: u16,
+    ifindex: i32,
+    freqs: &[u32],
+    ssids: &[&[u8]],
+) -> WifiResult<()> {
     let ifindex_bytes = ifindex.to_ne_bytes();
-    let attrs = [(NL80211_ATTR_IFINDEX, ifindex_bytes.as_slice())];
+    let freq_bytes: Vec<[u8; 4]> = freqs.iter().map(|f| f.to_ne_bytes()).collect();
+    let freq_attr = nested_attr(&freq_bytes);
+    let ssid_attr = nested_attr(ssids);
+
+    let mut attrs = vec![(NL80211_ATTR_IFINDEX, ifindex_bytes.as_slice())];
+    if !freqs.is_empty() {
+        attrs.push((NL80211_ATTR_SCAN_FREQUENCIES, freq_attr.as_slice()));
+    }
+    if !ssids.is_empty() {
+        attrs.push((NL80211_ATTR_SCAN_SSIDS, ssid_attr.as_slice()));
+    }
 
     let msg = build_nl_msg(
         family_id,
@@ -411,10 +502,14 @@ fn trigger_scan(fd: RawFd, family_id: u16, ifindex: i32) -> WifiResult<()> {
                     response[err_offset + 3],
                 ]);
                 if error < 0 {
-                    // -EBUSY (-16) means scan already in progress
-                    if error == -16 {
+                    // -EBUSY means scan already in progress
+                    if error == -libc::EBUSY {
                         return Ok(());
                     }
+                    // Triggering a scan requires CAP_NET_ADMIN
+                    if error == -libc::EPERM || error == -libc::EACCES {
+                        return Err(WifiError::PermissionDenied);
+                    }
                     return Err(WifiError::SystemError(error));
                 }
             }
@@ -479,7 +574,7 @@ fn get_scan_results(fd: RawFd, family_id: u16, ifindex: i32) -> WifiResult<Vec<S
 }
 
 /// Parse BSS (Basic Service Set) attributes
-fn parse_bss(data: &[u8]) -> Option<ScanResult> {
+pub(crate) fn parse_bss(data: &[u8]) -> Option<ScanResult> {
     let attrs = parse_attrs(data);
 
     let mut result = ScanResult {
@@ -539,14 +634,15 @@ fn parse_bss(data: &[u8]) -> Option<ScanResult> {
 }
 
 /// Parse Information Elements
-fn parse_ies(data: &[u8], result: &mut ScanResult) {
+pub(crate) fn parse_ies(data: &[u8], result: &mut ScanResult) {
     let mut offset = 0;
 
-    while offset + 2 <= data.len() {
+    while data.len() - offset >= 2 {
         let ie_type = data[offset];
         let ie_len = data[offset + 1] as usize;
 
-        if offset + 2 + ie_len > data.len() {
+        // Compare against the bytes left rather than summing, so no offset can overflow
+        if ie_len > data.len() - offset - 2 {
             break;
         }
 
@@ -569,6 +665,109 @@ fn parse_ies(data: &[u8], result: &mut ScanResult) {
     }
 }
 
+/// Entry point for fuzzing and corpus tests of `parse_bss`
+#[doc(hidden)] // pub for fuzz targets and tests
+pub fn fuzz_parse_bss(data: &[u8]) -> Option<ScanResult> {
+    parse_bss(data)
+}
+
+/// Entry point for fuzzing and corpus tests of `parse_attrs`
+#[doc(hidden)] // pub for fuzz targets and tests
+pub fn fuzz_parse_attrs(data: &[u8]) -> HashMap<u16, Vec<u8>> {
+    parse_attrs(data)
+}
+
+/// Start the background thread that turns rtnetlink address changes into
+/// `IpAcquired`/`IpLost` events (once per process)
+fn start_addr_monitor() {
+    static STARTED: Once = Once::new();
+
+    STARTED.call_once(|| unsafe {
+        let fd = libc::socket(libc::AF_NETLINK, libc::SOCK_RAW, libc::NETLINK_ROUTE);
+        if fd < 0 {
+            return;
+        }
+
+        let mut addr: libc::sockaddr_nl = std::mem::zeroed();
+        addr.nl_family = libc::AF_NETLINK as u16;
+        addr.nl_groups = RTMGRP_IPV4_IFADDR;
+
+        let ret = libc::bind(
+            fd,
+            &addr as *const _ as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as u32,
+        );
+        if ret < 0 {
+            libc::close(fd);
+            return;
+        }
+
+        let spawned = thread::Builder::new()
+            .name("wifi-addr-monitor".into())
+            .spawn(move || addr_monitor_loop(fd));
+        if spawned.is_err() {
+            libc::close(fd);
+        }
+    });
+}
+
+/// Receive rtnetlink address notifications until the socket fails
+fn addr_monitor_loop(fd: RawFd) {
+    let mut buf = vec![0u8; 8192];
+    let hdr_len = std::mem::size_of::<NlMsgHdr>();
+    let ifa_len = std::mem::size_of::<IfAddrMsg>();
+
+    loop {
+        let len = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if len < 0 {
+            if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            break;
+        }
+
+        let ifindex = match current_iface() {
+            Ok(iface) => iface.ifindex,
+            Err(_) => continue,
+        };
+
+        let data = &buf[..len as usize];
+        let mut offset = 0;
+        while offset + hdr_len + ifa_len <= data.len() {
+            let nlh = unsafe { &*(data[offset..].as_ptr() as *const NlMsgHdr) };
+            let msg_len = nlh.nlmsg_len as usize;
+            if msg_len < hdr_len + ifa_len || offset + msg_len > data.len() {
+                break;
+            }
+
+            let ifa = unsafe { &*(data[offset + hdr_len..].as_ptr() as *const IfAddrMsg) };
+            if ifa.ifa_family == libc::AF_INET as u8 && ifa.ifa_index as i32 == ifindex {
+                match nlh.nlmsg_type {
+                    RTM_NEWADDR => {
+                        let attrs =
+                            parse_attrs(&data[offset + hdr_len + ifa_len..offset + msg_len]);
+                        let ip = attrs
+                            .get(&IFA_LOCAL)
+                            .or_else(|| attrs.get(&IFA_ADDRESS))
+                            .and_then(|a| <[u8; 4]>::try_from(a.as_slice()).ok());
+                        if let Some(ip) = ip {
+                            dispatch_event(WifiEvent::IpAcquired { ip });
+                        }
+                    }
+                    RTM_DELADDR => dispatch_event(WifiEvent::IpLost),
+                    _ => {}
+                }
+            }
+
+            offset += align4(msg_len);
+        }
+    }
+
+    unsafe {
+        libc::close(fd);
+    }
+}
+
 /// Convert frequency (MHz) to channel number
 fn freq_to_channel(freq: u32) -> u8 {
     if freq >= 2412 && freq <= 2484 {
@@ -600,53 +799,55 @@ fn as_bytes<T: Sized>(t: &T) -> &[u8] {
 
 /// Initialize WiFi subsystem
 pub fn wifi_initialize() -> WifiResult<()> {
-    unsafe {
-        if INITIALIZED {
-            return Ok(());
-        }
-
-        let fd = create_nl_socket()?;
-
-        // Resolve nl80211 family ID
-        let family_id = resolve_nl80211_family(fd)?;
-        NL80211_FAMILY_ID = family_id;
-
-        // Get WiFi interfaces
-        let interfaces = get_wifi_interfaces(fd, family_id)?;
-        close_nl_socket(fd);
+    // Held for the whole init so concurrent callers initialize only once
+    let mut slot = lock(&WIFI_IFACE);
+    if slot.is_some() {
+        return Ok(());
+    }
 
-        // Find first station-mode interface
-        for iface in interfaces {
-            if iface.iftype == NL80211_IFTYPE_STATION || iface.iftype == 0 {
-                WIFI_IFINDEX = iface.ifindex;
-                let name_bytes = iface.ifname.as_bytes();
-                let copy_len = name_bytes.len().min(15);
-                WIFI_IFNAME[..copy_len].copy_from_slice(&name_bytes[..copy_len]);
-                WIFI_MAC = iface.mac;
-                INITIALIZED = true;
-                return Ok(());
-            }
+    let fd = create_nl_socket()?;
+
+    // Resolve nl80211 family ID
+    let family_id = resolve_nl80211_family(fd)?;
+    NL80211_FAMILY_ID.store(family_id, Ordering::Relaxed);
+
+    // Get WiFi interfaces
+    let interfaces = get_wifi_interfaces(fd, family_id)?;
+    close_nl_socket(fd);
+
+    // Find first station-mode interface
+    for iface in interfaces {
+        if iface.iftype == NL80211_IFTYPE_STATION || iface.iftype == 0 {
+            let mut ifname = [0u8; 16];
+            let name_bytes = iface.ifname.as_bytes();
+            let copy_len = name_bytes.len().min(15);
+            ifname[..copy_len].copy_from_slice(&name_bytes[..copy_len]);
+
+            *slot = Some(WifiIfaceInfo {
+                ifindex: iface.ifindex,
+                ifname,
+                mac: iface.mac,
+            });
+            INITIALIZED.store(true, Ordering::Relaxed);
+            start_addr_monitor();
+            return Ok(());
         }
-
-        Err(WifiError::InterfaceNotFound)
     }
+
+    Err(WifiError::InterfaceNotFound)
 }
 
 /// Deinitialize WiFi subsystem
 pub fn wifi_deinitialize() -> WifiResult<()> {
-    unsafe {
-        INITIALIZED = false;
-        WIFI_IFINDEX = 0;
-        WIFI_IFNAME = [0u8; 16];
-        WIFI_MAC = [0u8; 6];
-        CACHED_SCAN_RESULTS = None;
-    }
+    *lock(&WIFI_IFACE) = None;
+    INITIALIZED.store(false, Ordering::Relaxed);
+    *lock(&CACHED_SCAN_RESULTS) = None;
     Ok(())
 }
 
 /// Check if WiFi is initialized
 pub fn wifi_is_initialized() -> bool {
-    unsafe { INITIALIZED }
+    INITIALIZED.load(Ordering::Relaxed)
 }
 
 /// Set WiFi operating mode
@@ -661,87 +862,147 @@ pub fn wifi_get_mode() -> WifiResult<WifiMode> {
     Ok(WifiMode::Station)
 }
 
+/// Run `iw dev <ifname> <args>` and return its output
+fn run_iw(args: &[&str]) -> WifiResult<String> {
+    let iface = current_iface()?;
+
+    let output = Command::new("iw")
+        .args(["dev", iface.ifname_str()])
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => WifiError::NotSupported,
+            io::ErrorKind::PermissionDenied => WifiError::PermissionDenied,
+            _ => WifiError::ConfigurationError,
+        })?;
+
+    if !output.status.success() {
+        // iw reports -EPERM as "Operation not permitted" (needs CAP_NET_ADMIN)
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not permitted") {
+            return Err(WifiError::PermissionDenied);
+        }
+        return Err(WifiError::ConfigurationError);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Set the radio power saving mode with `iw` (requires CAP_NET_ADMIN)
+///
+/// nl80211 only switches power saving on or off, so `Light` and `Maximum`
+/// both turn it on and the driver picks the sleep interval.
+pub fn wifi_set_power_save(mode: PowerSaveMode) -> WifiResult<()> {
+    let state = match mode {
+        PowerSaveMode::None => "off",
+        PowerSaveMode::Light | PowerSaveMode::Maximum => "on",
+    };
+    run_iw(&["set", "power_save", state]).map(drop)
+}
+
+/// Get the radio power saving mode; enabled reads back as `Light`
+pub fn wifi_get_power_save() -> WifiResult<PowerSaveMode> {
+    // Output: "Power save: on"
+    let output = run_iw(&["get", "power_save"])?;
+    match output.trim().rsplit(' ').next() {
+        Some("on") => Ok(PowerSaveMode::Light),
+        Some("off") => Ok(PowerSaveMode::None),
+        _ => Err(WifiError::ConfigurationError),
+    }
+}
+
 /// Start WiFi scan
 pub fn wifi_start_scan() -> WifiResult<()> {
-    unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
-        }
+    start_scan(&[], &[])
+}
 
-        let fd = create_nl_socket()?;
-        let result = trigger_scan(fd, NL80211_FAMILY_ID, WIFI_IFINDEX);
-        close_nl_socket(fd);
+/// Start a scan limited to `channels` (1-14 and 36-177)
+pub fn wifi_start_scan_on_channels(channels: &[u8]) -> WifiResult<()> {
+    if channels.is_empty() {
+        return Err(WifiError::ConfigurationError);
+    }
+    let freqs = channels
+        .iter()
+        .map(|&ch| channel_to_freq(ch).ok_or(WifiError::ConfigurationError))
+        .collect::<WifiResult<Vec<u32>>>()?;
+    start_scan(&freqs, &[])
+}
 
-        if result.is_ok() {
-            SCAN_IN_PROGRESS = true;
-            CACHED_SCAN_RESULTS = None;
-        }
+/// Start a scan that probes for `ssid`, so a hidden network answers too
+pub fn wifi_start_scan_for_ssid(ssid: &str) -> WifiResult<()> {
+    if ssid.is_empty() || ssid.len() > 32 {
+        return Err(WifiError::ConfigurationError);
+    }
+    start_scan(&[], &[ssid.as_bytes()])
+}
+
+fn start_scan(freqs: &[u32], ssids: &[&[u8]]) -> WifiResult<()> {
+    let iface = current_iface()?;
+
+    let fd = create_nl_socket()?;
+    let family_id = NL80211_FAMILY_ID.load(Ordering::Relaxed);
+    let result = trigger_scan(fd, family_id, iface.ifindex, freqs, ssids);
+    close_nl_socket(fd);
 
-        result
+    if result.is_ok() {
+        SCAN_IN_PROGRESS.store(true, Ordering::Relaxed);
+        *lock(&CACHED_SCAN_RESULTS) = None;
     }
+
+    result
 }
 
 /// Check if scan is complete
 pub fn wifi_scan_is_complete() -> WifiResult<bool> {
-    unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
-        }
+    let iface = current_iface()?;
 
-        // Try to get scan results - if we get them, scan is complete
-        let fd = create_nl_socket()?;
-        let results = get_scan_results(fd, NL80211_FAMILY_ID, WIFI_IFINDEX);
-        close_nl_socket(fd);
+    // Try to get scan results - if we get them, scan is complete
+    let fd = create_nl_socket()?;
+    let results = get_scan_results(fd, NL80211_FAMILY_ID.load(Ordering::Relaxed), iface.ifindex);
+    close_nl_socket(fd);
 
-        match results {
-            Ok(r) if !r.is_empty() => {
-                SCAN_IN_PROGRESS = false;
-                CACHED_SCAN_RESULTS = Some(r);
-                Ok(true)
-            }
-            Ok(_) => {
-                // Empty results - might still be scanning
-                if SCAN_IN_PROGRESS {
-                    Ok(false)
-                } else {
-                    Ok(true)
-                }
+    match results {
+        Ok(r) if !r.is_empty() => {
+            if SCAN_IN_PROGRESS.swap(false, Ordering::Relaxed) {
+                dispatch_event(WifiEvent::ScanComplete { count: r.len() });
             }
-            Err(_) => Ok(false),
+            *lock(&CACHED_SCAN_RESULTS) = Some(r);
+            Ok(true)
         }
+        Ok(_) => {
+            // Empty results - might still be scanning
+            Ok(!SCAN_IN_PROGRESS.load(Ordering::Relaxed))
+        }
+        Err(_) => Ok(false),
     }
 }
 
 /// Get scan results
 pub fn wifi_get_scan_results() -> WifiResult<([ScanResult; 16], usize)> {
-    unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
-        }
-
-        // Use cached results if available
-        if let Some(ref cached) = CACHED_SCAN_RESULTS {
-            let mut results: [ScanResult; 16] = std::array::from_fn(|_| ScanResult::default());
-            let count = cached.len().min(16);
-            for (i, r) in cached.iter().take(16).enumerate() {
-                results[i] = r.clone();
-            }
-            return Ok((results, count));
-        }
-
-        // Otherwise fetch fresh results
-        let fd = create_nl_socket()?;
-        let scan_results = get_scan_results(fd, NL80211_FAMILY_ID, WIFI_IFINDEX)?;
-        close_nl_socket(fd);
+    let iface = current_iface()?;
 
+    // Use cached results if available
+    if let Some(ref cached) = *lock(&CACHED_SCAN_RESULTS) {
         let mut results: [ScanResult; 16] = std::array::from_fn(|_| ScanResult::default());
-        let count = scan_results.len().min(16);
-        for (i, r) in scan_results.iter().take(16).enumerate() {
+        let count = cached.len().min(16);
+        for (i, r) in cached.iter().take(16).enumerate() {
             results[i] = r.clone();
         }
+        return Ok((results, count));
+    }
+
+    // Otherwise fetch fresh results
+    let fd = create_nl_socket()?;
+    let scan_results =
+        get_scan_results(fd, NL80211_FAMILY_ID.load(Ordering::Relaxed), iface.ifindex)?;
+    close_nl_socket(fd);
 
-        Ok((results, count))
+    let mut results: [ScanResult; 16] = std::array::from_fn(|_| ScanResult::default());
+    let count = scan_results.len().min(16);
+    for (i, r) in scan_results.iter().take(16).enumerate() {
+        results[i] = r.clone();
     }
+
+    Ok((results, count))
 }
 
 /// Connect to WiFi network
@@ -751,6 +1012,21 @@ pub fn wifi_connect(_config: &StationConfig) -> WifiResult<()> {
     Err(WifiError::NotSupported)
 }
 
+/// Start Access Point mode (not supported, requires hostapd)
+pub fn wifi_start_ap(_config: ApConfig) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+/// Stop Access Point mode
+pub fn wifi_stop_ap() -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+/// Get MAC addresses of stations associated with the AP
+pub fn wifi_get_ap_clients() -> WifiResult<Vec<[u8; 6]>> {
+    Err(WifiError::NotSupported)
+}
+
 /// Disconnect from WiFi network
 pub fn wifi_disconnect() -> WifiResult<()> {
     Err(WifiError::NotSupported)
@@ -759,26 +1035,18 @@ pub fn wifi_disconnect() -> WifiResult<()> {
 /// Get current connection status
 pub fn wifi_get_connection_status() -> WifiResult<ConnectionStatus> {
     // Check if we have an IP address on the interface
-    unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
+    let iface = current_iface()?;
+
+    // Check /sys/class/net/<ifname>/operstate
+    let path = format!("/sys/class/net/{}/operstate", iface.ifname_str());
+    if let Ok(state) = fs::read_to_string(&path) {
+        let state = state.trim();
+        if state == "up" {
+            return Ok(ConnectionStatus::Connected);
         }
-
-        let ifname = std::str::from_utf8(&WIFI_IFNAME)
-            .unwrap_or("")
-            .trim_end_matches('\0');
-
-        // Check /sys/class/net/<ifname>/operstate
-        let path = format!("/sys/class/net/{}/operstate", ifname);
-        if let Ok(state) = fs::read_to_string(&path) {
-            let state = state.trim();
-            if state == "up" {
-                return Ok(ConnectionStatus::Connected);
-            }
-        }
-
-        Ok(ConnectionStatus::Disconnected)
     }
+
+    Ok(ConnectionStatus::Disconnected)
 }
 
 /// Get current ESSID
@@ -792,17 +1060,164 @@ pub fn wifi_get_ip_info() -> WifiResult<IpInfo> {
     Err(WifiError::NotSupported)
 }
 
+/// Assign an IPv4 address to the WiFi interface with an AF_INET ioctl
+fn set_if_addr(
+    fd: RawFd,
+    iface: &WifiIfaceInfo,
+    request: libc::Ioctl,
+    addr: [u8; 4],
+) -> WifiResult<()> {
+    unsafe {
+        let mut req: libc::ifreq = std::mem::zeroed();
+        for (dst, &src) in req.ifr_name.iter_mut().zip(iface.ifname.iter()) {
+            *dst = src as libc::c_char;
+        }
+
+        let sin = &mut req.ifr_ifru.ifru_addr as *mut libc::sockaddr as *mut libc::sockaddr_in;
+        (*sin).sin_family = libc::AF_INET as libc::sa_family_t;
+        (*sin).sin_addr.s_addr = u32::from_ne_bytes(addr);
+
+        if libc::ioctl(fd, request, &req) < 0 {
+            return Err(match io::Error::last_os_error().raw_os_error() {
+                Some(libc::EPERM) | Some(libc::EACCES) => WifiError::PermissionDenied,
+                Some(errno) => WifiError::SystemError(errno),
+                None => WifiError::SocketError,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Assign a static IP address (requires CAP_NET_ADMIN)
+///
+/// The gateway is set as the interface destination address; no default
+/// route is added. The DNS server is only recorded for `wifi_get_ip_config`.
+pub fn wifi_set_static_ip(config: &IpConfig) -> WifiResult<()> {
+    let iface = current_iface()?;
+
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+    if fd < 0 {
+        return Err(WifiError::SocketError);
+    }
+
+    let result = set_if_addr(fd, &iface, libc::SIOCSIFADDR, config.ip)
+        .and_then(|_| set_if_addr(fd, &iface, libc::SIOCSIFNETMASK, config.netmask))
+        .and_then(|_| set_if_addr(fd, &iface, libc::SIOCSIFDSTADDR, config.gateway));
+    unsafe {
+        libc::close(fd);
+    }
+    result?;
+
+    *lock(&STATIC_IP_CONFIG) = Some(*config);
+    Ok(())
+}
+
+/// Drop the static IP config and request an address with `udhcpc`
+///
+/// Runs BusyBox `udhcpc`, which must be on PATH; if it is not, this fails
+/// with `DhcpClientNotFound` and the static config is kept. `udhcpc` needs
+/// CAP_NET_ADMIN and a default script (usually
+/// /usr/share/udhcpc/default.script) to apply the lease.
+pub fn wifi_set_dhcp() -> WifiResult<()> {
+    let iface = current_iface()?;
+
+    // -n: fail instead of retrying forever, -q: exit once a lease is obtained
+    let status = Command::new("udhcpc")
+        .args(["-i", iface.ifname_str(), "-n", "-q"])
+        .status()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => WifiError::DhcpClientNotFound,
+            io::ErrorKind::PermissionDenied => WifiError::PermissionDenied,
+            _ => WifiError::ConfigurationError,
+        })?;
+
+    *lock(&STATIC_IP_CONFIG) = None;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(WifiError::ConfigurationError)
+    }
+}
+
+/// Get the static IP config, or `IpConfig::default()` when using DHCP
+pub fn wifi_get_ip_config() -> WifiResult<IpConfig> {
+    current_iface()?;
+    Ok(lock(&STATIC_IP_CONFIG).unwrap_or_default())
+}
+
+/// Find the counters for `ifname` in the contents of /proc/net/dev
+#[doc(hidden)] // pub for tests
+pub fn parse_proc_net_dev(contents: &str, ifname: &str) -> Option<WifiStats> {
+    // Columns: 8 receive counters followed by 8 transmit counters
+    let line = contents.lines().find_map(|line| {
+        let (name, counters) = line.split_once(':')?;
+        (name.trim() == ifname).then_some(counters)
+    })?;
+
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .map(|f| f.parse().ok())
+        .collect::<Option<_>>()?;
+    if fields.len() < 16 {
+        return None;
+    }
+
+    Some(WifiStats {
+        rx_bytes: fields[0],
+        rx_packets: fields[1],
+        rx_errors: fields[2],
+        tx_bytes: fields[8],
+        tx_packets: fields[9],
+        tx_errors: fields[10],
+    })
+}
+
+/// Get interface traffic counters from /proc/net/dev
+pub fn wifi_get_stats() -> WifiResult<WifiStats> {
+    let iface = current_iface()?;
+
+    let contents = fs::read_to_string("/proc/net/dev").map_err(|_| WifiError::NotSupported)?;
+    parse_proc_net_dev(&contents, iface.ifname_str()).ok_or(WifiError::InterfaceNotFound)
+}
+
 /// Get signal strength
 pub fn wifi_get_rssi() -> WifiResult<i8> {
     Err(WifiError::NotSupported)
 }
 
+/// Get link quality of current connection
+pub fn wifi_get_link_quality() -> WifiResult<u8> {
+    Err(WifiError::NotSupported)
+}
+
 /// Get MAC address
 pub fn wifi_get_mac_address() -> WifiResult<[u8; 6]> {
+    current_iface().map(|iface| iface.mac)
+}
+
+/// Ping an IPv4 host, returning the RTT of each echo request in
+/// microseconds (`u32::MAX` for a missing reply)
+///
+/// Uses a raw ICMP socket (CAP_NET_RAW), falling back to an unprivileged
+/// ICMP datagram socket where `net.ipv4.ping_group_range` allows it.
+pub fn wifi_ping(ip: [u8; 4], count: u8, timeout_ms: u32) -> WifiResult<Vec<u32>> {
+    let mut raw = true;
+    let mut fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_RAW, libc::IPPROTO_ICMP) };
+    if fd < 0 {
+        raw = false;
+        fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, libc::IPPROTO_ICMP) };
+    }
+    if fd < 0 {
+        return Err(match io::Error::last_os_error().raw_os_error() {
+            Some(libc::EPERM) | Some(libc::EACCES) => WifiError::PermissionDenied,
+            _ => WifiError::SocketError,
+        });
+    }
+
+    let rtts = icmp_ping(fd, raw, ip, count, timeout_ms);
     unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
-        }
-        Ok(WIFI_MAC)
+        libc::close(fd);
     }
+    Ok(rtts)
 }
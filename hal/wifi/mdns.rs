@@ -0,0 +1,341 @@
+//! Multicast DNS responder (RFC 6762) with DNS-SD service records (RFC 6763)
+//!
+//! Answers queries for `<hostname>.local` (A) and for one service type,
+//! e.g. `_http._tcp.local` (PTR, with SRV/TXT/A for the instance). Records
+//! are written in DNS wire format without name compression.
+
+use super::{wifi_get_ip_info, UdpSocket, WifiError, WifiResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// mDNS multicast group and port
+const MDNS_GROUP: [u8; 4] = [224, 0, 0, 251];
+const MDNS_PORT: u16 = 5353;
+
+/// How often the responder thread checks the stop flag
+const MDNS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// Record types
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+
+const CLASS_IN: u16 = 1;
+/// Set on unique records so caches replace older data
+const CACHE_FLUSH: u16 = 0x8000;
+/// Set in a question to ask for a unicast reply
+const UNICAST_RESPONSE: u16 = 0x8000;
+
+/// Header flags: response, authoritative answer
+const FLAGS_RESPONSE: u16 = 0x8400;
+
+/// TTLs recommended by RFC 6762 for host and other records
+const TTL_HOST: u32 = 120;
+const TTL_OTHER: u32 = 4500;
+
+/// Advertises one host name and service on the local network
+///
+/// ```ignore
+/// let responder = MdnsResponder::new("rustcam", "_http._tcp", 80)?;
+/// responder.announce()?;
+/// let stop = Arc::new(AtomicBool::new(false));
+/// let thread = responder.run(Arc::clone(&stop))?;
+/// ```
+pub struct MdnsResponder {
+    socket: UdpSocket,
+    hostname: String,
+    service: String,
+    port: u16,
+}
+
+impl MdnsResponder {
+    /// Bind to port 5353 and join the mDNS group
+    ///
+    /// `hostname` is a single label (answered as `<hostname>.local`) and
+    /// `service_type` a DNS-SD type such as `_http._tcp`; a trailing
+    /// `.local` is accepted. `port` is advertised in the SRV record.
+    pub fn new(hostname: &str, service_type: &str, port: u16) -> WifiResult<MdnsResponder> {
+        let service = service_name(hostname, service_type)?;
+
+        let socket = UdpSocket::bind(MDNS_PORT)?;
+        socket.join_multicast(MDNS_GROUP)?;
+
+        Ok(MdnsResponder {
+            socket,
+            hostname: hostname.to_string(),
+            service: service.to_string(),
+            port,
+        })
+    }
+
+    /// Answer queries on a background thread until `stop` is set
+    pub fn run(&self, stop: Arc<AtomicBool>) -> WifiResult<JoinHandle<()>> {
+        let socket = self.socket.try_clone()?;
+        socket.set_read_timeout(Some(MDNS_POLL_INTERVAL))?;
+        let records = self.records();
+
+        thread::Builder::new()
+            .name("mdns-responder".into())
+            .spawn(move || {
+                let mut buf = [0u8; 1500];
+                while !stop.load(Ordering::Relaxed) {
+                    let Ok((from, port, len)) = socket.recv_from(&mut buf) else {
+                        continue;
+                    };
+                    let Some((id, wanted, unicast)) = records.match_query(&buf[..len]) else {
+                        continue;
+                    };
+                    let response = records.response(id, wanted, local_ip());
+                    // Legacy resolvers query from other ports and expect a direct reply
+                    if unicast || port != MDNS_PORT {
+                        let _ = socket.send_to(&response, from, port);
+                    } else {
+                        let _ = socket.send_to(&response, MDNS_GROUP, MDNS_PORT);
+                    }
+                }
+            })
+            .map_err(|_| WifiError::SystemError(-1))
+    }
+
+    /// Multicast an unsolicited response with all records
+    pub fn announce(&self) -> WifiResult<()> {
+        let response = self.records().response(0, Wanted::ALL, local_ip());
+        self.socket.send_to(&response, MDNS_GROUP, MDNS_PORT)?;
+        Ok(())
+    }
+
+    fn records(&self) -> Records {
+        Records::new(&self.hostname, &self.service, self.port)
+    }
+}
+
+/// Validate `hostname` and return `service_type` without a `.local` suffix
+fn service_name<'a>(hostname: &str, service_type: &'a str) -> WifiResult<&'a str> {
+    let service = service_type
+        .trim_end_matches('.')
+        .trim_end_matches(".local");
+    let labels_ok = |name: &str| name.split('.').all(|l| !l.is_empty() && l.len() <= 63);
+    if hostname.contains('.') || !labels_ok(hostname) || !labels_ok(service) {
+        return Err(WifiError::ConfigurationError);
+    }
+    Ok(service)
+}
+
+/// Response `MdnsResponder::new(hostname, service_type, port)` would send
+/// to `query`, with `ip` as the local address; `None` if it stays silent
+#[doc(hidden)] // pub for tests
+pub fn mdns_response(
+    hostname: &str,
+    service_type: &str,
+    port: u16,
+    query: &[u8],
+    ip: Option<[u8; 4]>,
+) -> WifiResult<Option<Vec<u8>>> {
+    let service = service_name(hostname, service_type)?;
+    let records = Records::new(hostname, service, port);
+    Ok(records
+        .match_query(query)
+        .map(|(id, wanted, _)| records.response(id, wanted, ip)))
+}
+
+/// Which records a query asked for
+#[derive(Clone, Copy, Default)]
+struct Wanted {
+    ptr: bool,
+    instance: bool,
+    host: bool,
+}
+
+impl Wanted {
+    const ALL: Wanted = Wanted {
+        ptr: true,
+        instance: true,
+        host: true,
+    };
+
+    fn any(self) -> bool {
+        self.ptr || self.instance || self.host
+    }
+}
+
+/// Fully qualified names and port of the advertised records
+struct Records {
+    host: String,
+    service: String,
+    instance: String,
+    port: u16,
+}
+
+impl Records {
+    fn new(hostname: &str, service: &str, port: u16) -> Records {
+        let service = format!("{}.local", service);
+        Records {
+            host: format!("{}.local", hostname),
+            instance: format!("{}.{}", hostname, service),
+            service,
+            port,
+        }
+    }
+
+    /// Parse a query, returning its ID, what it asks for and whether a
+    /// unicast reply was requested; `None` if nothing is ours to answer
+    fn match_query(&self, packet: &[u8]) -> Option<(u16, Wanted, bool)> {
+        let id = u16::from_be_bytes([*packet.first()?, *packet.get(1)?]);
+        let flags = u16::from_be_bytes([*packet.get(2)?, *packet.get(3)?]);
+        if flags & 0x8000 != 0 {
+            return None; // A response, possibly our own
+        }
+        let questions = u16::from_be_bytes([*packet.get(4)?, *packet.get(5)?]);
+
+        let mut wanted = Wanted::default();
+        let mut unicast = false;
+        let mut offset = 12;
+        for _ in 0..questions {
+            let (name, next) = read_name(packet, offset)?;
+            let qtype = u16::from_be_bytes([*packet.get(next)?, *packet.get(next + 1)?]);
+            let qclass = u16::from_be_bytes([*packet.get(next + 2)?, *packet.get(next + 3)?]);
+            offset = next + 4;
+
+            let asks = |t: u16| qtype == t || qtype == TYPE_ANY;
+            let before = wanted.any();
+            if name.eq_ignore_ascii_case(&self.service) && asks(TYPE_PTR) {
+                wanted.ptr = true;
+            }
+            if name.eq_ignore_ascii_case(&self.instance) && (asks(TYPE_SRV) || asks(TYPE_TXT)) {
+                wanted.instance = true;
+            }
+            if name.eq_ignore_ascii_case(&self.host) && asks(TYPE_A) {
+                wanted.host = true;
+            }
+            if wanted.any() && !before && qclass & UNICAST_RESPONSE != 0 {
+                unicast = true;
+            }
+        }
+
+        wanted.any().then_some((id, wanted, unicast))
+    }
+
+    /// Build a response with the wanted records as answers
+    ///
+    /// The A record is left out when the local address is unknown.
+    fn response(&self, id: u16, wanted: Wanted, ip: Option<[u8; 4]>) -> Vec<u8> {
+        let mut records = Vec::new();
+        let mut count = 0u16;
+
+        if wanted.ptr {
+            let mut rdata = Vec::new();
+            put_name(&mut rdata, &self.instance);
+            put_record(
+                &mut records,
+                &self.service,
+                TYPE_PTR,
+                false,
+                TTL_OTHER,
+                &rdata,
+            );
+            count += 1;
+        }
+        // SRV, TXT and A complete a PTR answer so clients need no follow-up query
+        if wanted.ptr || wanted.instance {
+            let mut srv = Vec::new();
+            srv.extend_from_slice(&0u16.to_be_bytes()); // priority
+            srv.extend_from_slice(&0u16.to_be_bytes()); // weight
+            srv.extend_from_slice(&self.port.to_be_bytes());
+            put_name(&mut srv, &self.host);
+            put_record(&mut records, &self.instance, TYPE_SRV, true, TTL_HOST, &srv);
+            // Empty TXT record: a single zero-length string
+            put_record(
+                &mut records,
+                &self.instance,
+                TYPE_TXT,
+                true,
+                TTL_OTHER,
+                &[0],
+            );
+            count += 2;
+        }
+        if let Some(ip) = ip {
+            put_record(&mut records, &self.host, TYPE_A, true, TTL_HOST, &ip);
+            count += 1;
+        }
+
+        let mut packet = Vec::with_capacity(12 + records.len());
+        packet.extend_from_slice(&id.to_be_bytes());
+        packet.extend_from_slice(&FLAGS_RESPONSE.to_be_bytes());
+        packet.extend_from_slice(&0u16.to_be_bytes()); // questions
+        packet.extend_from_slice(&count.to_be_bytes()); // answers
+        packet.extend_from_slice(&0u16.to_be_bytes()); // authority
+        packet.extend_from_slice(&0u16.to_be_bytes()); // additional
+        packet.extend_from_slice(&records);
+        packet
+    }
+}
+
+/// IPv4 address of the interface used to reach the mDNS group
+fn local_ip() -> Option<[u8; 4]> {
+    if let Ok(info) = wifi_get_ip_info() {
+        if info.ip != [0; 4] {
+            return Some(info.ip);
+        }
+    }
+    // Connecting a UDP socket sends nothing but makes the stack pick a source address
+    let probe = UdpSocket::bind_any().ok()?;
+    probe.connect(MDNS_GROUP, MDNS_PORT).ok()?;
+    let (ip, _) = probe.local_addr().ok()?;
+    (ip != [0; 4]).then_some(ip)
+}
+
+/// Append a resource record
+fn put_record(buf: &mut Vec<u8>, name: &str, rtype: u16, unique: bool, ttl: u32, rdata: &[u8]) {
+    let class = if unique {
+        CLASS_IN | CACHE_FLUSH
+    } else {
+        CLASS_IN
+    };
+    put_name(buf, name);
+    buf.extend_from_slice(&rtype.to_be_bytes());
+    buf.extend_from_slice(&class.to_be_bytes());
+    buf.extend_from_slice(&ttl.to_be_bytes());
+    buf.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+    buf.extend_from_slice(rdata);
+}
+
+/// Append a dotted name as length-prefixed labels
+fn put_name(buf: &mut Vec<u8>, name: &str) {
+    for label in name.split('.') {
+        buf.push(label.len() as u8);
+        buf.extend_from_slice(label.as_bytes());
+    }
+    buf.push(0);
+}
+
+/// Read a possibly compressed name at `offset`, returning it dotted and
+/// the offset just past it
+fn read_name(packet: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut end = None;
+    // Bounds the pointer chain so a looping packet cannot hang the responder
+    for _ in 0..128 {
+        let len = *packet.get(offset)? as usize;
+        if len == 0 {
+            return Some((name, end.unwrap_or(offset + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let target = ((len & 0x3F) << 8) | *packet.get(offset + 1)? as usize;
+            end.get_or_insert(offset + 2);
+            offset = target;
+            continue;
+        }
+        let label = packet.get(offset + 1..offset + 1 + len)?;
+        if !name.is_empty() {
+            name.push('.');
+        }
+        name.push_str(&String::from_utf8_lossy(label));
+        offset += 1 + len;
+    }
+    None
+}
@@ -0,0 +1,396 @@
+//! Mock WiFi backend for testing without hardware
+//!
+//! Replaces the unsupported-platform stub when the `mock` feature is
+//! enabled. Scans complete 200 ms after they start and return a preset
+//! list of networks (three synthetic ones unless replaced with
+//! `wifi_mock_set_scan_results`). Like the real backends, a finished scan
+//! is reported to event handlers when `wifi_scan_is_complete` first sees
+//! it. Connections report `Connecting` for 100 ms and then `Connected` to
+//! any network, unless a test makes them fail with
+//! `wifi_mock_set_connect_fails`.
+
+use super::{
+    channel_to_freq, dispatch_event, ApConfig, AuthMode, ConnectionStatus, IpConfig, IpInfo,
+    PowerSaveMode, ScanResult, StationConfig, WifiError, WifiEvent, WifiMode, WifiResult,
+    WifiStats,
+};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Time from `wifi_start_scan` until results are available
+const MOCK_SCAN_DURATION: Duration = Duration::from_millis(200);
+
+/// Time from `wifi_connect` until the station reports `Connected`
+const MOCK_CONNECT_DURATION: Duration = Duration::from_millis(100);
+
+/// Address handed out by the mock DHCP server
+const MOCK_IP_INFO: IpInfo = IpInfo {
+    ip: [192, 168, 1, 100],
+    netmask: [255, 255, 255, 0],
+    gateway: [192, 168, 1, 1],
+};
+
+/// Locally administered MAC address of the mock interface
+const MOCK_MAC_ADDRESS: [u8; 6] = [0x02, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+struct MockState {
+    initialized: bool,
+    mode: WifiMode,
+    power_save: PowerSaveMode,
+    scan_started: Option<Instant>,
+    /// `ScanComplete` not yet sent for the current scan
+    scan_event_pending: bool,
+    /// Channels the current scan is limited to, empty for all
+    scan_channels: Vec<u8>,
+    scan_results: Vec<ScanResult>,
+    /// Config and start time of the current connection
+    connection: Option<(StationConfig, Instant)>,
+    /// `wifi_connect` calls since the last reset
+    connect_attempts: u32,
+    /// Reject every `wifi_connect`
+    connect_fails: bool,
+    ip_config: IpConfig,
+    ap_running: bool,
+}
+
+static MOCK_STATE: LazyLock<Mutex<MockState>> = LazyLock::new(|| {
+    Mutex::new(MockState {
+        initialized: false,
+        mode: WifiMode::Station,
+        power_save: PowerSaveMode::None,
+        scan_started: None,
+        scan_event_pending: false,
+        scan_channels: Vec::new(),
+        scan_results: default_scan_results(),
+        connection: None,
+        connect_attempts: 0,
+        connect_fails: false,
+        ip_config: IpConfig::default(),
+        ap_running: false,
+    })
+});
+
+fn synthetic_network(ssid: &str, last_bssid_byte: u8, channel: u8, rssi: i8) -> ScanResult {
+    let mut result = ScanResult {
+        ssid_len: ssid.len().min(32),
+        bssid: [0x02, 0x00, 0x00, 0x00, 0x01, last_bssid_byte],
+        channel,
+        rssi,
+        auth_mode: AuthMode::Wpa2Psk,
+        ..ScanResult::default()
+    };
+    result.ssid[..result.ssid_len].copy_from_slice(&ssid.as_bytes()[..result.ssid_len]);
+    result
+}
+
+fn default_scan_results() -> Vec<ScanResult> {
+    vec![
+        synthetic_network("MockNet", 1, 6, -45),
+        synthetic_network("MockNet-5G", 2, 36, -55),
+        synthetic_network("MockGuest", 3, 11, -70),
+    ]
+}
+
+/// Lock the mock state, recovering from a panic in another test thread
+fn state() -> MutexGuard<'static, MockState> {
+    MOCK_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Lock the mock state, failing if WiFi is not initialized
+fn initialized_state() -> WifiResult<MutexGuard<'static, MockState>> {
+    let state = state();
+    if !state.initialized {
+        return Err(WifiError::NotInitialized);
+    }
+    Ok(state)
+}
+
+fn connection_status(state: &MockState) -> ConnectionStatus {
+    match &state.connection {
+        Some((_, started)) if started.elapsed() >= MOCK_CONNECT_DURATION => {
+            ConnectionStatus::Connected
+        }
+        Some(_) => ConnectionStatus::Connecting,
+        None => ConnectionStatus::Disconnected,
+    }
+}
+
+/// Replace the networks returned by subsequent scans
+pub fn wifi_mock_set_scan_results(results: Vec<ScanResult>) {
+    state().scan_results = results;
+}
+
+/// Make every `wifi_connect` fail with `ConnectionFailed` (until reset)
+pub fn wifi_mock_set_connect_fails(fails: bool) {
+    state().connect_fails = fails;
+}
+
+/// Number of `wifi_connect` calls since the last `wifi_mock_reset`
+pub fn wifi_mock_connect_attempts() -> u32 {
+    state().connect_attempts
+}
+
+/// Restore the initial mock state (deinitialized, default scan results)
+pub fn wifi_mock_reset() {
+    let mut state = state();
+    state.initialized = false;
+    state.mode = WifiMode::Station;
+    state.power_save = PowerSaveMode::None;
+    state.scan_started = None;
+    state.scan_event_pending = false;
+    state.scan_channels.clear();
+    state.scan_results = default_scan_results();
+    state.connection = None;
+    state.connect_attempts = 0;
+    state.connect_fails = false;
+    state.ip_config = IpConfig::default();
+    state.ap_running = false;
+}
+
+/// Initialize WiFi (mock: always succeeds)
+pub fn wifi_initialize() -> WifiResult<()> {
+    state().initialized = true;
+    Ok(())
+}
+
+/// Deinitialize WiFi, dropping any connection
+pub fn wifi_deinitialize() -> WifiResult<()> {
+    let mut state = initialized_state()?;
+    state.initialized = false;
+    state.scan_started = None;
+    state.scan_event_pending = false;
+    state.scan_channels.clear();
+    state.connection = None;
+    state.ap_running = false;
+    Ok(())
+}
+
+/// Check if WiFi is initialized
+pub fn wifi_is_initialized() -> bool {
+    state().initialized
+}
+
+/// Set WiFi operating mode
+pub fn wifi_set_mode(mode: WifiMode) -> WifiResult<()> {
+    initialized_state()?.mode = mode;
+    Ok(())
+}
+
+/// Get WiFi operating mode
+pub fn wifi_get_mode() -> WifiResult<WifiMode> {
+    Ok(initialized_state()?.mode)
+}
+
+/// Set the radio power saving mode
+pub fn wifi_set_power_save(mode: PowerSaveMode) -> WifiResult<()> {
+    initialized_state()?.power_save = mode;
+    Ok(())
+}
+
+/// Get the radio power saving mode
+pub fn wifi_get_power_save() -> WifiResult<PowerSaveMode> {
+    Ok(initialized_state()?.power_save)
+}
+
+/// Start a mock scan
+pub fn wifi_start_scan() -> WifiResult<()> {
+    start_scan(Vec::new())
+}
+
+/// Start a mock scan that only reports networks on `channels`
+pub fn wifi_start_scan_on_channels(channels: &[u8]) -> WifiResult<()> {
+    if channels.is_empty() || channels.iter().any(|&ch| channel_to_freq(ch).is_none()) {
+        return Err(WifiError::ConfigurationError);
+    }
+    start_scan(channels.to_vec())
+}
+
+/// Start a mock scan; every network answers, as hidden ones are not modelled
+pub fn wifi_start_scan_for_ssid(ssid: &str) -> WifiResult<()> {
+    if ssid.is_empty() || ssid.len() > 32 {
+        return Err(WifiError::ConfigurationError);
+    }
+    start_scan(Vec::new())
+}
+
+fn start_scan(channels: Vec<u8>) -> WifiResult<()> {
+    let mut state = initialized_state()?;
+    state.scan_started = Some(Instant::now());
+    state.scan_event_pending = true;
+    state.scan_channels = channels;
+    Ok(())
+}
+
+/// Networks the current scan finds
+fn scanned(state: &MockState) -> impl Iterator<Item = &ScanResult> {
+    state
+        .scan_results
+        .iter()
+        .filter(|r| state.scan_channels.is_empty() || state.scan_channels.contains(&r.channel))
+}
+
+/// Check if the scan has been running for 200 ms
+pub fn wifi_scan_is_complete() -> WifiResult<bool> {
+    let mut state = initialized_state()?;
+    let complete = match state.scan_started {
+        Some(started) => started.elapsed() >= MOCK_SCAN_DURATION,
+        None => return Err(WifiError::ScanFailed),
+    };
+
+    if complete && std::mem::take(&mut state.scan_event_pending) {
+        let count = scanned(&state).take(16).count();
+        drop(state);
+        dispatch_event(WifiEvent::ScanComplete { count });
+    }
+    Ok(complete)
+}
+
+/// Get the preset scan results (up to 16)
+pub fn wifi_get_scan_results() -> WifiResult<([ScanResult; 16], usize)> {
+    let state = initialized_state()?;
+    match state.scan_started {
+        Some(started) if started.elapsed() >= MOCK_SCAN_DURATION => {}
+        Some(_) => return Err(WifiError::ScanInProgress),
+        None => return Err(WifiError::ScanFailed),
+    }
+
+    let mut results: [ScanResult; 16] = std::array::from_fn(|_| ScanResult::default());
+    let mut count = 0;
+    for r in scanned(&state).take(16) {
+        results[count] = r.clone();
+        count += 1;
+    }
+    Ok((results, count))
+}
+
+/// Start connecting; the station reports `Connected` after 100 ms
+pub fn wifi_connect(config: &StationConfig) -> WifiResult<()> {
+    let mut state = initialized_state()?;
+    state.connect_attempts += 1;
+    if state.connect_fails {
+        return Err(WifiError::ConnectionFailed);
+    }
+    state.connection = Some((config.clone(), Instant::now()));
+    Ok(())
+}
+
+/// Disconnect from the current network
+pub fn wifi_disconnect() -> WifiResult<()> {
+    initialized_state()?.connection = None;
+    Ok(())
+}
+
+/// Get the mock connection status
+pub fn wifi_get_connection_status() -> WifiResult<ConnectionStatus> {
+    let state = initialized_state()?;
+    Ok(connection_status(&state))
+}
+
+/// Get the SSID of the current connection
+pub fn wifi_get_essid() -> WifiResult<([u8; 32], usize)> {
+    let state = initialized_state()?;
+    match &state.connection {
+        Some((config, _)) => Ok((config.ssid, config.ssid_len)),
+        None => Ok(([0; 32], 0)),
+    }
+}
+
+/// Get IP information: the static config if set, otherwise 192.168.1.100/24
+pub fn wifi_get_ip_info() -> WifiResult<IpInfo> {
+    let state = initialized_state()?;
+    if connection_status(&state) != ConnectionStatus::Connected {
+        return Err(WifiError::ConnectionFailed);
+    }
+    if state.ip_config == IpConfig::default() {
+        return Ok(MOCK_IP_INFO);
+    }
+    Ok(IpInfo {
+        ip: state.ip_config.ip,
+        netmask: state.ip_config.netmask,
+        gateway: state.ip_config.gateway,
+    })
+}
+
+/// Get RSSI of the current connection (mock: -50 dBm)
+pub fn wifi_get_rssi() -> WifiResult<i8> {
+    let state = initialized_state()?;
+    if connection_status(&state) != ConnectionStatus::Connected {
+        return Err(WifiError::ConnectionFailed);
+    }
+    Ok(-50)
+}
+
+/// Get link quality (mock: 70%)
+pub fn wifi_get_link_quality() -> WifiResult<u8> {
+    let state = initialized_state()?;
+    if connection_status(&state) != ConnectionStatus::Connected {
+        return Err(WifiError::ConnectionFailed);
+    }
+    Ok(70)
+}
+
+/// Use a static IP configuration
+pub fn wifi_set_static_ip(config: &IpConfig) -> WifiResult<()> {
+    initialized_state()?.ip_config = *config;
+    Ok(())
+}
+
+/// Use DHCP
+pub fn wifi_set_dhcp() -> WifiResult<()> {
+    initialized_state()?.ip_config = IpConfig::default();
+    Ok(())
+}
+
+/// Get the static IP configuration (all zeros when using DHCP)
+pub fn wifi_get_ip_config() -> WifiResult<IpConfig> {
+    Ok(initialized_state()?.ip_config)
+}
+
+/// Get the mock MAC address
+pub fn wifi_get_mac_address() -> WifiResult<[u8; 6]> {
+    let _state = initialized_state()?;
+    Ok(MOCK_MAC_ADDRESS)
+}
+
+/// Ping a host (mock: every request answered in 1 ms)
+pub fn wifi_ping(_ip: [u8; 4], count: u8, _timeout_ms: u32) -> WifiResult<Vec<u32>> {
+    let _state = initialized_state()?;
+    Ok(vec![1000; count as usize])
+}
+
+/// Start Access Point mode
+pub fn wifi_start_ap(_config: ApConfig) -> WifiResult<()> {
+    initialized_state()?.ap_running = true;
+    Ok(())
+}
+
+/// Stop Access Point mode
+pub fn wifi_stop_ap() -> WifiResult<()> {
+    initialized_state()?.ap_running = false;
+    Ok(())
+}
+
+/// Get clients of the access point (mock: always none)
+pub fn wifi_get_ap_clients() -> WifiResult<Vec<[u8; 6]>> {
+    if !initialized_state()?.ap_running {
+        return Err(WifiError::ConfigurationError);
+    }
+    Ok(Vec::new())
+}
+
+/// Get interface statistics (mock: all zero)
+pub fn wifi_get_stats() -> WifiResult<WifiStats> {
+    let _state = initialized_state()?;
+    Ok(WifiStats::default())
+}
+
+/// Resolve a hostname (mock: every name resolves to the gateway)
+pub fn wifi_resolve_hostname(_host: &str) -> WifiResult<[u8; 4]> {
+    let _state = initialized_state()?;
+    Ok(MOCK_IP_INFO.gateway)
+}
+
+/// Resolve all addresses of a hostname (mock: the gateway only)
+pub fn wifi_resolve_hostname_all(host: &str) -> WifiResult<Vec<[u8; 4]>> {
+    wifi_resolve_hostname(host).map(|ip| vec![ip])
+}
@@ -5,6 +5,7 @@
 //!
 //! - Linux: Uses nl80211 netlink API
 //! - NuttX: Uses WEXT-style socket/ioctl (same as WAPI)
+//! - No platform + `mock`: Simulated network for tests
 
 // Platform-specific implementations
 
@@ -21,12 +22,56 @@ mod linux;
 pub use linux::*;
 
 // Fallback stub for other platforms
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 mod none;
-#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
+#[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")))]
 pub use none::*;
 
+// Simulated network for tests, in place of the stub
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+mod mock;
+#[cfg(all(feature = "mock", not(any(feature = "platform-linux", feature = "platform-nuttx"))))]
+pub use mock::*;
+
+// ICMP echo on libc sockets, used by both backends' `wifi_ping`
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod icmp;
+
+// TCP and UDP sockets on libc, shared by the protocol helpers
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod tcp;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use tcp::TcpStream;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod udp;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use udp::UdpSocket;
+
+// Protocols built on those sockets
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod mqtt;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use mqtt::MqttClient;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod ntp;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use ntp::{ntp_to_unix, wifi_get_ntp_time, wifi_sync_system_time};
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+mod mdns;
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub use mdns::{mdns_response, MdnsResponder};
+
+// Platform-agnostic JSON encoding of scan results
+mod json;
+pub use json::{wifi_scan_result_from_json, wifi_scan_results_to_json};
+
+use crate::security::{self, ZeroOnDrop};
 use core::fmt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 /// WiFi operation errors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,6 +102,10 @@ pub enum WifiError {
     ConfigurationError,
     /// Operation not supported on this platform
     NotSupported,
+    /// Permission denied (e.g. scanning without CAP_NET_ADMIN)
+    PermissionDenied,
+    /// DHCP client program not found (`udhcpc` on Linux)
+    DhcpClientNotFound,
     /// System error with errno
     SystemError(i32),
 }
@@ -77,7 +126,59 @@ impl fmt::Display for WifiError {
             WifiError::Timeout => write!(f, "Timeout"),
             WifiError::ConfigurationError => write!(f, "Configuration error"),
             WifiError::NotSupported => write!(f, "Not supported on this platform"),
+            WifiError::PermissionDenied => write!(f, "Permission denied"),
+            WifiError::DhcpClientNotFound => write!(f, "DHCP client not found"),
+            #[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+            WifiError::SystemError(e) => {
+                write!(f, "System error: {} ({})", e, crate::errno_to_str(*e))
+            }
+            #[cfg(not(any(feature = "platform-linux", feature = "platform-nuttx")))]
             WifiError::SystemError(e) => write!(f, "System error: {}", e),
+        }?;
+        if let Some(help) = self.help_text() {
+            write!(f, " ({})", help)?;
+        }
+        Ok(())
+    }
+}
+
+impl WifiError {
+    /// Hint on how to fix the error, if there is one
+    ///
+    /// `Display` appends it in parentheses.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            // EPERM, in either sign convention
+            WifiError::PermissionDenied | WifiError::SystemError(1 | -1)
+                if cfg!(feature = "platform-linux") =>
+            {
+                Some("try: sudo setcap cap_net_admin+ep <binary>")
+            }
+            WifiError::InterfaceNotFound if cfg!(feature = "platform-linux") => {
+                Some("no wireless interface found; try ip link")
+            }
+            WifiError::DhcpClientNotFound => Some("install BusyBox udhcpc and put it on PATH"),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for WifiError {}
+
+/// Map an errno value; negated values (as returned by NuttX drivers) are
+/// accepted too
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+impl From<i32> for WifiError {
+    fn from(errno: i32) -> Self {
+        match errno.wrapping_abs() {
+            libc::EPERM | libc::EACCES => WifiError::PermissionDenied,
+            libc::ENODEV => WifiError::InterfaceNotFound,
+            libc::EINVAL => WifiError::ConfigurationError,
+            libc::EBUSY => WifiError::ScanInProgress,
+            libc::ETIMEDOUT => WifiError::Timeout,
+            libc::ECONNREFUSED => WifiError::ConnectionFailed,
+            libc::EOPNOTSUPP => WifiError::NotSupported,
+            errno => WifiError::SystemError(errno),
         }
     }
 }
@@ -100,6 +201,18 @@ pub enum WifiMode {
     Monitor = 6,
 }
 
+/// Radio power saving, trading latency for battery life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PowerSaveMode {
+    /// Radio always on
+    #[default]
+    None,
+    /// Wake for every DTIM beacon
+    Light,
+    /// Wake every 10th DTIM beacon; adds up to a second of latency
+    Maximum,
+}
+
 /// WiFi authentication mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AuthMode {
@@ -120,6 +233,17 @@ pub enum AuthMode {
     Unknown,
 }
 
+/// WiFi frequency band
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WifiBand {
+    /// 2.4 GHz (channels 1-14)
+    Band2G4,
+    /// 5 GHz (channels 32-177)
+    Band5G,
+    /// Unknown channel
+    Unknown,
+}
+
 /// WiFi scan result
 #[derive(Debug, Clone, Default)]
 pub struct ScanResult {
@@ -156,10 +280,245 @@ impl ScanResult {
         }
         buf
     }
+
+    /// Get the frequency band from the channel number
+    pub fn band(&self) -> WifiBand {
+        match self.channel {
+            1..=14 => WifiBand::Band2G4,
+            32..=177 => WifiBand::Band5G,
+            _ => WifiBand::Unknown,
+        }
+    }
 }
 
-/// Station mode configuration
+/// Pick the best access point for `ssid` from scan results
+///
+/// Returns the matching BSSID with the strongest RSSI, preferring 5 GHz
+/// when signal strength is tied. Passing its BSSID to
+/// `StationConfig::with_bssid` connects to that AP specifically.
+pub fn select_best_ap<'a>(results: &'a [ScanResult], ssid: &str) -> Option<&'a ScanResult> {
+    results
+        .iter()
+        .filter(|r| r.ssid_str() == Some(ssid))
+        .max_by_key(|r| (r.rssi, r.band() == WifiBand::Band5G))
+}
+
+/// Deduplicate scan results by BSSID and sort them by RSSI, strongest first
+///
+/// When the same BSSID appears more than once, the entry with the strongest
+/// RSSI is kept.
+pub fn dedup_scan_results(mut results: Vec<ScanResult>) -> Vec<ScanResult> {
+    let count = dedup_scan_results_in_place(&mut results);
+    results.truncate(count);
+    results
+}
+
+/// Deduplicate and sort scan results without allocating
+///
+/// Works like [`dedup_scan_results`] but reorders `results` in place and
+/// returns how many unique entries are now at the front. Both passes are
+/// O(n^2), which is fine for the 16-entry scan buffer.
+pub fn dedup_scan_results_in_place(results: &mut [ScanResult]) -> usize {
+    let mut count = 0;
+    for i in 0..results.len() {
+        match (0..count).find(|&j| results[j].bssid == results[i].bssid) {
+            // Ties keep the first entry seen
+            Some(j) if results[i].rssi > results[j].rssi => results.swap(i, j),
+            Some(_) => {}
+            None => {
+                results.swap(i, count);
+                count += 1;
+            }
+        }
+    }
+
+    // Insertion sort is stable, so equally strong entries keep their order
+    for i in 1..count {
+        let mut j = i;
+        while j > 0 && results[j - 1].rssi < results[j].rssi {
+            results.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    count
+}
+
+/// Format scan results as an aligned table with a header row
+///
+/// Hidden networks show as `<hidden>`; SSIDs that are not UTF-8 are shown
+/// lossily.
+pub fn wifi_scan_results_to_table(results: &[ScanResult]) -> String {
+    use core::fmt::Write;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<32} {:<17} {:>3} {:>5}  AUTH",
+        "SSID", "BSSID", "CH", "RSSI"
+    );
+    for r in results {
+        let ssid = String::from_utf8_lossy(&r.ssid[..r.ssid_len.min(32)]);
+        let ssid = if ssid.is_empty() {
+            "<hidden>".into()
+        } else {
+            ssid
+        };
+        let bssid = r.bssid_str();
+        let _ = writeln!(
+            out,
+            "{:<32} {:<17} {:>3} {:>5}  {}",
+            ssid,
+            core::str::from_utf8(&bssid).unwrap_or(""),
+            r.channel,
+            r.rssi,
+            json::auth_mode_name(r.auth_mode)
+        );
+    }
+    out
+}
+
+/// Get scan results deduplicated by BSSID, strongest first
+pub fn wifi_get_scan_results_sorted() -> WifiResult<Vec<ScanResult>> {
+    let (mut results, count) = wifi_get_scan_results()?;
+    let count = dedup_scan_results_in_place(&mut results[..count]);
+    Ok(results[..count].to_vec())
+}
+
+/// Get deduplicated scan results for one SSID, strongest first
+///
+/// Useful when a network is served by several APs (one BSSID each).
+pub fn wifi_get_scan_results_for_ssid(ssid: &str) -> WifiResult<Vec<ScanResult>> {
+    let mut results = wifi_get_scan_results_sorted()?;
+    results.retain(|r| r.ssid_str() == Some(ssid));
+    Ok(results)
+}
+
+/// Center frequency (MHz) of a 2.4 GHz (1-14) or 5 GHz (36-177) channel
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock"))]
+pub(crate) fn channel_to_freq(channel: u8) -> Option<u32> {
+    match channel {
+        1..=13 => Some(2412 + (channel as u32 - 1) * 5),
+        14 => Some(2484),
+        36..=177 => Some(5180 + (channel as u32 - 36) * 5),
+        _ => None,
+    }
+}
+
+/// Resolve `host` to all of its IPv4 addresses with getaddrinfo
+///
+/// Both backends' libc provide it; NuttX through its DNS client.
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+fn resolve_ipv4(host: &str) -> WifiResult<Vec<[u8; 4]>> {
+    let c_host = std::ffi::CString::new(host).map_err(|_| WifiError::ConfigurationError)?;
+
+    let mut hints: libc::addrinfo = unsafe { core::mem::zeroed() };
+    hints.ai_family = libc::AF_INET;
+    // One entry per address instead of one per socket type
+    hints.ai_socktype = libc::SOCK_STREAM;
+
+    let mut res: *mut libc::addrinfo = core::ptr::null_mut();
+    let rc = unsafe { libc::getaddrinfo(c_host.as_ptr(), core::ptr::null(), &hints, &mut res) };
+    if rc != 0 {
+        return Err(match rc {
+            libc::EAI_AGAIN => WifiError::Timeout,
+            libc::EAI_NONAME => WifiError::NetworkNotFound,
+            _ => WifiError::SystemError(rc),
+        });
+    }
+
+    let mut addrs = Vec::new();
+    let mut ai = res;
+    while !ai.is_null() {
+        let entry = unsafe { &*ai };
+        if entry.ai_family == libc::AF_INET && !entry.ai_addr.is_null() {
+            let sin = unsafe { &*(entry.ai_addr as *const libc::sockaddr_in) };
+            let ip = sin.sin_addr.s_addr.to_ne_bytes();
+            if !addrs.contains(&ip) {
+                addrs.push(ip);
+            }
+        }
+        ai = entry.ai_next;
+    }
+    unsafe { libc::freeaddrinfo(res) };
+
+    if addrs.is_empty() {
+        return Err(WifiError::NetworkNotFound);
+    }
+    Ok(addrs)
+}
+
+/// Resolve a hostname to its first IPv4 address
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub fn wifi_resolve_hostname(host: &str) -> WifiResult<[u8; 4]> {
+    wifi_resolve_hostname_all(host).map(|addrs| addrs[0])
+}
+
+/// Resolve a hostname to all of its IPv4 addresses (A records)
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub fn wifi_resolve_hostname_all(host: &str) -> WifiResult<Vec<[u8; 4]>> {
+    if !wifi_is_initialized() {
+        return Err(WifiError::NotInitialized);
+    }
+    resolve_ipv4(host)
+}
+
+/// Exponential moving average of RSSI, tracked per BSSID
+///
+/// Smooths out the scan-to-scan jitter in reported signal strength:
+/// `new = alpha * sample + (1 - alpha) * old`.
 #[derive(Debug, Clone)]
+pub struct RssiSmoother {
+    /// Weight of the newest sample (0.0-1.0)
+    alpha: f32,
+    /// Current average per BSSID
+    averages: HashMap<[u8; 6], f32>,
+}
+
+impl RssiSmoother {
+    /// Create a smoother with the given sample weight (clamped to 0.0-1.0)
+    pub fn new(alpha: f32) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            averages: HashMap::new(),
+        }
+    }
+
+    /// Feed a new set of scan results and return them with smoothed RSSI
+    ///
+    /// The first sample for a BSSID is taken as-is.
+    pub fn update(&mut self, results: &[ScanResult]) -> Vec<ScanResult> {
+        results
+            .iter()
+            .map(|r| {
+                let sample = r.rssi as f32;
+                let avg = self
+                    .averages
+                    .entry(r.bssid)
+                    .and_modify(|old| *old = self.alpha * sample + (1.0 - self.alpha) * *old)
+                    .or_insert(sample);
+
+                let mut smoothed = r.clone();
+                smoothed.rssi = avg.round().clamp(i8::MIN as f32, i8::MAX as f32) as i8;
+                smoothed
+            })
+            .collect()
+    }
+
+    /// Forget all tracked BSSIDs
+    pub fn reset(&mut self) {
+        self.averages.clear();
+    }
+}
+
+/// Environment variable holding the SSID for `StationConfig::from_env_default`
+pub const WIFI_SSID_ENV: &str = "RUSTCAM_WIFI_SSID";
+/// Environment variable holding the password for `StationConfig::from_env_default`
+pub const WIFI_PASS_ENV: &str = "RUSTCAM_WIFI_PASS";
+
+/// Station mode configuration
+///
+/// `Debug` does not show the password.
+#[derive(Clone)]
 pub struct StationConfig {
     /// SSID (network name)
     pub ssid: [u8; 32],
@@ -202,6 +561,126 @@ impl StationConfig {
 
         config
     }
+
+    /// Create a station config from environment variables
+    ///
+    /// Returns `None` if either variable is unset or not valid Unicode.
+    pub fn from_env(ssid_var: &str, pass_var: &str) -> Option<Self> {
+        let ssid = std::env::var(ssid_var).ok()?;
+        let password = std::env::var(pass_var).ok()?;
+        Some(Self::new(&ssid, &password))
+    }
+
+    /// Create a station config from `RUSTCAM_WIFI_SSID` and `RUSTCAM_WIFI_PASS`
+    pub fn from_env_default() -> Option<Self> {
+        Self::from_env(WIFI_SSID_ENV, WIFI_PASS_ENV)
+    }
+
+    /// Get SSID as string
+    pub fn ssid_str(&self) -> Option<&str> {
+        core::str::from_utf8(&self.ssid[..self.ssid_len]).ok()
+    }
+
+    /// Restrict the connection to a specific AP
+    pub fn with_bssid(mut self, bssid: [u8; 6]) -> Self {
+        self.bssid = Some(bssid);
+        self
+    }
+
+    /// Wrap the config for callers written before `StationConfig` wiped
+    /// its own password; the wrapper adds nothing now
+    #[deprecated(note = "`StationConfig` zeroes its password on drop, use it directly")]
+    #[allow(deprecated)]
+    pub fn zeroize_on_drop(self) -> ZeroizingStationConfig {
+        ZeroizingStationConfig(self)
+    }
+}
+
+impl fmt::Debug for StationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ssid = &self.ssid[..self.ssid_len.min(self.ssid.len())];
+        f.debug_struct("StationConfig")
+            .field("ssid", &String::from_utf8_lossy(ssid))
+            .field("password", &format_args!(".."))
+            .field("bssid", &self.bssid)
+            .field("channel", &self.channel)
+            .field("auth_mode", &self.auth_mode)
+            .finish()
+    }
+}
+
+/// Zeroes the password so it does not linger in freed memory
+///
+/// Each clone is wiped when it is dropped.
+impl Drop for StationConfig {
+    fn drop(&mut self) {
+        security::zeroize(&mut self.password);
+        // Volatile so the wipe of a soon-dead value is not optimized out
+        unsafe { core::ptr::write_volatile(&mut self.password_len, 0) };
+    }
+}
+
+/// Station config whose password bytes are zeroed on drop
+///
+/// Dereferences to the wrapped `StationConfig`, which already zeroes its
+/// password on drop.
+#[deprecated(note = "`StationConfig` zeroes its password on drop, use it directly")]
+#[derive(Debug)]
+pub struct ZeroizingStationConfig(StationConfig);
+
+#[allow(deprecated)]
+impl core::ops::Deref for ZeroizingStationConfig {
+    type Target = StationConfig;
+
+    fn deref(&self) -> &StationConfig {
+        &self.0
+    }
+}
+
+/// Access Point mode configuration
+#[derive(Debug, Clone)]
+pub struct ApConfig {
+    /// SSID (network name)
+    pub ssid: [u8; 32],
+    /// SSID length
+    pub ssid_len: usize,
+    /// WPA2 passphrase (empty for an open network), zeroed on drop
+    pub password: ZeroOnDrop<[u8; 64]>,
+    /// Password length
+    pub password_len: usize,
+    /// Channel to operate on
+    pub channel: u8,
+    /// Maximum number of associated stations
+    pub max_clients: u8,
+    /// Hide the SSID from beacons
+    pub hidden: bool,
+}
+
+impl ApConfig {
+    /// Create a new AP config from SSID and password strings
+    pub fn new(ssid: &str, password: &str, channel: u8) -> Self {
+        let mut config = Self {
+            ssid: [0; 32],
+            ssid_len: 0,
+            password: ZeroOnDrop::new([0; 64]),
+            password_len: 0,
+            channel,
+            max_clients: 4,
+            hidden: false,
+        };
+
+        let ssid_bytes = ssid.as_bytes();
+        let len = core::cmp::min(ssid_bytes.len(), 32);
+        config.ssid[..len].copy_from_slice(&ssid_bytes[..len]);
+        config.ssid_len = len;
+
+        let pwd_bytes = password.as_bytes();
+        let len = core::cmp::min(pwd_bytes.len(), 64);
+        config.password[..len].copy_from_slice(&pwd_bytes[..len]);
+        config.password_len = len;
+
+        config
+    }
 }
 
 /// Connection status
@@ -233,3 +712,547 @@ impl fmt::Display for IpInfo {
         write!(f, "{}.{}.{}.{}", self.ip[0], self.ip[1], self.ip[2], self.ip[3])
     }
 }
+
+/// Static IP address assignment
+///
+/// The default (all zeros) stands for "no static config, use DHCP".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IpConfig {
+    /// IP address
+    pub ip: [u8; 4],
+    /// Subnet mask
+    pub netmask: [u8; 4],
+    /// Gateway address
+    pub gateway: [u8; 4],
+    /// DNS server address
+    pub dns: [u8; 4],
+}
+
+/// Summary of a `wifi_ping` run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PingStats {
+    /// Echo requests sent
+    pub sent: u8,
+    /// Echo replies received
+    pub received: u8,
+    /// Fastest round trip in microseconds
+    pub min_rtt_us: u32,
+    /// Slowest round trip in microseconds
+    pub max_rtt_us: u32,
+    /// Mean round trip in microseconds
+    pub avg_rtt_us: u32,
+}
+
+impl PingStats {
+    /// Compute stats from `wifi_ping` RTTs (`u32::MAX` marks a lost reply)
+    pub fn from_rtts(rtts: &[u32]) -> Self {
+        let received: Vec<u32> = rtts
+            .iter()
+            .copied()
+            .filter(|&rtt| rtt != u32::MAX)
+            .collect();
+        let sum: u64 = received.iter().map(|&rtt| rtt as u64).sum();
+
+        Self {
+            sent: rtts.len().min(u8::MAX as usize) as u8,
+            received: received.len().min(u8::MAX as usize) as u8,
+            min_rtt_us: received.iter().copied().min().unwrap_or(0),
+            max_rtt_us: received.iter().copied().max().unwrap_or(0),
+            avg_rtt_us: sum.checked_div(received.len() as u64).unwrap_or(0) as u32,
+        }
+    }
+}
+
+impl fmt::Display for PingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} sent, {} received, rtt min/avg/max = {}/{}/{} us",
+            self.sent, self.received, self.min_rtt_us, self.avg_rtt_us, self.max_rtt_us
+        )
+    }
+}
+
+/// Interface traffic counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WifiStats {
+    /// Bytes transmitted
+    pub tx_bytes: u64,
+    /// Bytes received
+    pub rx_bytes: u64,
+    /// Packets transmitted
+    pub tx_packets: u64,
+    /// Packets received
+    pub rx_packets: u64,
+    /// Transmit errors
+    pub tx_errors: u64,
+    /// Receive errors
+    pub rx_errors: u64,
+}
+
+impl WifiStats {
+    /// Combined TX+RX throughput in Mbit/s since an earlier sample
+    pub fn bandwidth_mbps(&self, prev: &WifiStats, elapsed: Duration) -> f32 {
+        let secs = elapsed.as_secs_f32();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+        let bytes = (self.tx_bytes + self.rx_bytes).saturating_sub(prev.tx_bytes + prev.rx_bytes);
+        bytes as f32 * 8.0 / secs / 1_000_000.0
+    }
+}
+
+/// WiFi state change notification
+#[derive(Debug, Clone)]
+pub enum WifiEvent {
+    /// Associated with an AP
+    Connected { bssid: [u8; 6] },
+    /// Lost association (reason code, 0 if unknown)
+    Disconnected { reason: u8 },
+    /// Scan finished with the given number of results
+    ScanComplete { count: usize },
+    /// IPv4 address assigned to the interface
+    IpAcquired { ip: [u8; 4] },
+    /// IPv4 address removed from the interface
+    IpLost,
+}
+
+/// Callback invoked for every WiFi event
+pub type WifiEventHandler = Box<dyn Fn(WifiEvent) + Send + Sync + 'static>;
+
+/// A registered handler, shared so `dispatch_event` can call it after
+/// releasing the registry lock
+type SharedHandler = Arc<dyn Fn(WifiEvent) + Send + Sync + 'static>;
+
+/// Registered event handlers, keyed by caller-chosen id
+static HANDLERS: LazyLock<Mutex<HashMap<u8, SharedHandler>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Register an event handler under `id`, replacing any handler with that id
+///
+/// Handlers run on whichever thread detects the event (the caller of a
+/// polling function, or a backend monitor thread). They may register and
+/// unregister handlers; such changes apply from the next event on.
+pub fn wifi_register_event_handler(id: u8, handler: WifiEventHandler) -> WifiResult<()> {
+    let mut handlers = HANDLERS.lock().map_err(|_| WifiError::ConfigurationError)?;
+    handlers.insert(id, Arc::from(handler));
+    Ok(())
+}
+
+/// Remove the event handler registered under `id`
+pub fn wifi_unregister_event_handler(id: u8) {
+    if let Ok(mut handlers) = HANDLERS.lock() {
+        handlers.remove(&id);
+    }
+}
+
+/// Deliver an event to all registered handlers
+#[cfg_attr(
+    not(any(feature = "platform-linux", feature = "platform-nuttx", feature = "mock")),
+    allow(dead_code)
+)]
+fn dispatch_event(event: WifiEvent) {
+    // Call without the lock held, so a handler can't deadlock on it
+    let handlers: Vec<_> = match HANDLERS.lock() {
+        Ok(handlers) => handlers.values().cloned().collect(),
+        Err(_) => return,
+    };
+    for handler in handlers {
+        handler(event.clone());
+    }
+}
+
+/// Retry behaviour for `wifi_connect_with_retry`
+#[derive(Debug, Clone, Copy)]
+pub struct WifiConnectOptions {
+    /// Retries after the first attempt
+    pub max_retries: u8,
+    /// Delay before the first retry in milliseconds
+    pub initial_delay_ms: u32,
+    /// Factor applied to the delay after each retry
+    pub backoff_factor: f32,
+    /// Time to wait for each attempt to connect in milliseconds
+    pub timeout_per_attempt_ms: u32,
+}
+
+impl Default for WifiConnectOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay_ms: 1000,
+            backoff_factor: 2.0,
+            timeout_per_attempt_ms: 10_000,
+        }
+    }
+}
+
+/// Status poll interval while waiting for a connection
+const CONNECT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Connect to a network, retrying with exponential backoff
+///
+/// Each attempt calls `wifi_connect` and polls the connection status until
+/// connected, failed, or `timeout_per_attempt_ms` passes. Returns
+/// `ConnectionFailed` once all retries are exhausted. `NotInitialized` and
+/// `NotSupported` are returned immediately since retrying cannot help.
+pub fn wifi_connect_with_retry(
+    config: &StationConfig,
+    opts: &WifiConnectOptions,
+) -> WifiResult<()> {
+    let mut delay_ms = opts.initial_delay_ms as f32;
+
+    for attempt in 0..=opts.max_retries {
+        if attempt > 0 {
+            thread::sleep(Duration::from_millis(delay_ms as u64));
+            delay_ms *= opts.backoff_factor;
+        }
+
+        match wifi_connect(config) {
+            Ok(()) => {
+                if wait_for_connection(opts.timeout_per_attempt_ms) {
+                    return Ok(());
+                }
+            }
+            Err(e @ (WifiError::NotInitialized | WifiError::NotSupported)) => return Err(e),
+            Err(_) => {}
+        }
+    }
+
+    Err(WifiError::ConnectionFailed)
+}
+
+/// Poll the connection status until connected (true), failed or timed out
+fn wait_for_connection(timeout_ms: u32) -> bool {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+
+    loop {
+        match wifi_get_connection_status() {
+            Ok(ConnectionStatus::Connected) => return true,
+            Ok(ConnectionStatus::Failed) => return false,
+            _ => {}
+        }
+
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::sleep(CONNECT_POLL_INTERVAL);
+    }
+}
+
+/// Initialize WiFi if needed, connect, and wait for an IP address
+///
+/// Polls the connection status every 500 ms. Returns `ConnectionFailed` if
+/// the driver reports a failed connection, or `Timeout` if the station is
+/// still not connected after `timeout_ms`.
+pub fn wifi_connect_simple(ssid: &str, pass: &str, timeout_ms: u32) -> WifiResult<IpInfo> {
+    if !wifi_is_initialized() {
+        match wifi_initialize() {
+            Ok(()) | Err(WifiError::AlreadyInitialized) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    let config = StationConfig::new(ssid, pass);
+    wifi_connect(&config)?;
+
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+    loop {
+        match wifi_get_connection_status()? {
+            ConnectionStatus::Connected => return wifi_get_ip_info(),
+            ConnectionStatus::Failed => return Err(WifiError::ConnectionFailed),
+            _ => {}
+        }
+
+        if Instant::now() >= deadline {
+            return Err(WifiError::Timeout);
+        }
+        thread::sleep(CONNECT_POLL_INTERVAL);
+    }
+}
+
+/// Disconnect and deinitialize WiFi
+///
+/// WiFi is deinitialized even if disconnecting fails; that error is then
+/// returned afterwards.
+pub fn wifi_disconnect_simple() -> WifiResult<()> {
+    let disconnected = wifi_disconnect();
+    wifi_deinitialize()?;
+    disconnected
+}
+
+/// HTTP response bodies larger than this are rejected
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+const HTTP_MAX_RESPONSE: usize = 1024 * 1024;
+
+/// Fetch `url` with a minimal HTTP/1.1 GET and return the response body
+///
+/// Only `http://` URLs are supported (`https://` returns `NotSupported`).
+/// IPv4 literals are used as-is, other hosts go through
+/// `wifi_resolve_hostname`. The whole request, from connect to the last
+/// byte, must finish within `timeout_ms` or `Timeout` is returned. Chunked
+/// bodies are decoded; the status code is not checked. A malformed
+/// response returns `ConnectionFailed`.
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+pub fn wifi_http_get(url: &str, timeout_ms: u32) -> WifiResult<Vec<u8>> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+    let url = HttpUrl::parse(url)?;
+    let ip = match url.host.parse::<std::net::Ipv4Addr>() {
+        Ok(ip) => ip.octets(),
+        Err(_) => wifi_resolve_hostname(url.host)?,
+    };
+
+    let host = if url.port == 80 {
+        url.host.to_string()
+    } else {
+        format!("{}:{}", url.host, url.port)
+    };
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        url.path, host
+    );
+
+    let response = http_exchange(ip, url.port, request.as_bytes(), deadline)?;
+    http_response_body(&response)
+}
+
+/// Host, port and path of an `http://` URL
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+struct HttpUrl<'a> {
+    host: &'a str,
+    port: u16,
+    path: String,
+}
+
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+impl<'a> HttpUrl<'a> {
+    fn parse(url: &'a str) -> WifiResult<Self> {
+        if url.starts_with("https://") {
+            return Err(WifiError::NotSupported);
+        }
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or(WifiError::ConfigurationError)?;
+
+        let (authority, path) = match rest.find(['/', '?']) {
+            Some(i) if rest[i..].starts_with('?') => (&rest[..i], format!("/{}", &rest[i..])),
+            Some(i) => (&rest[..i], rest[i..].to_string()),
+            None => (rest, "/".to_string()),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse().map_err(|_| WifiError::ConfigurationError)?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(WifiError::ConfigurationError);
+        }
+
+        Ok(HttpUrl { host, port, path })
+    }
+}
+
+/// Connect to `ip:port`, send `request` and read until the server closes
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+fn http_exchange(
+    ip: [u8; 4],
+    port: u16,
+    request: &[u8],
+    deadline: Instant,
+) -> WifiResult<Vec<u8>> {
+    use std::io::{ErrorKind, Read, Write};
+
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let io_error = |e: std::io::Error| match e.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => WifiError::Timeout,
+        _ => WifiError::from(e.raw_os_error().unwrap_or(0)),
+    };
+
+    let mut stream = TcpStream::connect(ip, port, remaining().as_millis() as u32)?;
+    stream.write_all(request).map_err(io_error)?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let left = remaining();
+        if left.is_zero() {
+            return Err(WifiError::Timeout);
+        }
+        stream.set_read_timeout(Some(left))?;
+
+        match stream.read(&mut buf) {
+            Ok(0) => return Ok(response),
+            Ok(n) => response.extend_from_slice(&buf[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(io_error(e)),
+        }
+        if response.len() > HTTP_MAX_RESPONSE {
+            return Err(WifiError::ConnectionFailed);
+        }
+    }
+}
+
+/// Strip the status line and headers, decoding a chunked body
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+fn http_response_body(response: &[u8]) -> WifiResult<Vec<u8>> {
+    let header_end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or(WifiError::ConnectionFailed)?;
+    let headers = String::from_utf8_lossy(&response[..header_end]);
+    if !headers.starts_with("HTTP/1.") {
+        return Err(WifiError::ConnectionFailed);
+    }
+    let body = &response[header_end + 4..];
+
+    let header = |name: &str| {
+        headers.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_ascii_lowercase())
+        })
+    };
+
+    if header("transfer-encoding").is_some_and(|v| v.contains("chunked")) {
+        return decode_chunked(body);
+    }
+    match header("content-length").and_then(|v| v.parse::<usize>().ok()) {
+        Some(len) if len <= body.len() => Ok(body[..len].to_vec()),
+        Some(_) => Err(WifiError::ConnectionFailed),
+        None => Ok(body.to_vec()),
+    }
+}
+
+/// Decode a chunked transfer-encoded body (trailers are ignored)
+#[cfg(any(feature = "platform-linux", feature = "platform-nuttx"))]
+fn decode_chunked(mut body: &[u8]) -> WifiResult<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or(WifiError::ConnectionFailed)?;
+        let size_line =
+            core::str::from_utf8(&body[..line_end]).map_err(|_| WifiError::ConnectionFailed)?;
+        // Chunk extensions follow the size after ';'
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_hex, 16).map_err(|_| WifiError::ConnectionFailed)?;
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk = body.get(..size).ok_or(WifiError::ConnectionFailed)?;
+        decoded.extend_from_slice(chunk);
+        body = body
+            .get(size..)
+            .and_then(|rest| rest.strip_prefix(b"\r\n"))
+            .ok_or(WifiError::ConnectionFailed)?;
+    }
+}
+
+/// Longest wait between keep-alive reconnect attempts
+const KEEPALIVE_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Background thread that reconnects when the station drops off the AP
+///
+/// The connection status is polled every `check_interval_ms`. After a
+/// failed reconnect the delay doubles, up to 60 seconds, and resets once
+/// the link is back. Dropping the keep-alive stops the thread.
+pub struct WifiKeepAlive {
+    config: StationConfig,
+    interval_ms: u32,
+    stop_flag: Arc<AtomicBool>,
+    attempts: Arc<AtomicU32>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl WifiKeepAlive {
+    /// Start monitoring the connection (WiFi must be initialized)
+    pub fn start(config: StationConfig, check_interval_ms: u32) -> WifiResult<WifiKeepAlive> {
+        if !wifi_is_initialized() {
+            return Err(WifiError::NotInitialized);
+        }
+
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let attempts = Arc::new(AtomicU32::new(0));
+        let interval = Duration::from_millis(check_interval_ms.max(1) as u64);
+
+        let handle = {
+            let config = config.clone();
+            let stop_flag = stop_flag.clone();
+            let attempts = attempts.clone();
+            thread::Builder::new()
+                .name("wifi-keepalive".into())
+                .spawn(move || keepalive_loop(&config, interval, &stop_flag, &attempts))
+                .map_err(|_| WifiError::ConfigurationError)?
+        };
+
+        Ok(WifiKeepAlive {
+            config,
+            interval_ms: check_interval_ms,
+            stop_flag,
+            attempts,
+            handle: Some(handle),
+        })
+    }
+
+    /// Stop the background thread and wait for it to exit
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Number of reconnect attempts made so far
+    pub fn attempts_made(&self) -> u32 {
+        self.attempts.load(Ordering::Relaxed)
+    }
+
+    /// Network being kept alive
+    pub fn config(&self) -> &StationConfig {
+        &self.config
+    }
+
+    /// Connection check interval in milliseconds
+    pub fn interval_ms(&self) -> u32 {
+        self.interval_ms
+    }
+
+    fn shutdown(&mut self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for WifiKeepAlive {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Keep-alive thread body
+fn keepalive_loop(
+    config: &StationConfig,
+    interval: Duration,
+    stop_flag: &AtomicBool,
+    attempts: &AtomicU32,
+) {
+    let mut delay = interval;
+
+    while crate::sleep_unless_stopped(delay, stop_flag) {
+        match wifi_get_connection_status() {
+            Ok(ConnectionStatus::Disconnected) | Ok(ConnectionStatus::Failed) => {
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                println!("  [WIFI] Connection lost, reconnect attempt {}", attempt);
+                if let Err(e) = wifi_connect(config) {
+                    println!("  [WIFI] Reconnect failed: {}", e);
+                }
+                delay = (delay * 2).min(KEEPALIVE_MAX_BACKOFF);
+            }
+            _ => delay = interval,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Minimal MQTT 3.1.1 client over `TcpStream`
+//!
+//! Supports what a sensor node needs to push data to a broker: CONNECT
+//! with a clean session, QoS 0 PUBLISH, PINGREQ and DISCONNECT. Packets
+//! are built by hand following the OASIS MQTT 3.1.1 specification.
+
+use super::{TcpStream, WifiError, WifiResult};
+use std::io::{ErrorKind, Read, Write};
+use std::time::Duration;
+
+/// How long to wait for the broker to connect and answer CONNECT or PINGREQ
+const MQTT_TIMEOUT_MS: u32 = 5000;
+
+/// Largest value the variable-length "remaining length" field can encode
+const MAX_REMAINING_LENGTH: usize = 268_435_455;
+
+// Control packet types (first byte of the fixed header)
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const PINGREQ: u8 = 0xC0;
+const PINGRESP: u8 = 0xD0;
+const DISCONNECT: u8 = 0xE0;
+
+/// Protocol level for MQTT 3.1.1
+const PROTOCOL_LEVEL: u8 = 4;
+
+/// CONNECT flag: discard any previous session state
+const CLEAN_SESSION: u8 = 0x02;
+
+/// Connected MQTT client
+///
+/// ```ignore
+/// let mut client = MqttClient::connect([192, 168, 1, 10], 1883, "rustcam", 60)?;
+/// client.publish("rustcam/temp", b"23.5", 0)?;
+/// client.disconnect()?;
+/// ```
+pub struct MqttClient {
+    stream: TcpStream,
+    client_id: String,
+    keep_alive_secs: u16,
+}
+
+impl MqttClient {
+    /// Connect to a broker and wait for its CONNACK
+    ///
+    /// `keep_alive_secs` is the longest the client promises to stay silent;
+    /// call `ping()` more often than that when not publishing. A refused
+    /// connection returns `AuthenticationFailed` for bad credentials or
+    /// authorization, `ConnectionFailed` otherwise.
+    pub fn connect(
+        broker_ip: [u8; 4],
+        port: u16,
+        client_id: &str,
+        keep_alive_secs: u16,
+    ) -> WifiResult<MqttClient> {
+        let packet = connect_packet(client_id, keep_alive_secs)?;
+
+        let stream = TcpStream::connect(broker_ip, port, MQTT_TIMEOUT_MS)?;
+        stream.set_nodelay(true)?;
+        stream.set_read_timeout(Some(Duration::from_millis(MQTT_TIMEOUT_MS as u64)))?;
+        let mut client = MqttClient {
+            stream,
+            client_id: client_id.to_string(),
+            keep_alive_secs,
+        };
+        client.send(&packet)?;
+
+        // CONNACK: type, remaining length 2, session present flag, return code
+        let mut connack = [0u8; 4];
+        client.receive(&mut connack)?;
+        if connack[0] != CONNACK || connack[1] != 2 {
+            return Err(WifiError::ConnectionFailed);
+        }
+        match connack[3] {
+            0 => Ok(client),
+            // Bad user name or password / not authorized
+            4 | 5 => Err(WifiError::AuthenticationFailed),
+            _ => Err(WifiError::ConnectionFailed),
+        }
+    }
+
+    /// Client identifier sent in CONNECT
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Keep-alive interval agreed with the broker
+    pub fn keep_alive_secs(&self) -> u16 {
+        self.keep_alive_secs
+    }
+
+    /// Publish `payload` to `topic`
+    ///
+    /// Only QoS 0 (fire and forget) is supported; other levels return
+    /// `NotSupported`. Topics must be non-empty and free of wildcards.
+    pub fn publish(&mut self, topic: &str, payload: &[u8], qos: u8) -> WifiResult<()> {
+        if qos != 0 {
+            return Err(WifiError::NotSupported);
+        }
+        let packet = publish_packet(topic, payload)?;
+        self.send(&packet)
+    }
+
+    /// Send PINGREQ and wait for PINGRESP
+    pub fn ping(&mut self) -> WifiResult<()> {
+        self.send(&[PINGREQ, 0])?;
+        let mut pingresp = [0u8; 2];
+        self.receive(&mut pingresp)?;
+        if pingresp != [PINGRESP, 0] {
+            return Err(WifiError::ConnectionFailed);
+        }
+        Ok(())
+    }
+
+    /// Send DISCONNECT and close the connection
+    pub fn disconnect(mut self) -> WifiResult<()> {
+        self.send(&[DISCONNECT, 0])
+    }
+
+    fn send(&mut self, packet: &[u8]) -> WifiResult<()> {
+        self.stream.write_all(packet).map_err(io_error)
+    }
+
+    fn receive(&mut self, buf: &mut [u8]) -> WifiResult<()> {
+        self.stream.read_exact(buf).map_err(io_error)
+    }
+}
+
+fn io_error(e: std::io::Error) -> WifiError {
+    match e.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => WifiError::Timeout,
+        ErrorKind::UnexpectedEof => WifiError::ConnectionFailed,
+        _ => WifiError::from(e.raw_os_error().unwrap_or(0)),
+    }
+}
+
+/// Build a CONNECT packet with a clean session and no will or credentials
+fn connect_packet(client_id: &str, keep_alive_secs: u16) -> WifiResult<Vec<u8>> {
+    let mut body = Vec::new();
+    put_string(&mut body, "MQTT")?;
+    body.push(PROTOCOL_LEVEL);
+    body.push(CLEAN_SESSION);
+    body.extend_from_slice(&keep_alive_secs.to_be_bytes());
+    put_string(&mut body, client_id)?;
+    packet(CONNECT, &body)
+}
+
+/// Build a QoS 0 PUBLISH packet (no packet identifier)
+fn publish_packet(topic: &str, payload: &[u8]) -> WifiResult<Vec<u8>> {
+    if topic.is_empty() || topic.contains(['+', '#']) {
+        return Err(WifiError::ConfigurationError);
+    }
+    let mut body = Vec::with_capacity(2 + topic.len() + payload.len());
+    put_string(&mut body, topic)?;
+    body.extend_from_slice(payload);
+    packet(PUBLISH, &body)
+}
+
+/// Prefix `body` with the fixed header: packet type and remaining length
+fn packet(packet_type: u8, body: &[u8]) -> WifiResult<Vec<u8>> {
+    if body.len() > MAX_REMAINING_LENGTH {
+        return Err(WifiError::ConfigurationError);
+    }
+    let mut packet = Vec::with_capacity(5 + body.len());
+    packet.push(packet_type);
+
+    // Seven bits per byte, least significant first, high bit = more follows
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        packet.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+
+    packet.extend_from_slice(body);
+    Ok(packet)
+}
+
+/// Append a UTF-8 string with its 16-bit big-endian length prefix
+fn put_string(buf: &mut Vec<u8>, s: &str) -> WifiResult<()> {
+    let len = u16::try_from(s.len()).map_err(|_| WifiError::ConfigurationError)?;
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+    Ok(())
+}
@@ -1,7 +1,8 @@
 //! WiFi HAL stub for unsupported platforms
 
 use super::{
-    ConnectionStatus, IpInfo, ScanResult, StationConfig, WifiError, WifiMode, WifiResult,
+    ApConfig, ConnectionStatus, IpConfig, IpInfo, PowerSaveMode, ScanResult, StationConfig,
+    WifiError, WifiMode, WifiResult, WifiStats,
 };
 
 pub fn wifi_initialize() -> WifiResult<()> {
@@ -24,10 +25,26 @@ pub fn wifi_get_mode() -> WifiResult<WifiMode> {
     Err(WifiError::NotSupported)
 }
 
+pub fn wifi_set_power_save(_mode: PowerSaveMode) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_get_power_save() -> WifiResult<PowerSaveMode> {
+    Err(WifiError::NotSupported)
+}
+
 pub fn wifi_start_scan() -> WifiResult<()> {
     Err(WifiError::NotSupported)
 }
 
+pub fn wifi_start_scan_on_channels(_channels: &[u8]) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_start_scan_for_ssid(_ssid: &str) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
 pub fn wifi_scan_is_complete() -> WifiResult<bool> {
     Err(WifiError::NotSupported)
 }
@@ -60,6 +77,50 @@ pub fn wifi_get_rssi() -> WifiResult<i8> {
     Err(WifiError::NotSupported)
 }
 
+pub fn wifi_get_link_quality() -> WifiResult<u8> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_set_static_ip(_config: &IpConfig) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_set_dhcp() -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_get_ip_config() -> WifiResult<IpConfig> {
+    Err(WifiError::NotSupported)
+}
+
 pub fn wifi_get_mac_address() -> WifiResult<[u8; 6]> {
     Err(WifiError::NotSupported)
 }
+
+pub fn wifi_ping(_ip: [u8; 4], _count: u8, _timeout_ms: u32) -> WifiResult<Vec<u32>> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_start_ap(_config: ApConfig) -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_stop_ap() -> WifiResult<()> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_get_ap_clients() -> WifiResult<Vec<[u8; 6]>> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_get_stats() -> WifiResult<WifiStats> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_resolve_hostname(_host: &str) -> WifiResult<[u8; 4]> {
+    Err(WifiError::NotSupported)
+}
+
+pub fn wifi_resolve_hostname_all(_host: &str) -> WifiResult<Vec<[u8; 4]>> {
+    Err(WifiError::NotSupported)
+}
@@ -0,0 +1,91 @@
+//! SNTP client (RFC 4330) for setting the clock from a time server
+
+use super::{UdpSocket, WifiError, WifiResult};
+use std::time::{Duration, Instant};
+
+/// NTP server port
+const NTP_PORT: u16 = 123;
+
+/// Seconds from the NTP epoch (1900-01-01) to the Unix epoch (1970-01-01)
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// How long to wait for the server's reply
+const NTP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Size of an NTP packet without extensions
+const NTP_PACKET_LEN: usize = 48;
+
+/// Leap indicator 0, version 3, mode 3 (client)
+const NTP_CLIENT_HEADER: u8 = (3 << 3) | 3;
+
+/// Mode 4 (server), in the low three bits of the first byte
+const NTP_MODE_SERVER: u8 = 4;
+
+/// Query `server_ip` and return the current Unix time in seconds
+///
+/// Waits up to 3 seconds for a reply and returns `Timeout` without one.
+/// Kiss-o'-Death replies (stratum 0) return `ConnectionFailed`.
+pub fn wifi_get_ntp_time(server_ip: [u8; 4]) -> WifiResult<u64> {
+    let socket = UdpSocket::bind_any()?;
+
+    let mut request = [0u8; NTP_PACKET_LEN];
+    request[0] = NTP_CLIENT_HEADER;
+    socket.send_to(&request, server_ip, NTP_PORT)?;
+
+    let deadline = Instant::now() + NTP_TIMEOUT;
+    let mut response = [0u8; NTP_PACKET_LEN];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(WifiError::Timeout);
+        }
+        socket.set_read_timeout(Some(remaining))?;
+
+        let (from, port, len) = socket.recv_from(&mut response)?;
+        // Ignore stray datagrams from anyone but the server
+        if from != server_ip || port != NTP_PORT || len < NTP_PACKET_LEN {
+            continue;
+        }
+        if response[0] & 0x07 != NTP_MODE_SERVER {
+            continue;
+        }
+        if response[1] == 0 {
+            return Err(WifiError::ConnectionFailed);
+        }
+
+        // Transmit timestamp: seconds in bytes 40..44, fraction in 44..48
+        let secs = u32::from_be_bytes([response[40], response[41], response[42], response[43]]);
+        return Ok(ntp_to_unix(secs));
+    }
+}
+
+/// Set the system clock from `server_ip`
+///
+/// Needs CAP_SYS_TIME on Linux; returns `PermissionDenied` without it.
+pub fn wifi_sync_system_time(server_ip: [u8; 4]) -> WifiResult<()> {
+    let unix_secs = wifi_get_ntp_time(server_ip)?;
+
+    let tv = libc::timeval {
+        tv_sec: unix_secs as libc::time_t,
+        tv_usec: 0,
+    };
+    if unsafe { libc::settimeofday(&tv, core::ptr::null()) } < 0 {
+        let errno = std::io::Error::last_os_error().raw_os_error().unwrap_or(0);
+        return Err(WifiError::from(errno));
+    }
+    Ok(())
+}
+
+/// Convert NTP timestamp seconds to Unix seconds
+///
+/// Values below the Unix epoch offset are taken to be in NTP era 1, which
+/// starts on 2036-02-07.
+#[doc(hidden)] // pub for tests
+pub fn ntp_to_unix(ntp_secs: u32) -> u64 {
+    let secs = ntp_secs as u64;
+    if secs >= NTP_UNIX_OFFSET {
+        secs - NTP_UNIX_OFFSET
+    } else {
+        secs + (1 << 32) - NTP_UNIX_OFFSET
+    }
+}
@@ -4,8 +4,13 @@
 //! This works with ESP32S3 WiFi driver.
 
 use super::{
-    AuthMode, ConnectionStatus, IpInfo, ScanResult, StationConfig, WifiError, WifiMode, WifiResult,
+    channel_to_freq, dispatch_event, ApConfig, AuthMode, ConnectionStatus, IpConfig, IpInfo,
+    PowerSaveMode, ScanResult, StationConfig, WifiError, WifiEvent, WifiMode, WifiResult,
+    WifiStats,
 };
+use super::icmp::icmp_ping;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 /// Maximum ESSID size
 const IW_ESSID_MAX_SIZE: usize = 32;
@@ -13,6 +18,13 @@ const IW_ESSID_MAX_SIZE: usize = 32;
 /// Scan buffer size
 const IW_SCAN_MAX_DATA: usize = 4096;
 
+/// Maximum number of channels in a scan request
+const IW_MAX_FREQUENCIES: usize = 32;
+
+// iw_scan_req flags
+const IW_SCAN_THIS_ESSID: u16 = 0x0002;
+const IW_SCAN_THIS_FREQ: u16 = 0x0004;
+
 // WEXT ioctl commands (from nuttx/wireless/wireless.h)
 // These use _WLIOC macro which is _IOC(_WLIOCBASE, n)
 // _WLIOCBASE = 0x8b00, so SIOCSIWSCAN = 0x8b00 + 0x18 = 0x8b18
@@ -22,6 +34,7 @@ const SIOCSIWFREQ: i32 = 0x8b04;
 #[allow(dead_code)]
 const SIOCGIWFREQ: i32 = 0x8b05;
 const SIOCSIWMODE: i32 = 0x8b06;
+const SIOCGIWSTATS: i32 = 0x8b0f;
 const SIOCGIWMODE: i32 = 0x8b07;
 const SIOCSIWAP: i32 = 0x8b14;
 const SIOCGIWAP: i32 = 0x8b15;
@@ -33,6 +46,8 @@ const SIOCGIWESSID: i32 = 0x8b1b;
 const SIOCSIWENCODE: i32 = 0x8b2a;
 #[allow(dead_code)]
 const SIOCGIWENCODE: i32 = 0x8b2b;
+const SIOCSIWPOWER: i32 = 0x8b2c;
+const SIOCGIWPOWER: i32 = 0x8b2d;
 const SIOCSIWAUTH: i32 = 0x8b32;
 #[allow(dead_code)]
 const SIOCGIWAUTH: i32 = 0x8b33;
@@ -48,6 +63,9 @@ const IW_MODE_MONITOR: u32 = 6;
 // ESSID flags
 const IW_ESSID_ON: u16 = 1;
 
+// Link quality flags (iw_quality.updated)
+const IW_QUAL_DBM: u8 = 0x08;
+
 // Auth parameters
 const IW_AUTH_WPA_VERSION: u16 = 0;
 const IW_AUTH_CIPHER_PAIRWISE: u16 = 1;
@@ -87,8 +105,19 @@ const DEFAULT_IFNAME: &[u8] = b"wlan0\0";
 // NuttX ioctl uses int for request, not unsigned long like Linux
 extern "C" {
     fn ioctl(fd: libc::c_int, request: libc::c_int, ...) -> libc::c_int;
+
+    /// Run the DHCP client on an interface and apply the lease (netutils/netlib)
+    fn netlib_obtain_ipv4addr(ifname: *const libc::c_char) -> libc::c_int;
 }
 
+// Standard network ioctls
+const SIOCGIFADDR: i32 = 0x8915;
+const SIOCSIFADDR: i32 = 0x8916;
+const SIOCSIFDSTADDR: i32 = 0x8918;
+const SIOCGIFNETMASK: i32 = 0x891b;
+const SIOCSIFNETMASK: i32 = 0x891c;
+const SIOCGIFSTATS: i32 = 0x8947;
+
 /// Get last OS error code using std::io
 fn get_last_errno() -> i32 {
     std::io::Error::last_os_error()
@@ -100,6 +129,13 @@ fn get_last_errno() -> i32 {
 const EAGAIN: i32 = 11;
 
 /// E2BIG error code (argument list too long / buffer too small)
+// Power management: value is a sleep period
+const IW_POWER_PERIOD: u16 = 0x1000;
+
+// Sleep periods for PowerSaveMode::Light and ::Maximum, in DTIM intervals
+const POWER_SAVE_LIGHT_DTIM: i32 = 1;
+const POWER_SAVE_MAX_DTIM: i32 = 10;
+
 const E2BIG: i32 = 7;
 
 /// iw_point structure for data transfer
@@ -141,6 +177,16 @@ struct IwQuality {
     updated: u8,
 }
 
+/// iw_statistics structure returned by SIOCGIWSTATS
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct IwStats {
+    status: u16,
+    qual: IwQuality,
+    discard: [u32; 5], // nwid, code, fragment, retries, misc
+    miss_beacon: u32,
+}
+
 /// sockaddr structure for AP address
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -149,6 +195,21 @@ struct SockAddr {
     sa_data: [u8; 14],
 }
 
+/// iw_scan_req structure for directed scans
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct IwScanReq {
+    scan_type: u8,
+    essid_len: u8,
+    num_channels: u8,
+    flags: u8,
+    bssid: SockAddr,
+    essid: [u8; IW_ESSID_MAX_SIZE],
+    min_channel_time: u32,
+    max_channel_time: u32,
+    channel_list: [IwFreq; IW_MAX_FREQUENCIES],
+}
+
 /// Union for iwreq data
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -213,8 +274,45 @@ struct IwEncodeExt {
     // key data follows
 }
 
+/// sockaddr_in structure for interface address ioctls
+#[repr(C)]
+struct SockAddrIn {
+    sin_family: u16,
+    sin_port: u16,
+    sin_addr: [u8; 4],
+    sin_zero: [u8; 8],
+}
+
+/// ifreq structure for interface address ioctls
+#[repr(C)]
+struct IfReq {
+    ifr_name: [libc::c_char; 16],
+    ifr_addr: SockAddrIn,
+}
+
+impl IfReq {
+    fn new() -> Self {
+        let mut req: Self = unsafe { core::mem::zeroed() };
+        for (i, &b) in DEFAULT_IFNAME.iter().enumerate() {
+            if i < 16 {
+                req.ifr_name[i] = b as libc::c_char;
+            }
+        }
+        req
+    }
+}
+
 /// Global state
-static mut INITIALIZED: bool = false;
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+static STATIC_IP_CONFIG: Mutex<Option<IpConfig>> = Mutex::new(None);
+static SCAN_PENDING: AtomicBool = AtomicBool::new(false);
+/// AP MAC seen by the last connection status check (zero when disconnected)
+static LAST_BSSID: Mutex<[u8; 6]> = Mutex::new([0u8; 6]);
+
+/// Lock a global; poisoning is ignored since the guarded state is plain data
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
 /// Create a socket for ioctl operations
 fn make_socket() -> WifiResult<i32> {
@@ -232,42 +330,37 @@ fn close_socket(fd: i32) {
 
 /// Initialize WiFi subsystem
 pub fn wifi_initialize() -> WifiResult<()> {
-    unsafe {
-        if INITIALIZED {
-            // Already initialized - this is fine
-            return Ok(());
-        }
-
-        // Verify interface exists by checking if we can get its name
-        let fd = make_socket()?;
-        let mut req = IwReq::new();
+    if INITIALIZED.load(Ordering::Relaxed) {
+        // Already initialized - this is fine
+        return Ok(());
+    }
 
-        let ret = ioctl(fd, SIOCGIWNAME, &mut req as *mut IwReq);
-        close_socket(fd);
+    // Verify interface exists by checking if we can get its name
+    let fd = make_socket()?;
+    let mut req = IwReq::new();
 
-        if ret < 0 {
-            return Err(WifiError::InterfaceNotFound);
-        }
+    let ret = unsafe { ioctl(fd, SIOCGIWNAME, &mut req as *mut IwReq) };
+    close_socket(fd);
 
-        INITIALIZED = true;
-        Ok(())
+    if ret < 0 {
+        return Err(WifiError::InterfaceNotFound);
     }
+
+    INITIALIZED.store(true, Ordering::Relaxed);
+    Ok(())
 }
 
 /// Deinitialize WiFi subsystem
 pub fn wifi_deinitialize() -> WifiResult<()> {
-    unsafe {
-        if !INITIALIZED {
-            return Err(WifiError::NotInitialized);
-        }
-        INITIALIZED = false;
-        Ok(())
+    if !INITIALIZED.swap(false, Ordering::Relaxed) {
+        return Err(WifiError::NotInitialized);
     }
+    Ok(())
 }
 
 /// Check if WiFi is initialized
 pub fn wifi_is_initialized() -> bool {
-    unsafe { INITIALIZED }
+    INITIALIZED.load(Ordering::Relaxed)
 }
 
 /// Set WiFi operating mode
@@ -318,25 +411,126 @@ pub fn wifi_get_mode() -> WifiResult<WifiMode> {
     })
 }
 
+/// Set the radio power saving mode
+///
+/// `Light` wakes for every DTIM beacon, `Maximum` for every 10th.
+pub fn wifi_set_power_save(mode: PowerSaveMode) -> WifiResult<()> {
+    let fd = make_socket()?;
+    let mut req = IwReq::new();
+
+    let dtim = match mode {
+        PowerSaveMode::None => 0,
+        PowerSaveMode::Light => POWER_SAVE_LIGHT_DTIM,
+        PowerSaveMode::Maximum => POWER_SAVE_MAX_DTIM,
+    };
+    req.u.power = IwParam {
+        value: dtim,
+        fixed: 0,
+        disabled: (mode == PowerSaveMode::None) as u8,
+        flags: if mode == PowerSaveMode::None { 0 } else { IW_POWER_PERIOD },
+    };
+
+    let ret = unsafe { ioctl(fd, SIOCSIWPOWER, &mut req as *mut IwReq) };
+    close_socket(fd);
+
+    if ret < 0 {
+        return Err(WifiError::ConfigurationError);
+    }
+
+    Ok(())
+}
+
+/// Get the radio power saving mode
+pub fn wifi_get_power_save() -> WifiResult<PowerSaveMode> {
+    let fd = make_socket()?;
+    let mut req = IwReq::new();
+
+    let ret = unsafe { ioctl(fd, SIOCGIWPOWER, &mut req as *mut IwReq) };
+    close_socket(fd);
+
+    if ret < 0 {
+        return Err(WifiError::ConfigurationError);
+    }
+
+    let power = unsafe { req.u.power };
+    Ok(if power.disabled != 0 {
+        PowerSaveMode::None
+    } else if power.value >= POWER_SAVE_MAX_DTIM {
+        PowerSaveMode::Maximum
+    } else {
+        PowerSaveMode::Light
+    })
+}
+
 /// Start WiFi scan
 pub fn wifi_start_scan() -> WifiResult<()> {
+    // No scan request: scan all channels for any ESSID
+    start_scan(None, 0)
+}
+
+/// Start a scan limited to `channels` (1-14 and 36-177)
+pub fn wifi_start_scan_on_channels(channels: &[u8]) -> WifiResult<()> {
+    if channels.is_empty() || channels.len() > IW_MAX_FREQUENCIES {
+        return Err(WifiError::ConfigurationError);
+    }
+    if channels.iter().any(|&ch| channel_to_freq(ch).is_none()) {
+        return Err(WifiError::ConfigurationError);
+    }
+
+    let mut scan_req: IwScanReq = unsafe { core::mem::zeroed() };
+    scan_req.num_channels = channels.len() as u8;
+    for (i, &ch) in channels.iter().enumerate() {
+        // Exponent 0: the mantissa is a channel number, not a frequency
+        scan_req.channel_list[i].m = ch as i32;
+        scan_req.channel_list[i].i = i as u8;
+    }
+    start_scan(Some(&mut scan_req), IW_SCAN_THIS_FREQ)
+}
+
+/// Start a scan that probes for `ssid`, so a hidden network answers too
+pub fn wifi_start_scan_for_ssid(ssid: &str) -> WifiResult<()> {
+    let ssid = ssid.as_bytes();
+    if ssid.is_empty() || ssid.len() > IW_ESSID_MAX_SIZE {
+        return Err(WifiError::ConfigurationError);
+    }
+
+    let mut scan_req: IwScanReq = unsafe { core::mem::zeroed() };
+    scan_req.essid_len = ssid.len() as u8;
+    scan_req.essid[..ssid.len()].copy_from_slice(ssid);
+    start_scan(Some(&mut scan_req), IW_SCAN_THIS_ESSID)
+}
+
+/// Issue SIOCSIWSCAN, optionally with a scan request narrowing the scan
+fn start_scan(scan_req: Option<&mut IwScanReq>, flags: u16) -> WifiResult<()> {
     let fd = make_socket()?;
     let mut req = IwReq::new();
 
-    // Set up scan request with no specific ESSID (scan all)
-    req.u.data = IwPoint {
-        pointer: core::ptr::null_mut(),
-        length: 0,
-        flags: 0,
+    req.u.data = match scan_req {
+        Some(scan_req) => IwPoint {
+            pointer: scan_req as *mut IwScanReq as *mut libc::c_void,
+            length: core::mem::size_of::<IwScanReq>() as u16,
+            flags,
+        },
+        None => IwPoint {
+            pointer: core::ptr::null_mut(),
+            length: 0,
+            flags: 0,
+        },
     };
 
     let ret = unsafe { ioctl(fd, SIOCSIWSCAN, &mut req as *mut IwReq) };
+    let errno_val = if ret < 0 { get_last_errno() } else { 0 };
     close_socket(fd);
 
     if ret < 0 {
+        if errno_val == libc::EPERM || errno_val == libc::EACCES {
+            return Err(WifiError::PermissionDenied);
+        }
         return Err(WifiError::ScanFailed);
     }
 
+    SCAN_PENDING.store(true, Ordering::Relaxed);
+
     Ok(())
 }
 
@@ -521,6 +715,10 @@ pub fn wifi_get_scan_results() -> WifiResult<([ScanResult; 16], usize)> {
         count += 1;
     }
 
+    if SCAN_PENDING.swap(false, Ordering::Relaxed) {
+        dispatch_event(WifiEvent::ScanComplete { count });
+    }
+
     Ok((results, count))
 }
 
@@ -708,6 +906,87 @@ pub fn wifi_connect(config: &StationConfig) -> WifiResult<()> {
     Ok(())
 }
 
+/// Start Access Point mode
+///
+/// Switches the interface to master mode and applies security, channel and
+/// ESSID the same way `wifi_connect` does for station mode. WEXT has no
+/// ioctls for `max_clients` or `hidden`, so the driver defaults apply.
+pub fn wifi_start_ap(config: ApConfig) -> WifiResult<()> {
+    let fd = make_socket()?;
+    let mut req = IwReq::new();
+
+    req.u.mode = IW_MODE_MASTER;
+    let ret = unsafe { ioctl(fd, SIOCSIWMODE, &mut req as *mut IwReq) };
+    if ret < 0 {
+        close_socket(fd);
+        return Err(WifiError::ConfigurationError);
+    }
+
+    // Open network without a passphrase, WPA2-PSK (CCMP) otherwise
+    let result = if config.password_len == 0 {
+        set_auth_param(fd, IW_AUTH_WPA_VERSION, IW_AUTH_WPA_VERSION_DISABLED)
+    } else {
+        set_auth_param(fd, IW_AUTH_WPA_VERSION, IW_AUTH_WPA_VERSION_WPA2)
+            .and_then(|_| set_auth_param(fd, IW_AUTH_CIPHER_PAIRWISE, IW_AUTH_CIPHER_CCMP))
+            .and_then(|_| set_auth_param(fd, IW_AUTH_CIPHER_GROUP, IW_AUTH_CIPHER_CCMP))
+            .and_then(|_| set_auth_param(fd, IW_AUTH_KEY_MGMT, IW_AUTH_KEY_MGMT_PSK))
+            .and_then(|_| {
+                set_key_ext(
+                    fd,
+                    IW_ENCODE_ALG_CCMP,
+                    &config.password[..config.password_len],
+                )
+            })
+    };
+    if let Err(e) = result {
+        close_socket(fd);
+        return Err(e);
+    }
+
+    req.u.freq = IwFreq {
+        m: config.channel as i32,
+        e: 0,
+        i: 0,
+        flags: 0,
+    };
+    let ret = unsafe { ioctl(fd, SIOCSIWFREQ, &mut req as *mut IwReq) };
+    if ret < 0 {
+        close_socket(fd);
+        return Err(WifiError::ConfigurationError);
+    }
+
+    // Setting the ESSID brings the AP up
+    let mut essid_buf = [0u8; IW_ESSID_MAX_SIZE + 1];
+    essid_buf[..config.ssid_len].copy_from_slice(&config.ssid[..config.ssid_len]);
+
+    req.u.essid = IwPoint {
+        pointer: essid_buf.as_mut_ptr() as *mut libc::c_void,
+        length: config.ssid_len as u16,
+        flags: IW_ESSID_ON,
+    };
+
+    let ret = unsafe { ioctl(fd, SIOCSIWESSID, &mut req as *mut IwReq) };
+    close_socket(fd);
+
+    if ret < 0 {
+        return Err(WifiError::ConfigurationError);
+    }
+
+    Ok(())
+}
+
+/// Stop Access Point mode and return to station mode
+pub fn wifi_stop_ap() -> WifiResult<()> {
+    wifi_set_mode(WifiMode::Station)
+}
+
+/// Get MAC addresses of stations associated with the AP
+///
+/// WEXT has no station list ioctl, so this is not supported.
+pub fn wifi_get_ap_clients() -> WifiResult<Vec<[u8; 6]>> {
+    Err(WifiError::NotSupported)
+}
+
 /// Disconnect from WiFi network
 pub fn wifi_disconnect() -> WifiResult<()> {
     let fd = make_socket()?;
@@ -740,19 +1019,35 @@ pub fn wifi_get_connection_status() -> WifiResult<ConnectionStatus> {
     let ret = unsafe { ioctl(fd, SIOCGIWAP, &mut req as *mut IwReq) };
     close_socket(fd);
 
-    if ret < 0 {
-        return Ok(ConnectionStatus::Disconnected);
-    }
-
     // Check if we have a valid AP address (not all zeros or all ones)
-    let ap_addr = unsafe { &req.u.ap_addr.sa_data[..6] };
-    let all_zero = ap_addr.iter().all(|&b| b == 0);
-    let all_ones = ap_addr.iter().all(|&b| b == 0xff);
+    let mut bssid = [0u8; 6];
+    if ret >= 0 {
+        bssid.copy_from_slice(unsafe { &req.u.ap_addr.sa_data[..6] });
+        if bssid.iter().all(|&b| b == 0xff) {
+            bssid = [0u8; 6];
+        }
+    }
+    let connected = bssid != [0u8; 6];
+
+    // Report changes since the last check
+    let changed = {
+        let mut last = lock(&LAST_BSSID);
+        let changed = *last != bssid;
+        *last = bssid;
+        changed
+    };
+    if changed {
+        if connected {
+            dispatch_event(WifiEvent::Connected { bssid });
+        } else {
+            dispatch_event(WifiEvent::Disconnected { reason: 0 });
+        }
+    }
 
-    if all_zero || all_ones {
-        Ok(ConnectionStatus::Disconnected)
-    } else {
+    if connected {
         Ok(ConnectionStatus::Connected)
+    } else {
+        Ok(ConnectionStatus::Disconnected)
     }
 }
 
@@ -789,30 +1084,7 @@ pub fn wifi_get_ip_info() -> WifiResult<IpInfo> {
     // Use SIOCGIFADDR to get IP address
     let fd = make_socket()?;
 
-    #[repr(C)]
-    struct SockAddrIn {
-        sin_family: u16,
-        sin_port: u16,
-        sin_addr: [u8; 4],
-        sin_zero: [u8; 8],
-    }
-
-    #[repr(C)]
-    struct IfReq {
-        ifr_name: [libc::c_char; 16],
-        ifr_addr: SockAddrIn,
-    }
-
-    let mut req: IfReq = unsafe { core::mem::zeroed() };
-    for (i, &b) in DEFAULT_IFNAME.iter().enumerate() {
-        if i < 16 {
-            req.ifr_name[i] = b as libc::c_char;
-        }
-    }
-
-    // Standard network ioctls
-    const SIOCGIFADDR: i32 = 0x8915;
-    const SIOCGIFNETMASK: i32 = 0x891b;
+    let mut req = IfReq::new();
 
     let mut info = IpInfo {
         ip: [0; 4],
@@ -839,11 +1111,165 @@ pub fn wifi_get_ip_info() -> WifiResult<IpInfo> {
     Ok(info)
 }
 
+/// Assign an IPv4 address to the interface
+fn set_if_addr(fd: i32, request: i32, addr: [u8; 4]) -> WifiResult<()> {
+    let mut req = IfReq::new();
+    req.ifr_addr.sin_family = libc::AF_INET as u16;
+    req.ifr_addr.sin_addr = addr;
+
+    let ret = unsafe { ioctl(fd, request, &mut req as *mut IfReq) };
+    if ret < 0 {
+        let errno_val = get_last_errno();
+        if errno_val == libc::EPERM || errno_val == libc::EACCES {
+            return Err(WifiError::PermissionDenied);
+        }
+        return Err(WifiError::SystemError(errno_val));
+    }
+    Ok(())
+}
+
+/// Assign a static IP address
+///
+/// The gateway is set as the interface destination address. The DNS
+/// server is only recorded for `wifi_get_ip_config`.
+pub fn wifi_set_static_ip(config: &IpConfig) -> WifiResult<()> {
+    if !wifi_is_initialized() {
+        return Err(WifiError::NotInitialized);
+    }
+
+    let fd = make_socket()?;
+    let result = set_if_addr(fd, SIOCSIFADDR, config.ip)
+        .and_then(|_| set_if_addr(fd, SIOCSIFNETMASK, config.netmask))
+        .and_then(|_| set_if_addr(fd, SIOCSIFDSTADDR, config.gateway));
+    close_socket(fd);
+    result?;
+
+    *lock(&STATIC_IP_CONFIG) = Some(*config);
+    Ok(())
+}
+
+/// Drop the static IP config and obtain an address via DHCP (blocking)
+pub fn wifi_set_dhcp() -> WifiResult<()> {
+    if !wifi_is_initialized() {
+        return Err(WifiError::NotInitialized);
+    }
+
+    *lock(&STATIC_IP_CONFIG) = None;
+
+    let ret = unsafe { netlib_obtain_ipv4addr(DEFAULT_IFNAME.as_ptr() as *const libc::c_char) };
+    if ret < 0 {
+        return Err(WifiError::ConfigurationError);
+    }
+    Ok(())
+}
+
+/// Get the static IP config, or `IpConfig::default()` when using DHCP
+pub fn wifi_get_ip_config() -> WifiResult<IpConfig> {
+    if !wifi_is_initialized() {
+        return Err(WifiError::NotInitialized);
+    }
+    Ok(lock(&STATIC_IP_CONFIG).unwrap_or_default())
+}
+
+/// Device statistics (struct netdev_statistics_s, CONFIG_NETDEV_STATISTICS)
+#[repr(C)]
+#[derive(Default)]
+struct NetDevStats {
+    rx_packets: u32,
+    rx_fragments: u32,
+    rx_errors: u32,
+    rx_ipv4: u32,
+    rx_ipv6: u32,
+    rx_arp: u32,
+    rx_dropped: u32,
+    tx_packets: u32,
+    tx_done: u32,
+    tx_errors: u32,
+    tx_timeouts: u32,
+    errors: u32,
+}
+
+/// ifreq carrying a pointer to the statistics buffer
+#[repr(C)]
+struct IfStatsReq {
+    ifr_name: [libc::c_char; 16],
+    ifr_data: *mut NetDevStats,
+}
+
+/// Get interface traffic counters
+///
+/// NuttX only counts packets and errors; the byte counters stay zero.
+pub fn wifi_get_stats() -> WifiResult<WifiStats> {
+    if !wifi_is_initialized() {
+        return Err(WifiError::NotInitialized);
+    }
+
+    let mut stats = NetDevStats::default();
+    let mut req = IfStatsReq {
+        ifr_name: [0; 16],
+        ifr_data: &mut stats,
+    };
+    for (dst, &src) in req.ifr_name.iter_mut().zip(DEFAULT_IFNAME.iter()) {
+        *dst = src as libc::c_char;
+    }
+
+    let fd = make_socket()?;
+    let ret = unsafe { ioctl(fd, SIOCGIFSTATS, &mut req as *mut IfStatsReq) };
+    close_socket(fd);
+
+    if ret < 0 {
+        return Err(WifiError::NotSupported);
+    }
+
+    Ok(WifiStats {
+        tx_packets: stats.tx_packets as u64,
+        rx_packets: stats.rx_packets as u64,
+        tx_errors: stats.tx_errors as u64,
+        rx_errors: stats.rx_errors as u64,
+        ..WifiStats::default()
+    })
+}
+
+/// Read link statistics for the current connection
+fn get_link_stats() -> WifiResult<IwStats> {
+    let fd = make_socket()?;
+    let mut req = IwReq::new();
+    let mut stats: IwStats = unsafe { core::mem::zeroed() };
+
+    req.u.data = IwPoint {
+        pointer: &mut stats as *mut IwStats as *mut libc::c_void,
+        length: core::mem::size_of::<IwStats>() as u16,
+        flags: 0,
+    };
+
+    let ret = unsafe { ioctl(fd, SIOCGIWSTATS, &mut req as *mut IwReq) };
+    close_socket(fd);
+
+    if ret < 0 {
+        return Err(WifiError::NotSupported);
+    }
+
+    Ok(stats)
+}
+
 /// Get signal strength (RSSI) of current connection
+///
+/// Returns NotSupported if the driver reports signal level as a relative
+/// quality index rather than dBm.
 pub fn wifi_get_rssi() -> WifiResult<i8> {
-    // This would require getting link quality stats
-    // For now return a placeholder
-    Err(WifiError::NotSupported)
+    let stats = get_link_stats()?;
+
+    if stats.qual.updated & IW_QUAL_DBM == 0 {
+        return Err(WifiError::NotSupported);
+    }
+
+    // Level is a dBm value stored in an unsigned byte
+    Ok(stats.qual.level as i8)
+}
+
+/// Get link quality of current connection (driver-defined scale)
+pub fn wifi_get_link_quality() -> WifiResult<u8> {
+    Ok(get_link_stats()?.qual.qual)
 }
 
 /// Get MAC address of WiFi interface
@@ -889,3 +1315,23 @@ pub fn wifi_get_mac_address() -> WifiResult<[u8; 6]> {
     mac.copy_from_slice(&req.ifr_hwaddr.sa_data[..6]);
     Ok(mac)
 }
+
+/// Ping an IPv4 host, returning the RTT of each echo request in
+/// microseconds (`u32::MAX` for a missing reply)
+///
+/// NuttX exposes ICMP through datagram sockets (CONFIG_NET_ICMP_SOCKET),
+/// which deliver the ICMP message without the IP header.
+pub fn wifi_ping(ip: [u8; 4], count: u8, timeout_ms: u32) -> WifiResult<Vec<u32>> {
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, libc::IPPROTO_ICMP) };
+    if fd < 0 {
+        let errno_val = get_last_errno();
+        if errno_val == libc::EPERM || errno_val == libc::EACCES {
+            return Err(WifiError::PermissionDenied);
+        }
+        return Err(WifiError::SocketError);
+    }
+
+    let rtts = icmp_ping(fd, false, ip, count, timeout_ms);
+    close_socket(fd);
+    Ok(rtts)
+}
@@ -0,0 +1,221 @@
+//! Blocking TCP client socket on raw libc sockets
+//!
+//! Shared by the protocol helpers (HTTP, MQTT, ...) so that they work the
+//! same on Linux and NuttX. Connecting is bounded by a timeout; reads can
+//! be bounded with `set_read_timeout`.
+
+use super::{WifiError, WifiResult};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use std::io;
+use std::os::fd::RawFd;
+use std::time::{Duration, Instant};
+
+/// Connected TCP stream, closed on drop
+///
+/// `Send` but not `Sync`: move it to another thread rather than sharing it.
+pub struct TcpStream {
+    fd: RawFd,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl TcpStream {
+    /// Connect to `addr:port`, giving up with `Timeout` after `timeout_ms`
+    pub fn connect(addr: [u8; 4], port: u16, timeout_ms: u32) -> WifiResult<TcpStream> {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms as u64);
+
+        let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
+        if fd < 0 {
+            return Err(WifiError::SocketError);
+        }
+        // Owned from here on, so every early return closes the socket
+        let stream = TcpStream {
+            fd,
+            _not_sync: PhantomData,
+        };
+
+        // Connect non-blocking so the wait can be bounded, then go back to blocking
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) };
+
+        let sin = sockaddr_in(addr, port);
+        let rc = unsafe {
+            libc::connect(
+                fd,
+                &sin as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            let errno = last_errno();
+            if errno != libc::EINPROGRESS {
+                return Err(WifiError::from(errno));
+            }
+            wait_writable(fd, deadline)?;
+
+            let mut error: libc::c_int = 0;
+            stream.getsockopt(libc::SOL_SOCKET, libc::SO_ERROR, &mut error)?;
+            if error != 0 {
+                return Err(WifiError::from(error));
+            }
+        }
+
+        unsafe { libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) };
+        Ok(stream)
+    }
+
+    /// Bound each read; `None` blocks until data arrives
+    ///
+    /// A read that times out fails with `WouldBlock` (or `TimedOut`).
+    pub fn set_read_timeout(&self, d: Option<Duration>) -> WifiResult<()> {
+        setsockopt(self.fd, libc::SOL_SOCKET, libc::SO_RCVTIMEO, &timeval(d)?)
+    }
+
+    /// Enable or disable Nagle's algorithm (TCP_NODELAY)
+    pub fn set_nodelay(&self, nodelay: bool) -> WifiResult<()> {
+        let value = nodelay as libc::c_int;
+        setsockopt(self.fd, libc::IPPROTO_TCP, libc::TCP_NODELAY, &value)
+    }
+
+    /// Local IPv4 address and port of the connection
+    pub fn local_addr(&self) -> WifiResult<([u8; 4], u16)> {
+        local_addr(self.fd)
+    }
+
+    fn getsockopt<T>(
+        &self,
+        level: libc::c_int,
+        name: libc::c_int,
+        value: &mut T,
+    ) -> WifiResult<()> {
+        let mut len = core::mem::size_of::<T>() as libc::socklen_t;
+        let rc = unsafe {
+            libc::getsockopt(
+                self.fd,
+                level,
+                name,
+                value as *mut T as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if rc < 0 {
+            return Err(WifiError::from(last_errno()));
+        }
+        Ok(())
+    }
+}
+
+impl io::Read for TcpStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = unsafe { libc::recv(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(n as usize)
+    }
+}
+
+impl io::Write for TcpStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = unsafe { libc::send(self.fd, buf.as_ptr() as *const libc::c_void, buf.len(), 0) };
+        if n < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(n as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// IPv4 socket address in network byte order
+pub(super) fn sockaddr_in(addr: [u8; 4], port: u16) -> libc::sockaddr_in {
+    let mut sin: libc::sockaddr_in = unsafe { core::mem::zeroed() };
+    sin.sin_family = libc::AF_INET as libc::sa_family_t;
+    sin.sin_port = port.to_be();
+    sin.sin_addr.s_addr = u32::from_ne_bytes(addr);
+    sin
+}
+
+/// Convert a socket timeout; `None` disables it
+pub(super) fn timeval(d: Option<Duration>) -> WifiResult<libc::timeval> {
+    // A zero timeval means no timeout, so round sub-microsecond durations up
+    let micros = match d {
+        Some(d) if d.is_zero() => return Err(WifiError::ConfigurationError),
+        Some(d) => d.as_micros().max(1),
+        None => 0,
+    };
+    Ok(libc::timeval {
+        tv_sec: (micros / 1_000_000) as libc::time_t,
+        tv_usec: (micros % 1_000_000) as libc::suseconds_t,
+    })
+}
+
+pub(super) fn setsockopt<T>(
+    fd: RawFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    value: &T,
+) -> WifiResult<()> {
+    let rc = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            value as *const T as *const libc::c_void,
+            core::mem::size_of::<T>() as libc::socklen_t,
+        )
+    };
+    if rc < 0 {
+        return Err(WifiError::from(last_errno()));
+    }
+    Ok(())
+}
+
+/// Local IPv4 address and port a socket is bound to
+pub(super) fn local_addr(fd: RawFd) -> WifiResult<([u8; 4], u16)> {
+    let mut sin: libc::sockaddr_in = unsafe { core::mem::zeroed() };
+    let mut len = core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
+    let rc = unsafe { libc::getsockname(fd, &mut sin as *mut _ as *mut libc::sockaddr, &mut len) };
+    if rc < 0 {
+        return Err(WifiError::from(last_errno()));
+    }
+    Ok((
+        sin.sin_addr.s_addr.to_ne_bytes(),
+        u16::from_be(sin.sin_port),
+    ))
+}
+
+/// Wait until a connecting socket becomes writable, or `Timeout` at `deadline`
+fn wait_writable(fd: RawFd, deadline: Instant) -> WifiResult<()> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(WifiError::Timeout);
+        }
+
+        let mut pfd = libc::pollfd {
+            fd,
+            events: libc::POLLOUT,
+            revents: 0,
+        };
+        let wait_ms = remaining.as_micros().div_ceil(1000) as libc::c_int;
+        if unsafe { libc::poll(&mut pfd, 1, wait_ms) } > 0 {
+            // A failed connect also reports writable; SO_ERROR tells which
+            return Ok(());
+        }
+    }
+}
+
+pub(super) fn last_errno() -> i32 {
+    io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
@@ -0,0 +1,170 @@
+//! UDP datagram socket on raw libc sockets
+//!
+//! Used for datagram protocols (NTP, mDNS, ...) on both Linux and NuttX.
+
+use super::tcp::{last_errno, local_addr, setsockopt, sockaddr_in, timeval};
+use super::{WifiError, WifiResult};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use std::os::fd::RawFd;
+use std::time::Duration;
+
+/// Bound UDP socket, closed on drop
+///
+/// `Send` but not `Sync`, like `TcpStream`.
+pub struct UdpSocket {
+    fd: RawFd,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl UdpSocket {
+    /// Bind to `port` on all interfaces
+    ///
+    /// SO_REUSEADDR is set so that well-known ports (e.g. 5353) can be
+    /// shared with other listeners.
+    pub fn bind(port: u16) -> WifiResult<UdpSocket> {
+        let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM, 0) };
+        if fd < 0 {
+            return Err(WifiError::SocketError);
+        }
+        let socket = UdpSocket {
+            fd,
+            _not_sync: PhantomData,
+        };
+
+        let reuse: libc::c_int = 1;
+        setsockopt(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, &reuse)?;
+
+        let sin = sockaddr_in([0, 0, 0, 0], port);
+        let rc = unsafe {
+            libc::bind(
+                fd,
+                &sin as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            return Err(WifiError::from(last_errno()));
+        }
+        Ok(socket)
+    }
+
+    /// Bind to an ephemeral port chosen by the stack
+    pub fn bind_any() -> WifiResult<UdpSocket> {
+        Self::bind(0)
+    }
+
+    /// Send one datagram to `addr:port`, returning the bytes sent
+    pub fn send_to(&self, data: &[u8], addr: [u8; 4], port: u16) -> WifiResult<usize> {
+        let sin = sockaddr_in(addr, port);
+        let n = unsafe {
+            libc::sendto(
+                self.fd,
+                data.as_ptr() as *const libc::c_void,
+                data.len(),
+                0,
+                &sin as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if n < 0 {
+            return Err(WifiError::from(last_errno()));
+        }
+        Ok(n as usize)
+    }
+
+    /// Receive one datagram into `buf`, returning the sender and its length
+    ///
+    /// A datagram longer than `buf` is truncated. Returns `Timeout` if the
+    /// read timeout expires first.
+    pub fn recv_from(&self, buf: &mut [u8]) -> WifiResult<([u8; 4], u16, usize)> {
+        let mut sin: libc::sockaddr_in = unsafe { core::mem::zeroed() };
+        let mut len = core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
+        let n = unsafe {
+            libc::recvfrom(
+                self.fd,
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                0,
+                &mut sin as *mut _ as *mut libc::sockaddr,
+                &mut len,
+            )
+        };
+        if n < 0 {
+            let errno = last_errno();
+            if errno == libc::EAGAIN || errno == libc::EWOULDBLOCK {
+                return Err(WifiError::Timeout);
+            }
+            return Err(WifiError::from(errno));
+        }
+        Ok((
+            sin.sin_addr.s_addr.to_ne_bytes(),
+            u16::from_be(sin.sin_port),
+            n as usize,
+        ))
+    }
+
+    /// Bound each `recv_from`; `None` blocks until a datagram arrives
+    pub fn set_read_timeout(&self, d: Option<Duration>) -> WifiResult<()> {
+        setsockopt(self.fd, libc::SOL_SOCKET, libc::SO_RCVTIMEO, &timeval(d)?)
+    }
+
+    /// Receive datagrams sent to the IPv4 multicast `group` (on any interface)
+    pub fn join_multicast(&self, group: [u8; 4]) -> WifiResult<()> {
+        if group[0] & 0xF0 != 0xE0 {
+            return Err(WifiError::ConfigurationError);
+        }
+        let mreq = libc::ip_mreq {
+            imr_multiaddr: libc::in_addr {
+                s_addr: u32::from_ne_bytes(group),
+            },
+            imr_interface: libc::in_addr {
+                s_addr: libc::INADDR_ANY,
+            },
+        };
+        setsockopt(self.fd, libc::IPPROTO_IP, libc::IP_ADD_MEMBERSHIP, &mreq)
+    }
+
+    /// Set the default peer, so the stack picks the route and source address
+    ///
+    /// Datagrams from other senders are then dropped by the stack.
+    pub fn connect(&self, addr: [u8; 4], port: u16) -> WifiResult<()> {
+        let sin = sockaddr_in(addr, port);
+        let rc = unsafe {
+            libc::connect(
+                self.fd,
+                &sin as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            return Err(WifiError::from(last_errno()));
+        }
+        Ok(())
+    }
+
+    /// Open a second handle to the same socket (e.g. for another thread)
+    pub fn try_clone(&self) -> WifiResult<UdpSocket> {
+        let fd = unsafe { libc::dup(self.fd) };
+        if fd < 0 {
+            return Err(WifiError::from(last_errno()));
+        }
+        Ok(UdpSocket {
+            fd,
+            _not_sync: PhantomData,
+        })
+    }
+
+    /// Local IPv4 address and port the socket is bound to
+    pub fn local_addr(&self) -> WifiResult<([u8; 4], u16)> {
+        local_addr(self.fd)
+    }
+}
+
+impl Drop for UdpSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
@@ -545,6 +545,42 @@ int rust_ble_wrapper_is_connected(void)
     return g_ble_connected;
 }
 
+/****************************************************************************
+ * Name: rust_ble_wrapper_get_rssi
+ *
+ * Description:
+ *   Read the RSSI of an active connection.
+ *
+ * Parameters:
+ *   conn_handle - Connection handle
+ *   rssi_out    - Receives the RSSI in dBm
+ *
+ * Returns:
+ *   0 on success, -ENOTCONN if the handle is not connected,
+ *   negative errno on other failures
+ ****************************************************************************/
+
+int rust_ble_wrapper_get_rssi(uint16_t conn_handle, int8_t *rssi_out)
+{
+    if (!g_ble_initialized) {
+        return -ENODEV;
+    }
+
+    if (rssi_out == NULL) {
+        return -EINVAL;
+    }
+
+    if (!g_ble_connected || conn_handle != g_conn_handle) {
+        return -ENOTCONN;
+    }
+
+    if (ble_gap_conn_rssi(conn_handle, rssi_out) != 0) {
+        return -EIO;
+    }
+
+    return 0;
+}
+
 /****************************************************************************
  * Name: rust_ble_wrapper_run
  *
@@ -1156,6 +1192,24 @@ int rust_ble_wrapper_is_connected(void)
     return 0;
 }
 
+/****************************************************************************
+ * Name: rust_ble_wrapper_get_rssi
+ *
+ * Description:
+ *   Read the RSSI of an active connection.
+ *   Note: Connection tracking not yet implemented for native BLE.
+ *
+ * Returns:
+ *   -ENOTSUP
+ ****************************************************************************/
+
+int rust_ble_wrapper_get_rssi(uint16_t conn_handle, int8_t *rssi_out)
+{
+    (void)conn_handle;
+    (void)rssi_out;
+    return -ENOTSUP;
+}
+
 /****************************************************************************
  * Name: rust_ble_wrapper_run
  *
@@ -1348,6 +1402,13 @@ int rust_ble_wrapper_is_connected(void)
     return 0;
 }
 
+int rust_ble_wrapper_get_rssi(uint16_t conn_handle, int8_t *rssi_out)
+{
+    (void)conn_handle;
+    (void)rssi_out;
+    return -ENOTSUP;
+}
+
 void rust_ble_wrapper_run(void)
 {
 }
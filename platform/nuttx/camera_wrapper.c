@@ -29,7 +29,7 @@
  * Pre-processor Definitions
  ****************************************************************************/
 
-#define CAMERA_DEV_PATH      "/dev/video0"
+#define CAMERA_DEV_FORMAT    "/dev/video%d"
 #define CAMERA_BUFFER_SIZE   (320 * 240 * 2)  /* Default QVGA RGB565 or JPEG */
 
 /* Pixel format codes matching Rust enum */
@@ -38,6 +38,7 @@
 #define PIXFMT_RGB888     2
 #define PIXFMT_YUV422     3
 #define PIXFMT_GRAYSCALE  4
+#define PIXFMT_NV12       5
 
 /****************************************************************************
  * Private Data
@@ -51,6 +52,54 @@ static size_t g_frame_len = 0;
 static int g_width = 320;
 static int g_height = 240;
 static int g_format = PIXFMT_JPEG;
+static int g_hmirror = 0;
+static int g_vflip = 0;
+
+/****************************************************************************
+ * Private Functions
+ ****************************************************************************/
+
+/****************************************************************************
+ * Name: camera_resolution_size
+ *
+ * Description:
+ *   Map a resolution enum value to frame dimensions.
+ ****************************************************************************/
+
+static void camera_resolution_size(int resolution, int *width, int *height)
+{
+  switch (resolution)
+    {
+      case 0: *width = 160;  *height = 120;  break;  /* QQVGA */
+      case 1: *width = 176;  *height = 144;  break;  /* QCIF */
+      case 2: *width = 240;  *height = 176;  break;  /* HQVGA */
+      case 3: *width = 320;  *height = 240;  break;  /* QVGA */
+      case 4: *width = 400;  *height = 296;  break;  /* CIF */
+      case 5: *width = 480;  *height = 320;  break;  /* HVGA */
+      case 6: *width = 640;  *height = 480;  break;  /* VGA */
+      case 7: *width = 800;  *height = 600;  break;  /* SVGA */
+      case 8: *width = 1024; *height = 768;  break;  /* XGA */
+      default: *width = 320; *height = 240;  break;  /* Default QVGA */
+    }
+}
+
+/****************************************************************************
+ * Name: camera_buffer_size
+ *
+ * Description:
+ *   Frame buffer size needed for the given format and dimensions.
+ ****************************************************************************/
+
+static size_t camera_buffer_size(int format, int width, int height)
+{
+  if (format == PIXFMT_JPEG)
+    {
+      /* JPEG typically smaller */
+      return width * height / 2;
+    }
+
+  return width * height * 2;  /* RGB565 */
+}
 
 /****************************************************************************
  * Public Functions (FFI Interface)
@@ -63,16 +112,22 @@ static int g_format = PIXFMT_JPEG;
  *   Initialize camera subsystem.
  *
  * Parameters:
+ *   device     - Video device number (opens /dev/video<device>)
  *   format     - Pixel format (0=JPEG, 1=RGB565, etc.)
  *   resolution - Resolution enum (0=QQVGA, 6=VGA, etc.)
  *   quality    - JPEG quality (1-100, only for JPEG)
+ *   hmirror    - Mirror horizontally (applied before streaming)
+ *   vflip      - Flip vertically (applied before streaming)
  *
  * Returns:
  *   0 on success, negative errno on failure
  ****************************************************************************/
 
-int rust_camera_wrapper_init(int format, int resolution, int quality)
+int rust_camera_wrapper_init(int device, int format, int resolution,
+                             int quality, int hmirror, int vflip)
 {
+  char path[16];
+
   (void)quality;
 
   if (g_camera_initialized)
@@ -83,11 +138,12 @@ int rust_camera_wrapper_init(int format, int resolution, int quality)
   printf("[CAM] Initializing camera...\n");
 
   /* Try to open the camera device */
-  g_camera_fd = open(CAMERA_DEV_PATH, O_RDWR);
+  snprintf(path, sizeof(path), CAMERA_DEV_FORMAT, device);
+  g_camera_fd = open(path, O_RDWR);
   if (g_camera_fd < 0)
     {
       int err = errno;
-      printf("[CAM] Failed to open %s: %d\n", CAMERA_DEV_PATH, err);
+      printf("[CAM] Failed to open %s: %d\n", path, err);
 
       if (err == ENOENT)
         {
@@ -99,29 +155,14 @@ int rust_camera_wrapper_init(int format, int resolution, int quality)
       return -err;
     }
 
-  /* Set format and resolution - use resolution enum for sizing */
+  /* Set format, orientation and resolution */
   g_format = format;
-  switch (resolution)
-    {
-      case 0: g_width = 160;  g_height = 120;  break;  /* QQVGA */
-      case 1: g_width = 176;  g_height = 144;  break;  /* QCIF */
-      case 2: g_width = 240;  g_height = 176;  break;  /* HQVGA */
-      case 3: g_width = 320;  g_height = 240;  break;  /* QVGA */
-      case 4: g_width = 400;  g_height = 296;  break;  /* CIF */
-      case 5: g_width = 480;  g_height = 320;  break;  /* HVGA */
-      case 6: g_width = 640;  g_height = 480;  break;  /* VGA */
-      case 7: g_width = 800;  g_height = 600;  break;  /* SVGA */
-      case 8: g_width = 1024; g_height = 768;  break;  /* XGA */
-      default: g_width = 320; g_height = 240;  break;  /* Default QVGA */
-    }
+  g_hmirror = hmirror ? 1 : 0;
+  g_vflip = vflip ? 1 : 0;
+  camera_resolution_size(resolution, &g_width, &g_height);
 
   /* Allocate frame buffer */
-  g_frame_buffer_size = g_width * g_height * 2;  /* RGB565 or compressed JPEG */
-  if (format == PIXFMT_JPEG)
-    {
-      /* JPEG typically smaller */
-      g_frame_buffer_size = g_width * g_height / 2;
-    }
+  g_frame_buffer_size = camera_buffer_size(g_format, g_width, g_height);
 
   g_frame_buffer = (uint8_t *)malloc(g_frame_buffer_size);
   if (!g_frame_buffer)
@@ -243,6 +284,56 @@ int rust_camera_wrapper_capture(uint32_t *width, uint32_t *height,
   return 0;
 }
 
+/****************************************************************************
+ * Name: rust_camera_wrapper_set_resolution
+ *
+ * Description:
+ *   Change capture resolution without reopening the device. The frame
+ *   buffer is reallocated only if its required size changes.
+ *
+ * Parameters:
+ *   resolution - Resolution enum (0=QQVGA, 6=VGA, etc.)
+ *
+ * Returns:
+ *   0 on success, negative errno on failure
+ ****************************************************************************/
+
+int rust_camera_wrapper_set_resolution(int resolution)
+{
+  int width;
+  int height;
+  size_t size;
+
+  if (!g_camera_initialized)
+    {
+      return -ENODEV;
+    }
+
+  camera_resolution_size(resolution, &width, &height);
+  size = camera_buffer_size(g_format, width, height);
+
+  if (size != g_frame_buffer_size)
+    {
+      uint8_t *buffer = (uint8_t *)realloc(g_frame_buffer, size);
+      if (!buffer)
+        {
+          printf("[CAM] Failed to resize frame buffer (%d bytes)\n",
+                 (int)size);
+          return -ENOMEM;
+        }
+
+      g_frame_buffer = buffer;
+      g_frame_buffer_size = size;
+    }
+
+  g_width = width;
+  g_height = height;
+  g_frame_len = 0;
+  printf("[CAM] Resolution set to %dx%d\n", g_width, g_height);
+
+  return 0;
+}
+
 /****************************************************************************
  * Name: rust_camera_wrapper_return_frame
  *
@@ -282,14 +373,15 @@ int rust_camera_wrapper_is_initialized(void)
  ****************************************************************************/
 
 int rust_camera_wrapper_get_sensor(int8_t *brightness, int8_t *contrast,
-                                    int8_t *saturation)
+                                    int8_t *saturation, int *hmirror,
+                                    int *vflip)
 {
   if (!g_camera_initialized)
     {
       return -ENODEV;
     }
 
-  if (!brightness || !contrast || !saturation)
+  if (!brightness || !contrast || !saturation || !hmirror || !vflip)
     {
       return -EINVAL;
     }
@@ -297,6 +389,8 @@ int rust_camera_wrapper_get_sensor(int8_t *brightness, int8_t *contrast,
   *brightness = 0;
   *contrast = 0;
   *saturation = 0;
+  *hmirror = g_hmirror;
+  *vflip = g_vflip;
 
   return 0;
 }
@@ -322,8 +416,9 @@ int rust_camera_wrapper_set_sensor(int8_t brightness, int8_t contrast,
   (void)brightness;
   (void)contrast;
   (void)saturation;
-  (void)hmirror;
-  (void)vflip;
+
+  g_hmirror = hmirror ? 1 : 0;
+  g_vflip = vflip ? 1 : 0;
 
   return 0;
 }